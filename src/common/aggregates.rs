@@ -0,0 +1,181 @@
+//! Rolling aggregates over the trade stream
+
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::types::{MarketEvent, Side, Trade};
+
+/// Buy and sell volume over a sliding time window
+///
+/// Trades are stamped with the local time they were added, so the window
+/// follows arrival order rather than exchange timestamps. Trades older than
+/// the window are evicted whenever a trade is added or
+/// [`expire`](Self::expire) is called; the totals are kept up to date
+/// incrementally, so each update only touches the trades it evicts.
+#[derive(Debug, Clone)]
+pub struct VolumeWindow {
+    window: Duration,
+    trades: VecDeque<(Instant, Trade)>,
+    buy_volume: Decimal,
+    sell_volume: Decimal,
+}
+
+impl VolumeWindow {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            trades: VecDeque::new(),
+            buy_volume: Decimal::ZERO,
+            sell_volume: Decimal::ZERO,
+        }
+    }
+
+    /// Add a trade event; other events are ignored
+    pub fn apply(&mut self, event: &MarketEvent) {
+        if let MarketEvent::Trade(trade) = event {
+            self.push(trade.clone());
+        }
+    }
+
+    /// Add a trade received now
+    pub fn push(&mut self, trade: Trade) {
+        self.push_at(Instant::now(), trade);
+    }
+
+    /// Add a trade received at `at`
+    ///
+    /// `at` should not be earlier than the previous trade's.
+    pub fn push_at(&mut self, at: Instant, trade: Trade) {
+        match trade.side {
+            Side::Buy => self.buy_volume += trade.size,
+            Side::Sell => self.sell_volume += trade.size,
+        }
+        self.trades.push_back((at, trade));
+        self.expire_at(at);
+    }
+
+    /// Evict trades that have left the window as of now
+    pub fn expire(&mut self) {
+        self.expire_at(Instant::now());
+    }
+
+    /// Evict trades older than the window as of `now`
+    pub fn expire_at(&mut self, now: Instant) {
+        while let Some((at, _)) = self.trades.front() {
+            if now.saturating_duration_since(*at) <= self.window {
+                break;
+            }
+            let (_, trade) = self.trades.pop_front().expect("front exists");
+            match trade.side {
+                Side::Buy => self.buy_volume -= trade.size,
+                Side::Sell => self.sell_volume -= trade.size,
+            }
+        }
+    }
+
+    /// Taker buy volume in the window
+    pub fn buy_volume(&self) -> Decimal {
+        self.buy_volume
+    }
+
+    /// Taker sell volume in the window
+    pub fn sell_volume(&self) -> Decimal {
+        self.sell_volume
+    }
+
+    /// `(buy - sell) / (buy + sell)`, from -1 (all sells) to 1 (all buys)
+    ///
+    /// Returns `None` when the window has no volume.
+    pub fn imbalance(&self) -> Option<Decimal> {
+        let total = self.buy_volume + self.sell_volume;
+        if total.is_zero() {
+            return None;
+        }
+        Some((self.buy_volume - self.sell_volume) / total)
+    }
+
+    /// Number of trades in the window
+    pub fn trade_count(&self) -> usize {
+        self.trades.len()
+    }
+
+    /// Length of the window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{Platform, TradeSideSource};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn trade(side: Side, size: Decimal) -> Trade {
+        Trade {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: "token".to_string(),
+            trade_id: String::new(),
+            price: dec!(0.50),
+            size,
+            side,
+            side_source: TradeSideSource::Reported,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_old_trades_evicted() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut window = VolumeWindow::new(Duration::from_secs(10));
+
+        window.push_at(at(0), trade(Side::Buy, dec!(100)));
+        window.push_at(at(4), trade(Side::Sell, dec!(30)));
+        window.push_at(at(8), trade(Side::Buy, dec!(20)));
+        assert_eq!(window.trade_count(), 3);
+        assert_eq!(window.buy_volume(), dec!(120));
+        assert_eq!(window.sell_volume(), dec!(30));
+
+        // A trade exactly one window old is still counted
+        window.push_at(at(10), trade(Side::Sell, dec!(10)));
+        assert_eq!(window.trade_count(), 4);
+
+        // The first buy falls out
+        window.push_at(at(11), trade(Side::Sell, dec!(10)));
+        assert_eq!(window.trade_count(), 4);
+        assert_eq!(window.buy_volume(), dec!(20));
+        assert_eq!(window.sell_volume(), dec!(50));
+
+        // Expiring without new trades empties the window
+        window.expire_at(at(30));
+        assert_eq!(window.trade_count(), 0);
+        assert_eq!(window.buy_volume(), dec!(0));
+        assert_eq!(window.sell_volume(), dec!(0));
+        assert_eq!(window.imbalance(), None);
+    }
+
+    #[test]
+    fn test_imbalance() {
+        let start = Instant::now();
+        let mut window = VolumeWindow::new(Duration::from_secs(60));
+
+        window.push_at(start, trade(Side::Buy, dec!(75)));
+        assert_eq!(window.imbalance(), Some(dec!(1)));
+
+        window.push_at(start, trade(Side::Sell, dec!(25)));
+        assert_eq!(window.imbalance(), Some(dec!(0.5)));
+
+        window.push_at(start, trade(Side::Sell, dec!(100)));
+        assert_eq!(window.imbalance(), Some(dec!(-0.25)));
+
+        window.apply(&MarketEvent::Heartbeat {
+            platform: Platform::Polymarket,
+        });
+        assert_eq!(window.trade_count(), 3);
+    }
+}
@@ -0,0 +1,316 @@
+//! A market client that fans out to several platform clients
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use super::errors::{ClientError, Result};
+use super::traits::MarketClient;
+use super::types::{MarketEvent, Platform};
+
+/// Combines several [`MarketClient`]s behind a single one
+///
+/// Each client is registered with the platform it serves. Connection calls
+/// are forwarded to every client in order, while subscriptions are routed
+/// with [`subscribe_to`](Self::subscribe_to) so each platform only sees its
+/// own IDs. `start` hands each client a clone of the same sender, so events
+/// from all platforms arrive merged on one channel. Unlike
+/// [`ClientSupervisor`](super::supervisor::ClientSupervisor), failed clients
+/// are not restarted.
+pub struct MultiMarketClient {
+    clients: Vec<(Platform, Box<dyn MarketClient>)>,
+}
+
+impl MultiMarketClient {
+    pub fn new(clients: Vec<(Platform, Box<dyn MarketClient>)>) -> Self {
+        Self { clients }
+    }
+
+    /// Add another client serving `platform`
+    pub fn with_client(mut self, platform: Platform, client: Box<dyn MarketClient>) -> Self {
+        self.clients.push((platform, client));
+        self
+    }
+
+    /// Number of underlying clients
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Whether there are no underlying clients
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Subscribe each client to the assets on its platform
+    ///
+    /// Fails with `ClientError::Configuration` if an asset's platform has no
+    /// client, before anything is sent.
+    pub async fn subscribe_to(&mut self, assets: &[(Platform, String)]) -> Result<()> {
+        for (platform, asset_ids) in self.route(assets)? {
+            for (_, client) in self.clients.iter_mut().filter(|(p, _)| *p == platform) {
+                client.subscribe(&asset_ids).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unsubscribe each client from the assets on its platform
+    pub async fn unsubscribe_from(&mut self, assets: &[(Platform, String)]) -> Result<()> {
+        for (platform, asset_ids) in self.route(assets)? {
+            for (_, client) in self.clients.iter_mut().filter(|(p, _)| *p == platform) {
+                client.unsubscribe(&asset_ids).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Group asset IDs by platform, checking each platform has a client
+    fn route(&self, assets: &[(Platform, String)]) -> Result<Vec<(Platform, Vec<String>)>> {
+        let mut routed: Vec<(Platform, Vec<String>)> = Vec::new();
+        for (platform, asset_id) in assets {
+            if !self.clients.iter().any(|(p, _)| p == platform) {
+                return Err(ClientError::Configuration(format!(
+                    "No {} client for asset {}",
+                    platform, asset_id
+                )));
+            }
+            match routed.iter_mut().find(|(p, _)| p == platform) {
+                Some((_, ids)) => ids.push(asset_id.clone()),
+                None => routed.push((*platform, vec![asset_id.clone()])),
+            }
+        }
+        Ok(routed)
+    }
+
+    /// The only platform served, if every client serves the same one
+    fn single_platform(&self) -> Option<Platform> {
+        let (first, _) = self.clients.first()?;
+        self.clients
+            .iter()
+            .all(|(platform, _)| platform == first)
+            .then_some(*first)
+    }
+
+    /// Tag bare asset IDs with the single platform served
+    ///
+    /// IDs alone don't say which platform they belong to, so this fails
+    /// once clients for more than one platform are registered.
+    fn tag(&self, asset_ids: &[String]) -> Result<Vec<(Platform, String)>> {
+        let platform = self.single_platform().ok_or_else(|| {
+            ClientError::Configuration(
+                "MultiMarketClient can't route bare asset IDs across platforms; use subscribe_to"
+                    .to_string(),
+            )
+        })?;
+        Ok(asset_ids.iter().map(|id| (platform, id.clone())).collect())
+    }
+}
+
+#[async_trait]
+impl MarketClient for MultiMarketClient {
+    async fn connect(&mut self) -> Result<()> {
+        for (_, client) in &mut self.clients {
+            client.connect().await?;
+        }
+        Ok(())
+    }
+
+    /// Subscribe when all clients serve one platform; see
+    /// [`subscribe_to`](MultiMarketClient::subscribe_to) otherwise
+    async fn subscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        let assets = self.tag(asset_ids)?;
+        self.subscribe_to(&assets).await
+    }
+
+    /// Unsubscribe when all clients serve one platform; see
+    /// [`unsubscribe_from`](MultiMarketClient::unsubscribe_from) otherwise
+    async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        let assets = self.tag(asset_ids)?;
+        self.unsubscribe_from(&assets).await
+    }
+
+    async fn start(&mut self, sender: mpsc::Sender<MarketEvent>) -> Result<()> {
+        for (_, client) in &mut self.clients {
+            client.start(sender.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Disconnect every client, returning the first error encountered
+    async fn disconnect(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for (_, client) in &mut self.clients {
+            if let Err(e) = client.disconnect().await {
+                warn!("Error disconnecting {}: {}", client.platform_name(), e);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// True only when there is at least one client and all are connected
+    fn is_connected(&self) -> bool {
+        !self.clients.is_empty() && self.clients.iter().all(|(_, c)| c.is_connected())
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "multi"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    /// Client that sends a single heartbeat once started
+    struct MockClient {
+        platform: Platform,
+        connected: bool,
+        subscriptions: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl MarketClient for MockClient {
+        async fn connect(&mut self) -> Result<()> {
+            self.connected = true;
+            Ok(())
+        }
+
+        async fn subscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .extend_from_slice(asset_ids);
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .retain(|id| !asset_ids.contains(id));
+            Ok(())
+        }
+
+        async fn start(&mut self, sender: mpsc::Sender<MarketEvent>) -> Result<()> {
+            let platform = self.platform;
+            tokio::spawn(async move {
+                let _ = sender.send(MarketEvent::Heartbeat { platform }).await;
+            });
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            self.connected = false;
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            self.connected
+        }
+
+        fn platform_name(&self) -> &'static str {
+            "Mock"
+        }
+    }
+
+    fn mock(platform: Platform) -> (Platform, Box<dyn MarketClient>) {
+        (platform, watched(platform).0)
+    }
+
+    /// A mock client along with the asset IDs it's subscribed to
+    fn watched(platform: Platform) -> (Box<dyn MarketClient>, Arc<Mutex<Vec<String>>>) {
+        let subscriptions = Arc::new(Mutex::new(Vec::new()));
+        let client = Box::new(MockClient {
+            platform,
+            connected: false,
+            subscriptions: subscriptions.clone(),
+        });
+        (client, subscriptions)
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_routed_by_platform() {
+        let (poly, poly_subs) = watched(Platform::Polymarket);
+        let (kalshi, kalshi_subs) = watched(Platform::Kalshi);
+        let mut client = MultiMarketClient::new(vec![(Platform::Polymarket, poly)])
+            .with_client(Platform::Kalshi, kalshi);
+
+        client
+            .subscribe_to(&[
+                (Platform::Polymarket, "token_yes".to_string()),
+                (Platform::Kalshi, "KXBTC".to_string()),
+                (Platform::Polymarket, "token_no".to_string()),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(*poly_subs.lock().unwrap(), ["token_yes", "token_no"]);
+        assert_eq!(*kalshi_subs.lock().unwrap(), ["KXBTC"]);
+
+        client
+            .unsubscribe_from(&[(Platform::Polymarket, "token_no".to_string())])
+            .await
+            .unwrap();
+        assert_eq!(*poly_subs.lock().unwrap(), ["token_yes"]);
+        assert_eq!(*kalshi_subs.lock().unwrap(), ["KXBTC"]);
+
+        // Bare IDs can't be routed across platforms
+        let err = client.subscribe(&["KXETH".to_string()]).await.unwrap_err();
+        assert!(matches!(err, ClientError::Configuration(_)));
+        assert_eq!(*kalshi_subs.lock().unwrap(), ["KXBTC"]);
+    }
+
+    #[tokio::test]
+    async fn test_bare_ids_with_one_platform() {
+        let (kalshi, kalshi_subs) = watched(Platform::Kalshi);
+        let mut client = MultiMarketClient::new(vec![(Platform::Kalshi, kalshi)]);
+
+        client.subscribe(&["KXBTC".to_string()]).await.unwrap();
+        assert_eq!(*kalshi_subs.lock().unwrap(), ["KXBTC"]);
+
+        // No client to route Polymarket assets to
+        let err = client
+            .subscribe_to(&[(Platform::Polymarket, "token".to_string())])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::Configuration(_)));
+    }
+
+    #[tokio::test]
+    async fn test_merges_events_from_all_clients() {
+        let (kalshi, kalshi_client) = mock(Platform::Kalshi);
+        let mut client = MultiMarketClient::new(vec![mock(Platform::Polymarket)])
+            .with_client(kalshi, kalshi_client);
+        assert_eq!(client.platform_name(), "multi");
+        assert!(!client.is_connected());
+
+        client.connect().await.unwrap();
+        assert!(client.is_connected());
+
+        let (tx, mut rx) = mpsc::channel(8);
+        client.start(tx).await.unwrap();
+
+        let mut platforms = HashSet::new();
+        for _ in 0..2 {
+            match timeout(Duration::from_secs(2), rx.recv()).await {
+                Ok(Some(MarketEvent::Heartbeat { platform })) => platforms.insert(platform),
+                other => panic!("Expected heartbeat, got {:?}", other),
+            };
+        }
+        assert_eq!(
+            platforms,
+            HashSet::from([Platform::Polymarket, Platform::Kalshi])
+        );
+
+        client.disconnect().await.unwrap();
+        assert!(!client.is_connected());
+        assert!(!MultiMarketClient::new(Vec::new()).is_connected());
+    }
+}
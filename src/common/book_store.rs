@@ -0,0 +1,164 @@
+//! Current order book per asset, maintained from the event stream
+
+use super::orderbook::OrderBookManager;
+use super::types::{MarketEvent, OrderBook, PriceLevel};
+
+/// Latest order book for every asset seen on the event stream
+///
+/// A read-side view over an [`OrderBookManager`], which does the snapshot
+/// and delta handling: snapshots replace an asset's book, deltas are merged
+/// into it, and deltas for an asset with no snapshot yet, or one waiting
+/// for a resync after a reconnect, are ignored. Quotes always come from the
+/// last book the manager accepted.
+#[derive(Debug, Clone, Default)]
+pub struct BookStore {
+    books: OrderBookManager,
+}
+
+impl BookStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a book event; other events are ignored
+    pub fn apply(&mut self, event: &MarketEvent) {
+        self.books.apply_event(event);
+    }
+
+    /// Get the current book for an asset
+    pub fn get(&self, asset_id: &str) -> Option<&OrderBook> {
+        self.books.get(asset_id)
+    }
+
+    /// Best bid and best ask for an asset, if both sides have liquidity
+    pub fn best_quotes(&self, asset_id: &str) -> Option<(PriceLevel, PriceLevel)> {
+        let book = self.books.get(asset_id)?;
+        Some((book.best_bid()?.clone(), book.best_ask()?.clone()))
+    }
+
+    /// Number of assets with a book
+    pub fn len(&self) -> usize {
+        self.books.len()
+    }
+
+    /// Whether no books have been received
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{ConnectionStatus, OrderBookUpdate, Platform};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn update(is_snapshot: bool, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> MarketEvent {
+        MarketEvent::OrderBookUpdate(OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: "token".to_string(),
+            bids,
+            asks,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            is_snapshot,
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    #[test]
+    fn test_snapshot_then_deltas() {
+        let mut store = BookStore::new();
+
+        // Deltas before the first snapshot have nothing to apply to
+        store.apply(&update(
+            false,
+            vec![PriceLevel::new(dec!(0.49), dec!(10))],
+            vec![],
+        ));
+        assert!(store.is_empty());
+
+        store.apply(&update(
+            true,
+            vec![
+                PriceLevel::new(dec!(0.48), dec!(100)),
+                PriceLevel::new(dec!(0.47), dec!(50)),
+            ],
+            vec![
+                PriceLevel::new(dec!(0.52), dec!(80)),
+                PriceLevel::new(dec!(0.53), dec!(60)),
+            ],
+        ));
+        assert_eq!(
+            store.best_quotes("token"),
+            Some((
+                PriceLevel::new(dec!(0.48), dec!(100)),
+                PriceLevel::new(dec!(0.52), dec!(80))
+            ))
+        );
+
+        // Improve the bid and take out the best ask
+        store.apply(&update(
+            false,
+            vec![PriceLevel::new(dec!(0.50), dec!(25))],
+            vec![PriceLevel::new(dec!(0.52), dec!(0))],
+        ));
+        assert_eq!(
+            store.best_quotes("token"),
+            Some((
+                PriceLevel::new(dec!(0.50), dec!(25)),
+                PriceLevel::new(dec!(0.53), dec!(60))
+            ))
+        );
+        assert_eq!(store.get("token").unwrap().bids.len(), 3);
+
+        store.apply(&MarketEvent::Heartbeat {
+            platform: Platform::Polymarket,
+        });
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.best_quotes("other"), None);
+    }
+
+    #[test]
+    fn test_deltas_wait_for_snapshot_after_reconnect() {
+        let mut store = BookStore::new();
+        store.apply(&update(
+            true,
+            vec![PriceLevel::new(dec!(0.48), dec!(100))],
+            vec![PriceLevel::new(dec!(0.52), dec!(80))],
+        ));
+        store.apply(&MarketEvent::ConnectionStatus {
+            platform: Platform::Polymarket,
+            status: ConnectionStatus::Reconnecting { attempt: 1 },
+        });
+
+        // The book may have moved while disconnected, so deltas are held off
+        store.apply(&update(
+            false,
+            vec![PriceLevel::new(dec!(0.50), dec!(25))],
+            vec![],
+        ));
+        assert_eq!(store.get("token").unwrap().bids.len(), 1);
+
+        store.apply(&update(
+            true,
+            vec![PriceLevel::new(dec!(0.49), dec!(40))],
+            vec![PriceLevel::new(dec!(0.51), dec!(40))],
+        ));
+        store.apply(&update(
+            false,
+            vec![PriceLevel::new(dec!(0.50), dec!(25))],
+            vec![],
+        ));
+        assert_eq!(
+            store.best_quotes("token"),
+            Some((
+                PriceLevel::new(dec!(0.50), dec!(25)),
+                PriceLevel::new(dec!(0.51), dec!(40))
+            ))
+        );
+    }
+}
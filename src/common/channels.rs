@@ -1,12 +1,27 @@
 //! Channel type definitions for inter-task communication
 
-use tokio::sync::mpsc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::error::{SendError, TrySendError};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
 
 use super::types::MarketEvent;
 
+/// Error from receiving on a broadcast channel, including how many events a
+/// lagging receiver missed
+pub use tokio::sync::broadcast::error::RecvError as BroadcastRecvError;
+
 /// Default channel buffer size
 pub const DEFAULT_CHANNEL_SIZE: usize = 1000;
 
+/// Fraction of capacity at which a [`MeteredSender`] reports the channel as
+/// near full
+pub const NEAR_FULL_RATIO: f64 = 0.8;
+
 /// Create a new market event channel with the default buffer size
 pub fn create_event_channel() -> (mpsc::Sender<MarketEvent>, mpsc::Receiver<MarketEvent>) {
     mpsc::channel(DEFAULT_CHANNEL_SIZE)
@@ -18,3 +33,567 @@ pub fn create_event_channel_with_size(
 ) -> (mpsc::Sender<MarketEvent>, mpsc::Receiver<MarketEvent>) {
     mpsc::channel(size)
 }
+
+/// What a [`MeteredSender`] does with an event when the channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait for the consumer to make room
+    #[default]
+    Block,
+    /// Discard the event being sent
+    DropNewest,
+    /// Hold the event back and discard the oldest held events once as many
+    /// are held as the channel holds
+    ///
+    /// Events already in the channel can't be recalled, so what gets
+    /// discarded is the oldest event still waiting to get in. Held events are
+    /// delivered, in order, ahead of later sends, and as soon as the consumer
+    /// makes room even if nothing else is sent.
+    DropOldest,
+}
+
+/// Snapshot of a [`MeteredSender`]'s counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Events delivered to the channel
+    pub sent: u64,
+    /// Sends that found the channel full and had to wait for room
+    pub blocked: u64,
+    /// Events lost because the receiver was gone
+    pub dropped: u64,
+    /// Events discarded by the [`OverflowPolicy`]
+    pub overflowed: u64,
+    /// Events currently queued
+    pub queue_depth: usize,
+    /// Events held back under [`OverflowPolicy::DropOldest`]
+    pub held: usize,
+    /// Channel buffer size
+    pub max_capacity: usize,
+    /// Mean time spent in `send`
+    pub avg_send_latency: Duration,
+    /// Longest time spent in a single `send`
+    pub max_send_latency: Duration,
+}
+
+impl ChannelStats {
+    /// Whether the queue is at or above [`NEAR_FULL_RATIO`] of capacity
+    pub fn is_near_full(&self) -> bool {
+        self.queue_depth as f64 >= self.max_capacity as f64 * NEAR_FULL_RATIO
+    }
+}
+
+/// Counters shared between clones of a [`MeteredSender`]
+#[derive(Debug, Default)]
+struct ChannelMetrics {
+    sent: AtomicU64,
+    blocked: AtomicU64,
+    dropped: AtomicU64,
+    overflowed: AtomicU64,
+    total_send_nanos: AtomicU64,
+    max_send_nanos: AtomicU64,
+    /// Set while the queue is above the near-full mark, so it's only
+    /// reported once per episode
+    near_full: AtomicBool,
+}
+
+/// An event sender that tracks backpressure
+///
+/// A slow consumer makes sends on a bounded channel wait, which in turn
+/// stalls whatever is producing events (such as a WebSocket reader). This
+/// wrapper counts sends that had to wait, measures how long sends take and
+/// reports the current queue depth through [`stats`](Self::stats). A warning
+/// is logged when the queue crosses [`NEAR_FULL_RATIO`] of capacity and again
+/// only after it has drained below half of that.
+///
+/// By default a full channel makes `send` wait; an [`OverflowPolicy`] can
+/// trade data for never stalling the producer instead. Clones share counters
+/// and held events.
+#[derive(Debug, Clone)]
+pub struct MeteredSender {
+    inner: mpsc::Sender<MarketEvent>,
+    metrics: Arc<ChannelMetrics>,
+    policy: OverflowPolicy,
+    /// Events waiting for room under `DropOldest`
+    held: Arc<Mutex<VecDeque<MarketEvent>>>,
+    /// Set while a task is delivering held events
+    flushing: Arc<AtomicBool>,
+}
+
+impl MeteredSender {
+    pub fn new(inner: mpsc::Sender<MarketEvent>) -> Self {
+        Self {
+            inner,
+            metrics: Arc::new(ChannelMetrics::default()),
+            policy: OverflowPolicy::default(),
+            held: Arc::new(Mutex::new(VecDeque::new())),
+            flushing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set what happens to events sent while the channel is full
+    ///
+    /// Connection status and resync events are never discarded; they always
+    /// wait for room, so under `DropOldest` they can overtake held events.
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Send an event, handling a full channel according to the overflow policy
+    ///
+    /// Events discarded or held by the policy count as sent successfully; an
+    /// error means the receiver is gone.
+    pub async fn send(&self, event: MarketEvent) -> Result<(), SendError<MarketEvent>> {
+        let started = Instant::now();
+        let policy = match event {
+            MarketEvent::ConnectionStatus { .. } | MarketEvent::ResyncNeeded { .. } => {
+                OverflowPolicy::Block
+            }
+            _ => self.policy,
+        };
+        // Set when the receiver is gone and the event couldn't be delivered
+        let undelivered = match policy {
+            OverflowPolicy::Block => self.send_or_wait(event).await,
+            OverflowPolicy::DropNewest => self.send_or_discard(event),
+            OverflowPolicy::DropOldest => self.send_or_hold(event),
+        };
+
+        let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.metrics
+            .total_send_nanos
+            .fetch_add(nanos, Ordering::Relaxed);
+        self.metrics
+            .max_send_nanos
+            .fetch_max(nanos, Ordering::Relaxed);
+        self.check_depth();
+
+        match undelivered {
+            Some(event) => {
+                self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                Err(SendError(event))
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn send_or_wait(&self, event: MarketEvent) -> Option<MarketEvent> {
+        let event = match self.inner.try_send(event) {
+            Ok(()) => {
+                self.delivered();
+                return None;
+            }
+            Err(TrySendError::Full(event)) => event,
+            Err(TrySendError::Closed(event)) => return Some(event),
+        };
+        self.metrics.blocked.fetch_add(1, Ordering::Relaxed);
+        match self.inner.send(event).await {
+            Ok(()) => {
+                self.delivered();
+                None
+            }
+            Err(SendError(event)) => Some(event),
+        }
+    }
+
+    fn send_or_discard(&self, event: MarketEvent) -> Option<MarketEvent> {
+        match self.inner.try_send(event) {
+            Ok(()) => self.delivered(),
+            Err(TrySendError::Full(_)) => self.overflowed(1),
+            Err(TrySendError::Closed(event)) => return Some(event),
+        }
+        None
+    }
+
+    fn send_or_hold(&self, event: MarketEvent) -> Option<MarketEvent> {
+        let mut held = self.held.lock().unwrap();
+        held.push_back(event);
+        while let Some(event) = held.pop_front() {
+            match self.inner.try_send(event) {
+                Ok(()) => self.delivered(),
+                Err(TrySendError::Full(event)) => {
+                    held.push_front(event);
+                    break;
+                }
+                Err(TrySendError::Closed(event)) => {
+                    held.clear();
+                    return Some(event);
+                }
+            }
+        }
+
+        let excess = held.len().saturating_sub(self.inner.max_capacity());
+        if excess > 0 {
+            held.drain(..excess);
+            self.overflowed(excess as u64);
+        }
+        if !held.is_empty() {
+            self.spawn_flush();
+        }
+        None
+    }
+
+    /// Deliver held events as room frees up, unless a task already is
+    ///
+    /// Without this, events held when the stream goes quiet would wait for
+    /// the next send. Events are taken and sent under the lock, so they
+    /// can't be reordered by a concurrent `send_or_hold`.
+    fn spawn_flush(&self) {
+        if self.flushing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let sender = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match sender.inner.reserve().await {
+                    Ok(permit) => {
+                        let mut held = sender.held.lock().unwrap();
+                        if let Some(event) = held.pop_front() {
+                            permit.send(event);
+                            sender.delivered();
+                            continue;
+                        }
+                    }
+                    Err(_) => sender.held.lock().unwrap().clear(),
+                }
+                sender.flushing.store(false, Ordering::Release);
+                // Something may have been held after the queue looked empty
+                if sender.held.lock().unwrap().is_empty()
+                    || sender.flushing.swap(true, Ordering::AcqRel)
+                {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn delivered(&self) {
+        self.metrics.sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn overflowed(&self, count: u64) {
+        self.metrics.overflowed.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Events currently queued in the channel
+    pub fn queue_depth(&self) -> usize {
+        self.inner.max_capacity() - self.inner.capacity()
+    }
+
+    /// Current counters and queue depth
+    pub fn stats(&self) -> ChannelStats {
+        stats_from(
+            &self.metrics,
+            &self.held,
+            self.queue_depth(),
+            self.inner.max_capacity(),
+        )
+    }
+
+    /// A handle to this sender's stats that doesn't keep the channel open
+    pub fn stats_handle(&self) -> ChannelStatsHandle {
+        ChannelStatsHandle {
+            inner: self.inner.downgrade(),
+            metrics: self.metrics.clone(),
+            held: self.held.clone(),
+            max_capacity: self.inner.max_capacity(),
+        }
+    }
+
+    /// Log when the queue crosses the near-full mark
+    fn check_depth(&self) {
+        let depth = self.queue_depth() as f64;
+        let near_full_mark = self.inner.max_capacity() as f64 * NEAR_FULL_RATIO;
+        if depth >= near_full_mark {
+            if !self.metrics.near_full.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "Event channel near full ({}/{} queued), consumer is falling behind",
+                    self.queue_depth(),
+                    self.inner.max_capacity()
+                );
+            }
+        } else if depth < near_full_mark / 2.0 {
+            self.metrics.near_full.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Read-only view of a [`MeteredSender`]'s counters
+///
+/// Holds only a weak reference to the channel, so the receiver still sees
+/// the channel close once every sender is dropped. The queue depth reads as
+/// zero from then on.
+#[derive(Debug, Clone)]
+pub struct ChannelStatsHandle {
+    inner: mpsc::WeakSender<MarketEvent>,
+    metrics: Arc<ChannelMetrics>,
+    held: Arc<Mutex<VecDeque<MarketEvent>>>,
+    max_capacity: usize,
+}
+
+impl ChannelStatsHandle {
+    /// Current counters and queue depth
+    pub fn stats(&self) -> ChannelStats {
+        let queue_depth = self
+            .inner
+            .upgrade()
+            .map(|inner| inner.max_capacity() - inner.capacity())
+            .unwrap_or(0);
+        stats_from(&self.metrics, &self.held, queue_depth, self.max_capacity)
+    }
+}
+
+fn stats_from(
+    metrics: &ChannelMetrics,
+    held: &Mutex<VecDeque<MarketEvent>>,
+    queue_depth: usize,
+    max_capacity: usize,
+) -> ChannelStats {
+    let sent = metrics.sent.load(Ordering::Relaxed);
+    let dropped = metrics.dropped.load(Ordering::Relaxed);
+    let attempts = sent + dropped;
+    let total_nanos = metrics.total_send_nanos.load(Ordering::Relaxed);
+    ChannelStats {
+        sent,
+        blocked: metrics.blocked.load(Ordering::Relaxed),
+        dropped,
+        overflowed: metrics.overflowed.load(Ordering::Relaxed),
+        queue_depth,
+        held: held.lock().unwrap().len(),
+        max_capacity,
+        avg_send_latency: Duration::from_nanos(total_nanos.checked_div(attempts).unwrap_or(0)),
+        max_send_latency: Duration::from_nanos(metrics.max_send_nanos.load(Ordering::Relaxed)),
+    }
+}
+
+/// Create a market event channel that every receiver sees in full
+///
+/// Further receivers come from [`broadcast::Sender::subscribe`] and only get
+/// events sent after they subscribed. Sending never blocks: the channel keeps
+/// the last `size` events, and a receiver that falls further behind gets
+/// [`BroadcastRecvError::Lagged`] with the number of events it missed, then
+/// resumes from the oldest event still held. Use [`recv_broadcast`] to skip
+/// over lag instead of handling it.
+pub fn create_broadcast_channel(
+    size: usize,
+) -> (
+    broadcast::Sender<MarketEvent>,
+    broadcast::Receiver<MarketEvent>,
+) {
+    broadcast::channel(size)
+}
+
+/// Forward every event from a client's `mpsc` channel into a broadcast channel
+///
+/// The task ends when `rx` closes. Events sent while there are no broadcast
+/// receivers are dropped.
+pub fn bridge_mpsc_to_broadcast(
+    mut rx: mpsc::Receiver<MarketEvent>,
+    tx: broadcast::Sender<MarketEvent>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if tx.send(event).is_err() {
+                debug!("No broadcast receivers, dropping event");
+            }
+        }
+        debug!("Event stream closed, stopping broadcast bridge");
+    })
+}
+
+/// Receive the next broadcast event, skipping over any lag
+///
+/// Missed events are logged. Returns `None` once the channel is closed.
+pub async fn recv_broadcast(rx: &mut broadcast::Receiver<MarketEvent>) -> Option<MarketEvent> {
+    loop {
+        match rx.recv().await {
+            Ok(event) => return Some(event),
+            Err(BroadcastRecvError::Lagged(missed)) => {
+                warn!("Broadcast receiver lagged, skipped {} events", missed)
+            }
+            Err(BroadcastRecvError::Closed) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Platform;
+
+    fn heartbeat(platform: Platform) -> MarketEvent {
+        MarketEvent::Heartbeat { platform }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_reaches_every_receiver() {
+        let (client_tx, client_rx) = create_event_channel();
+        let (tx, mut first) = create_broadcast_channel(16);
+        let mut second = tx.subscribe();
+        let bridge = bridge_mpsc_to_broadcast(client_rx, tx);
+
+        let events = vec![heartbeat(Platform::Polymarket), heartbeat(Platform::Kalshi)];
+        for event in &events {
+            client_tx.send(event.clone()).await.unwrap();
+        }
+        drop(client_tx);
+        bridge.await.unwrap();
+
+        for rx in [&mut first, &mut second] {
+            let mut received = Vec::new();
+            while let Some(event) = recv_broadcast(rx).await {
+                received.push(event);
+            }
+            assert_eq!(received, events);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metered_sender_tracks_depth() {
+        let (tx, mut rx) = create_event_channel_with_size(4);
+        let sender = MeteredSender::new(tx);
+
+        for _ in 0..4 {
+            sender.send(heartbeat(Platform::Polymarket)).await.unwrap();
+        }
+        let stats = sender.stats();
+        assert_eq!(stats.sent, 4);
+        assert_eq!(stats.queue_depth, 4);
+        assert_eq!(stats.max_capacity, 4);
+        assert_eq!(stats.blocked, 0);
+        assert!(stats.is_near_full());
+
+        // A fifth send has to wait until the consumer makes room
+        let blocked = tokio::spawn({
+            let sender = sender.clone();
+            async move { sender.send(heartbeat(Platform::Kalshi)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        rx.recv().await.unwrap();
+        blocked.await.unwrap().unwrap();
+
+        let stats = sender.stats();
+        assert_eq!(stats.sent, 5);
+        assert_eq!(stats.blocked, 1);
+        assert_eq!(stats.queue_depth, 4);
+        assert!(stats.max_send_latency >= Duration::from_millis(10));
+
+        while rx.try_recv().is_ok() {}
+        assert_eq!(sender.stats().queue_depth, 0);
+        drop(rx);
+        assert!(sender.send(heartbeat(Platform::Kalshi)).await.is_err());
+        assert_eq!(sender.stats().dropped, 1);
+    }
+
+    fn message(event: MarketEvent) -> String {
+        match event {
+            MarketEvent::Raw { message, .. } => message,
+            other => panic!("Expected raw event, got {:?}", other),
+        }
+    }
+
+    fn numbered(n: usize) -> MarketEvent {
+        MarketEvent::Raw {
+            platform: Platform::Polymarket,
+            message: n.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overflow_policies_on_full_channel() {
+        // Block waits for room, so every event arrives
+        let (tx, mut rx) = create_event_channel_with_size(2);
+        let sender = MeteredSender::new(tx).with_overflow_policy(OverflowPolicy::Block);
+        for n in 0..2 {
+            sender.send(numbered(n)).await.unwrap();
+        }
+        let pending = tokio::spawn({
+            let sender = sender.clone();
+            async move { sender.send(numbered(2)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!pending.is_finished());
+        let received: Vec<String> = [
+            rx.recv().await.unwrap(),
+            rx.recv().await.unwrap(),
+            rx.recv().await.unwrap(),
+        ]
+        .into_iter()
+        .map(message)
+        .collect();
+        pending.await.unwrap().unwrap();
+        assert_eq!(received, vec!["0", "1", "2"]);
+        assert_eq!(sender.stats().blocked, 1);
+
+        // DropNewest never waits and discards what doesn't fit
+        let (tx, mut rx) = create_event_channel_with_size(2);
+        let sender = MeteredSender::new(tx).with_overflow_policy(OverflowPolicy::DropNewest);
+        for n in 0..5 {
+            sender.send(numbered(n)).await.unwrap();
+        }
+        let stats = sender.stats();
+        assert_eq!((stats.sent, stats.overflowed, stats.blocked), (2, 3, 0));
+        assert_eq!(message(rx.recv().await.unwrap()), "0");
+        assert_eq!(message(rx.recv().await.unwrap()), "1");
+
+        // DropOldest holds up to a channel's worth and discards the oldest held
+        let (tx, mut rx) = create_event_channel_with_size(2);
+        let sender = MeteredSender::new(tx).with_overflow_policy(OverflowPolicy::DropOldest);
+        for n in 0..7 {
+            sender.send(numbered(n)).await.unwrap();
+        }
+        let stats = sender.stats();
+        assert_eq!((stats.sent, stats.held, stats.overflowed), (2, 2, 3));
+        assert_eq!(message(rx.recv().await.unwrap()), "0");
+        assert_eq!(message(rx.recv().await.unwrap()), "1");
+
+        // Held events go out ahead of the next one
+        sender.send(numbered(7)).await.unwrap();
+        let received: Vec<String> = (0..2).map(|_| message(rx.try_recv().unwrap())).collect();
+        assert_eq!(received, vec!["5", "6"]);
+        assert_eq!(sender.stats().held, 1);
+    }
+
+    #[tokio::test]
+    async fn test_held_events_delivered_without_further_sends() {
+        let (tx, mut rx) = create_event_channel_with_size(2);
+        let sender = MeteredSender::new(tx).with_overflow_policy(OverflowPolicy::DropOldest);
+        for n in 0..4 {
+            sender.send(numbered(n)).await.unwrap();
+        }
+        assert_eq!(sender.stats().held, 2);
+
+        // The burst ends here; the held tail still arrives as room frees up
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let event = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+                .await
+                .expect("held event never delivered")
+                .unwrap();
+            received.push(message(event));
+        }
+        assert_eq!(received, vec!["0", "1", "2", "3"]);
+        let stats = sender.stats();
+        assert_eq!((stats.sent, stats.held, stats.overflowed), (4, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_lagged_receiver_skips_ahead() {
+        let (tx, mut rx) = create_broadcast_channel(2);
+        for _ in 0..3 {
+            tx.send(heartbeat(Platform::Polymarket)).unwrap();
+        }
+        tx.send(heartbeat(Platform::Kalshi)).unwrap();
+        drop(tx);
+
+        // The oldest two events were overwritten
+        assert_eq!(
+            recv_broadcast(&mut rx).await,
+            Some(heartbeat(Platform::Polymarket))
+        );
+        assert_eq!(
+            recv_broadcast(&mut rx).await,
+            Some(heartbeat(Platform::Kalshi))
+        );
+        assert_eq!(recv_broadcast(&mut rx).await, None);
+    }
+}
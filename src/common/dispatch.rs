@@ -0,0 +1,104 @@
+//! Callback-style consumption of the market event channel
+
+use tokio::sync::mpsc;
+
+use super::traits::EventHandler;
+use super::types::{ConnectionStatus, MarketEvent};
+
+/// Route every event from `rx` to the matching [`EventHandler`] callback
+///
+/// `Connected` statuses call [`on_connect`](EventHandler::on_connect) and
+/// `Disconnected` statuses call
+/// [`on_disconnect`](EventHandler::on_disconnect) with the reason. All other
+/// events, including `Reconnecting` and `Error` statuses, go to
+/// [`handle_event`](EventHandler::handle_event).
+///
+/// Returns once the channel closes.
+pub async fn dispatch_events(mut rx: mpsc::Receiver<MarketEvent>, handler: &mut dyn EventHandler) {
+    while let Some(event) = rx.recv().await {
+        match &event {
+            MarketEvent::ConnectionStatus {
+                status: ConnectionStatus::Connected,
+                ..
+            } => handler.on_connect(),
+            MarketEvent::ConnectionStatus {
+                status: ConnectionStatus::Disconnected(reason),
+                ..
+            } => handler.on_disconnect(reason.as_deref()),
+            _ => handler.handle_event(&event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Platform;
+
+    /// Handler that records which callbacks fired
+    #[derive(Default)]
+    struct RecordingHandler {
+        calls: Vec<String>,
+    }
+
+    impl EventHandler for RecordingHandler {
+        fn handle_event(&mut self, event: &MarketEvent) {
+            let call = match event {
+                MarketEvent::Heartbeat { .. } => "event:heartbeat".to_string(),
+                MarketEvent::ConnectionStatus { status, .. } => format!("event:{:?}", status),
+                other => format!("event:{:?}", other),
+            };
+            self.calls.push(call);
+        }
+
+        fn on_connect(&mut self) {
+            self.calls.push("connect".to_string());
+        }
+
+        fn on_disconnect(&mut self, reason: Option<&str>) {
+            self.calls
+                .push(format!("disconnect:{}", reason.unwrap_or("-")));
+        }
+    }
+
+    fn status(status: ConnectionStatus) -> MarketEvent {
+        MarketEvent::ConnectionStatus {
+            platform: Platform::Polymarket,
+            status,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_callbacks_follow_event_sequence() {
+        let (tx, rx) = mpsc::channel(8);
+        for event in [
+            status(ConnectionStatus::Connected),
+            MarketEvent::Heartbeat {
+                platform: Platform::Polymarket,
+            },
+            status(ConnectionStatus::Disconnected(Some(
+                "stale connection".to_string(),
+            ))),
+            status(ConnectionStatus::Reconnecting { attempt: 1 }),
+            status(ConnectionStatus::Connected),
+            status(ConnectionStatus::Disconnected(None)),
+        ] {
+            tx.send(event).await.unwrap();
+        }
+        drop(tx);
+
+        let mut handler = RecordingHandler::default();
+        dispatch_events(rx, &mut handler).await;
+        assert_eq!(
+            handler.calls,
+            vec![
+                "connect",
+                "event:heartbeat",
+                "disconnect:stale connection",
+                "event:Reconnecting { attempt: 1 }",
+                "connect",
+                "disconnect:-",
+            ]
+        );
+    }
+}
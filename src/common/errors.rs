@@ -18,7 +18,7 @@ pub enum ClientError {
 
     /// HTTP request errors
     #[error("HTTP request error: {0}")]
-    HttpRequest(#[from] reqwest::Error),
+    HttpRequest(#[source] reqwest::Error),
 
     /// JSON serialization/deserialization errors
     #[error("JSON parsing error: {0}")]
@@ -47,6 +47,14 @@ pub enum ClientError {
     #[error("Market not found: {0}")]
     MarketNotFound(String),
 
+    /// Order not found
+    #[error("Order not found: {0}")]
+    OrderNotFound(String),
+
+    /// Order that the exchange would reject (size or price out of bounds)
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+
     /// Timeout errors
     #[error("Operation timed out: {0}")]
     Timeout(String),
@@ -64,6 +72,33 @@ pub enum ClientError {
     Internal(String),
 }
 
+impl ClientError {
+    /// Whether the failed operation may succeed if retried
+    ///
+    /// Covers rate limits, timeouts (including requests stalled waiting on a
+    /// saturated connection pool), and transient connection failures.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::RateLimit { .. }
+            | ClientError::Timeout(_)
+            | ClientError::WebSocketConnection(_) => true,
+            ClientError::HttpRequest(err) => err.is_connect() || err.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        // Timeouts are surfaced explicitly so callers can back off and retry
+        if err.is_timeout() {
+            ClientError::Timeout(err.to_string())
+        } else {
+            ClientError::HttpRequest(err)
+        }
+    }
+}
+
 impl From<tokio_tungstenite::tungstenite::Error> for ClientError {
     fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
         ClientError::WebSocketCommunication(err.to_string())
@@ -0,0 +1,173 @@
+//! Latency recording for production code paths
+//!
+//! [`SpeedTest`](super::speedtest::SpeedTest) times code under test; this
+//! module times live work such as parsing messages and applying book
+//! updates. Timings go into a [`LatencyRecorder`], which summarizes them
+//! with the same statistics as a benchmark.
+//!
+//! The hooks take an `Option<&LatencyRecorder>`; with `None` they only run
+//! the wrapped code, without reading the clock.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::speedtest::{BenchmarkOptions, BenchmarkStats, SpeedTest};
+
+/// Timings kept by [`LatencyRecorder::default`]
+pub const DEFAULT_LATENCY_WINDOW: usize = 1024;
+
+/// Accumulates timings of a repeated operation
+///
+/// Only the most recent `window` timings are kept for statistics; the
+/// count covers every timing recorded.
+#[derive(Debug)]
+pub struct LatencyRecorder {
+    samples: Mutex<VecDeque<Duration>>,
+    window: usize,
+    count: AtomicU64,
+}
+
+/// Point-in-time summary of a [`LatencyRecorder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Timings recorded in total
+    pub samples: u64,
+    /// Average of the retained timings
+    pub average: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_LATENCY_WINDOW)
+    }
+}
+
+impl LatencyRecorder {
+    /// Keep the last `window` timings (at least one)
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            samples: Mutex::new(VecDeque::with_capacity(window)),
+            window,
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Add a timing, evicting the oldest once the window is full
+    pub fn record(&self, elapsed: Duration) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == self.window {
+            samples.pop_front();
+        }
+        samples.push_back(elapsed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Timings recorded in total
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Benchmark statistics over the retained timings, None if empty
+    pub fn stats(&self, name: &str) -> Option<BenchmarkStats> {
+        let samples: Vec<Duration> = self.samples.lock().unwrap().iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        let total = samples.iter().sum();
+        Some(SpeedTest::calculate_stats(
+            name,
+            samples,
+            total,
+            &BenchmarkOptions::default(),
+        ))
+    }
+
+    /// Summary of the retained timings, None if empty
+    pub fn summary(&self) -> Option<LatencySummary> {
+        let stats = self.stats("")?;
+        Some(LatencySummary {
+            samples: self.count(),
+            average: stats.average,
+            median: stats.median,
+            p95: stats.p95,
+            p99: stats.p99,
+            max: stats.max,
+        })
+    }
+}
+
+/// Run `parse`, recording how long it took if a recorder is given
+pub fn timed_parse<T>(recorder: Option<&LatencyRecorder>, parse: impl FnOnce() -> T) -> T {
+    let Some(recorder) = recorder else {
+        return parse();
+    };
+    let start = Instant::now();
+    let parsed = parse();
+    recorder.record(start.elapsed());
+    parsed
+}
+
+/// Await `apply`, recording how long it took if a recorder is given
+pub async fn timed_apply<T>(
+    recorder: Option<&LatencyRecorder>,
+    apply: impl Future<Output = T>,
+) -> T {
+    let Some(recorder) = recorder else {
+        return apply.await;
+    };
+    let start = Instant::now();
+    let applied = apply.await;
+    recorder.record(start.elapsed());
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_keeps_latest_timings() {
+        let recorder = LatencyRecorder::new(3);
+        assert!(recorder.summary().is_none());
+
+        for millis in [100, 1, 2, 3] {
+            recorder.record(Duration::from_millis(millis));
+        }
+        let summary = recorder.summary().unwrap();
+        assert_eq!(summary.samples, 4);
+        // The 100ms timing has been evicted
+        assert_eq!(summary.max, Duration::from_millis(3));
+        assert_eq!(summary.average, Duration::from_millis(2));
+
+        let stats = recorder.stats("parse").unwrap();
+        assert_eq!(stats.name, "parse");
+        assert_eq!(stats.iterations, 3);
+    }
+
+    #[tokio::test]
+    async fn test_hooks_record_only_with_a_recorder() {
+        let recorder = LatencyRecorder::default();
+
+        assert_eq!(timed_parse(None, || 1), 1);
+        assert_eq!(timed_apply(None, async { 2 }).await, 2);
+        assert_eq!(recorder.count(), 0);
+
+        assert_eq!(timed_parse(Some(&recorder), || 1), 1);
+        let applied = timed_apply(Some(&recorder), async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            2
+        })
+        .await;
+        assert_eq!(applied, 2);
+        assert_eq!(recorder.count(), 2);
+        assert!(recorder.summary().unwrap().max >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,148 @@
+//! Price arithmetic for binary prediction markets
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use super::types::Side;
+
+/// Snap `price` to the tick grid without overpaying or underselling
+///
+/// Buys round down and sells round up, so the order never trades at a worse
+/// price than the strategy asked for. The result is kept within
+/// `[tick, 1 - tick]`, the range binary contracts can be quoted in. A zero
+/// tick means any price is accepted and returns `price` unchanged, as does a
+/// tick of 0.5 or more, which leaves no range to quote in.
+pub fn round_to_tick(price: Decimal, tick: Decimal, side: Side) -> Decimal {
+    let strategy = match side {
+        Side::Buy => RoundingStrategy::ToNegativeInfinity,
+        Side::Sell => RoundingStrategy::ToPositiveInfinity,
+    };
+    snap(price, tick, strategy)
+}
+
+/// Snap `price` to the closest tick, halves rounding away from zero
+///
+/// Clamped to `[tick, 1 - tick]` like [`round_to_tick`].
+pub fn nearest_tick(price: Decimal, tick: Decimal) -> Decimal {
+    snap(price, tick, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Price of the NO side of a binary contract whose YES trades at `yes`
+pub fn no_price(yes: Decimal) -> Decimal {
+    Decimal::ONE - yes
+}
+
+/// Odds in favour implied by a contract price, `price / (1 - price)`
+///
+/// A price of 0.75 implies 3 to 1. Returns `None` at a price of 1 or more,
+/// where the odds are unbounded.
+pub fn implied_odds(price: Decimal) -> Option<Decimal> {
+    let against = no_price(price);
+    if against <= Decimal::ZERO {
+        return None;
+    }
+    Some(price / against)
+}
+
+/// Gross edge of buying YES at `yes_a` and NO against `yes_b` elsewhere
+///
+/// The pair pays out 1 whichever way the market resolves, so the edge is
+/// `1 - yes_a - no_price(yes_b)`, before fees. Positive means the pair costs
+/// less than it pays.
+pub fn arb_edge(yes_a: Decimal, yes_b: Decimal) -> Decimal {
+    Decimal::ONE - yes_a - no_price(yes_b)
+}
+
+fn snap(price: Decimal, tick: Decimal, strategy: RoundingStrategy) -> Decimal {
+    // Ticks come from exchange data; ones outside (0, 0.5) can't be snapped to
+    if tick <= Decimal::ZERO || tick >= Decimal::new(5, 1) {
+        return price;
+    }
+    let ticks = (price / tick).round_dp_with_strategy(0, strategy);
+    (ticks * tick).clamp(tick, Decimal::ONE - tick).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_round_to_tick_by_side() {
+        for (price, tick, buy, sell) in [
+            (dec!(0.523), dec!(0.01), dec!(0.52), dec!(0.53)),
+            (dec!(0.52), dec!(0.01), dec!(0.52), dec!(0.52)),
+            (dec!(0.5234), dec!(0.001), dec!(0.523), dec!(0.524)),
+            (dec!(0.37), dec!(0.05), dec!(0.35), dec!(0.40)),
+            (dec!(0.4444), dec!(0.0001), dec!(0.4444), dec!(0.4444)),
+        ] {
+            assert_eq!(round_to_tick(price, tick, Side::Buy), buy);
+            assert_eq!(round_to_tick(price, tick, Side::Sell), sell);
+        }
+    }
+
+    #[test]
+    fn test_round_to_tick_edges() {
+        let tick = dec!(0.01);
+        // Rounding never leaves the quotable range
+        assert_eq!(round_to_tick(dec!(0.999), tick, Side::Buy), dec!(0.99));
+        assert_eq!(round_to_tick(dec!(0.999), tick, Side::Sell), dec!(0.99));
+        assert_eq!(round_to_tick(dec!(0.001), tick, Side::Buy), dec!(0.01));
+        assert_eq!(round_to_tick(dec!(0.001), tick, Side::Sell), dec!(0.01));
+        assert_eq!(
+            round_to_tick(dec!(0.999), dec!(0.001), Side::Sell),
+            dec!(0.999)
+        );
+
+        // No tick leaves the price alone
+        assert_eq!(
+            round_to_tick(dec!(0.1234), dec!(0), Side::Buy),
+            dec!(0.1234)
+        );
+
+        // Neither does a tick too coarse to leave a quotable range
+        for tick in [dec!(0.5), dec!(0.6), dec!(1), dec!(2)] {
+            assert_eq!(round_to_tick(dec!(0.37), tick, Side::Buy), dec!(0.37));
+            assert_eq!(round_to_tick(dec!(0.37), tick, Side::Sell), dec!(0.37));
+            assert_eq!(nearest_tick(dec!(0.37), tick), dec!(0.37));
+        }
+    }
+
+    #[test]
+    fn test_nearest_tick() {
+        assert_eq!(nearest_tick(dec!(0.524), dec!(0.01)), dec!(0.52));
+        assert_eq!(nearest_tick(dec!(0.525), dec!(0.01)), dec!(0.53));
+        assert_eq!(nearest_tick(dec!(0.526), dec!(0.01)), dec!(0.53));
+        assert_eq!(nearest_tick(dec!(0.37), dec!(0.05)), dec!(0.35));
+        assert_eq!(nearest_tick(dec!(0.38), dec!(0.05)), dec!(0.40));
+        assert_eq!(nearest_tick(dec!(0.999), dec!(0.01)), dec!(0.99));
+        assert_eq!(nearest_tick(dec!(0.001), dec!(0.01)), dec!(0.01));
+    }
+
+    #[test]
+    fn test_no_price() {
+        assert_eq!(no_price(dec!(0.35)), dec!(0.65));
+        assert_eq!(no_price(dec!(0)), dec!(1));
+        assert_eq!(no_price(dec!(1)), dec!(0));
+        assert_eq!(no_price(no_price(dec!(0.123))), dec!(0.123));
+    }
+
+    #[test]
+    fn test_implied_odds() {
+        assert_eq!(implied_odds(dec!(0.75)), Some(dec!(3)));
+        assert_eq!(implied_odds(dec!(0.5)), Some(dec!(1)));
+        assert_eq!(implied_odds(dec!(0.2)), Some(dec!(0.25)));
+        assert_eq!(implied_odds(dec!(0)), Some(dec!(0)));
+        assert_eq!(implied_odds(dec!(0.99)), Some(dec!(99)));
+        assert_eq!(implied_odds(dec!(1)), None);
+        assert_eq!(implied_odds(dec!(1.01)), None);
+    }
+
+    #[test]
+    fn test_arb_edge() {
+        // YES at 0.40 here and 0.45 there: pay 0.40 + 0.55 for a sure 1
+        assert_eq!(arb_edge(dec!(0.40), dec!(0.45)), dec!(0.05));
+        assert_eq!(arb_edge(dec!(0.45), dec!(0.45)), dec!(0));
+        assert_eq!(arb_edge(dec!(0.50), dec!(0.45)), dec!(-0.05));
+        assert_eq!(arb_edge(dec!(0), dec!(1)), dec!(1));
+    }
+}
@@ -0,0 +1,259 @@
+//! Scripted market client for tests and dry runs
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::errors::{ClientError, Result};
+use super::traits::MarketClient;
+use super::types::MarketEvent;
+
+/// A [`MarketClient`] that replays a fixed list of events
+///
+/// `start` spawns a task that sends the script, in order, to the given
+/// sender, optionally waiting between events. The client reports connected
+/// from `connect` until the script finishes, the receiver is dropped or
+/// `disconnect` is called. In looping mode the script repeats until one of
+/// the latter two happens, which is useful for stress-testing consumers.
+/// Connects can be scripted to fail a number of times before succeeding.
+pub struct MockMarketClient {
+    events: Vec<MarketEvent>,
+    delay: Duration,
+    looping: bool,
+    /// Connects that fail before one succeeds
+    failed_connects: u32,
+    connect_attempts: u32,
+    platform_name: &'static str,
+    connected: Arc<AtomicBool>,
+    subscriptions: Vec<String>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl MockMarketClient {
+    pub fn new(events: Vec<MarketEvent>) -> Self {
+        Self {
+            events,
+            delay: Duration::ZERO,
+            looping: false,
+            failed_connects: 0,
+            connect_attempts: 0,
+            platform_name: "mock",
+            connected: Arc::new(AtomicBool::new(false)),
+            subscriptions: Vec::new(),
+            task: None,
+        }
+    }
+
+    /// Wait `delay` before sending each event
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Repeat the script until disconnected
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Fail the first `failures` calls to `connect`
+    pub fn with_failed_connects(mut self, failures: u32) -> Self {
+        self.failed_connects = failures;
+        self
+    }
+
+    /// Name reported by `platform_name`, "mock" by default
+    pub fn with_platform_name(mut self, name: &'static str) -> Self {
+        self.platform_name = name;
+        self
+    }
+
+    /// Calls to `connect` so far, failed or not
+    pub fn connect_attempts(&self) -> u32 {
+        self.connect_attempts
+    }
+
+    /// Asset IDs currently subscribed to
+    pub fn subscriptions(&self) -> &[String] {
+        &self.subscriptions
+    }
+}
+
+#[async_trait]
+impl MarketClient for MockMarketClient {
+    async fn connect(&mut self) -> Result<()> {
+        self.connect_attempts += 1;
+        if self.connect_attempts <= self.failed_connects {
+            return Err(ClientError::WebSocketConnection(
+                "Mock connect refused".to_string(),
+            ));
+        }
+        self.connected.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn subscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        for id in asset_ids {
+            if !self.subscriptions.contains(id) {
+                self.subscriptions.push(id.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        self.subscriptions.retain(|id| !asset_ids.contains(id));
+        Ok(())
+    }
+
+    async fn start(&mut self, sender: mpsc::Sender<MarketEvent>) -> Result<()> {
+        if !self.is_connected() {
+            return Err(ClientError::WebSocketConnection(
+                "Mock client is not connected".to_string(),
+            ));
+        }
+
+        let events = self.events.clone();
+        let delay = self.delay;
+        // An empty script would spin forever
+        let looping = self.looping && !events.is_empty();
+        let connected = self.connected.clone();
+        self.task = Some(tokio::spawn(async move {
+            'replay: loop {
+                for event in &events {
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    if sender.send(event.clone()).await.is_err() {
+                        break 'replay;
+                    }
+                }
+                if !looping {
+                    break;
+                }
+            }
+            connected.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.connected.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    fn platform_name(&self) -> &'static str {
+        self.platform_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{
+        OrderBookUpdate, Platform, PriceLevel, Side, Trade, TradeSideSource,
+    };
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn script() -> Vec<MarketEvent> {
+        vec![
+            MarketEvent::OrderBookUpdate(OrderBookUpdate {
+                platform: Platform::Polymarket,
+                market_id: "condition".to_string(),
+                asset_id: "token".to_string(),
+                bids: vec![PriceLevel::new(dec!(0.48), dec!(100))],
+                asks: vec![PriceLevel::new(dec!(0.52), dec!(100))],
+                timestamp: Utc::now(),
+                received_at: Utc::now(),
+                is_snapshot: true,
+                sequence: 0,
+                hash: None,
+            }),
+            MarketEvent::Trade(Trade {
+                platform: Platform::Polymarket,
+                market_id: "condition".to_string(),
+                asset_id: "token".to_string(),
+                trade_id: "t1".to_string(),
+                price: dec!(0.52),
+                size: dec!(10),
+                side: Side::Buy,
+                side_source: TradeSideSource::Reported,
+                timestamp: Utc::now(),
+                received_at: Utc::now(),
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_replays_script_in_order() {
+        let mut client = MockMarketClient::new(script()).with_delay(Duration::from_millis(5));
+        let (tx, mut rx) = mpsc::channel(8);
+        assert!(client.start(tx.clone()).await.is_err());
+
+        client.connect().await.unwrap();
+        client.subscribe(&["token".to_string()]).await.unwrap();
+        client.start(tx).await.unwrap();
+        assert!(client.is_connected());
+        assert_eq!(client.subscriptions(), ["token".to_string()]);
+
+        assert!(matches!(
+            rx.recv().await,
+            Some(MarketEvent::OrderBookUpdate(update)) if update.is_snapshot
+        ));
+        assert!(matches!(
+            rx.recv().await,
+            Some(MarketEvent::Trade(trade)) if trade.trade_id == "t1"
+        ));
+
+        // The sender is dropped once the script is done
+        assert!(rx.recv().await.is_none());
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_looping_until_disconnect() {
+        let mut client = MockMarketClient::new(script()).with_looping(true);
+        let (tx, mut rx) = mpsc::channel(8);
+        client.connect().await.unwrap();
+        client.start(tx).await.unwrap();
+
+        for i in 0..10 {
+            let event = rx.recv().await.unwrap();
+            if i % 2 == 0 {
+                assert!(matches!(event, MarketEvent::OrderBookUpdate(_)));
+            } else {
+                assert!(matches!(event, MarketEvent::Trade(_)));
+            }
+        }
+
+        client.disconnect().await.unwrap();
+        assert!(!client.is_connected());
+        while rx.recv().await.is_some() {}
+    }
+
+    #[tokio::test]
+    async fn test_scripted_connect_failures() {
+        let mut client = MockMarketClient::new(script()).with_failed_connects(2);
+        for _ in 0..2 {
+            assert!(matches!(
+                client.connect().await,
+                Err(ClientError::WebSocketConnection(_))
+            ));
+            assert!(!client.is_connected());
+        }
+        client.connect().await.unwrap();
+        assert!(client.is_connected());
+        assert_eq!(client.connect_attempts(), 3);
+    }
+}
@@ -1,7 +1,21 @@
 //! Common module - Shared types and utilities
 
+pub mod aggregates;
+pub mod aggregator;
+pub mod book_store;
 pub mod channels;
+pub mod dispatch;
 pub mod errors;
+pub mod instrument;
+pub mod math;
+pub mod mock;
+pub mod orderbook;
+pub mod ratelimit;
+pub mod recorder;
+pub mod resilience;
+pub mod sequence;
 pub mod speedtest;
+pub mod state;
+pub mod supervisor;
 pub mod traits;
 pub mod types;
@@ -0,0 +1,436 @@
+//! Order book maintenance from snapshot and delta events
+
+use std::collections::HashMap;
+use tracing::{debug, warn};
+
+use super::types::{
+    ConnectionStatus, MarketEvent, OrderBook, OrderBookUpdate, Trade, TradeSideSource,
+};
+
+/// A maintained book plus its sync state
+#[derive(Debug, Clone)]
+struct ManagedBook {
+    book: OrderBook,
+    /// Set when the book can no longer be trusted (e.g. after a reconnect)
+    needs_resync: bool,
+}
+
+/// Maintains local order books per asset
+///
+/// Books are created from snapshots and kept current by applying deltas.
+/// After [`mark_all_for_resync`](Self::mark_all_for_resync) every book
+/// rejects deltas until a fresh snapshot for it arrives.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBookManager {
+    books: HashMap<String, ManagedBook>,
+    /// Flag books for resync when they don't match the exchange checksum
+    verify_checksums: bool,
+}
+
+impl OrderBookManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check books against the exchange checksum after every change
+    ///
+    /// A book that doesn't match is flagged for resync, so it shows up in
+    /// [`assets_needing_resync`](Self::assets_needing_resync) and rejects
+    /// deltas until a fresh snapshot arrives.
+    pub fn with_checksum_validation(mut self, enabled: bool) -> Self {
+        self.verify_checksums = enabled;
+        self
+    }
+
+    /// Get the current book for an asset
+    pub fn get(&self, asset_id: &str) -> Option<&OrderBook> {
+        self.books.get(asset_id).map(|managed| &managed.book)
+    }
+
+    /// Replace the book for an asset with a full snapshot
+    ///
+    /// When the snapshot resyncs a book that had drifted, how far it had
+    /// drifted is logged at debug level.
+    pub fn apply_snapshot(&mut self, book: OrderBook) {
+        if let Some(old) = self.books.get(&book.asset_id) {
+            if old.needs_resync {
+                let drift = old.book.diff(&book);
+                if !drift.is_empty() {
+                    debug!(
+                        "Resynced {}, local book had drifted: {:?}",
+                        book.asset_id, drift
+                    );
+                }
+            }
+        }
+        let needs_resync = !checksum_matches(self.verify_checksums, &book);
+        self.books
+            .insert(book.asset_id.clone(), ManagedBook { book, needs_resync });
+    }
+
+    /// Apply a snapshot or delta update
+    ///
+    /// Returns false if a delta was rejected because the book is unknown
+    /// or waiting for a resync. A delta that leaves the book out of line
+    /// with the exchange checksum is applied but flags the book for resync.
+    pub fn apply_update(&mut self, update: &OrderBookUpdate) -> bool {
+        if update.is_snapshot {
+            self.apply_snapshot(OrderBook::from(update));
+            return true;
+        }
+
+        let verify_checksums = self.verify_checksums;
+        match self.books.get_mut(&update.asset_id) {
+            Some(managed) if !managed.needs_resync => {
+                managed.book.apply_update(update);
+                managed.needs_resync = !checksum_matches(verify_checksums, &managed.book);
+                true
+            }
+            Some(_) => {
+                debug!("Rejecting delta for {}: awaiting resync", update.asset_id);
+                false
+            }
+            None => {
+                debug!("Rejecting delta for {}: no snapshot yet", update.asset_id);
+                false
+            }
+        }
+    }
+
+    /// Apply any book-related event, returning true if a book changed
+    ///
+    /// A `Reconnecting` status flags every book for resync and a
+    /// `ResyncNeeded` event flags the book it names.
+    pub fn apply_event(&mut self, event: &MarketEvent) -> bool {
+        match event {
+            MarketEvent::OrderBook(book) => {
+                self.apply_snapshot(book.clone());
+                true
+            }
+            MarketEvent::OrderBookUpdate(update) => self.apply_update(update),
+            MarketEvent::ConnectionStatus {
+                status: ConnectionStatus::Reconnecting { .. },
+                ..
+            } => {
+                self.mark_all_for_resync();
+                false
+            }
+            MarketEvent::ResyncNeeded { asset_id, .. } => {
+                self.mark_for_resync(asset_id);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Fill in the side of a trade the exchange didn't label
+    ///
+    /// Only trades tagged [`TradeSideSource::Unknown`] are touched. The side
+    /// is inferred from the trade price against the current book for its
+    /// asset; books awaiting a resync aren't trusted. Returns true if the
+    /// side was inferred.
+    pub fn infer_trade_side(&self, trade: &mut Trade) -> bool {
+        if trade.side_source != TradeSideSource::Unknown {
+            return false;
+        }
+        let inferred = self
+            .books
+            .get(&trade.asset_id)
+            .filter(|managed| !managed.needs_resync)
+            .and_then(|managed| managed.book.infer_aggressor_side(trade.price));
+
+        match inferred {
+            Some(side) => {
+                trade.side = side;
+                trade.side_source = TradeSideSource::Inferred;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Flag every maintained book as stale
+    pub fn mark_all_for_resync(&mut self) {
+        for managed in self.books.values_mut() {
+            managed.needs_resync = true;
+        }
+    }
+
+    /// Flag the book for an asset as stale, if there is one
+    pub fn mark_for_resync(&mut self, asset_id: &str) {
+        if let Some(managed) = self.books.get_mut(asset_id) {
+            managed.needs_resync = true;
+        }
+    }
+
+    /// Whether the book for an asset is waiting for a fresh snapshot
+    pub fn needs_resync(&self, asset_id: &str) -> bool {
+        self.books
+            .get(asset_id)
+            .map(|managed| managed.needs_resync)
+            .unwrap_or(false)
+    }
+
+    /// Assets whose books are waiting for a fresh snapshot
+    pub fn assets_needing_resync(&self) -> Vec<String> {
+        self.books
+            .iter()
+            .filter(|(_, managed)| managed.needs_resync)
+            .map(|(asset_id, _)| asset_id.clone())
+            .collect()
+    }
+
+    /// Number of assets with a book
+    pub fn len(&self) -> usize {
+        self.books.len()
+    }
+
+    /// Whether no books are maintained
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+}
+
+/// Whether `book` matches its exchange checksum
+///
+/// Always true when validation is off or the exchange sent no hash.
+fn checksum_matches(verify: bool, book: &OrderBook) -> bool {
+    match &book.hash {
+        Some(hash) if verify && !book.verify_checksum(hash) => {
+            warn!(
+                "Checksum mismatch for {}, flagging for resync",
+                book.asset_id
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{Platform, PriceLevel, Side};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn update(asset_id: &str, bid: PriceLevel, is_snapshot: bool) -> OrderBookUpdate {
+        OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "market".to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![bid],
+            asks: vec![PriceLevel::new(dec!(0.60), dec!(10))],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            is_snapshot,
+            sequence: 0,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_delta_requires_snapshot() {
+        let mut manager = OrderBookManager::new();
+        let delta = update("a", PriceLevel::new(dec!(0.40), dec!(5)), false);
+
+        assert!(!manager.apply_update(&delta));
+        assert!(manager.get("a").is_none());
+
+        assert!(manager.apply_update(&update("a", PriceLevel::new(dec!(0.50), dec!(5)), true)));
+        assert!(manager.apply_update(&delta));
+        assert_eq!(
+            manager.get("a").unwrap().best_bid().unwrap().price,
+            dec!(0.50)
+        );
+        assert_eq!(manager.get("a").unwrap().bids.len(), 2);
+    }
+
+    #[test]
+    fn test_resync_rejects_deltas_until_snapshot() {
+        let mut manager = OrderBookManager::new();
+        for asset_id in ["a", "b"] {
+            manager.apply_update(&update(
+                asset_id,
+                PriceLevel::new(dec!(0.50), dec!(5)),
+                true,
+            ));
+        }
+
+        manager.apply_event(&MarketEvent::ConnectionStatus {
+            platform: Platform::Polymarket,
+            status: ConnectionStatus::Reconnecting { attempt: 1 },
+        });
+
+        let mut stale = manager.assets_needing_resync();
+        stale.sort();
+        assert_eq!(stale, vec!["a".to_string(), "b".to_string()]);
+
+        let delta = update("a", PriceLevel::new(dec!(0.55), dec!(5)), false);
+        assert!(!manager.apply_update(&delta));
+        assert_eq!(
+            manager.get("a").unwrap().best_bid().unwrap().price,
+            dec!(0.50)
+        );
+
+        manager.apply_update(&update("a", PriceLevel::new(dec!(0.51), dec!(5)), true));
+        assert!(!manager.needs_resync("a"));
+        assert!(manager.needs_resync("b"));
+        assert!(manager.apply_update(&delta));
+        assert_eq!(
+            manager.get("a").unwrap().best_bid().unwrap().price,
+            dec!(0.55)
+        );
+    }
+
+    #[test]
+    fn test_resync_needed_flags_one_book() {
+        let mut manager = OrderBookManager::new();
+        for asset_id in ["a", "b"] {
+            manager.apply_update(&update(
+                asset_id,
+                PriceLevel::new(dec!(0.50), dec!(5)),
+                true,
+            ));
+        }
+
+        manager.apply_event(&MarketEvent::ResyncNeeded {
+            platform: Platform::Polymarket,
+            asset_id: "a".to_string(),
+        });
+        assert_eq!(manager.assets_needing_resync(), vec!["a".to_string()]);
+        let delta = update("a", PriceLevel::new(dec!(0.55), dec!(5)), false);
+        assert!(!manager.apply_update(&delta));
+    }
+
+    fn unlabeled_trade(asset_id: &str, price: rust_decimal::Decimal) -> Trade {
+        Trade {
+            platform: Platform::Polymarket,
+            market_id: "market".to_string(),
+            asset_id: asset_id.to_string(),
+            trade_id: "trade".to_string(),
+            price,
+            size: dec!(10),
+            side: Side::Sell,
+            side_source: TradeSideSource::Unknown,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_checksum_mismatch_flags_resync() {
+        let mut manager = OrderBookManager::new().with_checksum_validation(true);
+
+        let mut snapshot = update("a", PriceLevel::new(dec!(0.40), dec!(5)), true);
+        snapshot.hash = Some(OrderBook::from(&snapshot).checksum());
+        assert!(manager.apply_update(&snapshot));
+        assert!(!manager.needs_resync("a"));
+
+        // Delta carrying the checksum of the book it produces
+        let mut delta = update("a", PriceLevel::new(dec!(0.41), dec!(3)), false);
+        let mut expected = manager.get("a").unwrap().clone();
+        expected.apply_update(&delta);
+        delta.hash = Some(expected.checksum());
+        assert!(manager.apply_update(&delta));
+        assert!(!manager.needs_resync("a"));
+
+        // A delta we missed leaves the next checksum out of line
+        let mut delta = update("a", PriceLevel::new(dec!(0.42), dec!(7)), false);
+        delta.hash = snapshot.hash.clone();
+        assert!(manager.apply_update(&delta));
+        assert!(manager.needs_resync("a"));
+        assert_eq!(manager.assets_needing_resync(), vec!["a".to_string()]);
+
+        assert!(manager.apply_update(&snapshot));
+        assert!(!manager.needs_resync("a"));
+    }
+
+    #[test]
+    fn test_infer_trade_side_from_book() {
+        let mut manager = OrderBookManager::new();
+        // Bid 0.50, ask 0.60, mid 0.55
+        manager.apply_update(&update("a", PriceLevel::new(dec!(0.50), dec!(5)), true));
+
+        for (price, side) in [
+            (dec!(0.60), Side::Buy),
+            (dec!(0.57), Side::Buy),
+            (dec!(0.53), Side::Sell),
+            (dec!(0.50), Side::Sell),
+        ] {
+            let mut trade = unlabeled_trade("a", price);
+            assert!(manager.infer_trade_side(&mut trade));
+            assert_eq!(trade.side, side, "price {}", price);
+            assert_eq!(trade.side_source, TradeSideSource::Inferred);
+        }
+
+        // Exactly at mid is ambiguous
+        let mut trade = unlabeled_trade("a", dec!(0.55));
+        assert!(!manager.infer_trade_side(&mut trade));
+        assert_eq!(trade.side_source, TradeSideSource::Unknown);
+    }
+
+    #[test]
+    fn test_infer_trade_side_without_book() {
+        let mut manager = OrderBookManager::new();
+        let mut trade = unlabeled_trade("missing", dec!(0.90));
+        assert!(!manager.infer_trade_side(&mut trade));
+        assert_eq!(trade.side_source, TradeSideSource::Unknown);
+
+        // Reported sides are left alone
+        manager.apply_update(&update("a", PriceLevel::new(dec!(0.50), dec!(5)), true));
+        let mut trade = unlabeled_trade("a", dec!(0.90));
+        trade.side_source = TradeSideSource::Reported;
+        assert!(!manager.infer_trade_side(&mut trade));
+        assert_eq!(trade.side, Side::Sell);
+
+        // Stale books aren't trusted
+        manager.mark_all_for_resync();
+        let mut trade = unlabeled_trade("a", dec!(0.90));
+        assert!(!manager.infer_trade_side(&mut trade));
+    }
+
+    #[test]
+    fn test_diff_round_trip() {
+        let mut manager = OrderBookManager::new();
+        let old = OrderBook {
+            bids: vec![
+                PriceLevel::new(dec!(0.50), dec!(100)),
+                PriceLevel::new(dec!(0.49), dec!(50)),
+                PriceLevel::new(dec!(0.45), dec!(10)),
+            ],
+            ..OrderBook::from(&update("a", PriceLevel::new(dec!(0.50), dec!(100)), true))
+        };
+        let mut new = old.clone();
+        new.bids = vec![
+            PriceLevel::new(dec!(0.51), dec!(20)),
+            PriceLevel::new(dec!(0.50), dec!(100)),
+            PriceLevel::new(dec!(0.49), dec!(75)),
+        ];
+        new.asks = vec![PriceLevel::new(dec!(0.58), dec!(30))];
+        new.sequence = 7;
+
+        let delta = OrderBookUpdate::from_diff(&old, &new);
+        assert!(!delta.is_snapshot);
+        // Unchanged 0.50 bid is left out, removed 0.45 bid and 0.60 ask are zeroed
+        assert_eq!(
+            delta.bids,
+            vec![
+                PriceLevel::new(dec!(0.51), dec!(20)),
+                PriceLevel::new(dec!(0.49), dec!(75)),
+                PriceLevel::new(dec!(0.45), dec!(0)),
+            ]
+        );
+        assert_eq!(
+            delta.asks,
+            vec![
+                PriceLevel::new(dec!(0.58), dec!(30)),
+                PriceLevel::new(dec!(0.60), dec!(0)),
+            ]
+        );
+
+        manager.apply_snapshot(old);
+        assert!(manager.apply_update(&delta));
+        assert_eq!(manager.get("a"), Some(&new));
+    }
+}
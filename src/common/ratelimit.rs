@@ -0,0 +1,123 @@
+//! Client-side request throttling
+
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// Token bucket rate limiter
+///
+/// The bucket holds up to `capacity` tokens and refills continuously at
+/// `refill_per_second`. Each request takes one token, so bursts of up to
+/// `capacity` go through immediately and the sustained rate is capped at the
+/// refill rate. Waiters are served in the order they called
+/// [`acquire`](Self::acquire).
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    ///
+    /// # Panics
+    /// If `capacity` is zero or `refill_per_second` is not a positive number.
+    pub fn new(capacity: u32, refill_per_second: f64) -> Self {
+        assert!(capacity > 0, "token bucket capacity must be positive");
+        assert!(
+            refill_per_second.is_finite() && refill_per_second > 0.0,
+            "token bucket refill rate must be positive"
+        );
+        Self {
+            capacity: capacity as f64,
+            refill_per_second,
+            state: Mutex::new(BucketState {
+                tokens: capacity as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take a token, waiting until one is available
+    pub async fn acquire(&self) {
+        // The lock is held while sleeping so waiters queue up in order
+        let mut state = self.state.lock().await;
+        self.refill(&mut state);
+        if state.tokens < 1.0 {
+            let wait = (1.0 - state.tokens) / self.refill_per_second;
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            self.refill(&mut state);
+        }
+        // Refill timing can leave us a hair under one token
+        state.tokens = (state.tokens - 1.0).max(0.0);
+    }
+
+    /// Take a token if one is available right now
+    pub fn try_acquire(&self) -> bool {
+        let Ok(mut state) = self.state.try_lock() else {
+            return false;
+        };
+        self.refill(&mut state);
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Maximum number of tokens the bucket holds
+    pub fn capacity(&self) -> u32 {
+        self.capacity as u32
+    }
+
+    /// Tokens added per second
+    pub fn refill_per_second(&self) -> f64 {
+        self.refill_per_second
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        state.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_burst_then_throttled() {
+        let start = Instant::now();
+        let bucket = TokenBucket::new(3, 20.0);
+
+        for _ in 0..3 {
+            bucket.acquire().await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(20));
+        assert!(!bucket.try_acquire());
+
+        // Two more at 20/sec take at least 100ms
+        bucket.acquire().await;
+        bucket.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_refill_capped_at_capacity() {
+        let bucket = TokenBucket::new(2, 50.0);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+    }
+}
@@ -0,0 +1,211 @@
+//! JSON Lines recording of the market event stream
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use super::channels::create_event_channel;
+use super::errors::{ClientError, Result};
+use super::types::MarketEvent;
+
+/// Writes market events to a file, one JSON object per line
+///
+/// Used as a tee between a client and its consumer: every event is written
+/// and then forwarded downstream, and write failures are logged without
+/// interrupting the stream. Writes are buffered and flushed whenever the
+/// incoming channel runs dry, and on drop.
+///
+/// Recordings can be replayed with
+/// [`Backtester::replay_file`](crate::strategy::Backtester::replay_file).
+pub struct EventRecorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+}
+
+impl EventRecorder {
+    /// Append events to the file at `path`, creating it if needed
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                ClientError::Configuration(format!(
+                    "Failed to open event recording {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Path of the recording
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a single event
+    pub fn record(&mut self, event: &MarketEvent) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, event)?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|e| ClientError::Internal(format!("Failed to write event recording: {}", e)))
+    }
+
+    /// Flush buffered events to disk
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .map_err(|e| ClientError::Internal(format!("Failed to flush event recording: {}", e)))
+    }
+
+    /// Record every event from `rx` and forward it to `tx`
+    ///
+    /// Events are forwarded as they arrive and written in batches whenever
+    /// the channel runs dry. File writes run on the blocking pool so they
+    /// never stall the async workers. Returns when `rx` closes or `tx` has
+    /// no receiver left.
+    pub async fn run(mut self, mut rx: mpsc::Receiver<MarketEvent>, tx: mpsc::Sender<MarketEvent>) {
+        let mut pending = Vec::new();
+        while let Some(event) = rx.recv().await {
+            let mut open = Self::tee(event, &tx, &mut pending).await;
+            // Forward anything already queued before paying for a write
+            while open {
+                match rx.try_recv() {
+                    Ok(event) => open = Self::tee(event, &tx, &mut pending).await,
+                    Err(_) => break,
+                }
+            }
+
+            let lines = std::mem::take(&mut pending);
+            let write = tokio::task::spawn_blocking(move || {
+                if let Err(e) = self.write_lines(&lines) {
+                    warn!("{}", e);
+                }
+                self
+            });
+            self = match write.await {
+                Ok(recorder) => recorder,
+                Err(e) => {
+                    warn!("Event recording write failed: {}", e);
+                    return;
+                }
+            };
+            if !open {
+                return;
+            }
+        }
+        debug!("Event stream closed, stopping recorder");
+    }
+
+    /// Run the recorder on its own task, returning the downstream receiver
+    pub fn spawn(
+        self,
+        rx: mpsc::Receiver<MarketEvent>,
+    ) -> (mpsc::Receiver<MarketEvent>, JoinHandle<()>) {
+        let (tx, downstream) = create_event_channel();
+        let handle = tokio::spawn(self.run(rx, tx));
+        (downstream, handle)
+    }
+
+    /// Write already serialized lines and flush them to disk
+    fn write_lines(&mut self, lines: &[u8]) -> Result<()> {
+        self.writer.write_all(lines).map_err(|e| {
+            ClientError::Internal(format!("Failed to write event recording: {}", e))
+        })?;
+        self.flush()
+    }
+
+    /// Queue one event for writing and forward it, returning false if
+    /// downstream is gone
+    async fn tee(
+        event: MarketEvent,
+        tx: &mpsc::Sender<MarketEvent>,
+        pending: &mut Vec<u8>,
+    ) -> bool {
+        match serde_json::to_vec(&event) {
+            Ok(line) => {
+                pending.extend_from_slice(&line);
+                pending.push(b'\n');
+            }
+            Err(e) => warn!("Failed to serialize event for recording: {}", e),
+        }
+        if tx.send(event).await.is_err() {
+            debug!("Event consumer dropped, stopping recorder");
+            return false;
+        }
+        true
+    }
+}
+
+impl Drop for EventRecorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!("{}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{ConnectionStatus, Platform, Side, Trade, TradeSideSource};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use std::io::{BufRead, BufReader};
+
+    #[tokio::test]
+    async fn test_round_trip_and_forward() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let events = vec![
+            MarketEvent::ConnectionStatus {
+                platform: Platform::Polymarket,
+                status: ConnectionStatus::Connected,
+            },
+            MarketEvent::Trade(Trade {
+                platform: Platform::Polymarket,
+                market_id: "condition".to_string(),
+                asset_id: "token".to_string(),
+                trade_id: "t1".to_string(),
+                price: dec!(0.52),
+                size: dec!(25),
+                side: Side::Buy,
+                side_source: TradeSideSource::Inferred,
+                timestamp: Utc::now(),
+                received_at: Utc::now(),
+            }),
+            MarketEvent::Heartbeat {
+                platform: Platform::Kalshi,
+            },
+        ];
+
+        let (tx, rx) = mpsc::channel(8);
+        let (mut downstream, handle) = EventRecorder::new(&path).unwrap().spawn(rx);
+        for event in &events {
+            tx.send(event.clone()).await.unwrap();
+        }
+        drop(tx);
+
+        let mut forwarded = Vec::new();
+        while let Some(event) = downstream.recv().await {
+            forwarded.push(event);
+        }
+        handle.await.unwrap();
+        assert_eq!(forwarded, events);
+
+        let recorded: Vec<MarketEvent> = BufReader::new(File::open(&path).unwrap())
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        assert_eq!(recorded, events);
+    }
+}
@@ -0,0 +1,311 @@
+//! Circuit breaking for market client connections
+
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use super::errors::{ClientError, Result};
+use super::traits::MarketClient;
+use super::types::{ConnectionStatus, MarketEvent, Platform};
+
+/// Default consecutive connect failures before the circuit opens
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default time the circuit stays open before a probe is allowed
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// When a [`CircuitBreaker`] stops letting connects through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerPolicy {
+    /// Consecutive connect failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a single probe connect
+    pub cooldown: Duration,
+}
+
+impl CircuitBreakerPolicy {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_COOLDOWN)
+    }
+}
+
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Connects go through to the client
+    Closed,
+    /// Connects fail immediately until the cooldown has passed
+    Open,
+    /// The cooldown has passed; the next connect probes the client
+    HalfOpen,
+}
+
+/// A [`MarketClient`] that stops connecting to an exchange that keeps failing
+///
+/// Every `connect` is passed to the wrapped client until
+/// `failure_threshold` of them fail in a row. The circuit then opens and
+/// `connect` fails straight away, without touching the exchange, for the
+/// cooldown. After that one probe connect is let through: success closes
+/// the circuit, failure opens it for another cooldown.
+///
+/// The breaker doesn't retry on its own; whatever calls `connect` (such as a
+/// [`ClientSupervisor`](super::supervisor::ClientSupervisor)) decides when
+/// to try again. Transitions are reported as `ConnectionStatus` events on
+/// the channel last passed to `start`: `Error` when the circuit opens,
+/// `Reconnecting` before a probe and `Connected` once a probe succeeds.
+pub struct CircuitBreaker<C> {
+    inner: C,
+    policy: CircuitBreakerPolicy,
+    /// Consecutive failed connects
+    failures: u32,
+    /// When the circuit last opened, while it's open or half-open
+    opened_at: Option<Instant>,
+    events: Option<mpsc::Sender<MarketEvent>>,
+}
+
+/// Wrap `client` in a [`CircuitBreaker`]
+pub fn with_circuit_breaker<C: MarketClient>(
+    client: C,
+    policy: CircuitBreakerPolicy,
+) -> CircuitBreaker<C> {
+    CircuitBreaker::new(client, policy)
+}
+
+impl<C: MarketClient> CircuitBreaker<C> {
+    pub fn new(inner: C, policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            failures: 0,
+            opened_at: None,
+            events: None,
+        }
+    }
+
+    /// Report transitions on `sender` before `start` has been called
+    pub fn with_event_sender(mut self, sender: mpsc::Sender<MarketEvent>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    /// Current state of the circuit
+    pub fn state(&self) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.policy.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Consecutive failed connects
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// The wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Unwrap the client
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Send a status for the wrapped client's platform, if it has one
+    async fn report(&self, status: ConnectionStatus) {
+        let (Some(events), Some(platform)) =
+            (&self.events, platform_for(self.inner.platform_name()))
+        else {
+            return;
+        };
+        let _ = events
+            .send(MarketEvent::ConnectionStatus { platform, status })
+            .await;
+    }
+}
+
+/// Platform a client's `platform_name` refers to
+fn platform_for(name: &str) -> Option<Platform> {
+    if name.eq_ignore_ascii_case("polymarket") {
+        Some(Platform::Polymarket)
+    } else if name.eq_ignore_ascii_case("kalshi") {
+        Some(Platform::Kalshi)
+    } else {
+        None
+    }
+}
+
+#[async_trait]
+impl<C: MarketClient> MarketClient for CircuitBreaker<C> {
+    async fn connect(&mut self) -> Result<()> {
+        let name = self.inner.platform_name();
+        let probing = match self.state() {
+            CircuitState::Closed => false,
+            CircuitState::Open => {
+                let remaining = self
+                    .opened_at
+                    .map(|opened_at| self.policy.cooldown.saturating_sub(opened_at.elapsed()))
+                    .unwrap_or_default();
+                return Err(ClientError::WebSocketConnection(format!(
+                    "{} circuit open, retry in {:?}",
+                    name, remaining
+                )));
+            }
+            CircuitState::HalfOpen => {
+                info!("Probing {} after circuit cooldown", name);
+                self.report(ConnectionStatus::Reconnecting {
+                    attempt: self.failures + 1,
+                })
+                .await;
+                true
+            }
+        };
+
+        match self.inner.connect().await {
+            Ok(()) => {
+                self.failures = 0;
+                self.opened_at = None;
+                if probing {
+                    info!("{} recovered, closing circuit", name);
+                    self.report(ConnectionStatus::Connected).await;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.failures += 1;
+                if probing || self.failures >= self.policy.failure_threshold {
+                    warn!(
+                        "Opening {} circuit for {:?} after {} failed connects",
+                        name, self.policy.cooldown, self.failures
+                    );
+                    self.opened_at = Some(Instant::now());
+                    self.report(ConnectionStatus::Error(format!(
+                        "circuit open after {} failed connects: {}",
+                        self.failures, e
+                    )))
+                    .await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn subscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        self.inner.subscribe(asset_ids).await
+    }
+
+    async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<()> {
+        self.inner.unsubscribe(asset_ids).await
+    }
+
+    async fn start(&mut self, sender: mpsc::Sender<MarketEvent>) -> Result<()> {
+        self.events = Some(sender.clone());
+        self.inner.start(sender).await
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.inner.disconnect().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn platform_name(&self) -> &'static str {
+        self.inner.platform_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::mock::MockMarketClient;
+
+    /// Polymarket-named client whose first `failures` connects fail
+    fn flaky_client(failures: u32) -> MockMarketClient {
+        MockMarketClient::new(Vec::new())
+            .with_failed_connects(failures)
+            .with_platform_name("Polymarket")
+    }
+
+    fn statuses(rx: &mut mpsc::Receiver<MarketEvent>) -> Vec<ConnectionStatus> {
+        let mut statuses = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let MarketEvent::ConnectionStatus { status, .. } = event {
+                statuses.push(status);
+            }
+        }
+        statuses
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_threshold_and_recovers() {
+        let cooldown = Duration::from_millis(100);
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut client =
+            with_circuit_breaker(flaky_client(4), CircuitBreakerPolicy::new(3, cooldown))
+                .with_event_sender(tx);
+
+        for _ in 0..3 {
+            assert!(client.connect().await.is_err());
+        }
+        assert_eq!(client.state(), CircuitState::Open);
+        assert!(matches!(
+            statuses(&mut rx).as_slice(),
+            [ConnectionStatus::Error(_)]
+        ));
+
+        // While open, connects don't reach the client
+        assert!(client.connect().await.is_err());
+        assert_eq!(client.inner().connect_attempts(), 3);
+
+        // A failed probe reopens the circuit straight away
+        tokio::time::sleep(cooldown).await;
+        assert_eq!(client.state(), CircuitState::HalfOpen);
+        assert!(client.connect().await.is_err());
+        assert_eq!(client.state(), CircuitState::Open);
+        assert_eq!(client.failures(), 4);
+
+        // A successful probe closes it
+        tokio::time::sleep(cooldown).await;
+        client.connect().await.unwrap();
+        assert_eq!(client.state(), CircuitState::Closed);
+        assert_eq!(client.failures(), 0);
+        assert!(client.is_connected());
+        assert_eq!(
+            statuses(&mut rx),
+            vec![
+                ConnectionStatus::Reconnecting { attempt: 4 },
+                ConnectionStatus::Error(
+                    "circuit open after 4 failed connects: WebSocket connection error: Mock connect refused"
+                        .to_string()
+                ),
+                ConnectionStatus::Reconnecting { attempt: 5 },
+                ConnectionStatus::Connected,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_below_threshold_stay_closed() {
+        let mut client = with_circuit_breaker(flaky_client(2), CircuitBreakerPolicy::default());
+
+        assert!(client.connect().await.is_err());
+        assert!(client.connect().await.is_err());
+        assert_eq!(client.state(), CircuitState::Closed);
+        client.connect().await.unwrap();
+        assert_eq!(client.failures(), 0);
+    }
+}
@@ -0,0 +1,192 @@
+//! Sequence gap detection for order book deltas
+
+use std::collections::HashMap;
+use tracing::warn;
+
+use super::types::{MarketEvent, OrderBookUpdate};
+
+/// How a sequence number relates to the last one seen for its asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// First sequence seen for the asset
+    First,
+    /// Exactly one past the last sequence
+    InOrder,
+    /// At or before the last sequence; already applied or stale
+    Duplicate { last: u64 },
+    /// One or more sequences were skipped
+    Gap { expected: u64, received: u64 },
+}
+
+/// Tracks the last sequence per asset and spots missed deltas
+///
+/// A delta whose sequence isn't exactly one past the last one means the
+/// local book has diverged from the exchange. Snapshots reset the count, so
+/// after a gap the book is trusted again once a fresh snapshot is applied.
+/// Sequence 0 means the feed doesn't number its messages and is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    last: HashMap<String, u64>,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check a sequence against the last one for `asset_id` and record it
+    ///
+    /// Duplicates aren't recorded. After a gap the received sequence becomes
+    /// the last one, so deltas following it aren't reported again.
+    pub fn observe(&mut self, asset_id: &str, sequence: u64) -> SequenceCheck {
+        let Some(last) = self.last.get_mut(asset_id) else {
+            self.last.insert(asset_id.to_string(), sequence);
+            return SequenceCheck::First;
+        };
+
+        if sequence <= *last {
+            return SequenceCheck::Duplicate { last: *last };
+        }
+        let expected = *last + 1;
+        *last = sequence;
+        if sequence == expected {
+            SequenceCheck::InOrder
+        } else {
+            SequenceCheck::Gap {
+                expected,
+                received: sequence,
+            }
+        }
+    }
+
+    /// Track an update, returning a `ResyncNeeded` event if deltas were missed
+    ///
+    /// Snapshots and unsequenced updates never need a resync; snapshots
+    /// restart tracking from their own sequence.
+    pub fn apply_update(&mut self, update: &OrderBookUpdate) -> Option<MarketEvent> {
+        if update.sequence == 0 {
+            return None;
+        }
+        if update.is_snapshot {
+            self.reset(&update.asset_id, update.sequence);
+            return None;
+        }
+
+        match self.observe(&update.asset_id, update.sequence) {
+            SequenceCheck::Gap { expected, received } => {
+                warn!(
+                    "Sequence gap for {}: expected {}, received {}",
+                    update.asset_id, expected, received
+                );
+                Some(MarketEvent::ResyncNeeded {
+                    platform: update.platform,
+                    asset_id: update.asset_id.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Restart tracking for an asset from `sequence`
+    pub fn reset(&mut self, asset_id: &str, sequence: u64) {
+        self.last.insert(asset_id.to_string(), sequence);
+    }
+
+    /// Stop tracking an asset
+    pub fn remove(&mut self, asset_id: &str) {
+        self.last.remove(asset_id);
+    }
+
+    /// Last sequence recorded for an asset
+    pub fn last_sequence(&self, asset_id: &str) -> Option<u64> {
+        self.last.get(asset_id).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Platform;
+    use chrono::Utc;
+
+    fn delta(asset_id: &str, sequence: u64, is_snapshot: bool) -> OrderBookUpdate {
+        OrderBookUpdate {
+            platform: Platform::Kalshi,
+            market_id: "market".to_string(),
+            asset_id: asset_id.to_string(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            is_snapshot,
+            sequence,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_in_order_sequences() {
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.observe("a", 5), SequenceCheck::First);
+        assert_eq!(tracker.observe("a", 6), SequenceCheck::InOrder);
+        assert_eq!(tracker.observe("a", 7), SequenceCheck::InOrder);
+        // Assets are tracked independently
+        assert_eq!(tracker.observe("b", 1), SequenceCheck::First);
+        assert_eq!(tracker.last_sequence("a"), Some(7));
+
+        tracker.apply_update(&delta("c", 1, true));
+        for sequence in 2..5 {
+            assert_eq!(tracker.apply_update(&delta("c", sequence, false)), None);
+        }
+    }
+
+    #[test]
+    fn test_gap_requests_resync() {
+        let mut tracker = SequenceTracker::new();
+        tracker.apply_update(&delta("a", 10, true));
+        assert_eq!(tracker.apply_update(&delta("a", 11, false)), None);
+
+        assert_eq!(
+            tracker.apply_update(&delta("a", 14, false)),
+            Some(MarketEvent::ResyncNeeded {
+                platform: Platform::Kalshi,
+                asset_id: "a".to_string(),
+            })
+        );
+        // Following deltas continue from the gap
+        assert_eq!(tracker.apply_update(&delta("a", 15, false)), None);
+
+        // A fresh snapshot restarts the count, even backwards
+        tracker.apply_update(&delta("a", 12, true));
+        assert_eq!(tracker.last_sequence("a"), Some(12));
+        assert_eq!(tracker.apply_update(&delta("a", 13, false)), None);
+        assert_eq!(
+            tracker.observe("a", 20),
+            SequenceCheck::Gap {
+                expected: 14,
+                received: 20
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicates_ignored() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe("a", 3);
+        tracker.observe("a", 4);
+        assert_eq!(
+            tracker.observe("a", 4),
+            SequenceCheck::Duplicate { last: 4 }
+        );
+        assert_eq!(
+            tracker.observe("a", 2),
+            SequenceCheck::Duplicate { last: 4 }
+        );
+        assert_eq!(tracker.last_sequence("a"), Some(4));
+        assert_eq!(tracker.apply_update(&delta("a", 4, false)), None);
+
+        // Unsequenced feeds are never tracked
+        assert_eq!(tracker.apply_update(&delta("b", 0, false)), None);
+        assert_eq!(tracker.last_sequence("b"), None);
+    }
+}
@@ -5,7 +5,7 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```ignore
 //! use polymarket_websocket::common::speedtest::{SpeedTest, SpeedTestResult};
 //!
 //! // Simple timing
@@ -20,7 +20,7 @@
 //! });
 //!
 //! // Async timing
-//! let result = SpeedTest::time_async("async_op", async {
+//! let result = SpeedTest::time_async("async_op", || async {
 //!     // async code
 //! }).await;
 //!
@@ -29,12 +29,52 @@
 //!     // run 100 times
 //! });
 //! println!("Average: {:?}", stats.average);
+//!
+//! // More warmup, outliers trimmed, extra percentiles
+//! let opts = BenchmarkOptions {
+//!     warmup_iters: 10,
+//!     trim_percent: 1.0,
+//!     extra_percentiles: vec![50.0, 99.9],
+//! };
+//! let stats = SpeedTest::benchmark_with("operation", 1000, &opts, || {
+//!     // run 1000 times after 10 warmup runs
+//! });
+//! println!("P99.9: {:?}", stats.percentile(99.9));
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::fs;
 use std::future::Future;
+use std::io;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
+/// Environment variable that makes [`SpeedTest::assert_no_regression`]
+/// record a new baseline instead of checking against the old one
+pub const BLESS_ENV: &str = "BLESS";
+
+/// What [`SpeedTest::assert_against_baseline`] does with a saved baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineMode {
+    /// Fail if the run regressed from the saved baseline
+    Check,
+    /// Record the run as the new baseline
+    Bless,
+}
+
+impl BaselineMode {
+    /// [`Bless`](Self::Bless) if the [`BLESS_ENV`] variable is set,
+    /// [`Check`](Self::Check) otherwise
+    pub fn from_env() -> Self {
+        if std::env::var_os(BLESS_ENV).is_some() {
+            Self::Bless
+        } else {
+            Self::Check
+        }
+    }
+}
+
 /// Result of a single speed test measurement
 #[derive(Debug, Clone)]
 pub struct SpeedTestResult<T> {
@@ -87,13 +127,38 @@ impl<T: fmt::Debug> fmt::Display for SpeedTestResult<T> {
     }
 }
 
+/// Settings for [`SpeedTest::benchmark_with`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkOptions {
+    /// Untimed runs before measuring
+    pub warmup_iters: usize,
+    /// Percentage of the fastest and of the slowest timings to drop before
+    /// computing stats, e.g. `5.0` drops the bottom and top 5%
+    pub trim_percent: f64,
+    /// Percentiles (0-100) reported in [`BenchmarkStats::percentiles`]
+    pub extra_percentiles: Vec<f64>,
+}
+
+impl Default for BenchmarkOptions {
+    fn default() -> Self {
+        Self {
+            warmup_iters: 1,
+            trim_percent: 0.0,
+            extra_percentiles: Vec::new(),
+        }
+    }
+}
+
 /// Statistics from running a benchmark multiple times
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkStats {
     /// Name of the benchmark
     pub name: String,
     /// Number of iterations run
     pub iterations: usize,
+    /// Number of timings dropped as outliers; the remaining stats exclude them
+    #[serde(default)]
+    pub trimmed: usize,
     /// Total time for all iterations
     pub total: Duration,
     /// Average time per iteration
@@ -110,6 +175,9 @@ pub struct BenchmarkStats {
     pub p99: Duration,
     /// Standard deviation (in nanoseconds)
     pub std_dev_nanos: f64,
+    /// Requested extra percentiles as `(percentile, time)` pairs
+    #[serde(default)]
+    pub percentiles: Vec<(f64, Duration)>,
 }
 
 impl BenchmarkStats {
@@ -130,12 +198,45 @@ impl BenchmarkStats {
     pub fn p95_is_faster_than(&self, max_duration: Duration) -> bool {
         self.p95 < max_duration
     }
+
+    /// Time at an extra percentile requested in [`BenchmarkOptions`]
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        self.percentiles
+            .iter()
+            .find(|(p, _)| *p == percentile)
+            .map(|(_, d)| *d)
+    }
+
+    /// Machine-readable form of these stats
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("benchmark stats serialize")
+    }
+
+    /// Write these stats to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read stats previously written with [`save`](Self::save)
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Check if the average is more than `tolerance_pct` percent slower
+    /// than the baseline average
+    pub fn regressed_from(&self, baseline: &BenchmarkStats, tolerance_pct: f64) -> bool {
+        let limit = baseline.average.as_nanos() as f64 * (1.0 + tolerance_pct / 100.0);
+        self.average.as_nanos() as f64 > limit
+    }
 }
 
 impl fmt::Display for BenchmarkStats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "[Benchmark] {}", self.name)?;
         writeln!(f, "  Iterations: {}", self.iterations)?;
+        if self.trimmed > 0 {
+            writeln!(f, "  Trimmed:    {}", self.trimmed)?;
+        }
         writeln!(f, "  Total:      {:?}", self.total)?;
         writeln!(f, "  Average:    {:?}", self.average)?;
         writeln!(f, "  Min:        {:?}", self.min)?;
@@ -143,6 +244,9 @@ impl fmt::Display for BenchmarkStats {
         writeln!(f, "  Median:     {:?}", self.median)?;
         writeln!(f, "  P95:        {:?}", self.p95)?;
         writeln!(f, "  P99:        {:?}", self.p99)?;
+        for (percentile, duration) in &self.percentiles {
+            writeln!(f, "  {:<12}{:?}", format!("P{}:", percentile), duration)?;
+        }
         writeln!(f, "  Std Dev:    {:.2} µs", self.std_dev_nanos / 1000.0)?;
         writeln!(f, "  Ops/sec:    {:.2}", self.ops_per_second())
     }
@@ -187,7 +291,7 @@ impl SpeedTest {
     ///
     /// # Example
     /// ```ignore
-    /// let result = SpeedTest::time_async("http_request", async {
+    /// let result = SpeedTest::time_async("http_request", || async {
     ///     client.get("https://example.com").await
     /// }).await;
     /// ```
@@ -219,14 +323,43 @@ impl SpeedTest {
     /// });
     /// println!("{}", stats);
     /// ```
-    pub fn benchmark<F>(name: &str, iterations: usize, mut f: F) -> BenchmarkStats
+    pub fn benchmark<F>(name: &str, iterations: usize, f: F) -> BenchmarkStats
+    where
+        F: FnMut(),
+    {
+        Self::benchmark_with(name, iterations, &BenchmarkOptions::default(), f)
+    }
+
+    /// Run a benchmark with custom warmup, outlier trimming and percentiles
+    ///
+    /// # Example
+    /// ```
+    /// use polymarket_websocket::common::speedtest::{BenchmarkOptions, SpeedTest};
+    ///
+    /// let opts = BenchmarkOptions {
+    ///     warmup_iters: 10,
+    ///     trim_percent: 5.0,
+    ///     extra_percentiles: vec![50.0, 99.9],
+    /// };
+    /// let stats = SpeedTest::benchmark_with("json_parse", 1000, &opts, || {
+    ///     let _: serde_json::Value = serde_json::from_str("{}").unwrap();
+    /// });
+    /// assert!(stats.percentile(99.9).is_some());
+    /// ```
+    pub fn benchmark_with<F>(
+        name: &str,
+        iterations: usize,
+        opts: &BenchmarkOptions,
+        mut f: F,
+    ) -> BenchmarkStats
     where
         F: FnMut(),
     {
         assert!(iterations > 0, "Iterations must be greater than 0");
 
-        // Warmup run
-        f();
+        for _ in 0..opts.warmup_iters {
+            f();
+        }
 
         // Collect timings
         let mut durations: Vec<Duration> = Vec::with_capacity(iterations);
@@ -240,7 +373,7 @@ impl SpeedTest {
 
         let total = total_start.elapsed();
 
-        Self::calculate_stats(name, durations, total)
+        Self::calculate_stats(name, durations, total, opts)
     }
 
     /// Run an async benchmark with multiple iterations
@@ -266,16 +399,27 @@ impl SpeedTest {
 
         let total = total_start.elapsed();
 
-        Self::calculate_stats(name, durations, total)
+        Self::calculate_stats(name, durations, total, &BenchmarkOptions::default())
     }
 
     /// Calculate statistics from collected durations
-    fn calculate_stats(name: &str, mut durations: Vec<Duration>, total: Duration) -> BenchmarkStats {
-        let iterations = durations.len();
+    pub(crate) fn calculate_stats(
+        name: &str,
+        mut durations: Vec<Duration>,
+        total: Duration,
+        opts: &BenchmarkOptions,
+    ) -> BenchmarkStats {
+        let measured = durations.len();
 
-        // Sort for percentile calculations
+        // Sort for trimming and percentile calculations
         durations.sort();
 
+        // Drop the same number of timings from each end, keeping at least one
+        let per_side =
+            ((measured as f64 * opts.trim_percent / 100.0) as usize).min((measured - 1) / 2);
+        let durations = &durations[per_side..measured - per_side];
+        let iterations = durations.len();
+
         let min = durations[0];
         let max = durations[iterations - 1];
         let median = durations[iterations / 2];
@@ -297,9 +441,19 @@ impl SpeedTest {
             / iterations as f64;
         let std_dev_nanos = variance.sqrt();
 
+        let percentiles = opts
+            .extra_percentiles
+            .iter()
+            .map(|&p| {
+                let index = (iterations as f64 * p / 100.0) as usize;
+                (p, durations[index.min(iterations - 1)])
+            })
+            .collect();
+
         BenchmarkStats {
             name: name.to_string(),
-            iterations,
+            iterations: measured,
+            trimmed: measured - iterations,
             total,
             average,
             min,
@@ -308,6 +462,7 @@ impl SpeedTest {
             p95,
             p99,
             std_dev_nanos,
+            percentiles,
         }
     }
 
@@ -419,6 +574,93 @@ impl SpeedTest {
         stats
     }
 
+    /// Run a benchmark and assert it hasn't slowed down since the baseline
+    ///
+    /// Same as [`assert_against_baseline`](Self::assert_against_baseline)
+    /// with the mode taken from the [`BLESS_ENV`] environment variable.
+    ///
+    /// # Panics
+    /// Panics on a regression, or if the baseline can't be read or written
+    pub fn assert_no_regression<F>(
+        name: &str,
+        baseline_path: impl AsRef<Path>,
+        tolerance_pct: f64,
+        iterations: usize,
+        f: F,
+    ) -> BenchmarkStats
+    where
+        F: FnMut(),
+    {
+        Self::assert_against_baseline(
+            name,
+            baseline_path,
+            tolerance_pct,
+            iterations,
+            BaselineMode::from_env(),
+            f,
+        )
+    }
+
+    /// Run a benchmark and check it against, or record, a saved baseline
+    ///
+    /// In [`BaselineMode::Check`], fails if the average time exceeds the
+    /// average saved at `baseline_path` by more than `tolerance_pct`
+    /// percent. In [`BaselineMode::Bless`], the new stats are written to
+    /// `baseline_path` instead.
+    ///
+    /// # Panics
+    /// Panics on a regression, or if the baseline can't be read or written
+    pub fn assert_against_baseline<F>(
+        name: &str,
+        baseline_path: impl AsRef<Path>,
+        tolerance_pct: f64,
+        iterations: usize,
+        mode: BaselineMode,
+        f: F,
+    ) -> BenchmarkStats
+    where
+        F: FnMut(),
+    {
+        let path = baseline_path.as_ref();
+        let stats = Self::benchmark(name, iterations, f);
+
+        if mode == BaselineMode::Bless {
+            if let Err(e) = stats.save(path) {
+                panic!(
+                    "[SpeedTest] failed to write baseline {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+            println!(
+                "[SpeedTest] {} baseline written to {}",
+                name,
+                path.display()
+            );
+            return stats;
+        }
+
+        let baseline = BenchmarkStats::load(path).unwrap_or_else(|e| {
+            panic!(
+                "[SpeedTest FAILED] {} has no usable baseline at {} ({}); run with {}=1 to record one",
+                name,
+                path.display(),
+                e,
+                BLESS_ENV
+            )
+        });
+        assert!(
+            !stats.regressed_from(&baseline, tolerance_pct),
+            "[SpeedTest FAILED] {} average {:?} regressed more than {}% from baseline {:?}\n{}",
+            name,
+            stats.average,
+            tolerance_pct,
+            baseline.average,
+            stats
+        );
+        stats
+    }
+
     /// Compare two operations and assert one is faster
     ///
     /// Returns (slower_result, faster_result)
@@ -586,6 +828,53 @@ mod tests {
         assert!(stats.ops_per_second() > 0.0);
     }
 
+    #[test]
+    fn test_benchmark_trims_outliers() {
+        // One slow run among fast ones
+        let run = |opts: &BenchmarkOptions| {
+            let mut calls = 0;
+            let stats = SpeedTest::benchmark_with("trim", 20, opts, || {
+                calls += 1;
+                if calls == opts.warmup_iters + 5 {
+                    thread::sleep(Duration::from_millis(5));
+                }
+            });
+            assert_eq!(calls, opts.warmup_iters + 20);
+            stats
+        };
+
+        let untrimmed = run(&BenchmarkOptions::default());
+        assert!(untrimmed.max >= Duration::from_millis(5));
+        assert_eq!(untrimmed.trimmed, 0);
+
+        let trimmed = run(&BenchmarkOptions {
+            warmup_iters: 3,
+            trim_percent: 10.0,
+            extra_percentiles: Vec::new(),
+        });
+        assert!(trimmed.max < untrimmed.max);
+        assert!(trimmed.max < Duration::from_millis(5));
+        assert_eq!(trimmed.iterations, 20);
+        assert_eq!(trimmed.trimmed, 4);
+    }
+
+    #[test]
+    fn test_benchmark_extra_percentiles() {
+        let opts = BenchmarkOptions {
+            extra_percentiles: vec![50.0, 90.0, 99.9],
+            ..Default::default()
+        };
+        let stats = SpeedTest::benchmark_with("percentiles", 50, &opts, || {});
+
+        let reported: Vec<f64> = stats.percentiles.iter().map(|(p, _)| *p).collect();
+        assert_eq!(reported, vec![50.0, 90.0, 99.9]);
+        assert_eq!(stats.percentile(50.0), Some(stats.median));
+        assert!(stats.percentile(90.0).unwrap() <= stats.percentile(99.9).unwrap());
+        assert!(stats.percentile(99.9).unwrap() <= stats.max);
+        assert_eq!(stats.percentile(75.0), None);
+        assert!(format!("{}", stats).contains("P99.9:"));
+    }
+
     #[test]
     fn test_assert_faster_than_ms_passes() {
         let result = SpeedTest::assert_faster_than_ms("fast_op", 1000, || {
@@ -634,7 +923,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_async_timing() {
-        let result = SpeedTest::time_async("async_op", async {
+        let result = SpeedTest::time_async("async_op", || async {
             tokio::time::sleep(Duration::from_millis(10)).await;
             "done"
         })
@@ -655,6 +944,97 @@ mod tests {
         println!("{}", stats);
     }
 
+    /// Baseline file for a test, with an average of `average`
+    fn baseline_file(test: &str, average: Duration) -> tempfile::TempPath {
+        let mut baseline = SpeedTest::benchmark("baseline", 1, || {});
+        baseline.average = average;
+
+        let path = tempfile::Builder::new()
+            .prefix(&format!("speedtest_{}_", test))
+            .suffix(".json")
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        baseline.save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_benchmark_stats_json() {
+        let stats = SpeedTest::benchmark("json_export", 10, || {});
+        let json = stats.to_json();
+        assert_eq!(json["name"], "json_export");
+        assert_eq!(json["iterations"], 10);
+
+        let path = baseline_file("json_export", stats.average);
+        let loaded = BenchmarkStats::load(&path).unwrap();
+        assert_eq!(loaded.average, stats.average);
+    }
+
+    #[test]
+    fn test_regression_tolerance() {
+        let mut baseline = SpeedTest::benchmark("tolerance", 1, || {});
+        baseline.average = Duration::from_micros(100);
+        let mut run = baseline.clone();
+
+        run.average = Duration::from_micros(109);
+        assert!(!run.regressed_from(&baseline, 10.0));
+        run.average = Duration::from_micros(111);
+        assert!(run.regressed_from(&baseline, 10.0));
+    }
+
+    #[test]
+    fn test_no_regression_faster_run() {
+        let path = baseline_file("faster_run", Duration::from_millis(50));
+        let result = std::panic::catch_unwind(|| {
+            SpeedTest::assert_against_baseline(
+                "faster_run",
+                &path,
+                10.0,
+                5,
+                BaselineMode::Check,
+                || {
+                    let sum: i32 = (0..100).sum();
+                    assert_eq!(sum, 4950);
+                },
+            )
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_no_regression_slower_run() {
+        let path = baseline_file("slower_run", Duration::from_micros(100));
+        let result = std::panic::catch_unwind(|| {
+            SpeedTest::assert_against_baseline(
+                "slower_run",
+                &path,
+                10.0,
+                3,
+                BaselineMode::Check,
+                || thread::sleep(Duration::from_millis(5)),
+            )
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("regressed more than 10%"));
+    }
+
+    #[test]
+    fn test_bless_overwrites_baseline() {
+        let path = baseline_file("bless_run", Duration::from_micros(100));
+        let slow = || thread::sleep(Duration::from_millis(5));
+        let blessed = SpeedTest::assert_against_baseline(
+            "bless_run",
+            &path,
+            10.0,
+            3,
+            BaselineMode::Bless,
+            slow,
+        );
+        let saved = BenchmarkStats::load(&path).unwrap();
+        assert_eq!(saved.average, blessed.average);
+    }
+
     #[test]
     fn test_display_formatting() {
         let result = SpeedTest::time("display_test", || 42);
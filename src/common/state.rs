@@ -0,0 +1,109 @@
+//! Subscription state persisted across restarts, and the JSON file helpers
+//! shared with other persisted state
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::fs;
+
+use super::errors::{ClientError, Result};
+
+/// Subscribed assets and the last sequence seen for each
+///
+/// Saved as JSON so a restarted process can resubscribe to the same assets
+/// and tell how far behind its books are.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriptionState {
+    /// Subscribed asset IDs, in subscription order
+    pub assets: Vec<String>,
+    /// Last book sequence seen per asset ID
+    #[serde(default)]
+    pub last_sequences: HashMap<String, u64>,
+}
+
+impl SubscriptionState {
+    pub fn new(assets: Vec<String>) -> Self {
+        Self {
+            assets,
+            last_sequences: HashMap::new(),
+        }
+    }
+
+    /// Record a sequence for an asset, keeping the highest seen
+    pub fn record_sequence(&mut self, asset_id: &str, sequence: u64) {
+        let last = self.last_sequences.entry(asset_id.to_string()).or_default();
+        *last = (*last).max(sequence);
+    }
+
+    /// Write the state to `path` as JSON
+    ///
+    /// The file is written next to `path` and renamed into place, so a crash
+    /// mid-write never leaves a truncated state file behind.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        save_json(path.as_ref(), self, "subscription state").await
+    }
+
+    /// Read state previously written by [`save`](Self::save)
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        load_json(path.as_ref(), "subscription state").await
+    }
+}
+
+/// Write `value` to `path` as pretty-printed JSON via a renamed temp file
+///
+/// `what` names the file in errors.
+pub(crate) async fn save_json<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    let written = match fs::write(&tmp, json).await {
+        Ok(()) => fs::rename(&tmp, path).await,
+        Err(e) => Err(e),
+    };
+    written.map_err(|e| {
+        ClientError::Internal(format!(
+            "Failed to write {} {}: {}",
+            what,
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Read JSON written by [`save_json`]
+///
+/// A missing or unreadable file is a `ClientError::Configuration`.
+pub(crate) async fn load_json<T: DeserializeOwned>(path: &Path, what: &str) -> Result<T> {
+    let json = fs::read_to_string(path).await.map_err(|e| {
+        ClientError::Configuration(format!("Failed to read {} {}: {}", what, path.display(), e))
+    })?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.json");
+
+        let mut state = SubscriptionState::new(vec!["asset_a".to_string(), "asset_b".to_string()]);
+        state.record_sequence("asset_a", 42);
+        state.record_sequence("asset_a", 17);
+        state.record_sequence("asset_b", 7);
+        assert_eq!(state.last_sequences["asset_a"], 42);
+
+        state.save(&path).await.unwrap();
+        let loaded = SubscriptionState::load(&path).await.unwrap();
+        assert_eq!(loaded, state);
+
+        assert!(matches!(
+            SubscriptionState::load(dir.path().join("missing.json")).await,
+            Err(ClientError::Configuration(_))
+        ));
+    }
+}
@@ -0,0 +1,440 @@
+//! Lifecycle management for multiple market clients
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::channels::{create_event_channel, DEFAULT_CHANNEL_SIZE};
+use super::errors::Result;
+use super::traits::MarketClient;
+use super::types::{ConnectionStatus, MarketEvent, Platform};
+use crate::config::types::AppSettings;
+
+/// Default delay before the first restart attempt
+const DEFAULT_RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Default upper bound on the restart delay
+const DEFAULT_MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// A client and the assets it should be subscribed to
+struct Supervised {
+    client: Box<dyn MarketClient>,
+    asset_ids: Vec<String>,
+}
+
+/// Owns several market clients and keeps them running
+///
+/// [`start`](Self::start) connects every client, subscribes it to its
+/// assets, and merges their events into one channel. A client that reports
+/// `Disconnected` or `Error` is disconnected and restarted with exponential
+/// backoff; a `Reconnecting` status is emitted before each attempt once the
+/// client's platform is known from its events.
+pub struct ClientSupervisor {
+    clients: Vec<Supervised>,
+    restart_delay: Duration,
+    max_restart_delay: Duration,
+    /// Restarts without receiving market data before a client is given up
+    /// on (0 = infinite)
+    max_restarts: u32,
+}
+
+impl ClientSupervisor {
+    pub fn new() -> Self {
+        Self {
+            clients: Vec::new(),
+            restart_delay: DEFAULT_RESTART_DELAY,
+            max_restart_delay: DEFAULT_MAX_RESTART_DELAY,
+            max_restarts: 0,
+        }
+    }
+
+    /// Create a supervisor using the reconnect settings from configuration
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::new()
+            .with_backoff(
+                Duration::from_millis(settings.reconnect_delay_ms),
+                DEFAULT_MAX_RESTART_DELAY,
+            )
+            .with_max_restarts(settings.max_reconnect_attempts)
+    }
+
+    /// Add a client to supervise, subscribed to `asset_ids` on every start
+    pub fn with_client(mut self, client: Box<dyn MarketClient>, asset_ids: Vec<String>) -> Self {
+        self.clients.push(Supervised { client, asset_ids });
+        self
+    }
+
+    /// Set the initial and maximum delay between restarts
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.restart_delay = initial;
+        self.max_restart_delay = max;
+        self
+    }
+
+    /// Give up on a client after `max_restarts` consecutive failures (0 = never)
+    pub fn with_max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Start every client, returning a handle and the merged event stream
+    pub fn start(self) -> (SupervisorHandle, mpsc::Receiver<MarketEvent>) {
+        let (events_tx, events_rx) = create_event_channel();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let mut health = Vec::with_capacity(self.clients.len());
+        let mut tasks = Vec::with_capacity(self.clients.len());
+        for supervised in self.clients {
+            let connected = Arc::new(AtomicBool::new(false));
+            health.push((supervised.client.platform_name(), connected.clone()));
+
+            let policy = RestartPolicy {
+                delay: self.restart_delay,
+                max_delay: self.max_restart_delay,
+                max_restarts: self.max_restarts,
+            };
+            tasks.push(tokio::spawn(supervise(
+                supervised,
+                policy,
+                events_tx.clone(),
+                shutdown_rx.clone(),
+                connected,
+            )));
+        }
+
+        let handle = SupervisorHandle {
+            shutdown: shutdown_tx,
+            tasks,
+            health,
+        };
+        (handle, events_rx)
+    }
+}
+
+impl Default for ClientSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle to a running [`ClientSupervisor`]
+pub struct SupervisorHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+    health: Vec<(&'static str, Arc<AtomicBool>)>,
+}
+
+impl SupervisorHandle {
+    /// Connection state of each client, in the order they were added
+    pub fn health(&self) -> Vec<(&'static str, bool)> {
+        self.health
+            .iter()
+            .map(|(name, connected)| (*name, connected.load(Ordering::SeqCst)))
+            .collect()
+    }
+
+    /// Returns true if every client is connected
+    pub fn is_healthy(&self) -> bool {
+        self.health
+            .iter()
+            .all(|(_, connected)| connected.load(Ordering::SeqCst))
+    }
+
+    /// Disconnect every client and wait for the supervision tasks to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            if let Err(e) = task.await {
+                error!("Supervisor task panicked: {}", e);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RestartPolicy {
+    delay: Duration,
+    max_delay: Duration,
+    max_restarts: u32,
+}
+
+impl RestartPolicy {
+    /// Delay before the given restart attempt (1-based)
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Run one client until shutdown, restarting it whenever it fails
+async fn supervise(
+    mut supervised: Supervised,
+    policy: RestartPolicy,
+    events: mpsc::Sender<MarketEvent>,
+    mut shutdown: watch::Receiver<bool>,
+    connected: Arc<AtomicBool>,
+) {
+    let name = supervised.client.platform_name();
+    let mut attempt = 0u32;
+    // Learned from the client's events, for tagging `Reconnecting` statuses
+    let mut platform: Option<Platform> = None;
+
+    loop {
+        let (client_tx, mut client_rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        match start_client(&mut supervised, client_tx).await {
+            Ok(()) => loop {
+                tokio::select! {
+                    event = client_rx.recv() => {
+                        let Some(event) = event else {
+                            warn!("{} event stream ended", name);
+                            break;
+                        };
+                        platform = Some(event.platform());
+                        // Only a connection that delivered data counts as
+                        // recovered, so flapping connections keep backing off
+                        if event.received_at().is_some() {
+                            attempt = 0;
+                        }
+                        let failed = match &event {
+                            MarketEvent::ConnectionStatus { status, .. } => match status {
+                                ConnectionStatus::Connected => {
+                                    connected.store(true, Ordering::SeqCst);
+                                    false
+                                }
+                                ConnectionStatus::Disconnected(_) | ConnectionStatus::Error(_) => {
+                                    true
+                                }
+                                _ => false,
+                            },
+                            _ => false,
+                        };
+                        if events.send(event).await.is_err() {
+                            info!("Event receiver dropped, stopping {}", name);
+                            let _ = supervised.client.disconnect().await;
+                            return;
+                        }
+                        if failed {
+                            break;
+                        }
+                    }
+                    _ = shutdown.changed() => {
+                        stop(&mut supervised, &connected).await;
+                        return;
+                    }
+                }
+            },
+            Err(e) => error!("Failed to start {}: {}", name, e),
+        }
+
+        connected.store(false, Ordering::SeqCst);
+        let _ = supervised.client.disconnect().await;
+
+        attempt += 1;
+        if policy.max_restarts > 0 && attempt > policy.max_restarts {
+            error!(
+                "Giving up on {} after {} restarts",
+                name, policy.max_restarts
+            );
+            return;
+        }
+
+        let delay = policy.delay_for(attempt);
+        warn!("Restarting {} in {:?} (attempt {})", name, delay, attempt);
+        if let Some(platform) = platform {
+            let status = MarketEvent::ConnectionStatus {
+                platform,
+                status: ConnectionStatus::Reconnecting { attempt },
+            };
+            if events.send(status).await.is_err() {
+                return;
+            }
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.changed() => {
+                stop(&mut supervised, &connected).await;
+                return;
+            }
+        }
+    }
+}
+
+/// Connect, subscribe and start forwarding events for a client
+async fn start_client(
+    supervised: &mut Supervised,
+    sender: mpsc::Sender<MarketEvent>,
+) -> Result<()> {
+    supervised.client.connect().await?;
+    if !supervised.asset_ids.is_empty() {
+        supervised.client.subscribe(&supervised.asset_ids).await?;
+    }
+    supervised.client.start(sender).await
+}
+
+async fn stop(supervised: &mut Supervised, connected: &AtomicBool) {
+    connected.store(false, Ordering::SeqCst);
+    if let Err(e) = supervised.client.disconnect().await {
+        warn!(
+            "Error disconnecting {}: {}",
+            supervised.client.platform_name(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicU32;
+    use tokio::time::timeout;
+
+    /// Client whose first `failures` starts drop the connection right away
+    struct MockClient {
+        failures: u32,
+        starts: Arc<AtomicU32>,
+        disconnects: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl MarketClient for MockClient {
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe(&mut self, _asset_ids: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, _asset_ids: &[String]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self, sender: mpsc::Sender<MarketEvent>) -> Result<()> {
+            let start = self.starts.fetch_add(1, Ordering::SeqCst) + 1;
+            let fail = start <= self.failures;
+            tokio::spawn(async move {
+                let status = |status| MarketEvent::ConnectionStatus {
+                    platform: Platform::Kalshi,
+                    status,
+                };
+                let _ = sender.send(status(ConnectionStatus::Connected)).await;
+                if fail {
+                    let _ = sender
+                        .send(status(ConnectionStatus::Disconnected(None)))
+                        .await;
+                } else {
+                    sender.closed().await;
+                }
+            });
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            self.disconnects.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            false
+        }
+
+        fn platform_name(&self) -> &'static str {
+            "Mock"
+        }
+    }
+
+    fn mock(failures: u32) -> (Box<dyn MarketClient>, Arc<AtomicU32>, Arc<AtomicU32>) {
+        let starts = Arc::new(AtomicU32::new(0));
+        let disconnects = Arc::new(AtomicU32::new(0));
+        let client = MockClient {
+            failures,
+            starts: starts.clone(),
+            disconnects: disconnects.clone(),
+        };
+        (Box::new(client), starts, disconnects)
+    }
+
+    async fn next_status(rx: &mut mpsc::Receiver<MarketEvent>) -> ConnectionStatus {
+        match timeout(Duration::from_secs(2), rx.recv()).await {
+            Ok(Some(MarketEvent::ConnectionStatus { status, .. })) => status,
+            other => panic!("Expected connection status, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restarts_failed_client() {
+        let (client, starts, disconnects) = mock(2);
+        let (handle, mut rx) = ClientSupervisor::new()
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+            .with_client(client, vec!["asset".to_string()])
+            .start();
+
+        for attempt in 1..=2 {
+            assert_eq!(next_status(&mut rx).await, ConnectionStatus::Connected);
+            assert_eq!(
+                next_status(&mut rx).await,
+                ConnectionStatus::Disconnected(None)
+            );
+            assert_eq!(
+                next_status(&mut rx).await,
+                ConnectionStatus::Reconnecting { attempt }
+            );
+        }
+        assert_eq!(next_status(&mut rx).await, ConnectionStatus::Connected);
+
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+        assert_eq!(disconnects.load(Ordering::SeqCst), 2);
+        assert!(handle.is_healthy());
+        assert_eq!(handle.health(), vec![("Mock", true)]);
+
+        handle.shutdown().await;
+        assert_eq!(disconnects.load(Ordering::SeqCst), 3);
+        // Every sender is gone once the supervisor has stopped
+        assert!(timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_restarts() {
+        let (client, starts, _) = mock(u32::MAX);
+        let (healthy, _, _) = mock(0);
+        let (handle, mut rx) = ClientSupervisor::new()
+            .with_backoff(Duration::from_millis(5), Duration::from_millis(5))
+            .with_max_restarts(1)
+            .with_client(client, Vec::new())
+            .with_client(healthy, Vec::new())
+            .start();
+
+        // Drain until the failing client has been given up on
+        while starts.load(Ordering::SeqCst) < 2 {
+            next_status(&mut rx).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        assert_eq!(handle.health(), vec![("Mock", false), ("Mock", true)]);
+        assert!(!handle.is_healthy());
+
+        handle.shutdown().await;
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RestartPolicy {
+            delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            max_restarts: 0,
+        };
+        assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3), Duration::from_millis(400));
+        assert_eq!(policy.delay_for(4), Duration::from_millis(500));
+        assert_eq!(policy.delay_for(40), Duration::from_millis(500));
+    }
+}
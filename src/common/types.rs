@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
 
 /// Source platform identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -29,6 +30,16 @@ pub enum Side {
     Sell,
 }
 
+impl Side {
+    /// The other side of the book
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+}
+
 impl std::fmt::Display for Side {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -52,6 +63,11 @@ impl PriceLevel {
     pub fn new(price: Decimal, size: Decimal) -> Self {
         Self { price, size }
     }
+
+    /// Value of the level (price * size)
+    pub fn notional(&self) -> Decimal {
+        self.price * self.size
+    }
 }
 
 /// Full order book for a market
@@ -69,9 +85,15 @@ pub struct OrderBook {
     pub asks: Vec<PriceLevel>,
     /// Timestamp of this snapshot
     pub timestamp: DateTime<Utc>,
+    /// When this snapshot was received locally
+    #[serde(default = "Utc::now")]
+    pub received_at: DateTime<Utc>,
     /// Sequence number for ordering updates
     #[serde(default)]
     pub sequence: u64,
+    /// Exchange checksum of this book, if one was provided
+    #[serde(default)]
+    pub hash: Option<String>,
 }
 
 impl OrderBook {
@@ -100,6 +122,298 @@ impl OrderBook {
             _ => None,
         }
     }
+
+    /// Volume-weighted average price to fill `size` against this book
+    ///
+    /// A buy walks the asks and a sell walks the bids, best price first.
+    /// Returns None if the book lacks the depth to fill `size`.
+    pub fn vwap_for_size(&self, side: Side, size: Decimal) -> Option<Decimal> {
+        if size <= Decimal::ZERO {
+            return None;
+        }
+
+        let mut remaining = size;
+        let mut notional = Decimal::ZERO;
+        for level in self.levels_to_take(side) {
+            if level.size >= remaining {
+                notional += remaining * level.price;
+                return Some(notional / size);
+            }
+            notional += level.notional();
+            remaining -= level.size;
+        }
+        None
+    }
+
+    /// Total size available to `side` at prices no worse than `worst_price`
+    pub fn depth_within(&self, side: Side, worst_price: Decimal) -> Decimal {
+        self.levels_to_take(side)
+            .iter()
+            .take_while(|level| match side {
+                Side::Buy => level.price <= worst_price,
+                Side::Sell => level.price >= worst_price,
+            })
+            .map(|level| level.size)
+            .sum()
+    }
+
+    /// Single number for comparing how liquid books are; higher is better
+    ///
+    /// The thinner side's size within `depth` of the midpoint, scaled down
+    /// by the spread relative to the midpoint:
+    /// `min(bid_size, ask_size) * (1 - spread / midpoint)`. A book missing
+    /// either side, or with a spread as wide as its midpoint, scores zero.
+    pub fn liquidity_score(&self, depth: Decimal) -> Decimal {
+        let (Some(mid), Some(spread)) = (self.midpoint(), self.spread()) else {
+            return Decimal::ZERO;
+        };
+        let Some((bid_size, ask_size)) = self.size_around_mid(depth) else {
+            return Decimal::ZERO;
+        };
+        if mid.is_zero() {
+            return Decimal::ZERO;
+        }
+        let spread_factor = (Decimal::ONE - spread / mid).max(Decimal::ZERO);
+        bid_size.min(ask_size) * spread_factor
+    }
+
+    /// Whether the spread is at most `max_spread` and each side has at least
+    /// `min_depth` within `max_spread` of the midpoint
+    pub fn is_tradeable(&self, max_spread: Decimal, min_depth: Decimal) -> bool {
+        if self.spread().is_none_or(|spread| spread > max_spread) {
+            return false;
+        }
+        self.size_around_mid(max_spread)
+            .is_some_and(|(bid_size, ask_size)| bid_size >= min_depth && ask_size >= min_depth)
+    }
+
+    /// Bid and ask size within `distance` of the midpoint
+    fn size_around_mid(&self, distance: Decimal) -> Option<(Decimal, Decimal)> {
+        let mid = self.midpoint()?;
+        Some((
+            self.depth_within(Side::Sell, mid - distance),
+            self.depth_within(Side::Buy, mid + distance),
+        ))
+    }
+
+    /// Size imbalance over the top `levels` of each side
+    ///
+    /// `(bid_size - ask_size) / (bid_size + ask_size)`, from -1 (only asks)
+    /// to 1 (only bids). Returns None if there is no size in those levels.
+    pub fn imbalance(&self, levels: usize) -> Option<Decimal> {
+        let bid_size: Decimal = self.bids.iter().take(levels).map(|l| l.size).sum();
+        let ask_size: Decimal = self.asks.iter().take(levels).map(|l| l.size).sum();
+        let total = bid_size + ask_size;
+        if total.is_zero() {
+            return None;
+        }
+        Some((bid_size - ask_size) / total)
+    }
+
+    /// Size-weighted midpoint of the top of book
+    ///
+    /// `(bid * ask_size + ask * bid_size) / (bid_size + ask_size)`, which
+    /// leans towards the ask when bids are heavier and vice versa. Returns
+    /// None if either side is empty.
+    pub fn microprice(&self) -> Option<Decimal> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        let total = bid.size + ask.size;
+        if total.is_zero() {
+            return None;
+        }
+        Some((bid.price * ask.size + ask.price * bid.size) / total)
+    }
+
+    /// Infer the aggressor side of a trade at `price`
+    ///
+    /// Trades at or through the best ask are buys and trades at or through
+    /// the best bid are sells; inside the spread the trade is compared to the
+    /// midpoint. Returns None for an empty book or a trade exactly at mid.
+    pub fn infer_aggressor_side(&self, price: Decimal) -> Option<Side> {
+        if self.best_ask().is_some_and(|ask| price >= ask.price) {
+            return Some(Side::Buy);
+        }
+        if self.best_bid().is_some_and(|bid| price <= bid.price) {
+            return Some(Side::Sell);
+        }
+        match self.midpoint()?.cmp(&price) {
+            std::cmp::Ordering::Less => Some(Side::Buy),
+            std::cmp::Ordering::Greater => Some(Side::Sell),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Levels a taker on `side` trades against, best price first
+    pub(crate) fn levels_to_take(&self, side: Side) -> &[PriceLevel] {
+        match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        }
+    }
+
+    /// Apply an update to this book
+    ///
+    /// Snapshots replace both sides; deltas upsert each level and remove
+    /// levels whose size is zero. Sort order is preserved.
+    pub fn apply_update(&mut self, update: &OrderBookUpdate) {
+        if update.is_snapshot {
+            self.bids = update.bids.clone();
+            self.asks = update.asks.clone();
+        } else {
+            merge_levels(&mut self.bids, &update.bids);
+            merge_levels(&mut self.asks, &update.asks);
+        }
+        self.bids.retain(|level| !level.size.is_zero());
+        self.asks.retain(|level| !level.size.is_zero());
+        self.bids
+            .sort_by_key(|level| std::cmp::Reverse(level.price));
+        self.asks.sort_by_key(|level| level.price);
+        self.timestamp = update.timestamp;
+        self.received_at = update.received_at;
+        self.sequence = update.sequence;
+        self.hash = update.hash.clone();
+    }
+
+    /// Polymarket checksum of this book
+    ///
+    /// SHA-1 of the compact JSON summary the CLOB hashes: market, asset,
+    /// millisecond timestamp, levels in exchange order (bids ascending, asks
+    /// descending) and an empty hash field. Prices and sizes keep the scale
+    /// they were parsed with. Mirrors `generate_orderbook_summary_hash` in
+    /// Polymarket's py-clob-client.
+    pub fn checksum(&self) -> String {
+        #[derive(Serialize)]
+        struct LevelSummary {
+            price: String,
+            size: String,
+        }
+
+        #[derive(Serialize)]
+        struct BookSummary<'a> {
+            market: &'a str,
+            asset_id: &'a str,
+            timestamp: String,
+            bids: Vec<LevelSummary>,
+            asks: Vec<LevelSummary>,
+            hash: &'a str,
+        }
+
+        let summarize = |levels: &[PriceLevel]| -> Vec<LevelSummary> {
+            levels
+                .iter()
+                .rev()
+                .map(|level| LevelSummary {
+                    price: level.price.to_string(),
+                    size: level.size.to_string(),
+                })
+                .collect()
+        };
+
+        let summary = BookSummary {
+            market: &self.market_id,
+            asset_id: &self.asset_id,
+            timestamp: self.timestamp.timestamp_millis().to_string(),
+            bids: summarize(&self.bids),
+            asks: summarize(&self.asks),
+            hash: "",
+        };
+        let json = serde_json::to_string(&summary).expect("book summary serializes");
+        hex::encode(Sha1::digest(json.as_bytes()))
+    }
+
+    /// Check this book against an exchange-provided checksum
+    ///
+    /// A mismatch means the local book has drifted from the exchange and
+    /// should be replaced with a fresh snapshot.
+    pub fn verify_checksum(&self, expected: &str) -> bool {
+        self.checksum().eq_ignore_ascii_case(expected)
+    }
+
+    /// Compact price ladder of the top `depth` levels on each side
+    ///
+    /// Asks are listed highest first above the bids, so the best prices meet
+    /// in the middle, with prices and sizes right-aligned in columns. A
+    /// closing line gives the mid and spread without trailing zeros, or `-`
+    /// when a side is empty.
+    pub fn format_ladder(&self, depth: usize) -> String {
+        let asks = self.asks.iter().take(depth).rev();
+        let bids = self.bids.iter().take(depth);
+        let rows: Vec<_> = asks
+            .map(|level| ("ask", level))
+            .chain(bids.map(|level| ("bid", level)))
+            .map(|(side, level)| (side, level.price.to_string(), level.size.to_string()))
+            .collect();
+        let (price_width, size_width) = rows.iter().fold((0, 0), |(pw, sw), (_, price, size)| {
+            (pw.max(price.len()), sw.max(size.len()))
+        });
+
+        let mut out = format!("{} {}", self.platform, self.asset_id);
+        for (side, price, size) in &rows {
+            out.push_str(&format!(
+                "\n  {} {:>price_width$}  {:>size_width$}",
+                side, price, size
+            ));
+        }
+        let show = |value: Option<Decimal>| match value {
+            Some(value) => value.normalize().to_string(),
+            None => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "\n  mid {}  spread {}",
+            show(self.midpoint()),
+            show(self.spread())
+        ));
+        out
+    }
+
+    /// Level-by-level differences from this book to `other`
+    ///
+    /// Added levels are only in `other`, removed ones only in `self`, and
+    /// changed ones are at a price in both with a different size.
+    /// Identifiers, timestamps and sequence numbers aren't compared.
+    pub fn diff(&self, other: &OrderBook) -> BookDiff {
+        BookDiff {
+            bids: SideDiff::between(&self.bids, &other.bids),
+            asks: SideDiff::between(&self.asks, &other.asks),
+        }
+    }
+}
+
+/// Levels per side shown when an [`OrderBook`] is displayed
+const DEFAULT_LADDER_DEPTH: usize = 5;
+
+impl std::fmt::Display for OrderBook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format_ladder(DEFAULT_LADDER_DEPTH))
+    }
+}
+
+impl From<&OrderBookUpdate> for OrderBook {
+    fn from(update: &OrderBookUpdate) -> Self {
+        let mut book = OrderBook {
+            platform: update.platform,
+            market_id: update.market_id.clone(),
+            asset_id: update.asset_id.clone(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            timestamp: update.timestamp,
+            received_at: update.received_at,
+            sequence: update.sequence,
+            hash: None,
+        };
+        book.apply_update(update);
+        book
+    }
+}
+
+/// Upsert `changes` into `levels` by price
+fn merge_levels(levels: &mut Vec<PriceLevel>, changes: &[PriceLevel]) {
+    for change in changes {
+        match levels.iter_mut().find(|level| level.price == change.price) {
+            Some(level) => level.size = change.size,
+            None => levels.push(change.clone()),
+        }
+    }
 }
 
 /// Order book update (delta or snapshot)
@@ -117,11 +431,125 @@ pub struct OrderBookUpdate {
     pub asks: Vec<PriceLevel>,
     /// Timestamp of this update
     pub timestamp: DateTime<Utc>,
+    /// When this update was received locally
+    #[serde(default = "Utc::now")]
+    pub received_at: DateTime<Utc>,
     /// Whether this is a full snapshot or a delta update
     pub is_snapshot: bool,
     /// Sequence number for ordering
     #[serde(default)]
     pub sequence: u64,
+    /// Exchange checksum of the book after this update, if provided
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+impl OrderBookUpdate {
+    /// Build the delta that turns `old` into `new`
+    ///
+    /// Only levels whose size changed are included; levels missing from
+    /// `new` are sent with size zero. Identifiers and timestamps are taken
+    /// from `new`.
+    pub fn from_diff(old: &OrderBook, new: &OrderBook) -> Self {
+        let diff = old.diff(new);
+        let mut bids = diff.bids.into_delta();
+        let mut asks = diff.asks.into_delta();
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+
+        Self {
+            platform: new.platform,
+            market_id: new.market_id.clone(),
+            asset_id: new.asset_id.clone(),
+            bids,
+            asks,
+            timestamp: new.timestamp,
+            received_at: new.received_at,
+            is_snapshot: false,
+            sequence: new.sequence,
+            hash: new.hash.clone(),
+        }
+    }
+}
+
+/// Differences between two order books, from [`OrderBook::diff`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BookDiff {
+    pub bids: SideDiff,
+    pub asks: SideDiff,
+}
+
+impl BookDiff {
+    /// Whether the books have the same levels
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// Differences between one side of two order books
+///
+/// Levels are listed in the order they appear in their book.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SideDiff {
+    /// Levels only in the other book
+    pub added: Vec<PriceLevel>,
+    /// Levels only in this book
+    pub removed: Vec<PriceLevel>,
+    /// Levels in both books with different sizes
+    pub changed: Vec<LevelChange>,
+}
+
+/// A price level whose size differs between two books
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelChange {
+    pub price: Decimal,
+    /// Size in this book
+    pub old_size: Decimal,
+    /// Size in the other book
+    pub new_size: Decimal,
+}
+
+impl SideDiff {
+    fn between(old: &[PriceLevel], new: &[PriceLevel]) -> Self {
+        let find = |levels: &[PriceLevel], price| {
+            levels.iter().find(|level| level.price == price).cloned()
+        };
+        let mut diff = Self::default();
+        for level in old {
+            match find(new, level.price) {
+                None => diff.removed.push(level.clone()),
+                Some(other) if other.size != level.size => diff.changed.push(LevelChange {
+                    price: level.price,
+                    old_size: level.size,
+                    new_size: other.size,
+                }),
+                Some(_) => {}
+            }
+        }
+        diff.added = new
+            .iter()
+            .filter(|level| find(old, level.price).is_none())
+            .cloned()
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Levels to send as a delta, with removals as size zero
+    fn into_delta(self) -> Vec<PriceLevel> {
+        let changed = self
+            .changed
+            .into_iter()
+            .map(|change| PriceLevel::new(change.price, change.new_size));
+        let removed = self
+            .removed
+            .into_iter()
+            .map(|level| PriceLevel::new(level.price, Decimal::ZERO));
+        self.added.into_iter().chain(changed).chain(removed).collect()
+    }
 }
 
 /// A single trade execution
@@ -141,8 +569,34 @@ pub struct Trade {
     pub size: Decimal,
     /// Side of the taker order
     pub side: Side,
+    /// Where `side` came from
+    #[serde(default)]
+    pub side_source: TradeSideSource,
     /// Timestamp of the trade
     pub timestamp: DateTime<Utc>,
+    /// When this trade was received locally
+    #[serde(default = "Utc::now")]
+    pub received_at: DateTime<Utc>,
+}
+
+impl Trade {
+    /// Traded value (price * size)
+    pub fn notional(&self) -> Decimal {
+        self.price * self.size
+    }
+}
+
+/// How a trade's aggressor side was determined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSideSource {
+    /// Reported by the exchange
+    #[default]
+    Reported,
+    /// Inferred from the trade price relative to the order book
+    Inferred,
+    /// Not reported and no book was available; `side` is a placeholder
+    Unknown,
 }
 
 /// Market metadata and status
@@ -170,6 +624,61 @@ pub struct MarketInfo {
     pub neg_risk: bool,
 }
 
+/// Lifecycle state of one of our orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// Resting on the book with nothing filled
+    Live,
+    /// Part of the size has filled and the rest is still working
+    PartiallyFilled,
+    /// The whole size has filled
+    Filled,
+    /// Cancelled before it filled completely
+    Cancelled,
+    /// Reached its good-til-date before it filled completely
+    Expired,
+}
+
+impl OrderStatus {
+    /// Whether the order can still fill
+    pub fn is_open(&self) -> bool {
+        matches!(self, OrderStatus::Live | OrderStatus::PartiallyFilled)
+    }
+}
+
+/// Change in the state of one of our orders
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderUpdate {
+    /// Platform the order is on
+    pub platform: Platform,
+    /// Order identifier
+    pub order_id: String,
+    /// Market/condition identifier
+    pub market_id: String,
+    /// Asset/token ID
+    pub asset_id: String,
+    pub side: Side,
+    /// Limit price
+    pub price: Decimal,
+    /// Size the order was placed for
+    pub original_size: Decimal,
+    /// Size filled so far, including `fill`
+    pub size_matched: Decimal,
+    /// Price and size of the fill this update reports, if any
+    pub fill: Option<PriceLevel>,
+    pub status: OrderStatus,
+    /// When the change happened
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrderUpdate {
+    /// Size still working
+    pub fn remaining(&self) -> Decimal {
+        self.original_size - self.size_matched
+    }
+}
+
 /// Connection status for a client
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConnectionStatus {
@@ -184,7 +693,7 @@ pub enum ConnectionStatus {
 }
 
 /// Unified market event from any platform
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MarketEvent {
     /// Full order book snapshot
     OrderBook(OrderBook),
@@ -192,8 +701,17 @@ pub enum MarketEvent {
     OrderBookUpdate(OrderBookUpdate),
     /// Trade execution
     Trade(Trade),
+    /// Price of the most recent trade in an asset
+    LastTradePrice {
+        platform: Platform,
+        asset_id: String,
+        price: Decimal,
+        timestamp: DateTime<Utc>,
+    },
     /// Market info/metadata update
     MarketInfo(MarketInfo),
+    /// One of our orders was placed, filled, cancelled or expired
+    OrderUpdate(OrderUpdate),
     /// Connection status change
     ConnectionStatus {
         platform: Platform,
@@ -206,6 +724,12 @@ pub enum MarketEvent {
         platform: Platform,
         message: String,
     },
+    /// A sequence gap was detected; the book for this asset should be
+    /// refetched from a fresh snapshot
+    ResyncNeeded {
+        platform: Platform,
+        asset_id: String,
+    },
 }
 
 impl MarketEvent {
@@ -215,10 +739,58 @@ impl MarketEvent {
             MarketEvent::OrderBook(ob) => ob.platform,
             MarketEvent::OrderBookUpdate(update) => update.platform,
             MarketEvent::Trade(trade) => trade.platform,
+            MarketEvent::LastTradePrice { platform, .. } => *platform,
             MarketEvent::MarketInfo(info) => info.platform,
+            MarketEvent::OrderUpdate(update) => update.platform,
             MarketEvent::ConnectionStatus { platform, .. } => *platform,
             MarketEvent::Heartbeat { platform } => *platform,
             MarketEvent::Raw { platform, .. } => *platform,
+            MarketEvent::ResyncNeeded { platform, .. } => *platform,
+        }
+    }
+
+    /// Exchange timestamp of the market data in this event
+    ///
+    /// Returns None for market info, connection, heartbeat and raw events.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        match self {
+            MarketEvent::OrderBook(ob) => Some(ob.timestamp),
+            MarketEvent::OrderBookUpdate(update) => Some(update.timestamp),
+            MarketEvent::Trade(trade) => Some(trade.timestamp),
+            MarketEvent::LastTradePrice { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        }
+    }
+
+    /// Asset this event is about
+    ///
+    /// Returns None for market info, connection, heartbeat and raw events.
+    pub fn asset_id(&self) -> Option<&str> {
+        match self {
+            MarketEvent::OrderBook(ob) => Some(&ob.asset_id),
+            MarketEvent::OrderBookUpdate(update) => Some(&update.asset_id),
+            MarketEvent::Trade(trade) => Some(&trade.asset_id),
+            MarketEvent::LastTradePrice { asset_id, .. } => Some(asset_id),
+            MarketEvent::OrderUpdate(update) => Some(&update.asset_id),
+            MarketEvent::ResyncNeeded { asset_id, .. } => Some(asset_id),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a trade worth less than `min_notional`
+    pub fn is_trade_below(&self, min_notional: Decimal) -> bool {
+        matches!(self, MarketEvent::Trade(trade) if trade.notional() < min_notional)
+    }
+
+    /// When the market data in this event was received locally
+    ///
+    /// Returns None for connection, heartbeat and other non-data events.
+    pub fn received_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            MarketEvent::OrderBook(ob) => Some(ob.received_at),
+            MarketEvent::OrderBookUpdate(update) => Some(update.received_at),
+            MarketEvent::Trade(trade) => Some(trade.received_at),
+            _ => None,
         }
     }
 }
@@ -258,6 +830,133 @@ mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn test_side_opposite() {
+        assert_eq!(Side::Buy.opposite(), Side::Sell);
+        assert_eq!(Side::Sell.opposite(), Side::Buy);
+        assert_eq!(Side::Buy.opposite().opposite(), Side::Buy);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let level = |price: &str, size: &str| {
+            PriceLevel::new(price.parse().unwrap(), size.parse().unwrap())
+        };
+        let mut book = OrderBook {
+            platform: Platform::Polymarket,
+            market_id: "0xabc".to_string(),
+            asset_id: "123".to_string(),
+            bids: vec![level("0.50", "20.5"), level("0.48", "100")],
+            asks: vec![level("0.52", "30"), level("0.55", "200")],
+            timestamp: DateTime::from_timestamp_millis(1_700_000_000_000).unwrap(),
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        };
+
+        // Computed with the py-clob-client algorithm, not this implementation:
+        //   summary = {"market": "0xabc", "asset_id": "123",
+        //              "timestamp": "1700000000000",
+        //              "bids": [{"price": "0.48", "size": "100"},
+        //                       {"price": "0.50", "size": "20.5"}],
+        //              "asks": [{"price": "0.55", "size": "200"},
+        //                       {"price": "0.52", "size": "30"}],
+        //              "hash": ""}
+        //   hashlib.sha1(json.dumps(summary, separators=(",", ":")).encode()).hexdigest()
+        let expected = "f9dbb73a3c30d86573f340abe38677094b2f9e87";
+        assert_eq!(book.checksum(), expected);
+        assert!(book.verify_checksum(expected));
+        assert!(book.verify_checksum(&expected.to_uppercase()));
+
+        book.bids[1] = level("0.48", "90");
+        assert!(!book.verify_checksum(expected));
+    }
+
+    #[test]
+    fn test_event_timestamp() {
+        let ts = Utc::now() - chrono::Duration::seconds(5);
+        let book = OrderBook {
+            platform: Platform::Polymarket,
+            market_id: "m".to_string(),
+            asset_id: "a".to_string(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            timestamp: ts,
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        };
+        let update = OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "m".to_string(),
+            asset_id: "a".to_string(),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            timestamp: ts,
+            received_at: Utc::now(),
+            is_snapshot: false,
+            sequence: 0,
+            hash: None,
+        };
+        let trade = Trade {
+            platform: Platform::Polymarket,
+            market_id: "m".to_string(),
+            asset_id: "a".to_string(),
+            trade_id: "t".to_string(),
+            price: dec!(0.5),
+            size: dec!(1),
+            side: Side::Buy,
+            side_source: TradeSideSource::Reported,
+            timestamp: ts,
+            received_at: Utc::now(),
+        };
+
+        assert_eq!(MarketEvent::OrderBook(book).timestamp(), Some(ts));
+        assert_eq!(MarketEvent::OrderBookUpdate(update).timestamp(), Some(ts));
+        assert_eq!(MarketEvent::Trade(trade).timestamp(), Some(ts));
+
+        let platform = Platform::Kalshi;
+        for event in [
+            MarketEvent::ConnectionStatus {
+                platform,
+                status: ConnectionStatus::Connected,
+            },
+            MarketEvent::Heartbeat { platform },
+            MarketEvent::Raw {
+                platform,
+                message: "{}".to_string(),
+            },
+        ] {
+            assert_eq!(event.timestamp(), None);
+        }
+    }
+
+    #[test]
+    fn test_trade_notional_threshold() {
+        let trade = |price, size| {
+            MarketEvent::Trade(Trade {
+                platform: Platform::Polymarket,
+                market_id: "m".to_string(),
+                asset_id: "a".to_string(),
+                trade_id: "t".to_string(),
+                price,
+                size,
+                side: Side::Buy,
+                side_source: TradeSideSource::Reported,
+                timestamp: Utc::now(),
+                received_at: Utc::now(),
+            })
+        };
+
+        assert!(trade(dec!(0.50), dec!(1)).is_trade_below(dec!(1)));
+        assert!(!trade(dec!(0.50), dec!(2)).is_trade_below(dec!(1)));
+        assert!(!trade(dec!(0.50), dec!(10)).is_trade_below(dec!(1)));
+        assert!(!MarketEvent::Heartbeat {
+            platform: Platform::Polymarket
+        }
+        .is_trade_below(dec!(1)));
+    }
+
     #[test]
     fn test_order_book_midpoint() {
         let order_book = OrderBook {
@@ -267,7 +966,9 @@ mod tests {
             bids: vec![PriceLevel::new(dec!(0.45), dec!(100))],
             asks: vec![PriceLevel::new(dec!(0.55), dec!(100))],
             timestamp: Utc::now(),
+            received_at: Utc::now(),
             sequence: 1,
+            hash: None,
         };
 
         assert_eq!(order_book.midpoint(), Some(dec!(0.50)));
@@ -283,7 +984,9 @@ mod tests {
             bids: vec![],
             asks: vec![],
             timestamp: Utc::now(),
+            received_at: Utc::now(),
             sequence: 0,
+            hash: None,
         };
 
         assert!(order_book.midpoint().is_none());
@@ -291,4 +994,121 @@ mod tests {
         assert!(order_book.best_bid().is_none());
         assert!(order_book.best_ask().is_none());
     }
+
+    #[test]
+    fn test_apply_delta_updates_and_removes_levels() {
+        let mut order_book = OrderBook {
+            platform: Platform::Polymarket,
+            market_id: "test".to_string(),
+            asset_id: "token123".to_string(),
+            bids: vec![
+                PriceLevel::new(dec!(0.45), dec!(100)),
+                PriceLevel::new(dec!(0.44), dec!(50)),
+            ],
+            asks: vec![PriceLevel::new(dec!(0.55), dec!(100))],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: 1,
+            hash: None,
+        };
+        let update = OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "test".to_string(),
+            asset_id: "token123".to_string(),
+            bids: vec![
+                PriceLevel::new(dec!(0.46), dec!(10)),
+                PriceLevel::new(dec!(0.45), dec!(0)),
+            ],
+            asks: vec![PriceLevel::new(dec!(0.55), dec!(40))],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            is_snapshot: false,
+            sequence: 2,
+            hash: None,
+        };
+
+        order_book.apply_update(&update);
+
+        assert_eq!(
+            order_book.bids,
+            vec![
+                PriceLevel::new(dec!(0.46), dec!(10)),
+                PriceLevel::new(dec!(0.44), dec!(50)),
+            ]
+        );
+        assert_eq!(order_book.asks, vec![PriceLevel::new(dec!(0.55), dec!(40))]);
+        assert_eq!(order_book.sequence, 2);
+    }
+
+    fn diff_book(bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> OrderBook {
+        OrderBook {
+            platform: Platform::Polymarket,
+            market_id: "test".to_string(),
+            asset_id: "token123".to_string(),
+            bids,
+            asks,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_of_identical_books_is_empty() {
+        let book = diff_book(
+            vec![PriceLevel::new(dec!(0.45), dec!(100))],
+            vec![PriceLevel::new(dec!(0.55), dec!(50))],
+        );
+        let mut later = book.clone();
+        later.sequence = 7;
+        later.timestamp = book.timestamp + chrono::Duration::seconds(1);
+
+        assert!(book.diff(&later).is_empty());
+        assert_eq!(book.diff(&later), BookDiff::default());
+    }
+
+    #[test]
+    fn test_diff_reports_each_level() {
+        let local = diff_book(
+            vec![
+                PriceLevel::new(dec!(0.45), dec!(100)),
+                PriceLevel::new(dec!(0.44), dec!(20)),
+            ],
+            vec![PriceLevel::new(dec!(0.55), dec!(50))],
+        );
+
+        // One bid changed size
+        let mut fetched = local.clone();
+        fetched.bids[0].size = dec!(80);
+        let diff = local.diff(&fetched);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.bids.changed,
+            vec![LevelChange {
+                price: dec!(0.45),
+                old_size: dec!(100),
+                new_size: dec!(80),
+            }]
+        );
+        assert!(diff.bids.added.is_empty() && diff.bids.removed.is_empty());
+        assert!(diff.asks.is_empty());
+
+        // One ask added, one bid gone
+        let mut fetched = local.clone();
+        fetched.bids.pop();
+        fetched.asks.push(PriceLevel::new(dec!(0.56), dec!(10)));
+        let diff = local.diff(&fetched);
+        assert_eq!(
+            diff.bids.removed,
+            vec![PriceLevel::new(dec!(0.44), dec!(20))]
+        );
+        assert_eq!(diff.asks.added, vec![PriceLevel::new(dec!(0.56), dec!(10))]);
+        assert!(diff.bids.changed.is_empty() && diff.asks.removed.is_empty());
+
+        // The reverse diff swaps added and removed
+        let reverse = fetched.diff(&local);
+        assert_eq!(reverse.bids.added, diff.bids.removed);
+        assert_eq!(reverse.asks.removed, diff.asks.added);
+    }
 }
@@ -3,7 +3,7 @@
 use config::{Config, Environment, File};
 use std::path::Path;
 
-use super::types::AppConfig;
+use super::types::{default_kalshi_rest_url, default_kalshi_ws_url, AppConfig, KalshiConfig};
 use crate::common::errors::{ClientError, Result};
 
 /// Load configuration from file and environment variables
@@ -12,6 +12,8 @@ use crate::common::errors::{ClientError, Result};
 /// 1. Environment variables (prefixed with APP_)
 /// 2. Configuration file (TOML format)
 /// 3. Default values
+///
+/// The result is checked with [`AppConfig::validate`].
 pub fn load_config(config_path: Option<&str>) -> Result<AppConfig> {
     let mut builder = Config::builder();
 
@@ -41,35 +43,135 @@ pub fn load_config(config_path: Option<&str>) -> Result<AppConfig> {
         .build()
         .map_err(|e| ClientError::Configuration(e.to_string()))?;
 
-    config
+    let config: AppConfig = config
         .try_deserialize()
-        .map_err(|e| ClientError::Configuration(e.to_string()))
+        .map_err(|e| ClientError::Configuration(e.to_string()))?;
+    config.validate()?;
+    Ok(config)
 }
 
 /// Load configuration from environment variables only
+///
+/// Like [`load_config`], the result is checked with [`AppConfig::validate`].
 pub fn load_from_env() -> Result<AppConfig> {
     // Try to load from .env file
     dotenvy::dotenv().ok();
 
+    config_from_vars(|name| std::env::var(name).ok())
+}
+
+/// Validated configuration from variables looked up with `var`
+fn config_from_vars(var: impl Fn(&str) -> Option<String>) -> Result<AppConfig> {
     let polymarket_config = super::types::PolymarketConfig {
-        api_key: std::env::var("POLYMARKET_API_KEY").ok(),
-        api_secret: std::env::var("POLYMARKET_API_SECRET").ok(),
-        api_passphrase: std::env::var("POLYMARKET_API_PASSPHRASE").ok(),
-        rest_url: std::env::var("POLYMARKET_REST_URL")
-            .unwrap_or_else(|_| "https://clob.polymarket.com".to_string()),
-        websocket_url: std::env::var("POLYMARKET_WS_URL")
-            .unwrap_or_else(|_| "wss://ws-subscriptions-clob.polymarket.com".to_string()),
-        gamma_url: std::env::var("POLYMARKET_GAMMA_URL")
-            .unwrap_or_else(|_| "https://gamma-api.polymarket.com".to_string()),
-        markets: std::env::var("POLYMARKET_MARKETS")
-            .map(|s| s.split(',').map(|m| m.trim().to_string()).collect())
-            .unwrap_or_default(),
+        api_key: var("POLYMARKET_API_KEY"),
+        api_secret: var("POLYMARKET_API_SECRET"),
+        api_passphrase: var("POLYMARKET_API_PASSPHRASE"),
+        rest_url: var("POLYMARKET_REST_URL")
+            .unwrap_or_else(|| "https://clob.polymarket.com".to_string()),
+        websocket_url: var("POLYMARKET_WS_URL")
+            .unwrap_or_else(|| "wss://ws-subscriptions-clob.polymarket.com".to_string()),
+        gamma_url: var("POLYMARKET_GAMMA_URL")
+            .unwrap_or_else(|| "https://gamma-api.polymarket.com".to_string()),
+        markets: parse_list(var("POLYMARKET_MARKETS")),
+        ..Default::default()
     };
 
-    Ok(AppConfig {
+    let config = AppConfig {
         polymarket: polymarket_config,
-        kalshi: None,
+        kalshi: kalshi_from_vars(&var),
         database: None,
         settings: super::types::AppSettings::default(),
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+/// Environment variables read by [`kalshi_from_vars`]
+const KALSHI_ENV_VARS: [&str; 5] = [
+    "KALSHI_API_KEY",
+    "KALSHI_PRIVATE_KEY_PATH",
+    "KALSHI_REST_URL",
+    "KALSHI_WS_URL",
+    "KALSHI_MARKETS",
+];
+
+/// Kalshi configuration from environment variables
+///
+/// `var` looks up a variable by name, e.g. from the process environment.
+/// Returns `None` when none of the Kalshi variables are set, so
+/// Polymarket-only setups don't get a Kalshi section.
+fn kalshi_from_vars(var: impl Fn(&str) -> Option<String>) -> Option<KalshiConfig> {
+    if !KALSHI_ENV_VARS.iter().any(|name| var(name).is_some()) {
+        return None;
+    }
+
+    Some(KalshiConfig {
+        api_key: var("KALSHI_API_KEY"),
+        api_secret: None,
+        private_key_path: var("KALSHI_PRIVATE_KEY_PATH"),
+        rest_url: var("KALSHI_REST_URL").unwrap_or_else(default_kalshi_rest_url),
+        websocket_url: var("KALSHI_WS_URL").unwrap_or_else(default_kalshi_ws_url),
+        markets: parse_list(var("KALSHI_MARKETS")),
     })
 }
+
+/// Comma-separated list from a variable (empty if unset)
+fn parse_list(value: Option<String>) -> Vec<String> {
+    value
+        .map(|s| s.split(',').map(|m| m.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_kalshi_from_env() {
+        assert!(kalshi_from_vars(|_| None).is_none());
+
+        let vars = HashMap::from([
+            ("KALSHI_API_KEY", "key-id"),
+            ("KALSHI_PRIVATE_KEY_PATH", "/etc/kalshi/key.pem"),
+            ("KALSHI_WS_URL", "wss://demo.kalshi.co/trade-api/ws/v2"),
+            ("KALSHI_MARKETS", "KXBTC-25, KXETH-25"),
+        ]);
+        let kalshi = kalshi_from_vars(|name| vars.get(name).map(|v| v.to_string()))
+            .expect("Kalshi config should be loaded");
+        assert_eq!(kalshi.api_key.as_deref(), Some("key-id"));
+        assert_eq!(
+            kalshi.private_key_path.as_deref(),
+            Some("/etc/kalshi/key.pem")
+        );
+        assert_eq!(kalshi.rest_url, default_kalshi_rest_url());
+        assert_eq!(kalshi.websocket_url, "wss://demo.kalshi.co/trade-api/ws/v2");
+        assert_eq!(kalshi.markets, vec!["KXBTC-25", "KXETH-25"]);
+    }
+
+    #[test]
+    fn test_env_config_is_validated() {
+        let lookup = |vars: HashMap<&'static str, &'static str>| {
+            move |name: &str| vars.get(name).map(|v| v.to_string())
+        };
+
+        let config = config_from_vars(lookup(HashMap::new())).unwrap();
+        assert!(config.kalshi.is_none());
+
+        for (name, value) in [
+            ("KALSHI_WS_URL", ""),
+            ("POLYMARKET_REST_URL", "not a url"),
+            ("POLYMARKET_WS_URL", "https://example.com"),
+        ] {
+            assert!(
+                matches!(
+                    config_from_vars(lookup(HashMap::from([(name, value)]))),
+                    Err(ClientError::Configuration(_))
+                ),
+                "{}={:?} should be rejected",
+                name,
+                value
+            );
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::common::errors::{ClientError, Result};
+
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -18,6 +20,93 @@ pub struct AppConfig {
     pub settings: AppSettings,
 }
 
+impl AppConfig {
+    /// Check the configuration for values that would only fail at runtime
+    ///
+    /// URLs must be non-empty and valid with a scheme matching their use,
+    /// Polymarket credentials must be given all together or not at all, the
+    /// REST retry cap must be at least the base delay, and the heartbeat
+    /// interval must be positive.
+    pub fn validate(&self) -> Result<()> {
+        let polymarket = &self.polymarket;
+        validate_url(
+            "polymarket.rest_url",
+            &polymarket.rest_url,
+            &["http", "https"],
+        )?;
+        validate_url(
+            "polymarket.websocket_url",
+            &polymarket.websocket_url,
+            &["ws", "wss"],
+        )?;
+        validate_url(
+            "polymarket.gamma_url",
+            &polymarket.gamma_url,
+            &["http", "https"],
+        )?;
+
+        let credentials = [
+            &polymarket.api_key,
+            &polymarket.api_secret,
+            &polymarket.api_passphrase,
+        ];
+        let provided = credentials.iter().filter(|c| c.is_some()).count();
+        if provided != 0 && provided != credentials.len() {
+            return Err(ClientError::Configuration(
+                "Polymarket credentials are incomplete: set api_key, api_secret and \
+                 api_passphrase together, or none of them"
+                    .to_string(),
+            ));
+        }
+
+        if polymarket.max_retry_delay_ms < polymarket.retry_base_delay_ms {
+            return Err(ClientError::Configuration(format!(
+                "polymarket.max_retry_delay_ms ({}) must be at least retry_base_delay_ms ({})",
+                polymarket.max_retry_delay_ms, polymarket.retry_base_delay_ms
+            )));
+        }
+
+        if let Some(kalshi) = &self.kalshi {
+            validate_url("kalshi.rest_url", &kalshi.rest_url, &["http", "https"])?;
+            validate_url(
+                "kalshi.websocket_url",
+                &kalshi.websocket_url,
+                &["ws", "wss"],
+            )?;
+        }
+
+        if self.settings.heartbeat_interval_seconds == 0 {
+            return Err(ClientError::Configuration(
+                "settings.heartbeat_interval_seconds must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that `value` is a valid URL using one of `schemes`
+fn validate_url(field: &str, value: &str, schemes: &[&str]) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(ClientError::Configuration(format!(
+            "{} must not be empty",
+            field
+        )));
+    }
+    let url = url::Url::parse(value).map_err(|e| {
+        ClientError::Configuration(format!("{} is not a valid URL ({}): {}", field, value, e))
+    })?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(ClientError::Configuration(format!(
+            "{} must use {} (got {})",
+            field,
+            schemes.join(" or "),
+            value
+        )));
+    }
+    Ok(())
+}
+
 /// Polymarket platform configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolymarketConfig {
@@ -42,6 +131,16 @@ pub struct PolymarketConfig {
     /// List of market/token IDs to subscribe to
     #[serde(default)]
     pub markets: Vec<String>,
+    /// Retries for rate-limited (429) REST GET requests
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Backoff before the first REST retry in milliseconds, doubled per attempt
+    #[serde(default = "default_retry_base_delay")]
+    pub retry_base_delay_ms: u64,
+    /// Longest a single REST retry waits in milliseconds, even if the
+    /// server's `Retry-After` asks for more
+    #[serde(default = "default_max_retry_delay")]
+    pub max_retry_delay_ms: u64,
 }
 
 impl Default for PolymarketConfig {
@@ -54,11 +153,14 @@ impl Default for PolymarketConfig {
             websocket_url: default_polymarket_ws_url(),
             gamma_url: default_polymarket_gamma_url(),
             markets: Vec::new(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay(),
+            max_retry_delay_ms: default_max_retry_delay(),
         }
     }
 }
 
-fn default_polymarket_rest_url() -> String {
+pub(crate) fn default_polymarket_rest_url() -> String {
     "https://clob.polymarket.com".to_string()
 }
 
@@ -66,10 +168,22 @@ fn default_polymarket_ws_url() -> String {
     "wss://ws-subscriptions-clob.polymarket.com".to_string()
 }
 
-fn default_polymarket_gamma_url() -> String {
+pub(crate) fn default_polymarket_gamma_url() -> String {
     "https://gamma-api.polymarket.com".to_string()
 }
 
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_base_delay() -> u64 {
+    500
+}
+
+fn default_max_retry_delay() -> u64 {
+    30_000
+}
+
 /// Kalshi platform configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KalshiConfig {
@@ -79,6 +193,9 @@ pub struct KalshiConfig {
     /// API secret for signing requests
     #[serde(default)]
     pub api_secret: Option<String>,
+    /// Path to the PEM-encoded RSA private key used to sign requests
+    #[serde(default)]
+    pub private_key_path: Option<String>,
     /// Base URL for the REST API
     #[serde(default = "default_kalshi_rest_url")]
     pub rest_url: String,
@@ -90,11 +207,11 @@ pub struct KalshiConfig {
     pub markets: Vec<String>,
 }
 
-fn default_kalshi_rest_url() -> String {
+pub(super) fn default_kalshi_rest_url() -> String {
     "https://trading-api.kalshi.com/trade-api/v2".to_string()
 }
 
-fn default_kalshi_ws_url() -> String {
+pub(super) fn default_kalshi_ws_url() -> String {
     "wss://trading-api.kalshi.com/trade-api/ws/v2".to_string()
 }
 
@@ -184,3 +301,105 @@ impl ApiCredentials {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AppConfig {
+        AppConfig {
+            polymarket: PolymarketConfig {
+                api_key: None,
+                api_secret: None,
+                api_passphrase: None,
+                rest_url: default_polymarket_rest_url(),
+                websocket_url: default_polymarket_ws_url(),
+                gamma_url: default_polymarket_gamma_url(),
+                markets: Vec::new(),
+                max_retries: default_max_retries(),
+                retry_base_delay_ms: default_retry_base_delay(),
+                max_retry_delay_ms: default_max_retry_delay(),
+            },
+            kalshi: None,
+            database: None,
+            settings: AppSettings::default(),
+        }
+    }
+
+    fn error(config: &AppConfig) -> String {
+        match config.validate() {
+            Err(ClientError::Configuration(message)) => message,
+            other => panic!("Expected configuration error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(config().validate().is_ok());
+
+        let mut config = config();
+        config.polymarket.api_key = Some("key".to_string());
+        config.polymarket.api_secret = Some("secret".to_string());
+        config.polymarket.api_passphrase = Some("passphrase".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_url_rejected() {
+        let mut config = config();
+        config.polymarket.rest_url = String::new();
+        assert_eq!(error(&config), "polymarket.rest_url must not be empty");
+    }
+
+    #[test]
+    fn test_malformed_url_rejected() {
+        let mut config = config();
+        config.polymarket.gamma_url = "gamma-api.polymarket.com".to_string();
+        assert!(error(&config).starts_with("polymarket.gamma_url is not a valid URL"));
+
+        let mut config = self::config();
+        config.polymarket.websocket_url =
+            "https://ws-subscriptions-clob.polymarket.com".to_string();
+        assert!(error(&config).starts_with("polymarket.websocket_url must use ws or wss"));
+
+        let mut config = self::config();
+        config.kalshi = Some(KalshiConfig {
+            api_key: None,
+            api_secret: None,
+            private_key_path: None,
+            rest_url: "not a url".to_string(),
+            websocket_url: default_kalshi_ws_url(),
+            markets: Vec::new(),
+        });
+        assert!(error(&config).starts_with("kalshi.rest_url is not a valid URL"));
+    }
+
+    #[test]
+    fn test_partial_credentials_rejected() {
+        let mut config = config();
+        config.polymarket.api_key = Some("key".to_string());
+        config.polymarket.api_secret = Some("secret".to_string());
+        assert!(error(&config).starts_with("Polymarket credentials are incomplete"));
+    }
+
+    #[test]
+    fn test_retry_cap_below_base_delay_rejected() {
+        let mut config = config();
+        config.polymarket.retry_base_delay_ms = 1000;
+        config.polymarket.max_retry_delay_ms = 100;
+        assert_eq!(
+            error(&config),
+            "polymarket.max_retry_delay_ms (100) must be at least retry_base_delay_ms (1000)"
+        );
+    }
+
+    #[test]
+    fn test_zero_heartbeat_rejected() {
+        let mut config = config();
+        config.settings.heartbeat_interval_seconds = 0;
+        assert_eq!(
+            error(&config),
+            "settings.heartbeat_interval_seconds must be greater than 0"
+        );
+    }
+}
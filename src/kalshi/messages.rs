@@ -0,0 +1,552 @@
+//! Kalshi-specific message types
+//!
+//! Kalshi prices are integer cents of the YES contract's dollar payout and
+//! sizes are whole contracts. A Kalshi book lists resting bids on both
+//! sides: YES bids at `p` are bids for the contract, and NO bids at `p`
+//! are offers to sell YES at `100 - p`. The converters here turn both into
+//! the unified bids/asks of the YES contract, keyed by market ticker.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::warn;
+
+use crate::common::errors::Result;
+use crate::common::types::{
+    MarketEvent, OrderBookUpdate, Platform, PriceLevel, Side, Trade, TradeSideSource,
+};
+
+/// Side of a Kalshi market: the YES or the NO contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KalshiSide {
+    Yes,
+    No,
+}
+
+/// Command subscribing to channels for some markets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiSubscribeCommand {
+    /// Echoed in the reply so it can be matched to this command
+    pub id: u64,
+    /// Always "subscribe"
+    pub cmd: String,
+    pub params: KalshiSubscribeParams,
+}
+
+/// Parameters of a [`KalshiSubscribeCommand`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiSubscribeParams {
+    /// Channel names, e.g. "orderbook_delta", "ticker" or "trade"
+    pub channels: Vec<String>,
+    /// Markets to subscribe to; all markets if omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_tickers: Option<Vec<String>>,
+}
+
+impl KalshiSubscribeCommand {
+    /// Subscribe to `channels` for `market_tickers`
+    pub fn new(id: u64, channels: &[&str], market_tickers: Vec<String>) -> Self {
+        Self {
+            id,
+            cmd: "subscribe".to_string(),
+            params: KalshiSubscribeParams {
+                channels: channels.iter().map(|c| c.to_string()).collect(),
+                market_tickers: Some(market_tickers),
+            },
+        }
+    }
+}
+
+/// Reply confirming a subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiSubscribed {
+    pub channel: String,
+    /// Subscription ID carried by the channel's messages
+    pub sid: u64,
+}
+
+/// Reply to a command that failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiErrorMessage {
+    pub code: i64,
+    pub msg: String,
+}
+
+/// Full book of a market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiOrderbookSnapshot {
+    pub market_ticker: String,
+    /// YES bids as `[price_cents, contracts]`; omitted when empty
+    #[serde(default)]
+    pub yes: Vec<[i64; 2]>,
+    /// NO bids as `[price_cents, contracts]`; omitted when empty
+    #[serde(default)]
+    pub no: Vec<[i64; 2]>,
+}
+
+/// Change in size at one level of a market's book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiOrderbookDelta {
+    pub market_ticker: String,
+    /// Price in cents
+    pub price: i64,
+    /// Signed change in contracts resting at `price`
+    pub delta: i64,
+    pub side: KalshiSide,
+}
+
+/// Market price summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiTicker {
+    pub market_ticker: String,
+    /// Last traded YES price in cents
+    pub price: i64,
+    #[serde(default)]
+    pub yes_bid: Option<i64>,
+    #[serde(default)]
+    pub yes_ask: Option<i64>,
+    #[serde(default)]
+    pub volume: Option<i64>,
+    #[serde(default)]
+    pub open_interest: Option<i64>,
+    /// Unix time in seconds
+    pub ts: i64,
+}
+
+/// Public trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KalshiTrade {
+    pub trade_id: String,
+    pub market_ticker: String,
+    pub yes_price: i64,
+    pub no_price: i64,
+    /// Contracts traded
+    pub count: i64,
+    pub taker_side: KalshiSide,
+    /// Unix time in seconds
+    pub ts: i64,
+}
+
+/// Incoming WebSocket message types
+///
+/// Deserialized by the `type` tag; use [`KalshiIncomingMessage::from_value`]
+/// to fall back to `Unknown` for unrecognized types. Market data messages
+/// carry the subscription ID (`sid`), and book messages a per-subscription
+/// sequence number (`seq`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KalshiIncomingMessage {
+    OrderbookSnapshot {
+        sid: u64,
+        seq: u64,
+        msg: KalshiOrderbookSnapshot,
+    },
+    OrderbookDelta {
+        sid: u64,
+        seq: u64,
+        msg: KalshiOrderbookDelta,
+    },
+    Ticker {
+        sid: u64,
+        msg: KalshiTicker,
+    },
+    Trade {
+        sid: u64,
+        msg: KalshiTrade,
+    },
+    Subscribed {
+        #[serde(default)]
+        id: Option<u64>,
+        msg: KalshiSubscribed,
+    },
+    Error {
+        #[serde(default)]
+        id: Option<u64>,
+        msg: KalshiErrorMessage,
+    },
+    /// Generic/unknown message
+    #[serde(skip)]
+    Unknown(serde_json::Value),
+}
+
+impl KalshiIncomingMessage {
+    /// Message types with a dedicated variant
+    const KNOWN_TYPES: [&'static str; 6] = [
+        "orderbook_snapshot",
+        "orderbook_delta",
+        "ticker",
+        "trade",
+        "subscribed",
+        "error",
+    ];
+
+    /// Classify a JSON message by its `type`
+    ///
+    /// Messages with a missing or unrecognized type become `Unknown`; a
+    /// recognized type with a malformed body is an error.
+    pub fn from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let known = value
+            .get("type")
+            .and_then(|v| v.as_str())
+            .map(|t| Self::KNOWN_TYPES.contains(&t))
+            .unwrap_or(false);
+
+        if known {
+            serde_json::from_value(value)
+        } else {
+            Ok(Self::Unknown(value))
+        }
+    }
+}
+
+/// Price in dollars of a price in cents
+fn dollars(cents: i64) -> Decimal {
+    Decimal::new(cents, 2)
+}
+
+/// Unified level for a Kalshi bid on `side`, as seen from the YES contract
+fn yes_level(side: KalshiSide, cents: i64, contracts: i64) -> PriceLevel {
+    let price = match side {
+        KalshiSide::Yes => dollars(cents),
+        KalshiSide::No => Decimal::ONE - dollars(cents),
+    };
+    PriceLevel::new(price, Decimal::from(contracts))
+}
+
+/// Contracts resting at each price (in cents) on both sides of a market
+#[derive(Debug, Clone, Default)]
+struct KalshiBook {
+    yes: BTreeMap<i64, i64>,
+    no: BTreeMap<i64, i64>,
+}
+
+impl KalshiBook {
+    fn side_mut(&mut self, side: KalshiSide) -> &mut BTreeMap<i64, i64> {
+        match side {
+            KalshiSide::Yes => &mut self.yes,
+            KalshiSide::No => &mut self.no,
+        }
+    }
+}
+
+/// Turns Kalshi messages into unified [`MarketEvent`]s
+///
+/// Kalshi book deltas are signed changes in size, while an
+/// [`OrderBookUpdate`] carries the new size of each level, so the converter
+/// keeps the size of every level since the last snapshot. A delta for a
+/// market without a snapshot, or one that would leave a level negative,
+/// means the local book is out of step: it's dropped and a
+/// [`MarketEvent::ResyncNeeded`] is emitted instead.
+///
+/// Snapshots become `OrderBookUpdate`s with `is_snapshot` set, tickers
+/// become `LastTradePrice` events and trades become [`Trade`]s priced in
+/// YES terms. Both the market and asset ID are the market ticker.
+#[derive(Debug, Clone, Default)]
+pub struct KalshiEventConverter {
+    books: HashMap<String, KalshiBook>,
+}
+
+impl KalshiEventConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a text frame into an event
+    ///
+    /// Replies to commands and unrecognized messages come back as `Raw`.
+    pub fn parse(&mut self, text: &str) -> Result<MarketEvent> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let message = KalshiIncomingMessage::from_value(value)?;
+        Ok(self.convert(message).unwrap_or_else(|| MarketEvent::Raw {
+            platform: Platform::Kalshi,
+            message: text.to_string(),
+        }))
+    }
+
+    /// Convert a market data message, None for command replies and unknowns
+    pub fn convert(&mut self, message: KalshiIncomingMessage) -> Option<MarketEvent> {
+        match message {
+            KalshiIncomingMessage::OrderbookSnapshot { seq, msg, .. } => {
+                Some(self.convert_snapshot(msg, seq))
+            }
+            KalshiIncomingMessage::OrderbookDelta { seq, msg, .. } => {
+                Some(self.convert_delta(msg, seq))
+            }
+            KalshiIncomingMessage::Ticker { msg, .. } => Some(Self::convert_ticker(msg)),
+            KalshiIncomingMessage::Trade { msg, .. } => Some(Self::convert_trade(msg)),
+            KalshiIncomingMessage::Subscribed { .. }
+            | KalshiIncomingMessage::Error { .. }
+            | KalshiIncomingMessage::Unknown(_) => None,
+        }
+    }
+
+    /// Replace the tracked book and emit it as a snapshot update
+    fn convert_snapshot(&mut self, snapshot: KalshiOrderbookSnapshot, seq: u64) -> MarketEvent {
+        let book = KalshiBook {
+            yes: snapshot.yes.iter().map(|[p, s]| (*p, *s)).collect(),
+            no: snapshot.no.iter().map(|[p, s]| (*p, *s)).collect(),
+        };
+        // Best YES bid is the highest price; best ask is the highest NO bid
+        let bids = book
+            .yes
+            .iter()
+            .rev()
+            .map(|(&p, &s)| yes_level(KalshiSide::Yes, p, s))
+            .collect();
+        let asks = book
+            .no
+            .iter()
+            .rev()
+            .map(|(&p, &s)| yes_level(KalshiSide::No, p, s))
+            .collect();
+        self.books.insert(snapshot.market_ticker.clone(), book);
+
+        Self::book_update(snapshot.market_ticker, bids, asks, true, seq)
+    }
+
+    /// Apply a signed delta and emit the level's new size
+    fn convert_delta(&mut self, delta: KalshiOrderbookDelta, seq: u64) -> MarketEvent {
+        let Some(book) = self.books.get_mut(&delta.market_ticker) else {
+            return Self::resync(delta.market_ticker);
+        };
+        let levels = book.side_mut(delta.side);
+        let size = levels.get(&delta.price).copied().unwrap_or(0) + delta.delta;
+        if size < 0 {
+            warn!(
+                "Kalshi delta of {} at {} leaves {} negative; resyncing",
+                delta.delta, delta.price, delta.market_ticker
+            );
+            self.books.remove(&delta.market_ticker);
+            return Self::resync(delta.market_ticker);
+        }
+        if size == 0 {
+            levels.remove(&delta.price);
+        } else {
+            levels.insert(delta.price, size);
+        }
+
+        let level = yes_level(delta.side, delta.price, size);
+        let (bids, asks) = match delta.side {
+            KalshiSide::Yes => (vec![level], Vec::new()),
+            KalshiSide::No => (Vec::new(), vec![level]),
+        };
+        Self::book_update(delta.market_ticker, bids, asks, false, seq)
+    }
+
+    fn book_update(
+        ticker: String,
+        bids: Vec<PriceLevel>,
+        asks: Vec<PriceLevel>,
+        is_snapshot: bool,
+        seq: u64,
+    ) -> MarketEvent {
+        // Book messages carry no exchange time
+        let now = Utc::now();
+        MarketEvent::OrderBookUpdate(OrderBookUpdate {
+            platform: Platform::Kalshi,
+            market_id: ticker.clone(),
+            asset_id: ticker,
+            bids,
+            asks,
+            timestamp: now,
+            received_at: now,
+            is_snapshot,
+            sequence: seq,
+            hash: None,
+        })
+    }
+
+    fn resync(ticker: String) -> MarketEvent {
+        MarketEvent::ResyncNeeded {
+            platform: Platform::Kalshi,
+            asset_id: ticker,
+        }
+    }
+
+    fn convert_ticker(ticker: KalshiTicker) -> MarketEvent {
+        MarketEvent::LastTradePrice {
+            platform: Platform::Kalshi,
+            asset_id: ticker.market_ticker,
+            price: dollars(ticker.price),
+            timestamp: exchange_time(ticker.ts),
+        }
+    }
+
+    /// A YES taker buys the contract; a NO taker sells it
+    fn convert_trade(trade: KalshiTrade) -> MarketEvent {
+        let side = match trade.taker_side {
+            KalshiSide::Yes => Side::Buy,
+            KalshiSide::No => Side::Sell,
+        };
+        MarketEvent::Trade(Trade {
+            platform: Platform::Kalshi,
+            market_id: trade.market_ticker.clone(),
+            asset_id: trade.market_ticker,
+            trade_id: trade.trade_id,
+            price: dollars(trade.yes_price),
+            size: Decimal::from(trade.count),
+            side,
+            side_source: TradeSideSource::Reported,
+            timestamp: exchange_time(trade.ts),
+            received_at: Utc::now(),
+        })
+    }
+}
+
+/// Exchange timestamp in seconds, falling back to now if out of range
+fn exchange_time(ts: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const SNAPSHOT: &str = r#"{"type": "orderbook_snapshot", "sid": 2, "seq": 2, "msg": {"market_ticker": "FED-23DEC-T3.00", "yes": [[8, 300], [22, 333]], "no": [[54, 20], [56, 146]]}}"#;
+
+    fn update(event: MarketEvent) -> OrderBookUpdate {
+        match event {
+            MarketEvent::OrderBookUpdate(update) => update,
+            other => panic!("Expected OrderBookUpdate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_maps_no_bids_to_yes_asks() {
+        let mut converter = KalshiEventConverter::new();
+        let book = update(converter.parse(SNAPSHOT).unwrap());
+
+        assert_eq!(book.platform, Platform::Kalshi);
+        assert_eq!(book.asset_id, "FED-23DEC-T3.00");
+        assert!(book.is_snapshot);
+        assert_eq!(book.sequence, 2);
+        assert_eq!(
+            book.bids,
+            vec![
+                PriceLevel::new(dec!(0.22), dec!(333)),
+                PriceLevel::new(dec!(0.08), dec!(300)),
+            ]
+        );
+        // NO bids at 56 and 54 cents are YES offers at 44 and 46
+        assert_eq!(
+            book.asks,
+            vec![
+                PriceLevel::new(dec!(0.44), dec!(146)),
+                PriceLevel::new(dec!(0.46), dec!(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_signed_deltas_become_level_sizes() {
+        let mut converter = KalshiEventConverter::new();
+        let delta = |price: i64, delta: i64, side: &str, seq: u64| {
+            format!(
+                r#"{{"type": "orderbook_delta", "sid": 2, "seq": {seq}, "msg": {{"market_ticker": "FED-23DEC-T3.00", "price": {price}, "delta": {delta}, "side": "{side}"}}}}"#
+            )
+        };
+
+        // Without a snapshot there's nothing to apply a delta to
+        assert!(matches!(
+            converter.parse(&delta(22, 10, "yes", 1)).unwrap(),
+            MarketEvent::ResyncNeeded {
+                platform: Platform::Kalshi,
+                ..
+            }
+        ));
+
+        converter.parse(SNAPSHOT).unwrap();
+        let added = update(converter.parse(&delta(22, 17, "yes", 3)).unwrap());
+        assert!(!added.is_snapshot);
+        assert_eq!(added.sequence, 3);
+        assert_eq!(added.bids, vec![PriceLevel::new(dec!(0.22), dec!(350))]);
+        assert!(added.asks.is_empty());
+
+        let removed = update(converter.parse(&delta(56, -146, "no", 4)).unwrap());
+        assert_eq!(removed.asks, vec![PriceLevel::new(dec!(0.44), dec!(0))]);
+
+        let new_level = update(converter.parse(&delta(60, 5, "no", 5)).unwrap());
+        assert_eq!(new_level.asks, vec![PriceLevel::new(dec!(0.40), dec!(5))]);
+
+        // Taking out more than rests at a level means we missed an update
+        assert!(matches!(
+            converter.parse(&delta(8, -301, "yes", 6)).unwrap(),
+            MarketEvent::ResyncNeeded { .. }
+        ));
+        assert!(matches!(
+            converter.parse(&delta(22, 1, "yes", 7)).unwrap(),
+            MarketEvent::ResyncNeeded { .. }
+        ));
+    }
+
+    #[test]
+    fn test_trade_and_ticker() {
+        let mut converter = KalshiEventConverter::new();
+        let trade = r#"{"type": "trade", "sid": 11, "msg": {"trade_id": "d91bc706-ee49-470d-82d8-11418bda6fed", "market_ticker": "HIGHNY-22DEC23-B53.5", "yes_price": 36, "no_price": 64, "count": 136, "taker_side": "no", "ts": 1669149841}}"#;
+        match converter.parse(trade).unwrap() {
+            MarketEvent::Trade(trade) => {
+                assert_eq!(trade.platform, Platform::Kalshi);
+                assert_eq!(trade.asset_id, "HIGHNY-22DEC23-B53.5");
+                assert_eq!(trade.trade_id, "d91bc706-ee49-470d-82d8-11418bda6fed");
+                assert_eq!(trade.price, dec!(0.36));
+                assert_eq!(trade.size, dec!(136));
+                assert_eq!(trade.side, Side::Sell);
+                assert_eq!(trade.timestamp.timestamp(), 1669149841);
+            }
+            other => panic!("Expected Trade, got {:?}", other),
+        }
+
+        let ticker = r#"{"type": "ticker", "sid": 11, "msg": {"market_ticker": "FED-23DEC-T3.00", "price": 48, "yes_bid": 45, "yes_ask": 53, "volume": 33896, "open_interest": 20422, "dollar_volume": 16948, "dollar_open_interest": 10211, "ts": 1669149841}}"#;
+        match converter.parse(ticker).unwrap() {
+            MarketEvent::LastTradePrice {
+                asset_id, price, ..
+            } => {
+                assert_eq!(asset_id, "FED-23DEC-T3.00");
+                assert_eq!(price, dec!(0.48));
+            }
+            other => panic!("Expected LastTradePrice, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_round_trip() {
+        let command =
+            KalshiSubscribeCommand::new(1, &["orderbook_delta"], vec!["FED-23DEC-T3.00".into()]);
+        assert_eq!(
+            serde_json::to_value(&command).unwrap(),
+            serde_json::json!({
+                "id": 1,
+                "cmd": "subscribe",
+                "params": {"channels": ["orderbook_delta"], "market_tickers": ["FED-23DEC-T3.00"]}
+            })
+        );
+
+        let reply = serde_json::json!({"id": 1, "type": "subscribed", "msg": {"channel": "orderbook_delta", "sid": 1}});
+        match KalshiIncomingMessage::from_value(reply).unwrap() {
+            KalshiIncomingMessage::Subscribed { id, msg } => {
+                assert_eq!(id, Some(1));
+                assert_eq!(msg.sid, 1);
+            }
+            other => panic!("Expected Subscribed, got {:?}", other),
+        }
+
+        // Replies aren't market data
+        let mut converter = KalshiEventConverter::new();
+        let error =
+            r#"{"id": 2, "type": "error", "msg": {"code": 6, "msg": "Already subscribed"}}"#;
+        assert!(matches!(
+            converter.parse(error).unwrap(),
+            MarketEvent::Raw {
+                platform: Platform::Kalshi,
+                ..
+            }
+        ));
+        assert!(matches!(
+            KalshiIncomingMessage::from_value(serde_json::json!({"type": "fill"})).unwrap(),
+            KalshiIncomingMessage::Unknown(_)
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+//! Kalshi module - Message types for the Kalshi trading API
+
+pub mod messages;
+
+pub use messages::KalshiEventConverter;
@@ -5,22 +5,48 @@
 
 pub mod common;
 pub mod config;
+pub mod kalshi;
 pub mod polymarket;
 pub mod strategy;
 
 // Re-export commonly used types
+pub use common::aggregates::VolumeWindow;
+pub use common::aggregator::MultiMarketClient;
+pub use common::book_store::BookStore;
+pub use common::dispatch::dispatch_events;
 pub use common::errors::{ClientError, Result};
-pub use common::speedtest::{BenchmarkStats, SpeedTest, SpeedTestGuard, SpeedTestResult};
+pub use common::instrument::{LatencyRecorder, LatencySummary};
+pub use common::mock::MockMarketClient;
+pub use common::orderbook::OrderBookManager;
+pub use common::ratelimit::TokenBucket;
+pub use common::recorder::EventRecorder;
+pub use common::resilience::{
+    with_circuit_breaker, CircuitBreaker, CircuitBreakerPolicy, CircuitState,
+};
+pub use common::sequence::{SequenceCheck, SequenceTracker};
+pub use common::speedtest::{
+    BaselineMode, BenchmarkOptions, BenchmarkStats, SpeedTest, SpeedTestGuard, SpeedTestResult,
+};
+pub use common::state::SubscriptionState;
+pub use common::supervisor::{ClientSupervisor, SupervisorHandle};
 pub use common::types::{MarketEvent, OrderBook, OrderBookUpdate, Platform, PriceLevel, Side, Trade};
 pub use config::types::AppConfig;
+pub use kalshi::KalshiEventConverter;
 pub use polymarket::client::PolymarketClient;
-pub use polymarket::rest::PolymarketRestClient;
-pub use polymarket::websocket::PolymarketWebSocketClient;
+pub use polymarket::discovery::MarketWatcher;
+pub use polymarket::rest::{PolymarketRestClient, PolymarketRestClientBuilder};
+pub use polymarket::websocket::{ConnectionHandles, PolymarketWebSocketClient};
 
 // Strategy types
 pub use strategy::{
-    BoxedSizeCalculator, BoxedStrategy, ComputedSize, Decision, FeeCalculator,
-    InMemorySizeCalculator, MarketSubscription, PlatformFees, Position, SizeCalculator, SizeKey,
-    SizedIntent, SizedLeg, Strategy, StrategyContext, TradeIntent, TradeLeg,
+    AtomicityPolicy, BacktestReport, Backtester, BoxedIntentSink, BoxedSizeCalculator,
+    BoxedStrategy, ComputedSize, Decision, DecisionOutcome, DecisionRecorder, ExecutionReport,
+    FeeCalculator, InMemorySizeCalculator, IntentSink, LiveSizeCalculator, LoggingSink,
+    MarketSubscription, OrderBookSimulator, OrderType, PairMap, PaperExecutionSink, PlatformFees,
+    Position, PositionDiscrepancy, PositionTracker, ReconciliationReport, RestExecutionSink,
+    RiskLimits, RiskViolation, SizeCalculator, SizeKey, SizedIntent, SizedLeg, Strategy,
+    StrategyContext, StrategyRunner, TradeIntent, TradeLeg, Trader,
 };
+// Strategies share `Platform` and `Side` with market data; these aliases are
+// kept for code written against the old separate enums
 pub use strategy::{Platform as StrategyPlatform, Side as StrategySide};
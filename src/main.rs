@@ -5,7 +5,13 @@
 
 use anyhow::Result;
 use clap::Parser;
-use tracing::{info, Level};
+use polymarket_websocket::common::channels::create_event_channel;
+use polymarket_websocket::common::traits::MarketClient;
+use polymarket_websocket::config::loader::load_config;
+use polymarket_websocket::config::types::PolymarketConfig;
+use polymarket_websocket::PolymarketClient;
+use std::future::Future;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 /// CLI arguments for the application
@@ -60,14 +66,150 @@ async fn main() -> Result<()> {
     // Load environment variables from .env file if present
     dotenvy::dotenv().ok();
 
-    // TODO: Initialize configuration, clients, and decision engine
-    // This will be implemented as the project grows
+    let mut config = load_config(Some(&args.config))?;
+    if let Some(ref markets) = args.polymarket_markets {
+        merge_markets(&mut config.polymarket.markets, markets);
+    }
+    if args.kalshi_markets.is_some() {
+        warn!("Kalshi streaming is not supported yet, ignoring --kalshi-markets");
+    }
 
     info!("Application initialized successfully");
 
-    // Keep the application running
-    tokio::signal::ctrl_c().await?;
-    info!("Received shutdown signal, cleaning up...");
+    let shutdown = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl-C: {}", e);
+        }
+        info!("Received shutdown signal, cleaning up...");
+    };
+    let received = stream_events(config.polymarket, shutdown).await?;
+    info!("Received {} events", received);
 
     Ok(())
 }
+
+/// Add comma-separated market IDs to `markets`, skipping duplicates
+fn merge_markets(markets: &mut Vec<String>, list: &str) {
+    for market in list.split(',').map(str::trim) {
+        if !market.is_empty() && !markets.iter().any(|m| m == market) {
+            markets.push(market.to_string());
+        }
+    }
+}
+
+/// Stream and log Polymarket events until `shutdown` completes
+///
+/// Subscribes to the configured markets and disconnects cleanly once
+/// `shutdown` completes or the stream ends. Returns how many events were
+/// received.
+async fn stream_events(
+    config: PolymarketConfig,
+    shutdown: impl Future<Output = ()>,
+) -> Result<u64> {
+    let markets = config.markets.clone();
+    if markets.is_empty() {
+        warn!("No Polymarket markets configured; only connection events will arrive");
+    }
+
+    let mut client = PolymarketClient::new(config)?;
+    client.subscribe(&markets).await?;
+    client.connect().await?;
+
+    let (tx, mut rx) = create_event_channel();
+    client.start(tx).await?;
+    info!("Streaming {} Polymarket markets", markets.len());
+
+    let mut received = 0;
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Some(event) => {
+                    received += 1;
+                    info!("{:?}", event);
+                }
+                None => {
+                    warn!("Event stream ended");
+                    break;
+                }
+            },
+            _ = &mut shutdown => break,
+        }
+    }
+
+    client.disconnect().await?;
+    Ok(received)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use std::time::Duration;
+    use tokio::sync::{mpsc, oneshot};
+    use tokio_tungstenite::tungstenite::Message;
+
+    const BOOK: &str = r#"[{
+        "event_type": "book",
+        "asset_id": "token_1",
+        "market": "0x123456",
+        "bids": [{"price": "0.50", "size": "100"}],
+        "asks": [{"price": "0.55", "size": "80"}],
+        "timestamp": 1704067200
+    }]"#;
+
+    #[test]
+    fn test_merge_markets() {
+        let mut markets = vec!["a".to_string()];
+        merge_markets(&mut markets, " b, a,,c ");
+        assert_eq!(markets, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_events_starts_and_shuts_down() {
+        // Server that answers the subscription with a book and reports
+        // every frame it receives afterwards
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, mut frames) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(frame)) = ws.next().await {
+                match frame {
+                    Message::Text(text) if text.contains("token_1") => {
+                        ws.send(Message::Text(BOOK.to_string())).await.unwrap();
+                    }
+                    Message::Close(_) => {
+                        let _ = frames_tx.send("CLOSE".to_string());
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        let config = PolymarketConfig {
+            websocket_url: url,
+            markets: vec!["token_1".to_string()],
+            ..Default::default()
+        };
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let stream = tokio::spawn(stream_events(config, async {
+            let _ = stop_rx.await;
+        }));
+
+        // Give the book time to arrive, then shut down
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        stop_tx.send(()).unwrap();
+        let received = tokio::time::timeout(Duration::from_secs(5), stream)
+            .await
+            .expect("didn't shut down")
+            .unwrap()
+            .unwrap();
+
+        // Connected status and the book snapshot
+        assert!(received >= 2);
+        assert_eq!(frames.recv().await.as_deref(), Some("CLOSE"));
+    }
+}
@@ -1,15 +1,22 @@
 //! Main Polymarket client that combines REST and WebSocket functionality
 
 use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
-use tracing::{info, instrument};
+use tracing::{debug, info, instrument, warn};
 
+use super::metrics::{Metrics, MetricsSnapshot};
 use super::rest::PolymarketRestClient;
 use super::websocket::PolymarketWebSocketClient;
-use crate::common::errors::Result;
+use crate::common::channels::{ChannelStats, OverflowPolicy};
+use crate::common::errors::{ClientError, Result};
+use crate::common::orderbook::OrderBookManager;
+use crate::common::state::SubscriptionState;
 use crate::common::traits::MarketClient;
-use crate::common::types::{MarketEvent, OrderBook};
+use crate::common::types::{ConnectionStatus, MarketEvent, OrderBook, Platform};
 use crate::config::types::{ApiCredentials, PolymarketConfig};
 
 /// Combined Polymarket client for REST API and WebSocket connections
@@ -20,18 +27,40 @@ pub struct PolymarketClient {
     ws_client: Option<PolymarketWebSocketClient>,
     /// Configuration
     config: PolymarketConfig,
-    /// API credentials (optional)
-    credentials: Option<ApiCredentials>,
     /// Current subscribed markets
     subscribed_markets: Arc<RwLock<Vec<String>>>,
     /// Event sender for WebSocket events
     event_sender: Option<mpsc::Sender<MarketEvent>>,
+    /// Order books to resync after a reconnect (optional)
+    order_books: Option<Arc<RwLock<OrderBookManager>>>,
+    /// Consecutive reconnect attempts since the last successful connection
+    reconnect_attempts: u32,
+    /// Trades worth less than this are dropped from the stream (optional)
+    min_trade_notional: Option<Decimal>,
+    /// What the WebSocket does with events when the consumer falls behind
+    overflow_policy: OverflowPolicy,
+    /// Largest WebSocket message and frame accepted, in bytes (optional)
+    message_limits: Option<(Option<usize>, Option<usize>)>,
+    /// Counters shared by every WebSocket connection this client makes
+    metrics: Arc<Metrics>,
+    /// Whether WebSocket connections time parsing and book updates
+    latency_tracking: bool,
+    /// Where subscription state is persisted (optional)
+    state_file: Option<PathBuf>,
+    /// State loaded from `state_file` on the first connect
+    restored_state: Option<SubscriptionState>,
 }
 
 impl PolymarketClient {
     /// Create a new Polymarket client from configuration
     pub fn new(config: PolymarketConfig) -> Result<Self> {
-        let rest_client = PolymarketRestClient::new(&config.rest_url, &config.gamma_url)?;
+        let rest_client = PolymarketRestClient::builder()
+            .clob_url(&config.rest_url)
+            .gamma_url(&config.gamma_url)
+            .max_retries(config.max_retries)
+            .retry_base_delay(Duration::from_millis(config.retry_base_delay_ms))
+            .max_retry_delay(Duration::from_millis(config.max_retry_delay_ms))
+            .build()?;
 
         let credentials = match (&config.api_key, &config.api_secret, &config.api_passphrase) {
             (Some(key), Some(secret), Some(passphrase)) => {
@@ -41,22 +70,174 @@ impl PolymarketClient {
         };
 
         // Apply credentials to REST client if available
-        let rest_client = if let Some(ref creds) = credentials {
-            rest_client.with_credentials(creds.clone())
-        } else {
-            rest_client
+        let rest_client = match credentials {
+            Some(creds) => rest_client.with_credentials(creds),
+            None => rest_client,
         };
 
         Ok(Self {
             rest_client,
             ws_client: None,
             config,
-            credentials,
             subscribed_markets: Arc::new(RwLock::new(Vec::new())),
             event_sender: None,
+            order_books: None,
+            reconnect_attempts: 0,
+            min_trade_notional: None,
+            overflow_policy: OverflowPolicy::default(),
+            message_limits: None,
+            metrics: Arc::new(Metrics::default()),
+            latency_tracking: false,
+            state_file: None,
+            restored_state: None,
         })
     }
 
+    /// Attach an order book manager
+    ///
+    /// The manager is kept current from the market stream, resynced on
+    /// reconnect, and used to infer the side of unlabeled trades.
+    pub fn with_order_book_manager(mut self, manager: Arc<RwLock<OrderBookManager>>) -> Self {
+        self.order_books = Some(manager);
+        self
+    }
+
+    /// Drop streamed trades whose notional (price * size) is below `min_notional`
+    pub fn with_min_trade_notional(mut self, min_notional: Decimal) -> Self {
+        self.min_trade_notional = Some(min_notional);
+        self
+    }
+
+    /// Set what happens to streamed events when the consumer falls behind
+    ///
+    /// See [`PolymarketWebSocketClient::with_overflow_policy`].
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// Set the largest WebSocket message and frame accepted, in bytes
+    ///
+    /// See [`PolymarketWebSocketClient::with_message_limits`].
+    pub fn with_message_limits(
+        mut self,
+        max_message_size: Option<usize>,
+        max_frame_size: Option<usize>,
+    ) -> Self {
+        self.message_limits = Some((max_message_size, max_frame_size));
+        self
+    }
+
+    /// Time message parsing and order book updates
+    ///
+    /// See [`PolymarketWebSocketClient::with_latency_tracking`].
+    pub fn with_latency_tracking(mut self, enabled: bool) -> Self {
+        self.latency_tracking = enabled;
+        self
+    }
+
+    /// Persist subscriptions to `path` so they survive restarts
+    ///
+    /// On the first `connect`, assets saved in the file (if it exists) are
+    /// added to the subscriptions. The file is rewritten whenever the
+    /// subscriptions change and on `disconnect`, including the last book
+    /// sequence per asset when an order book manager is attached.
+    pub fn with_state_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.state_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// State loaded from the state file, if one was found on connect
+    pub fn restored_state(&self) -> Option<&SubscriptionState> {
+        self.restored_state.as_ref()
+    }
+
+    /// Current subscriptions and last seen sequence per asset
+    ///
+    /// Sequences come from the attached order book manager, falling back to
+    /// the restored state for assets without a book yet.
+    pub async fn subscription_state(&self) -> SubscriptionState {
+        let assets = self.subscribed_markets.read().await.clone();
+        let mut state = SubscriptionState::new(assets.clone());
+        for asset_id in &assets {
+            let restored = self
+                .restored_state
+                .as_ref()
+                .and_then(|restored| restored.last_sequences.get(asset_id));
+            if let Some(&sequence) = restored {
+                state.record_sequence(asset_id, sequence);
+            }
+        }
+        if let Some(ref books) = self.order_books {
+            let books = books.read().await;
+            for asset_id in &assets {
+                if let Some(book) = books.get(asset_id) {
+                    state.record_sequence(asset_id, book.sequence);
+                }
+            }
+        }
+        state
+    }
+
+    /// Write the current subscription state to the state file
+    ///
+    /// Fails with `ClientError::Configuration` if no state file is set.
+    pub async fn save_state(&self) -> Result<()> {
+        let path = self.state_file.as_ref().ok_or_else(|| {
+            ClientError::Configuration("No subscription state file configured".to_string())
+        })?;
+        self.subscription_state().await.save(path).await
+    }
+
+    /// Save state if a state file is set, logging rather than failing
+    async fn persist_state(&self) {
+        if self.state_file.is_some() {
+            if let Err(e) = self.save_state().await {
+                warn!("Failed to persist subscription state: {}", e);
+            }
+        }
+    }
+
+    /// Load the state file once, merging its assets into the subscriptions
+    async fn restore_state(&mut self) -> Result<()> {
+        if self.restored_state.is_some() {
+            return Ok(());
+        }
+        let Some(ref path) = self.state_file else {
+            return Ok(());
+        };
+        if !path.exists() {
+            debug!("No subscription state at {}", path.display());
+            return Ok(());
+        }
+
+        let state = SubscriptionState::load(path).await?;
+        let mut markets = self.subscribed_markets.write().await;
+        for asset_id in &state.assets {
+            if !markets.contains(asset_id) {
+                markets.push(asset_id.clone());
+            }
+        }
+        info!(
+            "Restored {} subscriptions from {}",
+            state.assets.len(),
+            path.display()
+        );
+        drop(markets);
+        self.restored_state = Some(state);
+        Ok(())
+    }
+
+    /// Backpressure metrics for the WebSocket event channel (None until started)
+    pub fn channel_stats(&self) -> Option<ChannelStats> {
+        self.ws_client.as_ref()?.channel_stats()
+    }
+
+    /// Counts of what the WebSocket has received, across reconnects
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
     /// Get a reference to the REST client
     pub fn rest(&self) -> &PolymarketRestClient {
         &self.rest_client
@@ -76,16 +257,100 @@ impl PolymarketClient {
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
         self.rest_client.get_order_book(token_id).await
     }
+
+    /// Refetch the book for an asset from REST, e.g. after a `ResyncNeeded` event
+    ///
+    /// The snapshot replaces the attached order book manager's book, if any,
+    /// clearing its resync flag.
+    pub async fn resync_order_book(&self, asset_id: &str) -> Result<OrderBook> {
+        let book = self.rest_client.get_order_book(asset_id).await?;
+        if let Some(ref books) = self.order_books {
+            books.write().await.apply_snapshot(book.clone());
+        }
+        Ok(book)
+    }
+
+    /// Re-establish the WebSocket connection and resubscribe
+    ///
+    /// Every maintained book is flagged for resync first, so deltas are
+    /// rejected until a fresh snapshot arrives from either the socket or the
+    /// REST bootstrap performed once the connection is back.
+    #[instrument(skip(self))]
+    pub async fn reconnect(&mut self) -> Result<()> {
+        let sender = self.event_sender.clone().ok_or_else(|| {
+            ClientError::WebSocketConnection("Cannot reconnect before start()".to_string())
+        })?;
+
+        self.reconnect_attempts += 1;
+        info!(
+            "Reconnecting to Polymarket (attempt {})",
+            self.reconnect_attempts
+        );
+        let _ = sender
+            .send(MarketEvent::ConnectionStatus {
+                platform: Platform::Polymarket,
+                status: ConnectionStatus::Reconnecting {
+                    attempt: self.reconnect_attempts,
+                },
+            })
+            .await;
+
+        if let Some(ref books) = self.order_books {
+            books.write().await.mark_all_for_resync();
+        }
+
+        // Dropping the old client would leave its tasks reading the old socket
+        if let Some(mut old) = self.ws_client.take() {
+            old.disconnect().await;
+        }
+        self.connect().await?;
+        self.start(sender).await?;
+        self.reconnect_attempts = 0;
+
+        self.bootstrap_order_books().await;
+        Ok(())
+    }
+
+    /// Fetch REST snapshots for every book still awaiting a resync
+    async fn bootstrap_order_books(&self) {
+        let Some(ref books) = self.order_books else {
+            return;
+        };
+
+        let stale = books.read().await.assets_needing_resync();
+        for asset_id in stale {
+            match self.rest_client.get_order_book(&asset_id).await {
+                Ok(book) => books.write().await.apply_snapshot(book),
+                Err(e) => warn!("Failed to bootstrap order book for {}: {}", asset_id, e),
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl MarketClient for PolymarketClient {
     #[instrument(skip(self))]
     async fn connect(&mut self) -> Result<()> {
+        self.restore_state().await?;
+        if let Some(mut old) = self.ws_client.take() {
+            old.disconnect().await;
+        }
         info!("Creating Polymarket WebSocket client");
 
-        let ws_client = PolymarketWebSocketClient::new_market_channel(&self.config.websocket_url);
-        self.ws_client = Some(ws_client);
+        let mut ws_client =
+            PolymarketWebSocketClient::new_market_channel(&self.config.websocket_url)
+                .with_metrics(self.metrics.clone())
+                .with_latency_tracking(self.latency_tracking);
+        if let Some(ref books) = self.order_books {
+            ws_client = ws_client.with_order_book_manager(books.clone());
+        }
+        if let Some(min_notional) = self.min_trade_notional {
+            ws_client = ws_client.with_min_trade_notional(min_notional);
+        }
+        if let Some((max_message_size, max_frame_size)) = self.message_limits {
+            ws_client = ws_client.with_message_limits(max_message_size, max_frame_size);
+        }
+        self.ws_client = Some(ws_client.with_overflow_policy(self.overflow_policy));
 
         Ok(())
     }
@@ -93,20 +358,47 @@ impl MarketClient for PolymarketClient {
     #[instrument(skip(self))]
     async fn subscribe(&mut self, asset_ids: &[String]) -> Result<()> {
         let mut markets = self.subscribed_markets.write().await;
+
+        let mut new_ids: Vec<String> = Vec::new();
         for id in asset_ids {
-            if !markets.contains(id) {
-                markets.push(id.clone());
+            if !markets.contains(id) && !new_ids.contains(id) {
+                new_ids.push(id.clone());
             }
         }
+
+        if new_ids.is_empty() {
+            debug!("All requested markets already subscribed");
+            return Ok(());
+        }
+
+        // Live connections only learn about new assets via an incremental op
+        if let Some(ref ws_client) = self.ws_client {
+            if ws_client.is_connected() {
+                ws_client.subscribe(&new_ids).await?;
+            }
+        }
+
+        markets.extend(new_ids);
         info!("Subscribed to {} markets", markets.len());
+        drop(markets);
+        self.persist_state().await;
         Ok(())
     }
 
     #[instrument(skip(self))]
     async fn unsubscribe(&mut self, asset_ids: &[String]) -> Result<()> {
         let mut markets = self.subscribed_markets.write().await;
+
+        if let Some(ref ws_client) = self.ws_client {
+            if ws_client.is_connected() {
+                ws_client.unsubscribe(asset_ids).await?;
+            }
+        }
+
         markets.retain(|m| !asset_ids.contains(m));
         info!("Unsubscribed from markets, {} remaining", markets.len());
+        drop(markets);
+        self.persist_state().await;
         Ok(())
     }
 
@@ -125,8 +417,10 @@ impl MarketClient for PolymarketClient {
 
     #[instrument(skip(self))]
     async fn disconnect(&mut self) -> Result<()> {
-        // WebSocket will be dropped and closed
-        self.ws_client = None;
+        self.persist_state().await;
+        if let Some(mut ws_client) = self.ws_client.take() {
+            ws_client.disconnect().await;
+        }
         self.event_sender = None;
         info!("Disconnected from Polymarket");
         Ok(())
@@ -148,10 +442,295 @@ impl MarketClient for PolymarketClient {
 mod tests {
     use super::*;
 
+    use crate::polymarket::test_utils::{assert_no_data_frame, next_data_frame, spawn_test_server};
+
     #[test]
     fn test_client_creation() {
         let config = PolymarketConfig::default();
         let client = PolymarketClient::new(config);
         assert!(client.is_ok());
     }
+
+    /// Create a client connected to a local test server, initially subscribed to `initial`
+    async fn connected_client(
+        initial: &[&str],
+    ) -> (
+        PolymarketClient,
+        mpsc::UnboundedReceiver<String>,
+        mpsc::Receiver<MarketEvent>,
+    ) {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let config = PolymarketConfig {
+            websocket_url: url,
+            ..Default::default()
+        };
+        let mut client = PolymarketClient::new(config).unwrap();
+        let initial: Vec<String> = initial.iter().map(|s| s.to_string()).collect();
+        client.subscribe(&initial).await.unwrap();
+        client.connect().await.unwrap();
+
+        let (tx, rx) = mpsc::channel(100);
+        client.start(tx).await.unwrap();
+
+        // Drain the initial subscription message
+        next_data_frame(&mut frames).await;
+        (client, frames, rx)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_sends_op_for_new_assets() {
+        let (mut client, mut frames, _rx) = connected_client(&["asset_a"]).await;
+
+        client
+            .subscribe(&["asset_a".to_string(), "asset_b".to_string()])
+            .await
+            .unwrap();
+
+        let op: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(
+            op,
+            serde_json::json!({"operation": "subscribe", "assets_ids": ["asset_b"]})
+        );
+        assert_no_data_frame(&mut frames).await;
+        assert_eq!(client.subscribed_markets.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_sends_op_and_updates_list() {
+        let (mut client, mut frames, _rx) = connected_client(&["asset_a", "asset_b"]).await;
+
+        client.unsubscribe(&["asset_a".to_string()]).await.unwrap();
+
+        let op: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(
+            op,
+            serde_json::json!({"operation": "unsubscribe", "assets_ids": ["asset_a"]})
+        );
+        assert_eq!(
+            *client.subscribed_markets.read().await,
+            vec!["asset_b".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_duplicates_send_nothing() {
+        let (mut client, mut frames, _rx) = connected_client(&["asset_a", "asset_b"]).await;
+
+        client
+            .subscribe(&["asset_b".to_string(), "asset_a".to_string()])
+            .await
+            .unwrap();
+
+        assert_no_data_frame(&mut frames).await;
+        assert_eq!(client.subscribed_markets.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_resyncs_order_books() {
+        use crate::common::types::{OrderBookUpdate, PriceLevel};
+        use rust_decimal_macros::dec;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let rest = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/book"))
+            .and(query_param("token_id", "asset_a"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "market": "market",
+                "asset_id": "asset_a",
+                "bids": [{"price": "0.41", "size": "10"}],
+                "asks": [{"price": "0.59", "size": "10"}]
+            })))
+            .mount(&rest)
+            .await;
+
+        let snapshot = |asset_id: &str, bid| OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "market".to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![PriceLevel::new(bid, dec!(10))],
+            asks: vec![PriceLevel::new(dec!(0.60), dec!(10))],
+            timestamp: chrono::Utc::now(),
+            received_at: chrono::Utc::now(),
+            is_snapshot: true,
+            sequence: 0,
+            hash: None,
+        };
+        let books = Arc::new(RwLock::new(OrderBookManager::new()));
+        for asset_id in ["asset_a", "asset_b"] {
+            books
+                .write()
+                .await
+                .apply_update(&snapshot(asset_id, dec!(0.40)));
+        }
+
+        let (url, mut frames) = spawn_test_server(true).await;
+        let config = PolymarketConfig {
+            websocket_url: url,
+            rest_url: rest.uri(),
+            ..Default::default()
+        };
+        let mut client = PolymarketClient::new(config)
+            .unwrap()
+            .with_order_book_manager(books.clone());
+        let assets = vec!["asset_a".to_string(), "asset_b".to_string()];
+        client.subscribe(&assets).await.unwrap();
+        client.connect().await.unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        client.start(tx).await.unwrap();
+        next_data_frame(&mut frames).await;
+
+        client.reconnect().await.unwrap();
+        assert_eq!(client.metrics().reconnects, 1);
+        assert_eq!(next_data_frame(&mut frames).await, "CLOSE");
+
+        // The new connection resubscribes to everything
+        let sub: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(sub["assets_ids"], serde_json::json!(assets));
+        assert!(client.is_connected());
+
+        let mut saw_reconnecting = false;
+        while let Ok(event) = rx.try_recv() {
+            if let MarketEvent::ConnectionStatus {
+                status: ConnectionStatus::Reconnecting { attempt: 1 },
+                ..
+            } = event
+            {
+                saw_reconnecting = true;
+            }
+        }
+        assert!(saw_reconnecting);
+
+        // asset_a was bootstrapped over REST, asset_b is still stale
+        let mut books = books.write().await;
+        assert!(!books.needs_resync("asset_a"));
+        assert_eq!(
+            books.get("asset_a").unwrap().best_bid().unwrap().price,
+            dec!(0.41)
+        );
+        assert!(books.needs_resync("asset_b"));
+
+        let mut delta = snapshot("asset_b", dec!(0.45));
+        delta.is_snapshot = false;
+        assert!(!books.apply_update(&delta));
+
+        books.apply_update(&snapshot("asset_b", dec!(0.42)));
+        assert!(!books.needs_resync("asset_b"));
+        assert!(books.apply_update(&delta));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_stops_old_connection() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio::sync::broadcast;
+        use tokio_tungstenite::tungstenite::protocol::Message;
+
+        // Every connection forwards what the test publishes once subscribed
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (publish, _) = broadcast::channel::<String>(16);
+        let (subscribed_tx, mut subscribed) = mpsc::unbounded_channel();
+        let server_publish = publish.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut published = server_publish.subscribe();
+                let subscribed_tx = subscribed_tx.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    loop {
+                        tokio::select! {
+                            msg = ws.next() => match msg {
+                                Some(Ok(Message::Text(text))) if text.contains("assets_ids") => {
+                                    let _ = subscribed_tx.send(());
+                                }
+                                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                                _ => {}
+                            },
+                            Ok(text) = published.recv() => {
+                                if ws.send(Message::Text(text)).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let config = PolymarketConfig {
+            websocket_url: url,
+            ..Default::default()
+        };
+        let mut client = PolymarketClient::new(config).unwrap();
+        client.subscribe(&["asset_a".to_string()]).await.unwrap();
+        client.connect().await.unwrap();
+        let (tx, mut rx) = mpsc::channel(100);
+        client.start(tx).await.unwrap();
+        subscribed.recv().await.unwrap();
+
+        client.reconnect().await.unwrap();
+        client.reconnect().await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(3), async {
+            subscribed.recv().await.unwrap();
+            subscribed.recv().await.unwrap();
+        })
+        .await
+        .expect("reconnects didn't resubscribe");
+
+        let trade = r#"{"event_type": "trade", "asset_id": "asset_a", "id": "t1", "price": "0.5", "size": "1", "side": "BUY"}"#;
+        publish.send(trade.to_string()).unwrap();
+
+        let mut trades = 0;
+        while let Ok(Some(event)) =
+            tokio::time::timeout(std::time::Duration::from_millis(300), rx.recv()).await
+        {
+            if matches!(event, MarketEvent::Trade(_)) {
+                trades += 1;
+            }
+        }
+        assert_eq!(trades, 1);
+    }
+
+    #[tokio::test]
+    async fn test_state_file_restores_subscriptions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let mut saved = SubscriptionState::new(vec!["asset_a".to_string(), "asset_b".to_string()]);
+        saved.record_sequence("asset_a", 12);
+        saved.save(&path).await.unwrap();
+
+        let (url, mut frames) = spawn_test_server(true).await;
+        let config = PolymarketConfig {
+            websocket_url: url,
+            ..Default::default()
+        };
+        let mut client = PolymarketClient::new(config)
+            .unwrap()
+            .with_state_file(&path);
+        client.connect().await.unwrap();
+        assert_eq!(client.restored_state(), Some(&saved));
+
+        let (tx, _rx) = mpsc::channel(100);
+        client.start(tx).await.unwrap();
+        let subscription: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(
+            subscription["assets_ids"],
+            serde_json::json!(["asset_a", "asset_b"])
+        );
+
+        // Changes are written back, keeping restored sequences
+        client.subscribe(&["asset_c".to_string()]).await.unwrap();
+        client.unsubscribe(&["asset_b".to_string()]).await.unwrap();
+        let reloaded = SubscriptionState::load(&path).await.unwrap();
+        assert_eq!(reloaded.assets, vec!["asset_a", "asset_c"]);
+        assert_eq!(
+            reloaded.last_sequences,
+            std::collections::HashMap::from([("asset_a".to_string(), 12)])
+        );
+    }
 }
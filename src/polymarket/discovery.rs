@@ -0,0 +1,305 @@
+//! Polling for markets being listed and delisted on Gamma
+
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+use super::rest::PolymarketRestClient;
+use crate::common::errors::Result;
+use crate::common::types::{MarketEvent, MarketInfo};
+
+/// Default time between polls of the market listing
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default number of Gamma pages fetched per poll
+const DEFAULT_MAX_PAGES: usize = 20;
+
+/// Watches the Gamma market listing and reports what changed
+///
+/// Every poll fetches the active markets and compares them with the
+/// previous poll. New markets and markets whose metadata changed are sent
+/// as `MarketEvent::MarketInfo`. A market that closed, went inactive or
+/// dropped out of the listing is sent once more with `is_active: false`,
+/// which is the signal to stop trading it; it's then forgotten, so it's
+/// reported as new if it ever comes back. The first poll reports every
+/// active market. A listing cut short by `max_pages` can't tell delisted
+/// markets from ones on later pages, so such polls report no removals.
+pub struct MarketWatcher {
+    rest: PolymarketRestClient,
+    interval: Duration,
+    max_pages: usize,
+    /// Active markets from the last poll, by condition ID
+    known: HashMap<String, MarketInfo>,
+}
+
+impl MarketWatcher {
+    pub fn new(rest: PolymarketRestClient) -> Self {
+        Self {
+            rest,
+            interval: DEFAULT_POLL_INTERVAL,
+            max_pages: DEFAULT_MAX_PAGES,
+            known: HashMap::new(),
+        }
+    }
+
+    /// Set the time between polls
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how many pages of markets are fetched per poll
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Active markets as of the last poll
+    pub fn markets(&self) -> impl Iterator<Item = &MarketInfo> {
+        self.known.values()
+    }
+
+    /// Fetch the listing once and return the changes since the last poll
+    ///
+    /// Removals come after new and changed markets, ordered by market ID.
+    /// On error the previous listing is kept.
+    pub async fn poll(&mut self) -> Result<Vec<MarketEvent>> {
+        let listing = self
+            .rest
+            .get_gamma_markets_paged(Some(true), self.max_pages)
+            .await?;
+
+        let mut events = Vec::new();
+        let mut previous = std::mem::take(&mut self.known);
+        for info in listing.items.iter().filter_map(MarketInfo::from_gamma) {
+            let before = previous.remove(&info.market_id);
+            if before.as_ref() == Some(&info) {
+                self.known.insert(info.market_id.clone(), info);
+                continue;
+            }
+            // Inactive markets only matter if they were trading before
+            if !info.is_active && before.is_none() {
+                continue;
+            }
+            if info.is_active {
+                self.known.insert(info.market_id.clone(), info.clone());
+            }
+            events.push(MarketEvent::MarketInfo(info));
+        }
+
+        if !listing.complete {
+            // Unseen markets may just be past the last page fetched
+            debug!(
+                "Market listing truncated at {} pages, not reporting removals",
+                self.max_pages
+            );
+            self.known.extend(previous.drain());
+        }
+        let mut removed: Vec<MarketInfo> = previous.into_values().collect();
+        removed.sort_by(|a, b| a.market_id.cmp(&b.market_id));
+        events.extend(removed.into_iter().map(|mut info| {
+            info.is_active = false;
+            MarketEvent::MarketInfo(info)
+        }));
+
+        debug!(
+            "Market poll found {} changes, {} active markets",
+            events.len(),
+            self.known.len()
+        );
+        Ok(events)
+    }
+
+    /// Poll on a background task, sending changes to `sender`
+    ///
+    /// The first poll runs straight away. Failed polls are logged and
+    /// retried at the next interval. The task ends once the receiver is
+    /// dropped.
+    pub fn spawn(mut self, sender: mpsc::Sender<MarketEvent>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = sender.closed() => break,
+                }
+                let events = match self.poll().await {
+                    Ok(events) => events,
+                    Err(e) => {
+                        warn!("Market discovery poll failed: {}", e);
+                        continue;
+                    }
+                };
+                for event in events {
+                    if sender.send(event).await.is_err() {
+                        info!("Market discovery stopped, receiver dropped");
+                        return;
+                    }
+                }
+            }
+            info!("Market discovery stopped, receiver dropped");
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn market(id: &str, question: &str, active: bool, closed: bool) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "question": question,
+            "condition_id": format!("0x{}", id),
+            "active": active,
+            "closed": closed,
+            "tokens": [
+                {"token_id": format!("{}_yes", id), "outcome": "Yes"},
+                {"token_id": format!("{}_no", id), "outcome": "No"}
+            ]
+        })
+    }
+
+    async fn next_info(rx: &mut mpsc::Receiver<MarketEvent>) -> (String, String, bool) {
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("timed out waiting for a market event")
+            .expect("watcher stopped");
+        match event {
+            MarketEvent::MarketInfo(info) => (info.market_id, info.title, info.is_active),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reports_listing_changes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    market("a", "Rain?", true, false),
+                    market("b", "Snow?", true, false),
+                    market("c", "Hail?", true, false),
+                    market("d", "Fog?", true, true),
+                ]
+            })))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    market("a", "Rain tomorrow?", true, false),
+                    market("b", "Snow?", true, true),
+                    market("d", "Fog?", true, true),
+                    market("e", "Sleet?", true, false),
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let rest = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let handle = MarketWatcher::new(rest)
+            .with_interval(Duration::from_millis(50))
+            .spawn(tx);
+
+        // First poll lists every active market
+        let mut first = Vec::new();
+        for _ in 0..3 {
+            first.push(next_info(&mut rx).await);
+        }
+        let titled =
+            |id: &str, title: &str, active| (format!("0x{}", id), title.to_string(), active);
+        assert_eq!(
+            first,
+            vec![
+                titled("a", "Rain?", true),
+                titled("b", "Snow?", true),
+                titled("c", "Hail?", true),
+            ]
+        );
+
+        // Second poll: a renamed, b closed, c delisted, e listed
+        let mut second = Vec::new();
+        for _ in 0..4 {
+            second.push(next_info(&mut rx).await);
+        }
+        assert_eq!(
+            second,
+            vec![
+                titled("a", "Rain tomorrow?", true),
+                titled("b", "Snow?", false),
+                titled("e", "Sleet?", true),
+                titled("c", "Hail?", false),
+            ]
+        );
+
+        // Nothing changes after that
+        assert!(tokio::time::timeout(Duration::from_millis(200), rx.recv())
+            .await
+            .is_err());
+
+        drop(rx);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("watcher didn't stop")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_truncated_listing_reports_no_removals() {
+        let server = MockServer::start().await;
+        let pages = [
+            serde_json::json!({
+                "data": [market("a", "Rain?", true, false), market("b", "Snow?", true, false)],
+                "next_cursor": "MQ=="
+            }),
+            // b is only missing because the listing stops after one page
+            serde_json::json!({
+                "data": [market("a", "Rain?", true, false)],
+                "next_cursor": "MQ=="
+            }),
+        ];
+        for (priority, page) in (1..).zip(pages) {
+            Mock::given(method("GET"))
+                .and(path("/markets"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(page))
+                .up_to_n_times(1)
+                .with_priority(priority)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [market("a", "Rain?", true, false)]
+            })))
+            .mount(&server)
+            .await;
+
+        let rest = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let mut watcher = MarketWatcher::new(rest).with_max_pages(1);
+        assert_eq!(watcher.poll().await.unwrap().len(), 2);
+
+        assert!(watcher.poll().await.unwrap().is_empty());
+        assert_eq!(watcher.markets().count(), 2);
+
+        // A complete listing confirms the removal
+        let events = watcher.poll().await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [MarketEvent::MarketInfo(info)] if info.market_id == "0xb" && !info.is_active
+        ));
+        assert_eq!(watcher.markets().count(), 1);
+    }
+}
@@ -1,8 +1,12 @@
 //! Polymarket-specific message types
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::common::errors::{ClientError, Result};
+use crate::common::types::{MarketInfo, Platform, PriceLevel, Side};
 
 /// WebSocket channel types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -61,23 +65,56 @@ pub struct WsOperationMessage {
 }
 
 /// Incoming WebSocket message types
+///
+/// Deserialized by the `event_type` tag; use [`WsIncomingMessage::from_value`]
+/// to fall back to `Unknown` for unrecognized event types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "event_type")]
 pub enum WsIncomingMessage {
     /// Price change event
+    #[serde(rename = "price_change")]
     PriceChange(PriceChangeEvent),
     /// Order book snapshot/update
+    #[serde(rename = "book")]
     BookUpdate(BookUpdateEvent),
     /// Trade event
+    #[serde(rename = "trade")]
     Trade(TradeEvent),
     /// Last trade price event
+    #[serde(rename = "last_trade_price")]
     LastTradePrice(LastTradePriceEvent),
     /// User order update
+    #[serde(rename = "order")]
     OrderUpdate(OrderUpdateEvent),
     /// Generic/unknown message
+    #[serde(skip)]
     Unknown(serde_json::Value),
 }
 
+impl WsIncomingMessage {
+    /// Event types with a dedicated variant
+    const KNOWN_EVENT_TYPES: [&'static str; 5] =
+        ["price_change", "book", "trade", "last_trade_price", "order"];
+
+    /// Classify a JSON message by its `event_type`
+    ///
+    /// Messages with a missing or unrecognized type become `Unknown`; a
+    /// recognized type with a malformed body is an error.
+    pub fn from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let known = value
+            .get("event_type")
+            .and_then(|v| v.as_str())
+            .map(|t| Self::KNOWN_EVENT_TYPES.contains(&t))
+            .unwrap_or(false);
+
+        if known {
+            serde_json::from_value(value)
+        } else {
+            Ok(Self::Unknown(value))
+        }
+    }
+}
+
 /// Price change event from WebSocket
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceChangeEvent {
@@ -89,7 +126,12 @@ pub struct PriceChangeEvent {
     #[serde(default)]
     pub changes: Option<Vec<PriceChange>>,
     #[serde(default)]
+    pub hash: Option<String>,
+    #[serde(default)]
     pub timestamp: Option<i64>,
+    /// Per-asset sequence number, where the feed provides one
+    #[serde(default)]
+    pub sequence: Option<u64>,
 }
 
 /// A single price change
@@ -114,6 +156,9 @@ pub struct BookUpdateEvent {
     pub asks: Vec<BookLevel>,
     #[serde(default)]
     pub timestamp: Option<i64>,
+    /// Per-asset sequence number, where the feed provides one
+    #[serde(default)]
+    pub sequence: Option<u64>,
 }
 
 /// A price level in the book
@@ -123,6 +168,23 @@ pub struct BookLevel {
     pub size: String,
 }
 
+impl BookLevel {
+    /// Parse `price`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn price_decimal(&self) -> Result<Decimal> {
+        parse_decimal("level price", &self.price)
+    }
+
+    /// Parse `size`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn size_decimal(&self) -> Result<Decimal> {
+        parse_decimal("level size", &self.size)
+    }
+
+    /// Parse into a [`PriceLevel`]
+    pub fn to_price_level(&self) -> Result<PriceLevel> {
+        Ok(PriceLevel::new(self.price_decimal()?, self.size_decimal()?))
+    }
+}
+
 /// Trade event from WebSocket
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeEvent {
@@ -133,7 +195,9 @@ pub struct TradeEvent {
     pub id: Option<String>,
     pub price: String,
     pub size: String,
-    pub side: String,
+    /// Taker side; some trade messages omit it
+    #[serde(default)]
+    pub side: Option<String>,
     #[serde(default)]
     pub timestamp: Option<i64>,
 }
@@ -152,6 +216,8 @@ pub struct LastTradePriceEvent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderUpdateEvent {
     pub event_type: Option<String>,
+    /// Sent as `id` on the user channel
+    #[serde(alias = "id")]
     pub order_id: String,
     pub market: Option<String>,
     pub asset_id: Option<String>,
@@ -189,18 +255,61 @@ pub struct PriceResponse {
     pub price: String,
 }
 
+impl PriceResponse {
+    /// Parse `price`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn price_decimal(&self) -> Result<Decimal> {
+        parse_decimal("price", &self.price)
+    }
+}
+
+/// Entry in the request body for POST /prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceRequest {
+    pub token_id: String,
+    pub side: Side,
+}
+
+/// Response from POST /prices: token ID -> side ("BUY"/"SELL") -> price
+pub type PricesResponse = HashMap<String, HashMap<String, String>>;
+
+/// Entry in the request body for POST /midpoints and POST /spreads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRequest {
+    pub token_id: String,
+}
+
+/// Response from POST /midpoints: token ID -> midpoint
+pub type MidpointsResponse = HashMap<String, String>;
+
+/// Response from POST /spreads: token ID -> spread
+pub type SpreadsResponse = HashMap<String, String>;
+
 /// Response from GET /midpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MidpointResponse {
     pub mid: String,
 }
 
+impl MidpointResponse {
+    /// Parse `mid`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn mid_decimal(&self) -> Result<Decimal> {
+        parse_decimal("midpoint", &self.mid)
+    }
+}
+
 /// Response from GET /spread
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpreadResponse {
     pub spread: String,
 }
 
+impl SpreadResponse {
+    /// Parse `spread`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn spread_decimal(&self) -> Result<Decimal> {
+        parse_decimal("spread", &self.spread)
+    }
+}
+
 /// Response from GET /book
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookResponse {
@@ -220,6 +329,13 @@ pub struct LastTradePriceResponse {
     pub price: String,
 }
 
+impl LastTradePriceResponse {
+    /// Parse `price`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn price_decimal(&self) -> Result<Decimal> {
+        parse_decimal("price", &self.price)
+    }
+}
+
 /// Market from GET /markets or /simplified-markets
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketResponse {
@@ -259,6 +375,69 @@ pub struct MarketResponse {
     pub accepting_order_timestamp: Option<String>,
     #[serde(default)]
     pub neg_risk: Option<bool>,
+    /// Shared by every market in the same neg-risk group
+    #[serde(default)]
+    pub neg_risk_market_id: Option<String>,
+}
+
+impl MarketResponse {
+    /// Whether this market is one outcome of a neg-risk (multi-outcome) group
+    pub fn is_neg_risk(&self) -> bool {
+        self.neg_risk.unwrap_or(false)
+    }
+
+    /// Price increment orders must be placed on, if the market reports one
+    ///
+    /// Pass it to [`round_to_tick`](crate::common::math::round_to_tick) to
+    /// snap strategy prices onto the grid.
+    pub fn tick_size(&self) -> Result<Option<Decimal>> {
+        self.minimum_tick_size
+            .as_deref()
+            .map(|tick| parse_decimal("minimum_tick_size", tick))
+            .transpose()
+    }
+
+    /// Token of the other outcome, if this is a binary market holding `token_id`
+    pub fn complementary_token(&self, token_id: &str) -> Option<&str> {
+        let ids: Vec<&str> = self.tokens.iter().map(|t| t.token_id.as_str()).collect();
+        complementary_token(&ids, token_id)
+    }
+
+    /// YES tokens of the other markets in this market's neg-risk group
+    ///
+    /// In a neg-risk group each market is one outcome of a multi-outcome
+    /// event, so these are the competing outcomes. Empty if this market
+    /// isn't neg-risk or its group is unknown.
+    pub fn neg_risk_siblings<'a>(&self, markets: &'a [MarketResponse]) -> Vec<&'a str> {
+        let group = match self.neg_risk_market_id.as_deref() {
+            Some(group) if self.is_neg_risk() => group,
+            _ => return Vec::new(),
+        };
+        markets
+            .iter()
+            .filter(|m| m.condition_id != self.condition_id)
+            .filter(|m| m.is_neg_risk() && m.neg_risk_market_id.as_deref() == Some(group))
+            .filter_map(MarketResponse::yes_token)
+            .collect()
+    }
+
+    /// Token for the YES outcome (the first token if none is labelled Yes)
+    pub fn yes_token(&self) -> Option<&str> {
+        self.tokens
+            .iter()
+            .find(|t| t.outcome.eq_ignore_ascii_case("yes"))
+            .or_else(|| self.tokens.first())
+            .map(|t| t.token_id.as_str())
+    }
+}
+
+/// The other id of a two-token market, if `token_id` is one of them
+fn complementary_token<'a>(token_ids: &[&'a str], token_id: &str) -> Option<&'a str> {
+    match token_ids {
+        [a, b] if *a == token_id => Some(b),
+        [a, b] if *b == token_id => Some(a),
+        _ => None,
+    }
 }
 
 /// Token information within a market
@@ -334,6 +513,18 @@ pub struct TradeResponse {
     pub type_: Option<String>,
 }
 
+impl TradeResponse {
+    /// Parse `price`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn price_decimal(&self) -> Result<Decimal> {
+        parse_decimal("trade price", &self.price)
+    }
+
+    /// Parse `size`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn size_decimal(&self) -> Result<Decimal> {
+        parse_decimal("trade size", &self.size)
+    }
+}
+
 /// Paginated trades response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradesResponse {
@@ -346,6 +537,107 @@ pub struct TradesResponse {
     pub count: Option<u32>,
 }
 
+// ============================================================================
+// Order Types (Authenticated)
+// ============================================================================
+
+/// Order time-in-force type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OrderType {
+    /// Good-til-cancelled
+    Gtc,
+    /// Fill-or-kill
+    Fok,
+    /// Good-til-date (requires an expiration)
+    Gtd,
+}
+
+/// Request body for POST /order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRequest {
+    pub token_id: String,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub side: Side,
+    #[serde(rename = "orderType")]
+    pub order_type: OrderType,
+    /// Unix timestamp (seconds) after which a GTD order expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<i64>,
+}
+
+/// Response from POST /order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderResponse {
+    #[serde(rename = "orderID", alias = "order_id")]
+    pub order_id: String,
+    pub status: String,
+}
+
+/// Request body for DELETE /order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelOrderRequest {
+    #[serde(rename = "orderID")]
+    pub order_id: String,
+}
+
+/// Response from DELETE /order and DELETE /cancel-all
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CancelResponse {
+    /// IDs of orders that were cancelled
+    #[serde(default)]
+    pub canceled: Vec<String>,
+    /// Order IDs that could not be cancelled, with the reason
+    #[serde(default)]
+    pub not_canceled: HashMap<String, String>,
+}
+
+// ============================================================================
+// Account Types (Authenticated)
+// ============================================================================
+
+/// Position entry from GET /positions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionResponse {
+    /// Token ID the position is held in
+    #[serde(alias = "asset")]
+    pub asset_id: String,
+    /// Signed size: positive for long, negative for short
+    pub size: String,
+    /// Average entry price
+    #[serde(rename = "avg_price", alias = "avgPrice")]
+    pub avg_price: String,
+}
+
+impl PositionResponse {
+    /// Parse `size`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn size_decimal(&self) -> Result<Decimal> {
+        parse_decimal("position size", &self.size)
+    }
+
+    /// Parse `avg_price`, or `ClientError::InvalidResponse` if it isn't a decimal
+    pub fn avg_price_decimal(&self) -> Result<Decimal> {
+        parse_decimal("average price", &self.avg_price)
+    }
+}
+
+/// Response from GET /balance-allowance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceAllowanceResponse {
+    /// Collateral balance in USDC base units (6 decimals)
+    pub balance: String,
+    #[serde(default)]
+    pub allowance: Option<String>,
+}
+
+impl BalanceAllowanceResponse {
+    /// Balance in base units; divide by 10^6 for USDC
+    pub fn balance_decimal(&self) -> Result<Decimal> {
+        parse_decimal("balance", &self.balance)
+    }
+}
+
 // ============================================================================
 // Gamma API Response Types (Market Discovery)
 // ============================================================================
@@ -382,9 +674,11 @@ pub struct GammaMarket {
     pub volume: Option<String>,
     #[serde(default)]
     pub liquidity: Option<String>,
-    #[serde(default)]
+    /// Outcome labels; Gamma sends these as a JSON-encoded string
+    #[serde(default, deserialize_with = "string_list")]
     pub outcomes: Option<Vec<String>>,
-    #[serde(rename = "outcomePrices", default)]
+    /// Outcome prices in the same order as `outcomes`, also JSON-encoded
+    #[serde(rename = "outcomePrices", default, deserialize_with = "string_list")]
     pub outcome_prices: Option<Vec<String>>,
     #[serde(default)]
     pub active: Option<bool>,
@@ -396,6 +690,115 @@ pub struct GammaMarket {
     pub tokens: Option<Vec<GammaToken>>,
 }
 
+impl GammaMarket {
+    /// Outcome prices as decimals, or `None` if any fail to parse
+    pub fn parsed_outcome_prices(&self) -> Option<Vec<Decimal>> {
+        self.outcome_prices
+            .as_ref()?
+            .iter()
+            .map(|price| price.trim().parse().ok())
+            .collect()
+    }
+
+    /// Whether this market is one outcome of a neg-risk (multi-outcome) group
+    pub fn is_neg_risk(&self) -> bool {
+        self.neg_risk.unwrap_or(false)
+    }
+
+    /// Token of the other outcome, if this is a binary market holding `token_id`
+    pub fn complementary_token(&self, token_id: &str) -> Option<&str> {
+        let ids: Vec<&str> = self
+            .tokens
+            .iter()
+            .flatten()
+            .map(|t| t.token_id.as_str())
+            .collect();
+        complementary_token(&ids, token_id)
+    }
+}
+
+impl MarketInfo {
+    /// Unified metadata for a CLOB market
+    ///
+    /// A market is only active if the CLOB says so and it isn't closed. An
+    /// unparsable end date or tick size is left unset.
+    pub fn from_clob(m: &MarketResponse) -> MarketInfo {
+        MarketInfo {
+            platform: Platform::Polymarket,
+            market_id: m.condition_id.clone(),
+            title: m.question.clone().unwrap_or_default(),
+            description: m.description.clone().unwrap_or_default(),
+            token_ids: m.tokens.iter().map(|t| t.token_id.clone()).collect(),
+            is_active: m.active.unwrap_or(false) && !m.closed.unwrap_or(false),
+            end_date: m.end_date_iso.as_deref().and_then(parse_date),
+            tick_size: m.tick_size().ok().flatten(),
+            neg_risk: m.is_neg_risk(),
+        }
+    }
+
+    /// Unified metadata for a Gamma market
+    ///
+    /// Returns `None` without a condition ID, since that's what the CLOB
+    /// and the market stream identify the market by. Gamma doesn't report
+    /// a tick size.
+    pub fn from_gamma(m: &GammaMarket) -> Option<MarketInfo> {
+        Some(MarketInfo {
+            platform: Platform::Polymarket,
+            market_id: m.condition_id.clone()?,
+            title: m.question.clone(),
+            description: String::new(),
+            token_ids: m
+                .tokens
+                .iter()
+                .flatten()
+                .map(|t| t.token_id.clone())
+                .collect(),
+            is_active: m.active.unwrap_or(false) && !m.closed.unwrap_or(false),
+            end_date: m.end_date.as_deref().and_then(parse_date),
+            tick_size: None,
+            neg_risk: m.is_neg_risk(),
+        })
+    }
+}
+
+/// Parse an ISO 8601 timestamp, or a bare date taken as midnight UTC
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+/// Parse a numeric string field, naming the field in the error
+fn parse_decimal(field: &str, value: &str) -> Result<Decimal> {
+    value
+        .parse()
+        .map_err(|e| ClientError::InvalidResponse(format!("Invalid {} {:?}: {}", field, value, e)))
+}
+
+/// Deserialize a list of strings sent either as an array or as a string
+/// holding a JSON-encoded array (e.g. `"[\"Yes\", \"No\"]"`)
+fn string_list<'de, D>(deserializer: D) -> std::result::Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringList {
+        List(Vec<String>),
+        Encoded(String),
+    }
+
+    match Option::<StringList>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StringList::List(list)) => Ok(Some(list)),
+        Some(StringList::Encoded(encoded)) => serde_json::from_str(&encoded)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
 /// Token from Gamma API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GammaToken {
@@ -426,3 +829,165 @@ pub struct GammaMarketsResponse {
     #[serde(default)]
     pub next_cursor: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_not_misclassified_as_price_change() {
+        // Every event has `asset_id`, so an untagged parse would accept this
+        // as the first variant that matches (PriceChange)
+        let value = serde_json::json!({
+            "event_type": "book",
+            "asset_id": "123456",
+            "bids": [{"price": "0.50", "size": "100"}],
+            "asks": []
+        });
+
+        match WsIncomingMessage::from_value(value).unwrap() {
+            WsIncomingMessage::BookUpdate(book) => assert_eq!(book.bids.len(), 1),
+            other => panic!("Expected BookUpdate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_last_trade_price_not_misclassified_as_trade() {
+        let value = serde_json::json!({
+            "event_type": "last_trade_price",
+            "asset_id": "123456",
+            "price": "0.52",
+            "size": "10",
+            "side": "BUY"
+        });
+
+        assert!(matches!(
+            WsIncomingMessage::from_value(value).unwrap(),
+            WsIncomingMessage::LastTradePrice(_)
+        ));
+    }
+
+    #[test]
+    fn test_unrecognized_event_type_is_unknown() {
+        let value = serde_json::json!({"event_type": "tick_size_change", "asset_id": "1"});
+        assert!(matches!(
+            WsIncomingMessage::from_value(value).unwrap(),
+            WsIncomingMessage::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_gamma_outcomes_stringified() {
+        let json = r#"{
+            "id": "market_001",
+            "question": "Will it rain tomorrow?",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.65\", \"0.35\"]"
+        }"#;
+
+        let market: GammaMarket = serde_json::from_str(json).unwrap();
+        assert_eq!(market.outcomes, Some(vec!["Yes".into(), "No".into()]));
+        assert_eq!(
+            market.parsed_outcome_prices(),
+            Some(vec![Decimal::new(65, 2), Decimal::new(35, 2)])
+        );
+    }
+
+    #[test]
+    fn test_gamma_outcomes_array() {
+        let json = r#"{
+            "id": "market_001",
+            "question": "Will it rain tomorrow?",
+            "outcomes": ["Yes", "No"],
+            "outcomePrices": ["0.65", "0.35"]
+        }"#;
+
+        let market: GammaMarket = serde_json::from_str(json).unwrap();
+        assert_eq!(market.outcomes, Some(vec!["Yes".into(), "No".into()]));
+        assert_eq!(
+            market.parsed_outcome_prices(),
+            Some(vec![Decimal::new(65, 2), Decimal::new(35, 2)])
+        );
+
+        // Missing or null fields stay None
+        let market: GammaMarket =
+            serde_json::from_str(r#"{"id": "1", "question": "?", "outcomes": null}"#).unwrap();
+        assert_eq!(market.outcomes, None);
+        assert_eq!(market.parsed_outcome_prices(), None);
+    }
+
+    #[test]
+    fn test_malformed_known_event_is_error() {
+        let value = serde_json::json!({"event_type": "trade", "asset_id": "1"});
+        assert!(WsIncomingMessage::from_value(value).is_err());
+    }
+
+    #[test]
+    fn test_decimal_accessors() {
+        use rust_decimal_macros::dec;
+
+        let price: PriceResponse = serde_json::from_str(r#"{"price": "0.52"}"#).unwrap();
+        assert_eq!(price.price_decimal().unwrap(), dec!(0.52));
+        let mid: MidpointResponse = serde_json::from_str(r#"{"mid": "0.505"}"#).unwrap();
+        assert_eq!(mid.mid_decimal().unwrap(), dec!(0.505));
+
+        let book: OrderBookResponse = serde_json::from_str(
+            r#"{
+                "market": "condition",
+                "asset_id": "token",
+                "bids": [{"price": "0.48", "size": "100"}],
+                "asks": [{"price": "0.52", "size": "25.5"}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            book.asks[0].to_price_level().unwrap(),
+            PriceLevel::new(dec!(0.52), dec!(25.5))
+        );
+
+        let trade: TradeResponse = serde_json::from_str(
+            r#"{
+                "id": "t1",
+                "market": "condition",
+                "asset_id": "token",
+                "side": "BUY",
+                "size": "10",
+                "price": "0.45",
+                "status": "MATCHED"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(trade.price_decimal().unwrap(), dec!(0.45));
+        assert_eq!(trade.size_decimal().unwrap(), dec!(10));
+    }
+
+    #[test]
+    fn test_decimal_accessors_reject_garbage() {
+        let price = PriceResponse {
+            price: "n/a".to_string(),
+        };
+        let Err(ClientError::InvalidResponse(message)) = price.price_decimal() else {
+            panic!("expected InvalidResponse");
+        };
+        assert!(message.contains("price"));
+        assert!(message.contains("n/a"));
+
+        let level = BookLevel {
+            price: "0.50".to_string(),
+            size: "".to_string(),
+        };
+        assert!(matches!(
+            level.to_price_level(),
+            Err(ClientError::InvalidResponse(_))
+        ));
+        let position = PositionResponse {
+            asset_id: "token".to_string(),
+            size: "10".to_string(),
+            avg_price: "abc".to_string(),
+        };
+        assert!(matches!(
+            position.avg_price_decimal(),
+            Err(ClientError::InvalidResponse(_))
+        ));
+    }
+}
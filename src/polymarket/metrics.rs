@@ -0,0 +1,135 @@
+//! Counters describing what a WebSocket client has received
+
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use crate::common::instrument::{LatencyRecorder, LatencySummary};
+use crate::common::types::MarketEvent;
+
+/// Live counters updated by a WebSocket client's reader
+///
+/// Counts accumulate across reconnects. Read them through
+/// [`snapshot`](Self::snapshot).
+#[derive(Debug, Default)]
+pub struct Metrics {
+    connections: AtomicU64,
+    bytes_received: AtomicU64,
+    book_snapshots: AtomicU64,
+    book_deltas: AtomicU64,
+    trades: AtomicU64,
+    last_trade_prices: AtomicU64,
+    other_messages: AtomicU64,
+    pongs: AtomicU64,
+    parse_failures: AtomicU64,
+    /// Milliseconds since the epoch, zero until a frame arrives
+    last_message_millis: AtomicI64,
+    /// Time to parse each text frame, when latency tracking is on
+    parse_latency: LatencyRecorder,
+    /// Time to apply each event to the order books, when tracking is on
+    apply_latency: LatencyRecorder,
+}
+
+/// Point-in-time copy of a client's [`Metrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetricsSnapshot {
+    /// Times a connection was established after the first
+    pub reconnects: u64,
+    /// Payload bytes of every data frame received
+    pub bytes_received: u64,
+    /// Full book snapshots parsed
+    pub book_snapshots: u64,
+    /// Incremental book updates parsed
+    pub book_deltas: u64,
+    /// Trades parsed
+    pub trades: u64,
+    /// Last trade price updates parsed
+    pub last_trade_prices: u64,
+    /// Messages of a type the client doesn't interpret
+    pub other_messages: u64,
+    /// Heartbeat replies received
+    pub pongs: u64,
+    /// Text frames that couldn't be parsed
+    pub parse_failures: u64,
+    /// When the last frame of any kind arrived
+    pub last_message_at: Option<DateTime<Utc>>,
+    /// Text frame parse times, None unless latency tracking is on
+    pub parse_latency: Option<LatencySummary>,
+    /// Order book update times, None unless latency tracking is on and
+    /// books are maintained
+    pub apply_latency: Option<LatencySummary>,
+}
+
+impl MetricsSnapshot {
+    /// Market data events parsed, of any type
+    pub fn messages(&self) -> u64 {
+        self.book_snapshots
+            + self.book_deltas
+            + self.trades
+            + self.last_trade_prices
+            + self.other_messages
+    }
+}
+
+impl Metrics {
+    /// Copy the current counters
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let last_message_millis = self.last_message_millis.load(Ordering::Relaxed);
+        MetricsSnapshot {
+            reconnects: self.connections.load(Ordering::Relaxed).saturating_sub(1),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            book_snapshots: self.book_snapshots.load(Ordering::Relaxed),
+            book_deltas: self.book_deltas.load(Ordering::Relaxed),
+            trades: self.trades.load(Ordering::Relaxed),
+            last_trade_prices: self.last_trade_prices.load(Ordering::Relaxed),
+            other_messages: self.other_messages.load(Ordering::Relaxed),
+            pongs: self.pongs.load(Ordering::Relaxed),
+            parse_failures: self.parse_failures.load(Ordering::Relaxed),
+            last_message_at: (last_message_millis != 0)
+                .then(|| DateTime::from_timestamp_millis(last_message_millis))
+                .flatten(),
+            parse_latency: self.parse_latency.summary(),
+            apply_latency: self.apply_latency.summary(),
+        }
+    }
+
+    pub(crate) fn parse_latency(&self) -> &LatencyRecorder {
+        &self.parse_latency
+    }
+
+    pub(crate) fn apply_latency(&self) -> &LatencyRecorder {
+        &self.apply_latency
+    }
+
+    pub(crate) fn record_connection(&self) {
+        self.connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a received frame carrying `bytes` of payload
+    pub(crate) fn record_frame(&self, bytes: usize) {
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.last_message_millis
+            .store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Count a parsed event by type
+    pub(crate) fn record_event(&self, event: &MarketEvent) {
+        let counter = match event {
+            MarketEvent::OrderBook(_) => &self.book_snapshots,
+            MarketEvent::OrderBookUpdate(update) if update.is_snapshot => &self.book_snapshots,
+            MarketEvent::OrderBookUpdate(_) => &self.book_deltas,
+            MarketEvent::Trade(_) => &self.trades,
+            MarketEvent::LastTradePrice { .. } => &self.last_trade_prices,
+            _ => &self.other_messages,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_pong(&self) {
+        self.pongs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_parse_failure(&self) {
+        self.parse_failures.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -2,8 +2,14 @@
 
 pub mod auth;
 pub mod client;
+pub mod discovery;
 pub mod messages;
+pub mod metrics;
 pub mod rest;
 pub mod websocket;
 
+#[cfg(test)]
+mod test_utils;
+
 pub use client::PolymarketClient;
+pub use discovery::MarketWatcher;
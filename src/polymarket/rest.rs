@@ -1,15 +1,233 @@
 //! REST API client for Polymarket CLOB
 
-use reqwest::Client;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    RETRY_AFTER,
+};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 use super::auth::{generate_auth_headers, AuthHeaders};
 use super::messages::*;
 use crate::common::errors::{ClientError, Result};
-use crate::common::types::{OrderBook, Platform, PriceLevel, Side};
-use crate::config::types::ApiCredentials;
+use crate::common::ratelimit::TokenBucket;
+use crate::common::types::{OrderBook, Platform, PriceLevel, Side, Trade, TradeSideSource};
+use crate::config::types::{
+    default_polymarket_gamma_url, default_polymarket_rest_url, ApiCredentials,
+};
+
+/// Decimal places used by USDC collateral balances
+const USDC_DECIMALS: u32 = 6;
+
+/// Default timeout for a whole request
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of retries for rate-limited requests
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default delay before the first retry, doubled on each attempt
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Default cap on any single retry delay, including `Retry-After`
+const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Connection pool settings for the underlying HTTP client
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Maximum idle connections kept per host
+    pub max_idle_per_host: usize,
+    /// How long idle connections are kept before being closed
+    pub idle_timeout: Option<Duration>,
+    /// Timeout for establishing a new connection
+    pub connect_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: 32,
+            idle_timeout: Some(Duration::from_secs(90)),
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Builder for [`PolymarketRestClient`]
+///
+/// Unset options keep the same defaults as [`PolymarketRestClient::new`].
+///
+/// # Example
+/// ```
+/// use polymarket_websocket::polymarket::rest::PolymarketRestClient;
+/// use std::time::Duration;
+///
+/// let client = PolymarketRestClient::builder()
+///     .timeout(Duration::from_secs(10))
+///     .max_retries(5)
+///     .user_agent("my-bot/1.0")
+///     .header("Proxy-Authorization", "Basic dXNlcjpwYXNz")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolymarketRestClientBuilder {
+    clob_url: String,
+    gamma_url: String,
+    timeout: Duration,
+    credentials: Option<ApiCredentials>,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    max_retry_delay: Duration,
+    user_agent: Option<String>,
+    default_headers: HashMap<String, String>,
+    rate_limit: Option<(f64, u32)>,
+    pool: PoolConfig,
+}
+
+impl Default for PolymarketRestClientBuilder {
+    fn default() -> Self {
+        Self {
+            clob_url: default_polymarket_rest_url(),
+            gamma_url: default_polymarket_gamma_url(),
+            timeout: DEFAULT_TIMEOUT,
+            credentials: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            user_agent: None,
+            default_headers: HashMap::new(),
+            rate_limit: None,
+            pool: PoolConfig::default(),
+        }
+    }
+}
+
+impl PolymarketRestClientBuilder {
+    /// Set the CLOB API base URL
+    pub fn clob_url(mut self, url: &str) -> Self {
+        self.clob_url = url.to_string();
+        self
+    }
+
+    /// Set the Gamma API base URL
+    pub fn gamma_url(mut self, url: &str) -> Self {
+        self.gamma_url = url.to_string();
+        self
+    }
+
+    /// Set the timeout for a whole request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set API credentials for authenticated requests
+    pub fn credentials(mut self, credentials: ApiCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Set how many times a rate-limited GET request is retried
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the backoff before the first retry, doubled on each attempt
+    pub fn retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Set the longest a single retry waits, whatever `Retry-After` says
+    pub fn max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = delay;
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Add a header sent with every request, e.g. for proxy authentication
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Add headers sent with every request
+    pub fn default_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Throttle requests to `requests_per_second`, allowing bursts of up to
+    /// `burst` requests
+    ///
+    /// Every request, including each retry, waits for a token before it is
+    /// sent. Clones of the built client share the same limit.
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// Set the connection pool sizing
+    pub fn pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Build the client
+    ///
+    /// Fails with `ClientError::Configuration` if a header name or value is
+    /// invalid, or the rate limit is not positive.
+    pub fn build(self) -> Result<PolymarketRestClient> {
+        let rate_limiter = match self.rate_limit {
+            Some((requests_per_second, burst)) => {
+                if burst == 0 || !(requests_per_second.is_finite() && requests_per_second > 0.0) {
+                    return Err(ClientError::Configuration(format!(
+                        "Invalid rate limit: {} requests/sec, burst {}",
+                        requests_per_second, burst
+                    )));
+                }
+                Some(Arc::new(TokenBucket::new(burst, requests_per_second)))
+            }
+            None => None,
+        };
+        let client = PolymarketRestClient::http_client(
+            self.timeout,
+            &self.pool,
+            self.user_agent.as_deref(),
+            &self.default_headers,
+        )?;
+        let mut client =
+            PolymarketRestClient::from_http_client(client, &self.clob_url, &self.gamma_url);
+        client.credentials = self.credentials;
+        client.max_retries = self.max_retries;
+        client.retry_base_delay = self.retry_base_delay;
+        client.max_retry_delay = self.max_retry_delay;
+        client.rate_limiter = rate_limiter;
+        Ok(client)
+    }
+}
+
+/// Response body cached alongside its validators
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// In-memory conditional-request cache keyed by URL
+type ResponseCache = Arc<Mutex<HashMap<String, CachedResponse>>>;
 
 /// REST API client for Polymarket CLOB
 #[derive(Debug, Clone)]
@@ -22,27 +240,97 @@ pub struct PolymarketRestClient {
     gamma_url: String,
     /// Optional API credentials for authenticated endpoints
     credentials: Option<ApiCredentials>,
+    /// Cache for discovery endpoints (disabled when None)
+    cache: Option<ResponseCache>,
+    /// Maximum retries for rate-limited (429) GET requests
+    max_retries: u32,
+    /// Backoff before the first retry when no Retry-After is given
+    retry_base_delay: Duration,
+    /// Upper bound on any single retry delay
+    max_retry_delay: Duration,
+    /// Client-side throttle applied before every request (disabled when None)
+    rate_limiter: Option<Arc<TokenBucket>>,
 }
 
 impl PolymarketRestClient {
     /// Create a new REST client (unauthenticated)
     pub fn new(base_url: &str, gamma_url: &str) -> Result<Self> {
-        Self::with_timeout(base_url, gamma_url, Duration::from_secs(30))
+        Self::with_timeout(base_url, gamma_url, DEFAULT_TIMEOUT)
+    }
+
+    /// Start building a client, with the public Polymarket endpoints as
+    /// default URLs
+    pub fn builder() -> PolymarketRestClientBuilder {
+        PolymarketRestClientBuilder::default()
     }
 
     /// Create a new REST client with custom timeout
     pub fn with_timeout(base_url: &str, gamma_url: &str, timeout: Duration) -> Result<Self> {
-        let client = Client::builder()
+        Self::with_pool_config(base_url, gamma_url, timeout, PoolConfig::default())
+    }
+
+    /// Create a new REST client with custom timeout and connection pool sizing
+    ///
+    /// Requests that stall waiting on a saturated pool fail with the
+    /// retryable `ClientError::Timeout` once `timeout` elapses.
+    pub fn with_pool_config(
+        base_url: &str,
+        gamma_url: &str,
+        timeout: Duration,
+        pool: PoolConfig,
+    ) -> Result<Self> {
+        let client = Self::http_client(timeout, &pool, None, &HashMap::new())?;
+        Ok(Self::from_http_client(client, base_url, gamma_url))
+    }
+
+    /// Build the underlying HTTP client
+    ///
+    /// `default_headers` are sent with every request; an invalid header name
+    /// or value is a `ClientError::Configuration`.
+    fn http_client(
+        timeout: Duration,
+        pool: &PoolConfig,
+        user_agent: Option<&str>,
+        default_headers: &HashMap<String, String>,
+    ) -> Result<Client> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in default_headers {
+            let invalid = |e: &dyn std::fmt::Display| {
+                ClientError::Configuration(format!("Invalid header {}: {}", name, e))
+            };
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?,
+                HeaderValue::from_str(value).map_err(|e| invalid(&e))?,
+            );
+        }
+
+        let mut builder = Client::builder()
             .timeout(timeout)
+            .connect_timeout(pool.connect_timeout)
+            .pool_max_idle_per_host(pool.max_idle_per_host)
+            .pool_idle_timeout(pool.idle_timeout)
+            .default_headers(headers);
+        if let Some(user_agent) = user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        builder
             .build()
-            .map_err(|e| ClientError::Internal(e.to_string()))?;
+            .map_err(|e| ClientError::Internal(e.to_string()))
+    }
 
-        Ok(Self {
+    /// Wrap an HTTP client with default settings
+    fn from_http_client(client: Client, base_url: &str, gamma_url: &str) -> Self {
+        Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             gamma_url: gamma_url.trim_end_matches('/').to_string(),
             credentials: None,
-        })
+            cache: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            rate_limiter: None,
+        }
     }
 
     /// Set API credentials for authenticated requests
@@ -51,6 +339,33 @@ impl PolymarketRestClient {
         self
     }
 
+    /// Configure retries for rate-limited GET requests
+    ///
+    /// On HTTP 429 the client waits for the `Retry-After` duration, or
+    /// `base_delay` doubled per attempt if absent, up to `max_retries` times.
+    /// No single wait exceeds the maximum retry delay (30s by default).
+    pub fn with_retry(mut self, max_retries: u32, base_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_base_delay = base_delay;
+        self
+    }
+
+    /// Enable the in-memory response cache for market discovery endpoints
+    ///
+    /// Cached responses are revalidated with `If-None-Match` /
+    /// `If-Modified-Since`; a 304 reuses the cached body.
+    pub fn with_response_cache(mut self) -> Self {
+        self.cache = Some(Arc::new(Mutex::new(HashMap::new())));
+        self
+    }
+
+    /// Drop all cached responses
+    pub fn clear_cache(&self) {
+        if let Some(ref cache) = self.cache {
+            cache.lock().unwrap().clear();
+        }
+    }
+
     /// Generate authentication headers if credentials are set
     fn auth_headers(&self, method: &str, path: &str, body: &str) -> Result<Option<AuthHeaders>> {
         match &self.credentials {
@@ -69,6 +384,13 @@ impl PolymarketRestClient {
         }
     }
 
+    /// Generate authentication headers, failing if no credentials are set
+    fn require_auth_headers(&self, method: &str, path: &str, body: &str) -> Result<AuthHeaders> {
+        self.auth_headers(method, path, body)?.ok_or_else(|| {
+            ClientError::Authentication(format!("API credentials required for {} {}", method, path))
+        })
+    }
+
     // ========================================================================
     // Public Endpoints (No Authentication Required)
     // ========================================================================
@@ -77,7 +399,7 @@ impl PolymarketRestClient {
     #[instrument(skip(self))]
     pub async fn get_ok(&self) -> Result<bool> {
         let url = format!("{}/", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if response.status().is_success() {
             Ok(true)
@@ -90,7 +412,7 @@ impl PolymarketRestClient {
     #[instrument(skip(self))]
     pub async fn get_server_time(&self) -> Result<i64> {
         let url = format!("{}/time", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ClientError::InvalidResponse(format!(
@@ -123,7 +445,7 @@ impl PolymarketRestClient {
         );
         debug!("Fetching price from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -135,10 +457,42 @@ impl PolymarketRestClient {
         }
 
         let price_response: PriceResponse = response.json().await?;
-        price_response
-            .price
-            .parse()
-            .map_err(|e| ClientError::InvalidResponse(format!("Invalid price: {}", e)))
+        price_response.price_decimal()
+    }
+
+    /// Get prices for many tokens in a single request
+    ///
+    /// Tokens the server returns no (or an unparsable) price for are
+    /// omitted from the result.
+    #[instrument(skip(self, requests), fields(count = requests.len()))]
+    pub async fn get_prices(
+        &self,
+        requests: &[(String, Side)],
+    ) -> Result<HashMap<(String, Side), Decimal>> {
+        let url = format!("{}/prices", self.base_url);
+        let body: Vec<PriceRequest> = requests
+            .iter()
+            .map(|(token_id, side)| PriceRequest {
+                token_id: token_id.clone(),
+                side: *side,
+            })
+            .collect();
+        debug!("Fetching {} prices from: {}", body.len(), url);
+
+        let request = self.client.post(&url).json(&body);
+        let response = self.send_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ClientError::InvalidResponse(format!(
+                "Server returned status {}: {}",
+                status, body
+            )));
+        }
+
+        let prices_response: PricesResponse = response.json().await?;
+        Ok(parse_prices_response(prices_response))
     }
 
     /// Get midpoint price for a token
@@ -147,7 +501,7 @@ impl PolymarketRestClient {
         let url = format!("{}/midpoint?token_id={}", self.base_url, token_id);
         debug!("Fetching midpoint from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -159,10 +513,17 @@ impl PolymarketRestClient {
         }
 
         let midpoint_response: MidpointResponse = response.json().await?;
-        midpoint_response
-            .mid
-            .parse()
-            .map_err(|e| ClientError::InvalidResponse(format!("Invalid midpoint: {}", e)))
+        midpoint_response.mid_decimal()
+    }
+
+    /// Get midpoints for many tokens in a single request
+    ///
+    /// Tokens the server returns no (or an unparsable) midpoint for are
+    /// omitted from the result.
+    #[instrument(skip(self, token_ids), fields(count = token_ids.len()))]
+    pub async fn get_midpoints(&self, token_ids: &[String]) -> Result<HashMap<String, Decimal>> {
+        let midpoints: MidpointsResponse = self.post_token_batch("/midpoints", token_ids).await?;
+        Ok(parse_token_values(midpoints))
     }
 
     /// Get spread for a token
@@ -171,7 +532,7 @@ impl PolymarketRestClient {
         let url = format!("{}/spread?token_id={}", self.base_url, token_id);
         debug!("Fetching spread from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -183,19 +544,76 @@ impl PolymarketRestClient {
         }
 
         let spread_response: SpreadResponse = response.json().await?;
-        spread_response
-            .spread
-            .parse()
-            .map_err(|e| ClientError::InvalidResponse(format!("Invalid spread: {}", e)))
+        spread_response.spread_decimal()
+    }
+
+    /// Get spreads for many tokens in a single request
+    ///
+    /// Tokens the server returns no (or an unparsable) spread for are
+    /// omitted from the result.
+    #[instrument(skip(self, token_ids), fields(count = token_ids.len()))]
+    pub async fn get_spreads(&self, token_ids: &[String]) -> Result<HashMap<String, Decimal>> {
+        let spreads: SpreadsResponse = self.post_token_batch("/spreads", token_ids).await?;
+        Ok(parse_token_values(spreads))
+    }
+
+    /// POST a list of token IDs to a batch endpoint and decode the response
+    async fn post_token_batch<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        token_ids: &[String],
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let body: Vec<TokenRequest> = token_ids
+            .iter()
+            .map(|token_id| TokenRequest {
+                token_id: token_id.clone(),
+            })
+            .collect();
+        debug!("Fetching {} tokens from: {}", body.len(), url);
+
+        let request = self.client.post(&url).json(&body);
+        let response = self.send_with_retry(request).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ClientError::InvalidResponse(format!(
+                "Server returned status {}: {}",
+                status, body
+            )));
+        }
+
+        Ok(response.json().await?)
     }
 
     /// Get order book for a token
     #[instrument(skip(self))]
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
         let url = format!("{}/book?token_id={}", self.base_url, token_id);
+        self.fetch_order_book(&url).await
+    }
+
+    /// Get the top `depth` levels of each side of a token's order book
+    ///
+    /// The depth is passed to the API and the result is also truncated
+    /// locally, so the returned book never exceeds `depth` levels per side.
+    #[instrument(skip(self))]
+    pub async fn get_order_book_depth(&self, token_id: &str, depth: u32) -> Result<OrderBook> {
+        let url = format!(
+            "{}/book?token_id={}&depth={}",
+            self.base_url, token_id, depth
+        );
+        let mut book = self.fetch_order_book(&url).await?;
+        book.bids.truncate(depth as usize);
+        book.asks.truncate(depth as usize);
+        Ok(book)
+    }
+
+    async fn fetch_order_book(&self, url: &str) -> Result<OrderBook> {
         debug!("Fetching order book from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(url)).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -216,7 +634,7 @@ impl PolymarketRestClient {
         let url = format!("{}/last-trade-price?token_id={}", self.base_url, token_id);
         debug!("Fetching last trade price from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -228,10 +646,7 @@ impl PolymarketRestClient {
         }
 
         let ltp_response: LastTradePriceResponse = response.json().await?;
-        ltp_response
-            .price
-            .parse()
-            .map_err(|e| ClientError::InvalidResponse(format!("Invalid price: {}", e)))
+        ltp_response.price_decimal()
     }
 
     /// Get simplified markets list
@@ -240,18 +655,16 @@ impl PolymarketRestClient {
         let url = format!("{}/simplified-markets", self.base_url);
         debug!("Fetching simplified markets from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let (status, body) = self.get_cacheable(&url).await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
             return Err(ClientError::InvalidResponse(format!(
                 "Server returned status {}: {}",
                 status, body
             )));
         }
 
-        let markets: MarketsResponse = response.json().await?;
+        let markets: MarketsResponse = serde_json::from_str(&body)?;
         Ok(markets)
     }
 
@@ -261,140 +674,1379 @@ impl PolymarketRestClient {
         let url = format!("{}/markets/{}", self.base_url, condition_id);
         debug!("Fetching market from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let (status, body) = self.get_cacheable(&url).await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
+        if !status.is_success() {
             if status == reqwest::StatusCode::NOT_FOUND {
                 return Err(ClientError::MarketNotFound(condition_id.to_string()));
             }
-            let body = response.text().await.unwrap_or_default();
             return Err(ClientError::InvalidResponse(format!(
                 "Server returned status {}: {}",
                 status, body
             )));
         }
 
-        let market: MarketResponse = response.json().await?;
+        let market: MarketResponse = serde_json::from_str(&body)?;
         Ok(market)
     }
 
     // ========================================================================
-    // Gamma API Endpoints (Market Discovery)
+    // Authenticated Endpoints
     // ========================================================================
 
-    /// Get events from Gamma API
+    /// Place an order
+    ///
+    /// Requires API credentials; returns `ClientError::Authentication` otherwise.
     #[instrument(skip(self))]
-    pub async fn get_gamma_events(&self, limit: Option<u32>) -> Result<Vec<GammaEvent>> {
-        let mut url = format!("{}/events", self.gamma_url);
-        if let Some(l) = limit {
-            url = format!("{}?limit={}", url, l);
+    pub async fn post_order(&self, order: OrderRequest) -> Result<OrderResponse> {
+        let path = "/order";
+        let body = serde_json::to_string(&order)?;
+        let headers = self.require_auth_headers("POST", path, &body)?;
+
+        let url = format!("{}{}", self.base_url, path);
+        debug!("Posting order to: {}", url);
+
+        let request = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .body(body);
+        let response = self.send(headers.apply_to_request(request)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(ClientError::Authentication(body));
+            }
+            return Err(ClientError::InvalidResponse(format!(
+                "Server returned status {}: {}",
+                status, body
+            )));
         }
-        debug!("Fetching events from Gamma API: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let order_response: OrderResponse = response.json().await?;
+        Ok(order_response)
+    }
+
+    /// Cancel a single resting order
+    ///
+    /// Returns `ClientError::OrderNotFound` if the exchange doesn't know the order.
+    #[instrument(skip(self))]
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let path = "/order";
+        let body = serde_json::to_string(&CancelOrderRequest {
+            order_id: order_id.to_string(),
+        })?;
+        let response = self.send_cancel(path, body).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ClientError::OrderNotFound(order_id.to_string()));
+        }
+        let cancel_response = Self::parse_cancel_response(response).await?;
+
+        if let Some(reason) = cancel_response.not_canceled.get(order_id) {
+            return Err(ClientError::InvalidResponse(format!(
+                "Order {} not cancelled: {}",
+                order_id, reason
+            )));
+        }
+        Ok(())
+    }
+
+    /// Cancel every open order, returning how many were cancelled
+    #[instrument(skip(self))]
+    pub async fn cancel_all(&self) -> Result<u32> {
+        let response = self.send_cancel("/cancel-all", String::new()).await?;
+        let cancel_response = Self::parse_cancel_response(response).await?;
+        Ok(cancel_response.canceled.len() as u32)
+    }
+
+    /// Send a signed DELETE request to a cancellation endpoint
+    async fn send_cancel(&self, path: &str, body: String) -> Result<reqwest::Response> {
+        let headers = self.require_auth_headers("DELETE", path, &body)?;
+
+        let url = format!("{}{}", self.base_url, path);
+        debug!("Cancelling orders via: {}", url);
+
+        let mut request = self.client.delete(&url);
+        if !body.is_empty() {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+        self.send(headers.apply_to_request(request)).await
+    }
 
+    async fn parse_cancel_response(response: reqwest::Response) -> Result<CancelResponse> {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(ClientError::Authentication(body));
+            }
             return Err(ClientError::InvalidResponse(format!(
-                "Gamma API returned status {}: {}",
+                "Server returned status {}: {}",
                 status, body
             )));
         }
 
-        let events_response: GammaEventsResponse = response.json().await?;
-        Ok(events_response.data.or(events_response.events).unwrap_or_default())
+        let cancel_response: CancelResponse = response.json().await?;
+        Ok(cancel_response)
     }
 
-    /// Get markets from Gamma API
+    /// Get open positions for the authenticated account
+    ///
+    /// Positions are keyed by token ID. Long positions have a positive size
+    /// and short positions a negative size.
+    #[instrument(skip(self))]
+    pub async fn get_positions(&self) -> Result<Vec<PositionResponse>> {
+        self.get_authenticated("/positions", None).await
+    }
+
+    /// Get the available USDC collateral balance
+    #[instrument(skip(self))]
+    pub async fn get_balance(&self) -> Result<Decimal> {
+        let response: BalanceAllowanceResponse = self
+            .get_authenticated("/balance-allowance", Some("asset_type=COLLATERAL"))
+            .await?;
+
+        let raw = response.balance_decimal()?;
+        Ok(raw / Decimal::from(10u64.pow(USDC_DECIMALS)))
+    }
+
+    /// Get a page of the authenticated account's trade history
+    ///
+    /// Pass the previous page's `next_cursor` as `cursor` to continue.
     #[instrument(skip(self))]
-    pub async fn get_gamma_markets(&self, limit: Option<u32>, active: Option<bool>) -> Result<Vec<GammaMarket>> {
-        let mut url = format!("{}/markets", self.gamma_url);
+    pub async fn get_trades(
+        &self,
+        market: Option<&str>,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<TradesResponse> {
         let mut params = vec![];
-        
+        if let Some(m) = market {
+            params.push(format!("market={}", m));
+        }
         if let Some(l) = limit {
             params.push(format!("limit={}", l));
         }
-        if let Some(a) = active {
-            params.push(format!("active={}", a));
+        if let Some(c) = cursor {
+            params.push(cursor_param(c));
         }
-        
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
-        }
-        debug!("Fetching markets from Gamma API: {}", url);
+        let query = params.join("&");
+
+        self.get_authenticated(
+            "/data/trades",
+            (!query.is_empty()).then_some(query.as_str()),
+        )
+        .await
+    }
+
+    /// Get the full trade history for a market by following `next_cursor`
+    ///
+    /// Stops when the cursor is exhausted or repeats. Trades are
+    /// deduplicated by `id`.
+    #[instrument(skip(self))]
+    pub async fn get_all_trades(&self, market: &str) -> Result<Vec<TradeResponse>> {
+        collect_pages(
+            usize::MAX,
+            |trade: &TradeResponse| trade.id.clone(),
+            |cursor| async move {
+                let page = self
+                    .get_trades(Some(market), None, cursor.as_deref())
+                    .await?;
+                Ok((page.data, page.next_cursor))
+            },
+        )
+        .await
+        .map(|paged| paged.items)
+    }
+
+    /// Send a signed GET request and parse the JSON body
+    ///
+    /// Only `path` is signed; `query` is appended afterwards.
+    async fn get_authenticated<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: Option<&str>,
+    ) -> Result<T> {
+        let headers = self.require_auth_headers("GET", path, "")?;
 
-        let response = self.client.get(&url).send().await?;
+        let url = match query {
+            Some(q) => format!("{}{}?{}", self.base_url, path, q),
+            None => format!("{}{}", self.base_url, path),
+        };
+        debug!("Fetching authenticated data from: {}", url);
+
+        let response = self
+            .send_with_retry(headers.apply_to_request(self.client.get(&url)))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(ClientError::Authentication(body));
+            }
             return Err(ClientError::InvalidResponse(format!(
-                "Gamma API returned status {}: {}",
+                "Server returned status {}: {}",
                 status, body
             )));
         }
 
-        let markets_response: GammaMarketsResponse = response.json().await?;
-        Ok(markets_response.data.or(markets_response.markets).unwrap_or_default())
+        Ok(response.json().await?)
     }
 
     // ========================================================================
-    // Helper Methods
+    // Gamma API Endpoints (Market Discovery)
     // ========================================================================
 
-    /// Convert API order book response to unified OrderBook type
-    fn convert_order_book_response(&self, response: OrderBookResponse) -> Result<OrderBook> {
-        let bids: Result<Vec<PriceLevel>> = response
-            .bids
-            .into_iter()
-            .map(|level| {
-                Ok(PriceLevel {
-                    price: level
-                        .price
-                        .parse()
-                        .map_err(|e| ClientError::InvalidResponse(format!("Invalid bid price: {}", e)))?,
-                    size: level
-                        .size
-                        .parse()
-                        .map_err(|e| ClientError::InvalidResponse(format!("Invalid bid size: {}", e)))?,
-                })
-            })
-            .collect();
+    /// Get events from Gamma API
+    #[instrument(skip(self))]
+    pub async fn get_gamma_events(&self, limit: Option<u32>) -> Result<Vec<GammaEvent>> {
+        let events_response = self.get_gamma_events_page(limit, None).await?;
+        Ok(events_response
+            .data
+            .or(events_response.events)
+            .unwrap_or_default())
+    }
 
-        let asks: Result<Vec<PriceLevel>> = response
-            .asks
-            .into_iter()
-            .map(|level| {
-                Ok(PriceLevel {
-                    price: level
-                        .price
-                        .parse()
-                        .map_err(|e| ClientError::InvalidResponse(format!("Invalid ask price: {}", e)))?,
-                    size: level
-                        .size
-                        .parse()
-                        .map_err(|e| ClientError::InvalidResponse(format!("Invalid ask size: {}", e)))?,
-                })
-            })
-            .collect();
+    /// Get every event from Gamma API by following `next_cursor`
+    ///
+    /// Stops after `max_pages` pages, when the cursor is exhausted, or when a
+    /// cursor repeats. Events are deduplicated by `id`.
+    #[instrument(skip(self))]
+    pub async fn get_all_gamma_events(&self, max_pages: usize) -> Result<Vec<GammaEvent>> {
+        collect_pages(
+            max_pages,
+            |event: &GammaEvent| event.id.clone(),
+            |cursor| async move {
+                let page = self.get_gamma_events_page(None, cursor.as_deref()).await?;
+                Ok((
+                    page.data.or(page.events).unwrap_or_default(),
+                    page.next_cursor,
+                ))
+            },
+        )
+        .await
+        .map(|paged| paged.items)
+    }
 
-        Ok(OrderBook {
-            platform: Platform::Polymarket,
-            market_id: response.market,
-            asset_id: response.asset_id,
-            bids: bids?,
-            asks: asks?,
-            timestamp: chrono::Utc::now(),
-            sequence: 0,
-        })
+    /// Get markets from Gamma API
+    #[instrument(skip(self))]
+    pub async fn get_gamma_markets(
+        &self,
+        limit: Option<u32>,
+        active: Option<bool>,
+    ) -> Result<Vec<GammaMarket>> {
+        let markets_response = self.get_gamma_markets_page(limit, active, None).await?;
+        Ok(markets_response
+            .data
+            .or(markets_response.markets)
+            .unwrap_or_default())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Get every market from Gamma API by following `next_cursor`
+    ///
+    /// Stops after `max_pages` pages, when the cursor is exhausted, or when a
+    /// cursor repeats. Markets are deduplicated by `id`.
+    #[instrument(skip(self))]
+    pub async fn get_all_gamma_markets(
+        &self,
+        active: Option<bool>,
+        max_pages: usize,
+    ) -> Result<Vec<GammaMarket>> {
+        self.get_gamma_markets_paged(active, max_pages)
+            .await
+            .map(|paged| paged.items)
+    }
+
+    /// Like [`get_all_gamma_markets`](Self::get_all_gamma_markets), but also
+    /// reports whether the listing was cut short by `max_pages`
+    #[instrument(skip(self))]
+    pub async fn get_gamma_markets_paged(
+        &self,
+        active: Option<bool>,
+        max_pages: usize,
+    ) -> Result<Paged<GammaMarket>> {
+        collect_pages(
+            max_pages,
+            |market: &GammaMarket| market.id.clone(),
+            |cursor| async move {
+                let page = self
+                    .get_gamma_markets_page(None, active, cursor.as_deref())
+                    .await?;
+                Ok((
+                    page.data.or(page.markets).unwrap_or_default(),
+                    page.next_cursor,
+                ))
+            },
+        )
+        .await
+    }
+
+    /// Fetch a single page of Gamma events
+    async fn get_gamma_events_page(
+        &self,
+        limit: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<GammaEventsResponse> {
+        let url = gamma_url(&self.gamma_url, "/events", limit, None, cursor);
+        debug!("Fetching events from Gamma API: {}", url);
+
+        let (status, body) = self.get_cacheable(&url).await?;
+
+        if !status.is_success() {
+            return Err(ClientError::InvalidResponse(format!(
+                "Gamma API returned status {}: {}",
+                status, body
+            )));
+        }
+
+        let events_response: GammaEventsResponse = serde_json::from_str(&body)?;
+        Ok(events_response)
+    }
+
+    /// Fetch a single page of Gamma markets
+    async fn get_gamma_markets_page(
+        &self,
+        limit: Option<u32>,
+        active: Option<bool>,
+        cursor: Option<&str>,
+    ) -> Result<GammaMarketsResponse> {
+        let url = gamma_url(&self.gamma_url, "/markets", limit, active, cursor);
+        debug!("Fetching markets from Gamma API: {}", url);
+
+        let (status, body) = self.get_cacheable(&url).await?;
+
+        if !status.is_success() {
+            return Err(ClientError::InvalidResponse(format!(
+                "Gamma API returned status {}: {}",
+                status, body
+            )));
+        }
+
+        let markets_response: GammaMarketsResponse = serde_json::from_str(&body)?;
+        Ok(markets_response)
+    }
+
+    // ========================================================================
+    // Helper Methods
+    // ========================================================================
+
+    /// Send a request once the rate limiter, if any, allows it
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.acquire().await;
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Send a request, retrying with backoff while rate limited
+    ///
+    /// Returns `ClientError::RateLimit` once retries are exhausted; every
+    /// other response is returned to the caller unchanged.
+    async fn send_with_retry(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let attempt_request = request
+                .try_clone()
+                .ok_or_else(|| ClientError::Internal("Request cannot be retried".to_string()))?;
+            let response = self.send(attempt_request).await?;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok());
+
+            if attempt >= self.max_retries {
+                let body = response.text().await.unwrap_or_default();
+                return Err(ClientError::RateLimit {
+                    message: body,
+                    retry_after_seconds: retry_after,
+                });
+            }
+
+            // A huge Retry-After would otherwise stall the request indefinitely
+            let delay = retry_after
+                .map(Duration::from_secs)
+                .unwrap_or_else(|| {
+                    self.retry_base_delay
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                })
+                .min(self.max_retry_delay);
+            warn!(
+                "Rate limited, retrying in {:?} (attempt {}/{})",
+                delay,
+                attempt + 1,
+                self.max_retries
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// GET a URL, revalidating against the response cache when enabled
+    ///
+    /// Returns the status and body; a 304 is reported as 200 with the
+    /// cached body.
+    async fn get_cacheable(&self, url: &str) -> Result<(StatusCode, String)> {
+        let Some(ref cache) = self.cache else {
+            let response = self.send_with_retry(self.client.get(url)).await?;
+            let status = response.status();
+            return Ok((status, response.text().await.unwrap_or_default()));
+        };
+
+        let cached = cache.lock().unwrap().get(url).cloned();
+        let mut request = self.client.get(url);
+        if let Some(ref entry) = cached {
+            if let Some(ref etag) = entry.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(ref last_modified) = entry.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = self.send_with_retry(request).await?;
+        let status = response.status();
+
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                debug!("Using cached response for {}", url);
+                return Ok((StatusCode::OK, entry.body));
+            }
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await.unwrap_or_default();
+
+        if status.is_success() && (etag.is_some() || last_modified.is_some()) {
+            cache.lock().unwrap().insert(
+                url.to_string(),
+                CachedResponse {
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                },
+            );
+        }
+
+        Ok((status, body))
+    }
+
+    /// Convert API order book response to unified OrderBook type
+    fn convert_order_book_response(&self, response: OrderBookResponse) -> Result<OrderBook> {
+        let parse_levels = |levels: &[BookLevel]| {
+            levels
+                .iter()
+                .map(BookLevel::to_price_level)
+                .collect::<Result<Vec<PriceLevel>>>()
+        };
+
+        // The API doesn't guarantee best-first ordering
+        let mut bids = parse_levels(&response.bids)?;
+        let mut asks = parse_levels(&response.asks)?;
+        bids.sort_by_key(|level| std::cmp::Reverse(level.price));
+        asks.sort_by_key(|level| level.price);
+
+        // Kept from the exchange since book checksums cover it
+        let timestamp = response
+            .timestamp
+            .as_deref()
+            .and_then(|ts| ts.parse().ok())
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .unwrap_or_else(chrono::Utc::now);
+
+        Ok(OrderBook {
+            platform: Platform::Polymarket,
+            market_id: response.market,
+            asset_id: response.asset_id,
+            bids,
+            asks,
+            timestamp,
+            received_at: chrono::Utc::now(),
+            sequence: 0,
+            hash: response.hash,
+        })
+    }
+}
+
+impl TryFrom<TradeResponse> for Trade {
+    type Error = ClientError;
+
+    /// Convert a trade history entry, parsing its price, size and side
+    ///
+    /// The timestamp is the match time (unix seconds), falling back to the
+    /// creation time and then to now.
+    fn try_from(response: TradeResponse) -> Result<Self> {
+        let price = response.price_decimal()?;
+        let size = response.size_decimal()?;
+        let side = match response.side.to_uppercase().as_str() {
+            "BUY" => Side::Buy,
+            "SELL" => Side::Sell,
+            other => {
+                return Err(ClientError::InvalidResponse(format!(
+                    "Invalid trade side: {}",
+                    other
+                )))
+            }
+        };
+        let timestamp = [&response.match_time, &response.created_at]
+            .into_iter()
+            .flatten()
+            .find_map(|ts| {
+                ts.parse()
+                    .ok()
+                    .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            })
+            .unwrap_or_else(chrono::Utc::now);
+
+        Ok(Trade {
+            platform: Platform::Polymarket,
+            market_id: response.market,
+            asset_id: response.asset_id,
+            trade_id: response.id,
+            price,
+            size,
+            side,
+            side_source: TradeSideSource::Reported,
+            timestamp,
+            received_at: chrono::Utc::now(),
+        })
+    }
+}
+
+/// Flatten a POST /prices response into a map keyed by token and side
+fn parse_prices_response(response: PricesResponse) -> HashMap<(String, Side), Decimal> {
+    let mut prices = HashMap::new();
+    for (token_id, sides) in response {
+        for (side, price) in sides {
+            let side = match side.to_uppercase().as_str() {
+                "BUY" => Side::Buy,
+                "SELL" => Side::Sell,
+                _ => continue,
+            };
+            if let Ok(price) = price.parse() {
+                prices.insert((token_id.clone(), side), price);
+            }
+        }
+    }
+    prices
+}
+
+/// Parse a token ID -> value map, dropping values that aren't decimals
+fn parse_token_values(response: HashMap<String, String>) -> HashMap<String, Decimal> {
+    response
+        .into_iter()
+        .filter_map(|(token_id, value)| value.parse().ok().map(|value| (token_id, value)))
+        .collect()
+}
+
+/// Cursor Polymarket returns once there are no further pages
+const END_CURSOR: &str = "LTE=";
+
+/// Build a Gamma API URL with optional query parameters
+fn gamma_url(
+    base: &str,
+    path: &str,
+    limit: Option<u32>,
+    active: Option<bool>,
+    cursor: Option<&str>,
+) -> String {
+    let mut params = vec![];
+    if let Some(l) = limit {
+        params.push(format!("limit={}", l));
+    }
+    if let Some(a) = active {
+        params.push(format!("active={}", a));
+    }
+    if let Some(c) = cursor {
+        params.push(cursor_param(c));
+    }
+
+    if params.is_empty() {
+        format!("{}{}", base, path)
+    } else {
+        format!("{}{}?{}", base, path, params.join("&"))
+    }
+}
+
+/// `next_cursor` query parameter, percent-encoded
+///
+/// Cursors are opaque and often base64, so `+`, `/` and `=` must survive.
+fn cursor_param(cursor: &str) -> String {
+    let encoded: String = url::form_urlencoded::byte_serialize(cursor.as_bytes()).collect();
+    format!("next_cursor={}", encoded)
+}
+
+/// Unique items gathered by following `next_cursor`
+#[derive(Debug, Clone)]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    /// False if `max_pages` ran out while the cursor still pointed at more
+    pub complete: bool,
+}
+
+/// Follow `next_cursor` across pages, accumulating unique items
+async fn collect_pages<T, K, F, Fut>(max_pages: usize, key: K, mut fetch: F) -> Result<Paged<T>>
+where
+    K: Fn(&T) -> String,
+    F: FnMut(Option<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<String>)>>,
+{
+    let mut items = Vec::new();
+    let mut seen_ids = std::collections::HashSet::new();
+    let mut seen_cursors = std::collections::HashSet::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..max_pages {
+        let (page, next) = fetch(cursor.take()).await?;
+        for item in page {
+            if seen_ids.insert(key(&item)) {
+                items.push(item);
+            }
+        }
+
+        match next {
+            Some(next) if !next.is_empty() && next != END_CURSOR => {
+                if !seen_cursors.insert(next.clone()) {
+                    debug!("Cursor {} repeated, stopping pagination", next);
+                    break;
+                }
+                cursor = Some(next);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(Paged {
+        items,
+        complete: cursor.is_none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::auth::sign_request;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use rust_decimal_macros::dec;
+    use wiremock::matchers::{header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_credentials() -> ApiCredentials {
+        ApiCredentials::new(
+            "test_api_key".to_string(),
+            BASE64.encode(b"test_secret_key_12345"),
+            "test_passphrase".to_string(),
+        )
+    }
+
+    fn sample_order() -> OrderRequest {
+        OrderRequest {
+            token_id: "token_123".to_string(),
+            price: dec!(0.52),
+            size: dec!(10),
+            side: Side::Buy,
+            order_type: OrderType::Gtc,
+            expiration: None,
+        }
+    }
+
+    /// Read a header from a request captured by the mock server
+    fn header(request: &wiremock::Request, name: &str) -> String {
+        request
+            .headers
+            .iter()
+            .find(|(key, _)| key.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.last().as_str().to_string())
+            .unwrap_or_else(|| panic!("missing header {}", name))
+    }
+
+    /// Assert a captured request was signed for the given method and path
+    fn assert_signed(request: &wiremock::Request, method: &str, path: &str) {
+        let creds = test_credentials();
+        let timestamp: i64 = header(request, "POLY_TIMESTAMP").parse().unwrap();
+        let body = String::from_utf8(request.body.clone()).unwrap();
+        let expected = sign_request(&creds.api_secret, timestamp, method, path, &body).unwrap();
+
+        assert_eq!(header(request, "POLY_API_KEY"), creds.api_key);
+        assert_eq!(header(request, "POLY_PASSPHRASE"), creds.passphrase);
+        assert_eq!(header(request, "POLY_SIGNATURE"), expected);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_pool_timeout_is_retryable() {
+        // A listener that never accepts leaves requests queued in the backlog,
+        // the same symptom as a pool with no free connections
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let pool = PoolConfig {
+            max_idle_per_host: 0,
+            idle_timeout: None,
+            connect_timeout: Duration::from_millis(100),
+        };
+        let timeout = Duration::from_millis(200);
+        let clients = [
+            PolymarketRestClient::with_pool_config(&url, &url, timeout, pool.clone()).unwrap(),
+            PolymarketRestClient::builder()
+                .clob_url(&url)
+                .gamma_url(&url)
+                .timeout(timeout)
+                .pool(pool)
+                .build()
+                .unwrap(),
+        ];
+
+        for client in clients {
+            let err = client.get_server_time().await.unwrap_err();
+            assert!(matches!(err, ClientError::Timeout(_)), "got {:?}", err);
+            assert!(err.is_retryable());
+        }
+        drop(listener);
+    }
+
+    #[test]
+    fn test_order_request_serialization() {
+        let json = serde_json::to_value(sample_order()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "token_id": "token_123",
+                "price": "0.52",
+                "size": "10",
+                "side": "BUY",
+                "orderType": "GTC"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_order_signs_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/order"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "orderID": "order_1",
+                "status": "live"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        let response = client.post_order(sample_order()).await.unwrap();
+        assert_eq!(response.order_id, "order_1");
+        assert_eq!(response.status, "live");
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_signed(&requests[0], "POST", "/order");
+        assert_eq!(
+            requests[0].body,
+            serde_json::to_vec(&sample_order()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_order_requires_credentials() {
+        let server = MockServer::start().await;
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+
+        let result = client.post_order(sample_order()).await;
+
+        assert!(matches!(result, Err(ClientError::Authentication(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_signs_delete() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/order"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "canceled": ["order_1"],
+                "not_canceled": {}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        client.cancel_order("order_1").await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_signed(&requests[0], "DELETE", "/order");
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["orderID"], "order_1");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/order"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        let result = client.cancel_order("missing").await;
+
+        assert!(matches!(result, Err(ClientError::OrderNotFound(id)) if id == "missing"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_returns_count() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/cancel-all"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "canceled": ["order_1", "order_2", "order_3"],
+                "not_canceled": {}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        assert_eq!(client.cancel_all().await.unwrap(), 3);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_signed(&requests[0], "DELETE", "/cancel-all");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_requires_credentials() {
+        let server = MockServer::start().await;
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+
+        assert!(matches!(
+            client.cancel_order("order_1").await,
+            Err(ClientError::Authentication(_))
+        ));
+        assert!(matches!(
+            client.cancel_all().await,
+            Err(ClientError::Authentication(_))
+        ));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    const POSITIONS: &str = r#"[
+        {"asset_id": "token_yes", "size": "150.5", "avg_price": "0.42"},
+        {"asset": "token_no", "size": "-20", "avgPrice": "0.61"}
+    ]"#;
+
+    #[tokio::test]
+    async fn test_get_positions_preserves_sign() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/positions"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(POSITIONS, "application/json"))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        let positions = client.get_positions().await.unwrap();
+
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].asset_id, "token_yes");
+        assert_eq!(positions[0].size_decimal().unwrap(), dec!(150.5));
+        assert_eq!(positions[0].avg_price_decimal().unwrap(), dec!(0.42));
+        assert_eq!(positions[1].asset_id, "token_no");
+        assert_eq!(positions[1].size_decimal().unwrap(), dec!(-20));
+
+        let requests = server.received_requests().await.unwrap();
+        assert_signed(&requests[0], "GET", "/positions");
+    }
+
+    #[tokio::test]
+    async fn test_get_all_trades_follows_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data/trades"))
+            .and(query_param("next_cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": "trade_2", "market": "condition_1", "asset_id": "token_yes",
+                    "side": "SELL", "size": "40", "price": "0.55", "status": "CONFIRMED",
+                    "match_time": "1700000100"
+                }],
+                "next_cursor": "LTE="
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/data/trades"))
+            .and(query_param("market", "condition_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": "trade_1", "market": "condition_1", "asset_id": "token_yes",
+                    "side": "BUY", "size": "100.5", "price": "0.42", "status": "CONFIRMED",
+                    "match_time": "1700000000"
+                }],
+                "next_cursor": "page2"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        let trades = client.get_all_trades("condition_1").await.unwrap();
+
+        let ids: Vec<&str> = trades.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["trade_1", "trade_2"]);
+
+        let trade = Trade::try_from(trades[0].clone()).unwrap();
+        assert_eq!(trade.price, dec!(0.42));
+        assert_eq!(trade.size, dec!(100.5));
+        assert_eq!(trade.side, Side::Buy);
+        assert_eq!(trade.timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(Trade::try_from(trades[1].clone()).unwrap().side, Side::Sell);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_signed(&requests[0], "GET", "/data/trades");
+        assert_eq!(
+            requests[1].url.query(),
+            Some("market=condition_1&next_cursor=page2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_trades_encodes_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/data/trades"))
+            .and(query_param("next_cursor", "MTA+Lz0="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [],
+                "next_cursor": "LTE="
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        let page = client
+            .get_trades(None, None, Some("MTA+Lz0="))
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].url.query(), Some("next_cursor=MTA%2BLz0%3D"));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_scales_usdc() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/balance-allowance"))
+            .and(query_param("asset_type", "COLLATERAL"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "balance": "1250500000",
+                "allowance": "0"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_credentials(test_credentials());
+        assert_eq!(client.get_balance().await.unwrap(), dec!(1250.5));
+
+        let requests = server.received_requests().await.unwrap();
+        assert_signed(&requests[0], "GET", "/balance-allowance");
+    }
+
+    #[tokio::test]
+    async fn test_cached_body_reused_on_304() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .and(header_exists("if-none-match"))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_json(serde_json::json!({
+                        "data": [{"id": "market_001", "question": "Will it rain tomorrow?"}]
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_response_cache();
+
+        let first = client.get_gamma_markets(None, None).await.unwrap();
+        let second = client.get_gamma_markets(None, None).await.unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].id, first[0].id);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(header(&requests[1], "If-None-Match"), "\"v1\"");
+    }
+
+    #[tokio::test]
+    async fn test_get_all_gamma_markets_follows_cursor() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .and(query_param("next_cursor", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "market_2", "question": "Second?"},
+                    {"id": "market_3", "question": "Third?"}
+                ],
+                "next_cursor": "page2"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "market_1", "question": "First?"},
+                    {"id": "market_2", "question": "Second?"}
+                ],
+                "next_cursor": "page2"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let markets = client.get_all_gamma_markets(Some(true), 10).await.unwrap();
+
+        let ids: Vec<&str> = markets.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["market_1", "market_2", "market_3"]);
+        // The repeated cursor on page two ends pagination
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_gamma_url_encodes_cursor() {
+        let url = gamma_url(
+            "http://localhost",
+            "/markets",
+            None,
+            None,
+            Some("MTA+Lz0=&x"),
+        );
+        assert_eq!(url, "http://localhost/markets?next_cursor=MTA%2BLz0%3D%26x");
+    }
+
+    #[tokio::test]
+    async fn test_get_all_gamma_markets_respects_max_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "market_1", "question": "First?"}],
+                "next_cursor": "more"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let markets = client.get_all_gamma_markets(None, 1).await.unwrap();
+
+        assert_eq!(markets.len(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_after_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/time"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"timestamp": "1700000000"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+
+        assert_eq!(client.get_server_time().await.unwrap(), 1700000000);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_capped_at_max_retry_delay() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/time"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "86400"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"timestamp": "1700000000"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::builder()
+            .clob_url(&server.uri())
+            .max_retry_delay(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let time = tokio::time::timeout(Duration::from_secs(2), client.get_server_time())
+            .await
+            .expect("Retry-After wasn't capped");
+
+        assert_eq!(time.unwrap(), 1700000000);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_after_exhausting_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/time"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri())
+            .unwrap()
+            .with_retry(2, Duration::from_millis(1));
+        let err = client.get_server_time().await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            ClientError::RateLimit {
+                retry_after_seconds: Some(0),
+                ..
+            }
+        ));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_prices_response_omits_missing() {
+        let response: PricesResponse = serde_json::from_str(
+            r#"{
+                "token_a": {"BUY": "0.52", "SELL": "0.55"},
+                "token_b": {"BUY": "0.31"},
+                "token_c": {}
+            }"#,
+        )
+        .unwrap();
+
+        let prices = parse_prices_response(response);
+
+        assert_eq!(prices.len(), 3);
+        assert_eq!(prices[&("token_a".to_string(), Side::Buy)], dec!(0.52));
+        assert_eq!(prices[&("token_a".to_string(), Side::Sell)], dec!(0.55));
+        assert_eq!(prices[&("token_b".to_string(), Side::Buy)], dec!(0.31));
+        assert!(!prices.contains_key(&("token_b".to_string(), Side::Sell)));
+        assert!(!prices.contains_key(&("token_c".to_string(), Side::Buy)));
+    }
+
+    #[test]
+    fn test_parse_midpoints_response() {
+        let response: MidpointsResponse = serde_json::from_str(
+            r#"{
+                "token_a": "0.535",
+                "token_b": "0.1",
+                "token_c": ""
+            }"#,
+        )
+        .unwrap();
+
+        let midpoints = parse_token_values(response);
+
+        assert_eq!(midpoints.len(), 2);
+        assert_eq!(midpoints["token_a"], dec!(0.535));
+        assert_eq!(midpoints["token_b"], dec!(0.1));
+        assert!(!midpoints.contains_key("token_c"));
+    }
+
+    #[test]
+    fn test_parse_spreads_response() {
+        let response: SpreadsResponse = serde_json::from_str(
+            r#"{
+                "token_a": "0.03",
+                "token_b": "n/a"
+            }"#,
+        )
+        .unwrap();
+
+        let spreads = parse_token_values(response);
+
+        assert_eq!(spreads.len(), 1);
+        assert_eq!(spreads["token_a"], dec!(0.03));
+    }
+
+    #[tokio::test]
+    async fn test_get_midpoints_and_spreads_post_batch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/midpoints"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "token_a": "0.535"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/spreads"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "token_a": "0.03",
+                "token_b": "0.10"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let tokens = ["token_a".to_string(), "token_b".to_string()];
+
+        // token_b is missing from the midpoints response
+        let midpoints = client.get_midpoints(&tokens).await.unwrap();
+        assert_eq!(
+            midpoints,
+            HashMap::from([("token_a".to_string(), dec!(0.535))])
+        );
+
+        let spreads = client.get_spreads(&tokens).await.unwrap();
+        assert_eq!(spreads.len(), 2);
+        assert_eq!(spreads["token_b"], dec!(0.10));
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([{"token_id": "token_a"}, {"token_id": "token_b"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_prices_posts_batch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/prices"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "token_a": {"BUY": "0.52"},
+                "token_b": {"SELL": "0.48"}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let prices = client
+            .get_prices(&[
+                ("token_a".to_string(), Side::Buy),
+                ("token_b".to_string(), Side::Sell),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[&("token_b".to_string(), Side::Sell)], dec!(0.48));
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([
+                {"token_id": "token_a", "side": "BUY"},
+                {"token_id": "token_b", "side": "SELL"}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_order_book_depth_truncates() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/book"))
+            .and(query_param("token_id", "token_123"))
+            .and(query_param("depth", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "market": "0x123456",
+                "asset_id": "token_123",
+                "bids": [
+                    {"price": "0.45", "size": "150"},
+                    {"price": "0.48", "size": "200"},
+                    {"price": "0.50", "size": "100"}
+                ],
+                "asks": [
+                    {"price": "0.60", "size": "90"},
+                    {"price": "0.58", "size": "120"},
+                    {"price": "0.55", "size": "80"}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::new(&server.uri(), &server.uri()).unwrap();
+        let book = client.get_order_book_depth("token_123", 2).await.unwrap();
+
+        assert_eq!(
+            book.bids,
+            vec![
+                PriceLevel::new(dec!(0.50), dec!(100)),
+                PriceLevel::new(dec!(0.48), dec!(200)),
+            ]
+        );
+        assert_eq!(
+            book.asks,
+            vec![
+                PriceLevel::new(dec!(0.55), dec!(80)),
+                PriceLevel::new(dec!(0.58), dec!(120)),
+            ]
+        );
+    }
 
     #[test]
     fn test_client_creation() {
@@ -405,6 +2057,111 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_builder_defaults() {
+        let client = PolymarketRestClient::builder().build().unwrap();
+        assert_eq!(client.base_url, "https://clob.polymarket.com");
+        assert_eq!(client.gamma_url, "https://gamma-api.polymarket.com");
+        assert!(client.credentials.is_none());
+        assert_eq!(client.max_retries, DEFAULT_MAX_RETRIES);
+        assert_eq!(client.retry_base_delay, DEFAULT_RETRY_BASE_DELAY);
+        assert_eq!(client.max_retry_delay, DEFAULT_MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_builder_settings() {
+        let client = PolymarketRestClient::builder()
+            .clob_url("http://localhost:8080/")
+            .gamma_url("http://localhost:8081//")
+            .timeout(Duration::from_secs(5))
+            .credentials(test_credentials())
+            .max_retries(7)
+            .retry_base_delay(Duration::from_millis(50))
+            .max_retry_delay(Duration::from_secs(2))
+            .user_agent("test-agent/1.0")
+            .build()
+            .unwrap();
+        assert_eq!(client.base_url, "http://localhost:8080");
+        assert_eq!(client.gamma_url, "http://localhost:8081");
+        assert_eq!(client.credentials.unwrap().api_key, "test_api_key");
+        assert_eq!(client.max_retries, 7);
+        assert_eq!(client.retry_base_delay, Duration::from_millis(50));
+        assert_eq!(client.max_retry_delay, Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_builder_user_agent_and_headers_sent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .and(wiremock::matchers::header("user-agent", "test-agent/1.0"))
+            .and(wiremock::matchers::header("x-proxy-token", "abc123"))
+            .and(wiremock::matchers::header("x-team", "research"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::builder()
+            .clob_url(&server.uri())
+            .user_agent("test-agent/1.0")
+            .header("X-Proxy-Token", "abc123")
+            .default_headers(HashMap::from([(
+                "X-Team".to_string(),
+                "research".to_string(),
+            )]))
+            .build()
+            .unwrap();
+        assert!(client.get_ok().await.unwrap());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_header() {
+        let result = PolymarketRestClient::builder()
+            .header("bad header", "value")
+            .build();
+        assert!(matches!(result, Err(ClientError::Configuration(_))));
+
+        let result = PolymarketRestClient::builder()
+            .header("X-Ok", "line\nbreak")
+            .build();
+        assert!(matches!(result, Err(ClientError::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_throttles_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(5)
+            .mount(&server)
+            .await;
+
+        let client = PolymarketRestClient::builder()
+            .clob_url(&server.uri())
+            .rate_limit(2.0, 1)
+            .build()
+            .unwrap();
+
+        // The first request uses the burst; the other four wait 500ms each
+        let start = std::time::Instant::now();
+        for _ in 0..5 {
+            assert!(client.get_ok().await.unwrap());
+        }
+        assert!(start.elapsed() >= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_rate_limit() {
+        for (requests_per_second, burst) in [(0.0, 1), (-1.0, 1), (f64::NAN, 1), (2.0, 0)] {
+            let result = PolymarketRestClient::builder()
+                .rate_limit(requests_per_second, burst)
+                .build();
+            assert!(matches!(result, Err(ClientError::Configuration(_))));
+        }
+    }
+
     #[test]
     fn test_url_normalization() {
         let client = PolymarketRestClient::new(
@@ -0,0 +1,77 @@
+//! Local WebSocket server helpers for unit tests
+
+use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+/// Spawn a local WebSocket server that forwards every text frame it
+/// receives to the returned channel, optionally answering `PING` with `PONG`
+///
+/// A Close frame from the client is forwarded as `"CLOSE"`.
+pub async fn spawn_test_server(reply_pong: bool) -> (String, mpsc::UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        // Keep accepting so tests can exercise reconnects
+        while let Ok((stream, _)) = listener.accept().await {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                while let Some(Ok(msg)) = ws.next().await {
+                    match msg {
+                        Message::Text(text) => {
+                            if reply_pong && text == "PING" {
+                                let _ = ws.send(Message::Text("PONG".to_string())).await;
+                            }
+                            let _ = tx.send(text);
+                        }
+                        Message::Close(_) => {
+                            let _ = tx.send("CLOSE".to_string());
+                        }
+                        _ => {}
+                    }
+                }
+            });
+        }
+    });
+
+    (format!("ws://{}", addr), rx)
+}
+
+/// Wait for the next text frame the test server received
+pub async fn next_frame(rx: &mut mpsc::UnboundedReceiver<String>) -> String {
+    timeout(Duration::from_secs(3), rx.recv())
+        .await
+        .expect("timed out waiting for frame")
+        .expect("server channel closed")
+}
+
+/// Wait for the next non-heartbeat frame the test server received
+pub async fn next_data_frame(rx: &mut mpsc::UnboundedReceiver<String>) -> String {
+    loop {
+        let frame = next_frame(rx).await;
+        if frame != "PING" {
+            return frame;
+        }
+    }
+}
+
+/// Assert that no non-heartbeat frame arrives within a short window
+pub async fn assert_no_data_frame(rx: &mut mpsc::UnboundedReceiver<String>) {
+    let result = timeout(Duration::from_millis(300), async {
+        loop {
+            match rx.recv().await {
+                Some(frame) if frame != "PING" => return frame,
+                Some(_) => continue,
+                None => std::future::pending::<()>().await,
+            }
+        }
+    })
+    .await;
+    assert!(result.is_err(), "Unexpected frame: {:?}", result);
+}
@@ -1,24 +1,67 @@
 //! WebSocket client for Polymarket real-time data
 
+use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::interval;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, WebSocketConfig};
 use tokio_tungstenite::{
-    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
+    connect_async_with_config, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
 };
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, debug_span, error, info, info_span, instrument, warn, Instrument};
 
 use super::messages::*;
+use super::metrics::{Metrics, MetricsSnapshot};
+use crate::common::channels::{ChannelStats, ChannelStatsHandle, MeteredSender, OverflowPolicy};
 use crate::common::errors::{ClientError, Result};
+use crate::common::instrument::{timed_apply, timed_parse};
+use crate::common::orderbook::OrderBookManager;
+use crate::common::sequence::SequenceTracker;
 use crate::common::types::{
-    ConnectionStatus, MarketEvent, OrderBookUpdate, Platform, PriceLevel, Side, Trade,
+    ConnectionStatus, MarketEvent, OrderBookUpdate, OrderStatus, OrderUpdate, Platform, PriceLevel,
+    Side, Trade, TradeSideSource,
 };
 use crate::config::types::ApiCredentials;
 
+/// Write half of a Polymarket WebSocket connection
+type WsWriter = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+
+/// Write half shared between the client, heartbeat task, and message task
+type SharedWriter = Arc<Mutex<Option<WsWriter>>>;
+
+/// Default time without any incoming frame before the connection is stale
+const DEFAULT_MAX_SILENCE: Duration = Duration::from_secs(30);
+
+/// Default time allowed for the TCP, TLS and WebSocket handshakes
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long `disconnect` waits for the connection tasks before aborting them
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Close reason sent when the event receiver has been dropped
+const RECEIVER_DROPPED: &str = "event receiver dropped";
+
+/// Background tasks of a live connection
+///
+/// Each handle completes when its task exits, which happens when the socket
+/// closes, the connection goes stale, or
+/// [`disconnect`](PolymarketWebSocketClient::disconnect) is called. A task
+/// that panicked shows up as a `JoinError`.
+#[derive(Debug)]
+pub struct ConnectionHandles {
+    /// Task reading frames and forwarding events
+    pub reader: JoinHandle<()>,
+    /// Task sending PINGs and watching for a stale connection
+    pub heartbeat: JoinHandle<()>,
+}
+
 /// WebSocket client for Polymarket real-time data
 pub struct PolymarketWebSocketClient {
     /// WebSocket URL
@@ -29,10 +72,35 @@ pub struct PolymarketWebSocketClient {
     credentials: Option<ApiCredentials>,
     /// Heartbeat interval in seconds
     heartbeat_interval: u64,
+    /// Longest time without any incoming frame before forcing a disconnect
+    max_silence: Duration,
     /// Connected state flag
     is_connected: Arc<AtomicBool>,
     /// Current subscribed asset IDs
-    subscribed_assets: Vec<String>,
+    subscribed_assets: RwLock<Vec<String>>,
+    /// Write half of the live connection (None until connected)
+    writer: SharedWriter,
+    /// Books kept current from the stream, used to infer unlabeled trade sides
+    order_books: Option<Arc<RwLock<OrderBookManager>>>,
+    /// Trades worth less than this are dropped before being forwarded
+    min_trade_notional: Option<Decimal>,
+    /// What to do with events when the consumer falls behind
+    overflow_policy: OverflowPolicy,
+    /// Message and frame size limits for the connection
+    ws_config: WebSocketConfig,
+    /// How long connecting may take before giving up
+    connect_timeout: Duration,
+    /// Metrics of the current connection's event sender; doesn't keep the
+    /// consumer's channel open
+    event_stats: Option<ChannelStatsHandle>,
+    /// Counters updated by the reader, kept across reconnects
+    metrics: Arc<Metrics>,
+    /// Whether the reader times parsing and book updates into `metrics`
+    latency_tracking: bool,
+    /// Tells the current connection's tasks to close the socket and exit
+    shutdown: Option<watch::Sender<bool>>,
+    /// Reader and heartbeat tasks of the current connection
+    tasks: Vec<JoinHandle<()>>,
 }
 
 impl PolymarketWebSocketClient {
@@ -43,8 +111,20 @@ impl PolymarketWebSocketClient {
             channel_type: ChannelType::Market,
             credentials: None,
             heartbeat_interval: 10,
+            max_silence: DEFAULT_MAX_SILENCE,
             is_connected: Arc::new(AtomicBool::new(false)),
-            subscribed_assets: Vec::new(),
+            subscribed_assets: RwLock::new(Vec::new()),
+            writer: Arc::new(Mutex::new(None)),
+            order_books: None,
+            min_trade_notional: None,
+            overflow_policy: OverflowPolicy::default(),
+            ws_config: WebSocketConfig::default(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            event_stats: None,
+            metrics: Arc::new(Metrics::default()),
+            latency_tracking: false,
+            shutdown: None,
+            tasks: Vec::new(),
         }
     }
 
@@ -55,8 +135,20 @@ impl PolymarketWebSocketClient {
             channel_type: ChannelType::User,
             credentials: Some(credentials),
             heartbeat_interval: 10,
+            max_silence: DEFAULT_MAX_SILENCE,
             is_connected: Arc::new(AtomicBool::new(false)),
-            subscribed_assets: Vec::new(),
+            subscribed_assets: RwLock::new(Vec::new()),
+            writer: Arc::new(Mutex::new(None)),
+            order_books: None,
+            min_trade_notional: None,
+            overflow_policy: OverflowPolicy::default(),
+            ws_config: WebSocketConfig::default(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            event_stats: None,
+            metrics: Arc::new(Metrics::default()),
+            latency_tracking: false,
+            shutdown: None,
+            tasks: Vec::new(),
         }
     }
 
@@ -66,172 +158,606 @@ impl PolymarketWebSocketClient {
         self
     }
 
+    /// Set how long connecting may take, handshakes included
+    ///
+    /// Past this `connect_and_subscribe` fails with `ClientError::Timeout`.
+    /// Defaults to 10 seconds.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set how long the connection may go without receiving anything
+    ///
+    /// Past this the connection is treated as half-open: it's torn down and
+    /// a `ConnectionStatus::Error("stale connection")` is emitted so a
+    /// supervisor can reconnect. PONG replies count as traffic.
+    pub fn with_max_silence(mut self, max_silence: Duration) -> Self {
+        self.max_silence = max_silence;
+        self
+    }
+
+    /// Maintain `manager` from incoming book events
+    ///
+    /// Trades without a reported side have it inferred from the managed book
+    /// before being forwarded.
+    pub fn with_order_book_manager(mut self, manager: Arc<RwLock<OrderBookManager>>) -> Self {
+        self.order_books = Some(manager);
+        self
+    }
+
+    /// Drop trades whose notional (price * size) is below `min_notional`
+    pub fn with_min_trade_notional(mut self, min_notional: Decimal) -> Self {
+        self.min_trade_notional = Some(min_notional);
+        self
+    }
+
+    /// Set what happens to events when the event channel is full
+    ///
+    /// The default, [`OverflowPolicy::Block`], stops reading the socket
+    /// until the consumer catches up, which during a long burst can back up
+    /// the connection until the server drops it. The dropping policies keep
+    /// the socket drained at the cost of losing events; losses show up in
+    /// [`channel_stats`](Self::channel_stats).
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// Record into shared counters, e.g. to keep them across client instances
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Time message parsing and order book updates
+    ///
+    /// Timings show up as `parse_latency` and `apply_latency` in
+    /// [`metrics`](Self::metrics). Off by default, in which case the clock
+    /// isn't read at all.
+    pub fn with_latency_tracking(mut self, enabled: bool) -> Self {
+        self.latency_tracking = enabled;
+        self
+    }
+
+    /// Set the WebSocket protocol settings used when connecting
+    pub fn with_websocket_config(mut self, config: WebSocketConfig) -> Self {
+        self.ws_config = config;
+        self
+    }
+
+    /// Set the largest message and frame the connection accepts, in bytes
+    ///
+    /// `None` removes a limit. Snapshots of busy books can exceed the
+    /// tungstenite defaults (64 MiB messages, 16 MiB frames), and anything
+    /// over the limit drops the connection with a
+    /// `ConnectionStatus::Error` naming the size.
+    pub fn with_message_limits(
+        mut self,
+        max_message_size: Option<usize>,
+        max_frame_size: Option<usize>,
+    ) -> Self {
+        self.ws_config.max_message_size = max_message_size;
+        self.ws_config.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// WebSocket protocol settings used when connecting
+    pub fn websocket_config(&self) -> &WebSocketConfig {
+        &self.ws_config
+    }
+
     /// Check if connected
     pub fn is_connected(&self) -> bool {
         self.is_connected.load(Ordering::SeqCst)
     }
 
+    /// Backpressure metrics for the event channel (None until connected)
+    pub fn channel_stats(&self) -> Option<ChannelStats> {
+        self.event_stats.as_ref().map(ChannelStatsHandle::stats)
+    }
+
+    /// Counts of what the client has received, across reconnects
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Get the currently subscribed asset IDs
+    pub async fn subscribed_assets(&self) -> Vec<String> {
+        self.subscribed_assets.read().await.clone()
+    }
+
+    /// Subscribe to additional assets on the live connection
+    ///
+    /// Assets that are already subscribed are skipped; if nothing new remains
+    /// no operation is sent.
+    #[instrument(skip(self))]
+    pub async fn subscribe(&self, asset_ids: &[String]) -> Result<()> {
+        let mut assets = self.subscribed_assets.write().await;
+
+        let mut new_ids: Vec<String> = Vec::new();
+        for id in asset_ids {
+            if !assets.contains(id) && !new_ids.contains(id) {
+                new_ids.push(id.clone());
+            }
+        }
+
+        if new_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.send_operation("subscribe", &new_ids).await?;
+        assets.extend(new_ids);
+        Ok(())
+    }
+
+    /// Unsubscribe from assets on the live connection
+    ///
+    /// The assets are removed from the stored subscription list so a
+    /// reconnect does not re-subscribe them.
+    #[instrument(skip(self))]
+    pub async fn unsubscribe(&self, asset_ids: &[String]) -> Result<()> {
+        let mut assets = self.subscribed_assets.write().await;
+
+        let removed: Vec<String> = assets
+            .iter()
+            .filter(|id| asset_ids.contains(id))
+            .cloned()
+            .collect();
+
+        if removed.is_empty() {
+            return Ok(());
+        }
+
+        self.send_operation("unsubscribe", &removed).await?;
+        assets.retain(|id| !removed.contains(id));
+        Ok(())
+    }
+
+    /// Close the connection and wait for its tasks to finish
+    ///
+    /// The reader sends a Close frame and both tasks exit. Tasks still
+    /// running after a few seconds (e.g. blocked on a full event channel)
+    /// are aborted. Does nothing if there is no connection.
+    pub async fn disconnect(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(true);
+        }
+
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_TIMEOUT;
+        for mut task in self.tasks.drain(..) {
+            match tokio::time::timeout_at(deadline, &mut task).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("Connection task failed: {}", e),
+                Err(_) => {
+                    warn!("Connection task didn't stop in time, aborting it");
+                    task.abort();
+                }
+            }
+        }
+        self.is_connected.store(false, Ordering::SeqCst);
+    }
+
     /// Connect and start receiving messages
     ///
     /// This method connects to the WebSocket, subscribes to the given assets,
-    /// and spawns tasks to handle incoming messages and heartbeats.
-    #[instrument(skip(self, event_sender))]
+    /// and spawns tasks to handle incoming messages and heartbeats. Sends on
+    /// `event_sender` are metered; see [`channel_stats`](Self::channel_stats).
+    /// The tasks run until the connection drops or
+    /// [`disconnect`](Self::disconnect) is called.
     pub async fn connect_and_subscribe(
         &mut self,
         asset_ids: Vec<String>,
         event_sender: mpsc::Sender<MarketEvent>,
     ) -> Result<()> {
+        let handles = self
+            .connect_and_subscribe_handles(asset_ids, event_sender)
+            .await?;
+        self.tasks = vec![handles.reader, handles.heartbeat];
+        Ok(())
+    }
+
+    /// Connect and start receiving messages, returning the task handles
+    ///
+    /// Same as [`connect_and_subscribe`](Self::connect_and_subscribe), but
+    /// the caller owns the tasks and can await them to learn when the
+    /// connection has ended. [`disconnect`](Self::disconnect) still signals
+    /// the tasks to stop but can't wait for them.
+    #[instrument(skip(self, event_sender))]
+    pub async fn connect_and_subscribe_handles(
+        &mut self,
+        asset_ids: Vec<String>,
+        event_sender: mpsc::Sender<MarketEvent>,
+    ) -> Result<ConnectionHandles> {
         info!("Connecting to Polymarket WebSocket: {}", self.url);
 
         // Connect to WebSocket
-        let (ws_stream, _response) = connect_async(&self.url)
+        let connect = connect_async_with_config(&self.url, Some(self.ws_config), false);
+        let (mut ws_stream, _response) = tokio::time::timeout(self.connect_timeout, connect)
             .await
+            .map_err(|_| {
+                ClientError::Timeout(format!(
+                    "connecting to {} took over {:?}",
+                    self.url, self.connect_timeout
+                ))
+            })?
             .map_err(|e| ClientError::WebSocketConnection(e.to_string()))?;
 
         info!("WebSocket connection established");
         self.is_connected.store(true, Ordering::SeqCst);
-        self.subscribed_assets = asset_ids.clone();
+        self.metrics.record_connection();
+        let event_sender =
+            MeteredSender::new(event_sender).with_overflow_policy(self.overflow_policy);
+        self.event_stats = Some(event_sender.stats_handle());
+        *self.subscribed_assets.write().await = asset_ids.clone();
 
-        // Send connection status
-        let _ = event_sender
+        // Send connection status; without a receiver there's no point staying connected
+        if let Err(e) = event_sender
             .send(MarketEvent::ConnectionStatus {
                 platform: Platform::Polymarket,
                 status: ConnectionStatus::Connected,
             })
-            .await;
+            .await
+        {
+            self.is_connected.store(false, Ordering::SeqCst);
+            self.event_stats = None;
+            let close = CloseFrame {
+                code: CloseCode::Normal,
+                reason: RECEIVER_DROPPED.into(),
+            };
+            if let Err(e) = ws_stream.close(Some(close)).await {
+                debug!("Failed to send Close frame: {}", e);
+            }
+            return Err(ClientError::ChannelSend(e.to_string()));
+        }
 
-        let (mut write, mut read) = ws_stream.split();
+        let (write, mut read) = ws_stream.split();
+        *self.writer.lock().await = Some(write);
 
         // Send initial subscription message
         let subscribe_msg = self.create_subscribe_message(&asset_ids);
         let msg_json = serde_json::to_string(&subscribe_msg)?;
         debug!("Sending subscription message: {}", msg_json);
-        write.send(Message::Text(msg_json)).await?;
+        Self::send_with(&self.writer, Message::Text(msg_json)).await?;
 
         // Clone values for the spawned tasks
         let is_connected = self.is_connected.clone();
         let heartbeat_interval = self.heartbeat_interval;
+        let max_silence = self.max_silence;
         let event_sender_clone = event_sender.clone();
+        let last_pong = Arc::new(std::sync::Mutex::new(Instant::now()));
+        let last_message = Arc::new(std::sync::Mutex::new(Instant::now()));
+        // Lets the heartbeat task stop a reader blocked on a dead socket
+        let stop_reading = Arc::new(tokio::sync::Notify::new());
+        let (shutdown_tx, mut shutdown_heartbeat) = watch::channel(false);
+        let mut shutdown_reader = shutdown_tx.subscribe();
+        self.shutdown = Some(shutdown_tx);
 
         // Spawn heartbeat task
         let is_connected_heartbeat = is_connected.clone();
+        let writer_heartbeat = self.writer.clone();
+        let last_pong_heartbeat = last_pong.clone();
+        let event_sender_heartbeat = event_sender.clone();
+        let last_message_heartbeat = last_message.clone();
+        let stop_reading_heartbeat = stop_reading.clone();
         let (heartbeat_tx, mut heartbeat_rx) = mpsc::channel::<()>(1);
-        
-        tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(heartbeat_interval));
+
+        let heartbeat_task = tokio::spawn(async move {
+            let period = Duration::from_secs(heartbeat_interval);
+            let mut interval = interval(period);
             loop {
+                let silence_deadline = tokio::time::Instant::from_std(
+                    *last_message_heartbeat.lock().unwrap() + max_silence,
+                );
                 tokio::select! {
                     _ = interval.tick() => {
                         if !is_connected_heartbeat.load(Ordering::SeqCst) {
                             break;
                         }
-                        // Heartbeat is handled by the main loop
+
+                        // Treat the connection as dead if the server stopped answering pings
+                        let since_pong = last_pong_heartbeat.lock().unwrap().elapsed();
+                        if since_pong > period * 2 {
+                            warn!("No PONG received for {:?}, marking connection as failed", since_pong);
+                            is_connected_heartbeat.store(false, Ordering::SeqCst);
+                            let _ = event_sender_heartbeat
+                                .send(MarketEvent::ConnectionStatus {
+                                    platform: Platform::Polymarket,
+                                    status: ConnectionStatus::Error(format!(
+                                        "No PONG received for {} ms",
+                                        since_pong.as_millis()
+                                    )),
+                                })
+                                .await;
+                            stop_reading_heartbeat.notify_one();
+                            break;
+                        }
+
+                        debug!("Sending PING");
+                        if let Err(e) =
+                            Self::send_with(&writer_heartbeat, Message::Text("PING".to_string())).await
+                        {
+                            warn!("Failed to send PING: {}", e);
+                        }
+                    }
+                    _ = tokio::time::sleep_until(silence_deadline) => {
+                        // Frames may have arrived since the deadline was set
+                        let silence = last_message_heartbeat.lock().unwrap().elapsed();
+                        if silence < max_silence {
+                            continue;
+                        }
+                        warn!("Nothing received for {:?}, dropping stale connection", silence);
+                        is_connected_heartbeat.store(false, Ordering::SeqCst);
+                        let _ = event_sender_heartbeat
+                            .send(MarketEvent::ConnectionStatus {
+                                platform: Platform::Polymarket,
+                                status: ConnectionStatus::Error("stale connection".to_string()),
+                            })
+                            .await;
+                        stop_reading_heartbeat.notify_one();
+                        break;
                     }
                     _ = heartbeat_rx.recv() => {
                         // Shutdown signal received
                         break;
                     }
+                    true = Self::shutdown_requested(&mut shutdown_heartbeat) => {
+                        break;
+                    }
                 }
             }
         });
 
         // Spawn message handling task
         let is_connected_msg = is_connected.clone();
-        tokio::spawn(async move {
-            let mut ping_interval = interval(Duration::from_secs(heartbeat_interval));
-            
-            loop {
-                tokio::select! {
-                    msg = read.next() => {
-                        match msg {
-                            Some(Ok(Message::Text(text))) => {
-                                if text == "PONG" || text == "pong" {
-                                    debug!("Received PONG");
-                                    let _ = event_sender_clone
-                                        .send(MarketEvent::Heartbeat {
-                                            platform: Platform::Polymarket,
-                                        })
-                                        .await;
-                                    continue;
-                                }
+        let writer_msg = self.writer.clone();
+        let order_books_msg = self.order_books.clone();
+        let min_trade_notional = self.min_trade_notional;
+        let metrics = self.metrics.clone();
+        let latency_tracking = self.latency_tracking;
+        let reader_span = info_span!("reader", platform = %Platform::Polymarket);
+        let reader_task = tokio::spawn(async move {
+            let parse_latency = latency_tracking.then(|| metrics.parse_latency());
+            let apply_latency = latency_tracking.then(|| metrics.apply_latency());
+            let mut sequences = SequenceTracker::new();
+            // Set when the client, rather than the server, ends the connection
+            let mut close_reason: Option<&str> = None;
+            'read: loop {
+                let frame = tokio::select! {
+                    frame = read.next() => frame,
+                    _ = stop_reading.notified() => {
+                        debug!("Connection marked dead, closing reader");
+                        break 'read;
+                    }
+                    true = Self::shutdown_requested(&mut shutdown_reader) => {
+                        close_reason = Some("client shutdown");
+                        break 'read;
+                    }
+                };
+                if let Some(Ok(ref message)) = frame {
+                    *last_message.lock().unwrap() = Instant::now();
+                    metrics.record_frame(message.len());
+                }
 
-                                // Parse and forward the message
-                                match Self::parse_message(&text) {
-                                    Ok(event) => {
-                                        if let Err(e) = event_sender_clone.send(event).await {
-                                            error!("Failed to send event: {}", e);
-                                            break;
-                                        }
+                match frame {
+                    Some(Ok(Message::Text(text))) => {
+                        if text == "PONG" || text == "pong" {
+                            debug!("Received PONG");
+                            *last_pong.lock().unwrap() = Instant::now();
+                            metrics.record_pong();
+                            if let Err(e) = event_sender_clone
+                                .send(MarketEvent::Heartbeat {
+                                    platform: Platform::Polymarket,
+                                })
+                                .await
+                            {
+                                error!("Failed to send heartbeat: {}", e);
+                                close_reason = Some(RECEIVER_DROPPED);
+                                break 'read;
+                            }
+                            continue;
+                        }
+
+                        // Parse and forward each event in the message
+                        let parse_span = debug_span!(
+                            "parse",
+                            platform = %Platform::Polymarket,
+                            bytes = text.len(),
+                        );
+                        let parsed = parse_span
+                            .in_scope(|| timed_parse(parse_latency, || Self::parse(&text)));
+                        match parsed {
+                            Ok(events) => {
+                                for event in &events {
+                                    metrics.record_event(event);
+                                }
+                                for mut event in Self::check_sequences(&mut sequences, events) {
+                                    // Tags log lines about this event with its market
+                                    let span = info_span!(
+                                        "event",
+                                        platform = %event.platform(),
+                                        asset_id = event.asset_id().unwrap_or_default(),
+                                    );
+                                    if min_trade_notional
+                                        .is_some_and(|min| event.is_trade_below(min))
+                                    {
+                                        span.in_scope(|| {
+                                            debug!("Dropping trade below minimum notional")
+                                        });
+                                        continue;
                                     }
-                                    Err(e) => {
-                                        warn!("Failed to parse message: {} - {}", e, text);
-                                        // Send raw message for debugging
-                                        let _ = event_sender_clone
-                                            .send(MarketEvent::Raw {
-                                                platform: Platform::Polymarket,
-                                                message: text,
-                                            })
-                                            .await;
+                                    if let Some(books) = &order_books_msg {
+                                        timed_apply(
+                                            apply_latency,
+                                            Self::track_event(books, &mut event),
+                                        )
+                                        .instrument(span.clone())
+                                        .await;
+                                    }
+                                    let sent = tokio::select! {
+                                        sent = event_sender_clone.send(event) => sent,
+                                        true = Self::shutdown_requested(&mut shutdown_reader) => {
+                                            close_reason = Some("client shutdown");
+                                            break 'read;
+                                        }
+                                    };
+                                    if let Err(e) = sent {
+                                        span.in_scope(|| error!("Failed to send event: {}", e));
+                                        close_reason = Some(RECEIVER_DROPPED);
+                                        break 'read;
                                     }
                                 }
                             }
-                            Some(Ok(Message::Ping(data))) => {
-                                debug!("Received Ping, sending Pong");
-                                // Note: Pong should be sent automatically by tungstenite
-                            }
-                            Some(Ok(Message::Pong(_))) => {
-                                debug!("Received Pong");
-                            }
-                            Some(Ok(Message::Close(frame))) => {
-                                info!("WebSocket closed: {:?}", frame);
-                                is_connected_msg.store(false, Ordering::SeqCst);
-                                let _ = event_sender_clone
-                                    .send(MarketEvent::ConnectionStatus {
-                                        platform: Platform::Polymarket,
-                                        status: ConnectionStatus::Disconnected(
-                                            frame.map(|f| f.reason.to_string()),
-                                        ),
-                                    })
-                                    .await;
-                                break;
-                            }
-                            Some(Err(e)) => {
-                                error!("WebSocket error: {}", e);
-                                is_connected_msg.store(false, Ordering::SeqCst);
-                                let _ = event_sender_clone
-                                    .send(MarketEvent::ConnectionStatus {
-                                        platform: Platform::Polymarket,
-                                        status: ConnectionStatus::Error(e.to_string()),
-                                    })
-                                    .await;
-                                break;
-                            }
-                            None => {
-                                info!("WebSocket stream ended");
-                                is_connected_msg.store(false, Ordering::SeqCst);
-                                let _ = event_sender_clone
-                                    .send(MarketEvent::ConnectionStatus {
+                            Err(e) => {
+                                parse_span.in_scope(|| {
+                                    warn!("Failed to parse message: {} - {}", e, text)
+                                });
+                                metrics.record_parse_failure();
+                                // Send raw message for debugging
+                                if let Err(e) = event_sender_clone
+                                    .send(MarketEvent::Raw {
                                         platform: Platform::Polymarket,
-                                        status: ConnectionStatus::Disconnected(None),
+                                        message: text,
                                     })
-                                    .await;
-                                break;
+                                    .await
+                                {
+                                    error!("Failed to send raw message: {}", e);
+                                    close_reason = Some(RECEIVER_DROPPED);
+                                    break 'read;
+                                }
                             }
-                            _ => {}
                         }
                     }
-                    _ = ping_interval.tick() => {
-                        // Note: Ping sending would need write access
-                        // In production, you'd use a shared write handle
+                    Some(Ok(Message::Ping(_))) => {
+                        debug!("Received Ping, sending Pong");
+                        // Note: Pong should be sent automatically by tungstenite
+                    }
+                    Some(Ok(Message::Pong(_))) => {
+                        debug!("Received Pong");
+                        *last_pong.lock().unwrap() = Instant::now();
+                        metrics.record_pong();
+                    }
+                    Some(Ok(Message::Close(frame))) => {
+                        info!("WebSocket closed: {:?}", frame);
+                        is_connected_msg.store(false, Ordering::SeqCst);
+                        let _ = event_sender_clone
+                            .send(MarketEvent::ConnectionStatus {
+                                platform: Platform::Polymarket,
+                                status: ConnectionStatus::Disconnected(
+                                    frame.map(|f| f.reason.to_string()),
+                                ),
+                            })
+                            .await;
+                        break;
+                    }
+                    Some(Err(e)) => {
+                        error!("WebSocket error: {}", e);
+                        is_connected_msg.store(false, Ordering::SeqCst);
+                        let _ = event_sender_clone
+                            .send(MarketEvent::ConnectionStatus {
+                                platform: Platform::Polymarket,
+                                status: ConnectionStatus::Error(e.to_string()),
+                            })
+                            .await;
+                        break;
+                    }
+                    None => {
+                        info!("WebSocket stream ended");
+                        is_connected_msg.store(false, Ordering::SeqCst);
+                        let _ = event_sender_clone
+                            .send(MarketEvent::ConnectionStatus {
+                                platform: Platform::Polymarket,
+                                status: ConnectionStatus::Disconnected(None),
+                            })
+                            .await;
+                        break;
                     }
+                    _ => {}
+                }
+            }
+
+            if let Some(reason) = close_reason {
+                info!("Closing WebSocket connection: {}", reason);
+                is_connected_msg.store(false, Ordering::SeqCst);
+                let close = Message::Close(Some(CloseFrame {
+                    code: CloseCode::Normal,
+                    reason: reason.into(),
+                }));
+                if let Err(e) = Self::send_with(&writer_msg, close).await {
+                    debug!("Failed to send Close frame: {}", e);
                 }
             }
-            
-            // Signal heartbeat task to stop
+
+            // Release the write half and signal heartbeat task to stop
+            *writer_msg.lock().await = None;
             drop(heartbeat_tx);
-        });
+        }
+        .instrument(reader_span));
+        Ok(ConnectionHandles {
+            reader: reader_task,
+            heartbeat: heartbeat_task,
+        })
+    }
 
-        Ok(())
+    /// Wait for `disconnect` to signal shutdown
+    ///
+    /// Returns false if the client was dropped without signalling, in which
+    /// case the tasks keep running until the connection ends.
+    async fn shutdown_requested(shutdown: &mut watch::Receiver<bool>) -> bool {
+        shutdown.wait_for(|stop| *stop).await.is_ok()
+    }
+
+    /// Follow each book update with a `ResyncNeeded` event if deltas were missed
+    fn check_sequences(
+        sequences: &mut SequenceTracker,
+        events: Vec<MarketEvent>,
+    ) -> Vec<MarketEvent> {
+        let mut checked = Vec::with_capacity(events.len());
+        for event in events {
+            let resync = match &event {
+                MarketEvent::OrderBookUpdate(update) => sequences.apply_update(update),
+                _ => None,
+            };
+            checked.push(event);
+            checked.extend(resync);
+        }
+        checked
+    }
+
+    /// Update managed books from `event`, or infer its side if it's a trade
+    async fn track_event(books: &RwLock<OrderBookManager>, event: &mut MarketEvent) {
+        match event {
+            MarketEvent::Trade(trade) => {
+                if books.read().await.infer_trade_side(trade) {
+                    debug!("Inferred {} side for trade {}", trade.side, trade.trade_id);
+                }
+            }
+            _ => {
+                books.write().await.apply_event(event);
+            }
+        }
+    }
+
+    /// Send a subscribe/unsubscribe operation over the live connection
+    async fn send_operation(&self, operation: &str, asset_ids: &[String]) -> Result<()> {
+        let op_msg = self.create_operation_message(operation, asset_ids);
+        let msg_json = serde_json::to_string(&op_msg)?;
+        debug!("Sending {} operation: {}", operation, msg_json);
+        Self::send_with(&self.writer, Message::Text(msg_json)).await
+    }
+
+    /// Send a message over a shared write half
+    async fn send_with(writer: &SharedWriter, message: Message) -> Result<()> {
+        let mut guard = writer.lock().await;
+        match guard.as_mut() {
+            Some(write) => {
+                write.send(message).await?;
+                Ok(())
+            }
+            None => Err(ClientError::WebSocketConnection(
+                "WebSocket is not connected".to_string(),
+            )),
+        }
     }
 
     /// Create subscription message based on channel type
@@ -259,57 +785,79 @@ impl PolymarketWebSocketClient {
         }
     }
 
-    /// Parse an incoming WebSocket message into a MarketEvent
-    fn parse_message(text: &str) -> Result<MarketEvent> {
+    /// Create an operation message based on channel type
+    fn create_operation_message(
+        &self,
+        operation: &str,
+        asset_ids: &[String],
+    ) -> WsOperationMessage {
+        match self.channel_type {
+            ChannelType::Market => WsOperationMessage {
+                operation: operation.to_string(),
+                assets_ids: Some(asset_ids.to_vec()),
+                markets: None,
+            },
+            ChannelType::User => WsOperationMessage {
+                operation: operation.to_string(),
+                assets_ids: None,
+                markets: Some(asset_ids.to_vec()),
+            },
+        }
+    }
+
+    /// Parse an incoming WebSocket message into MarketEvents
+    ///
+    /// Polymarket may batch several events into a single text frame as a
+    /// JSON array; each element is converted individually, and one that is
+    /// malformed comes back as `MarketEvent::Raw` without losing the rest.
+    /// Messages of a type the client doesn't interpret are also `Raw`.
+    /// Fails if the text isn't JSON or a single recognized event is malformed.
+    pub fn parse(text: &str) -> Result<Vec<MarketEvent>> {
         // Try to parse as JSON
         let value: serde_json::Value = serde_json::from_str(text)?;
 
-        // Check for event_type field
-        if let Some(event_type) = value.get("event_type").and_then(|v| v.as_str()) {
-            match event_type {
-                "book" => {
-                    let book_event: BookUpdateEvent = serde_json::from_value(value)?;
-                    return Ok(Self::convert_book_update(book_event));
-                }
-                "price_change" => {
-                    let price_event: PriceChangeEvent = serde_json::from_value(value)?;
-                    return Ok(Self::convert_price_change(price_event));
-                }
-                "trade" | "last_trade_price" => {
-                    // Check if it's a trade or just a price update
-                    if value.get("id").is_some() {
-                        let trade_event: TradeEvent = serde_json::from_value(value)?;
-                        return Ok(Self::convert_trade(trade_event));
-                    } else {
-                        let ltp_event: LastTradePriceEvent = serde_json::from_value(value)?;
-                        return Ok(MarketEvent::Raw {
+        match value {
+            serde_json::Value::Array(items) => Ok(items
+                .into_iter()
+                .map(|item| {
+                    let raw = item.to_string();
+                    Self::parse_value(item, &raw).unwrap_or_else(|e| {
+                        warn!("Failed to parse batched message: {} - {}", e, raw);
+                        MarketEvent::Raw {
                             platform: Platform::Polymarket,
-                            message: format!(
-                                "Last trade price for {}: {}",
-                                ltp_event.asset_id, ltp_event.price
-                            ),
-                        });
-                    }
-                }
-                _ => {}
-            }
+                            message: raw,
+                        }
+                    })
+                })
+                .collect()),
+            value => Ok(vec![Self::parse_value(value, text)?]),
         }
+    }
 
-        // If we couldn't parse it specifically, try general parsing
-        if value.get("bids").is_some() && value.get("asks").is_some() {
-            let book_event: BookUpdateEvent = serde_json::from_value(value)?;
-            return Ok(Self::convert_book_update(book_event));
+    /// Parse a single JSON event object into a MarketEvent
+    fn parse_value(value: serde_json::Value, text: &str) -> Result<MarketEvent> {
+        match WsIncomingMessage::from_value(value)? {
+            WsIncomingMessage::BookUpdate(event) => Ok(Self::convert_book_update(event, true)),
+            WsIncomingMessage::PriceChange(event) => Ok(Self::convert_price_change(event)),
+            WsIncomingMessage::Trade(event) => Ok(Self::convert_trade(event)),
+            WsIncomingMessage::LastTradePrice(event) => Ok(Self::convert_last_trade_price(event)),
+            // Untyped messages carrying a book are treated as deltas
+            WsIncomingMessage::Unknown(value)
+                if value.get("bids").is_some() && value.get("asks").is_some() =>
+            {
+                let book_event: BookUpdateEvent = serde_json::from_value(value)?;
+                Ok(Self::convert_book_update(book_event, false))
+            }
+            WsIncomingMessage::OrderUpdate(event) => Ok(Self::convert_order_update(event)),
+            WsIncomingMessage::Unknown(_) => Ok(MarketEvent::Raw {
+                platform: Platform::Polymarket,
+                message: text.to_string(),
+            }),
         }
-
-        // Return as raw message
-        Ok(MarketEvent::Raw {
-            platform: Platform::Polymarket,
-            message: text.to_string(),
-        })
     }
 
     /// Convert a BookUpdateEvent to OrderBookUpdate
-    fn convert_book_update(event: BookUpdateEvent) -> MarketEvent {
+    fn convert_book_update(event: BookUpdateEvent, is_snapshot: bool) -> MarketEvent {
         let bids: Vec<PriceLevel> = event
             .bids
             .into_iter()
@@ -338,12 +886,43 @@ impl PolymarketWebSocketClient {
             asset_id: event.asset_id,
             bids,
             asks,
-            timestamp: chrono::Utc::now(),
-            is_snapshot: event.event_type.as_deref() == Some("book"),
-            sequence: 0,
+            timestamp: Self::exchange_time(event.timestamp),
+            received_at: chrono::Utc::now(),
+            is_snapshot,
+            sequence: event.sequence.unwrap_or_default(),
+            hash: event.hash,
         })
     }
 
+    /// Exchange timestamp, falling back to now when absent
+    ///
+    /// Polymarket sends some timestamps in seconds and others in
+    /// milliseconds; anything at or above 10^11 (year 5138 in seconds, 1973
+    /// in milliseconds) is taken as milliseconds.
+    fn exchange_time(timestamp: Option<i64>) -> chrono::DateTime<chrono::Utc> {
+        const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+        timestamp
+            .and_then(|ts| {
+                if ts.abs() >= MILLIS_THRESHOLD {
+                    chrono::DateTime::from_timestamp_millis(ts)
+                } else {
+                    chrono::DateTime::from_timestamp(ts, 0)
+                }
+            })
+            .unwrap_or_else(chrono::Utc::now)
+    }
+
+    /// Convert a LastTradePriceEvent to a LastTradePrice event
+    fn convert_last_trade_price(event: LastTradePriceEvent) -> MarketEvent {
+        MarketEvent::LastTradePrice {
+            platform: Platform::Polymarket,
+            asset_id: event.asset_id,
+            price: event.price.parse().unwrap_or_default(),
+            timestamp: Self::exchange_time(event.timestamp),
+        }
+    }
+
     /// Convert a PriceChangeEvent to OrderBookUpdate
     fn convert_price_change(event: PriceChangeEvent) -> MarketEvent {
         let mut bids = Vec::new();
@@ -368,17 +947,59 @@ impl PolymarketWebSocketClient {
             asset_id: event.asset_id,
             bids,
             asks,
-            timestamp: chrono::Utc::now(),
+            timestamp: Self::exchange_time(event.timestamp),
+            received_at: chrono::Utc::now(),
             is_snapshot: false,
-            sequence: 0,
+            sequence: event.sequence.unwrap_or_default(),
+            hash: event.hash,
+        })
+    }
+
+    /// Convert a user-channel OrderUpdateEvent to OrderUpdate
+    ///
+    /// The status comes from Polymarket's order status where it names one,
+    /// and otherwise from how much of the order has matched. The update
+    /// doesn't say what the latest fill was, so `fill` is left empty.
+    fn convert_order_update(event: OrderUpdateEvent) -> MarketEvent {
+        let original_size: Decimal = event.original_size.parse().unwrap_or_default();
+        let size_matched: Decimal = event.size_matched.parse().unwrap_or_default();
+        let side = match event.side.to_lowercase().as_str() {
+            "sell" | "ask" => Side::Sell,
+            _ => Side::Buy,
+        };
+        let status = match event.status.to_lowercase().as_str() {
+            "canceled" | "cancelled" => OrderStatus::Cancelled,
+            "expired" => OrderStatus::Expired,
+            _ if original_size > Decimal::ZERO && size_matched >= original_size => {
+                OrderStatus::Filled
+            }
+            _ if size_matched > Decimal::ZERO => OrderStatus::PartiallyFilled,
+            _ => OrderStatus::Live,
+        };
+
+        MarketEvent::OrderUpdate(OrderUpdate {
+            platform: Platform::Polymarket,
+            order_id: event.order_id,
+            market_id: event.market.unwrap_or_default(),
+            asset_id: event.asset_id.unwrap_or_default(),
+            side,
+            price: event.price.parse().unwrap_or_default(),
+            original_size,
+            size_matched,
+            fill: None,
+            status,
+            timestamp: Self::exchange_time(event.timestamp),
         })
     }
 
     /// Convert a TradeEvent to Trade
     fn convert_trade(event: TradeEvent) -> MarketEvent {
-        let side = match event.side.to_lowercase().as_str() {
-            "buy" | "bid" => Side::Buy,
-            _ => Side::Sell,
+        // Unlabeled trades keep the old `Sell` default but are tagged so the
+        // side can be inferred from the book downstream
+        let (side, side_source) = match event.side.as_deref().map(str::to_lowercase).as_deref() {
+            Some("buy" | "bid") => (Side::Buy, TradeSideSource::Reported),
+            Some("sell" | "ask") => (Side::Sell, TradeSideSource::Reported),
+            _ => (Side::Sell, TradeSideSource::Unknown),
         };
 
         MarketEvent::Trade(Trade {
@@ -389,7 +1010,9 @@ impl PolymarketWebSocketClient {
             price: event.price.parse().unwrap_or_default(),
             size: event.size.parse().unwrap_or_default(),
             side,
-            timestamp: chrono::Utc::now(),
+            side_source,
+            timestamp: Self::exchange_time(event.timestamp),
+            received_at: chrono::Utc::now(),
         })
     }
 }
@@ -397,6 +1020,600 @@ impl PolymarketWebSocketClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::types::OrderBook;
+    use crate::polymarket::test_utils::{next_data_frame, next_frame, spawn_test_server};
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn test_disconnect_closes_socket_and_stops_tasks() {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        let (tx, mut rx) = mpsc::channel(100);
+
+        client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        assert!(next_data_frame(&mut frames).await.contains("asset_1"));
+        assert!(client.is_connected());
+
+        timeout(Duration::from_secs(2), client.disconnect())
+            .await
+            .expect("tasks didn't stop");
+        assert!(!client.is_connected());
+        assert!(client.tasks.is_empty());
+        assert_eq!(next_data_frame(&mut frames).await, "CLOSE");
+
+        // The write half is released, so nothing more can be sent
+        assert!(client.subscribe(&["asset_2".to_string()]).await.is_err());
+        while let Ok(Some(event)) = timeout(Duration::from_millis(200), rx.recv()).await {
+            assert!(matches!(event, MarketEvent::ConnectionStatus { .. }));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_closes_event_channel() {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        let (tx, mut rx) = mpsc::channel(100);
+
+        client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        assert!(next_data_frame(&mut frames).await.contains("asset_1"));
+        client.disconnect().await;
+
+        // Only the stats are kept, so the consumer sees the end of the stream
+        loop {
+            let event = timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .expect("event channel left open after disconnect");
+            match event {
+                Some(event) => assert!(matches!(event, MarketEvent::ConnectionStatus { .. })),
+                None => break,
+            }
+        }
+        let stats = client.channel_stats().unwrap();
+        assert!(stats.sent >= 1);
+        assert_eq!(stats.queue_depth, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reader_handle_completes_when_server_closes() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            // Wait for the subscription, then hang up
+            ws.next().await;
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        let (tx, mut rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't finish")
+            .unwrap();
+        assert!(!client.is_connected());
+
+        // The heartbeat task follows the reader
+        timeout(Duration::from_secs(3), handles.heartbeat)
+            .await
+            .expect("heartbeat didn't finish")
+            .unwrap();
+
+        let mut statuses = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let MarketEvent::ConnectionStatus { status, .. } = event {
+                statuses.push(status);
+            }
+        }
+        assert_eq!(statuses[0], ConnectionStatus::Connected);
+        assert!(matches!(
+            statuses.last(),
+            Some(ConnectionStatus::Disconnected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reader_exits_when_receiver_dropped() {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let mut client =
+            PolymarketWebSocketClient::new_market_channel(&url).with_heartbeat_interval(1);
+        let (tx, rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        assert!(next_data_frame(&mut frames).await.contains("asset_1"));
+        drop(rx);
+
+        // The next PONG can't be forwarded, so the reader closes the socket
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+        assert!(!client.is_connected());
+        assert_eq!(next_data_frame(&mut frames).await, "CLOSE");
+        timeout(Duration::from_secs(3), handles.heartbeat)
+            .await
+            .expect("heartbeat didn't finish")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_fails_without_receiver() {
+        let (url, _frames) = spawn_test_server(true).await;
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        let (tx, rx) = mpsc::channel(100);
+        drop(rx);
+
+        let result = client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await;
+        assert!(matches!(result, Err(ClientError::ChannelSend(_))));
+        assert!(!client.is_connected());
+    }
+
+    #[test]
+    fn test_message_limits_stored() {
+        let client = PolymarketWebSocketClient::new_market_channel("wss://example.com")
+            .with_websocket_config(WebSocketConfig {
+                accept_unmasked_frames: true,
+                ..Default::default()
+            })
+            .with_message_limits(Some(128 << 20), None);
+
+        let config = client.websocket_config();
+        assert_eq!(config.max_message_size, Some(128 << 20));
+        assert_eq!(config.max_frame_size, None);
+        assert!(config.accept_unmasked_frames);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_message_reports_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            let _ = ws.send(Message::Text("x".repeat(2048))).await;
+            while ws.next().await.is_some() {}
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_message_limits(Some(1024), None);
+        let (tx, mut rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+
+        let mut error = None;
+        while let Ok(event) = rx.try_recv() {
+            if let MarketEvent::ConnectionStatus {
+                status: ConnectionStatus::Error(message),
+                ..
+            } = event
+            {
+                error = Some(message);
+            }
+        }
+        assert!(error.unwrap().contains("2048 > 1024"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_messages_and_parse_failures() {
+        let frames = [
+            r#"{"event_type": "book", "asset_id": "asset_1", "market": "m", "bids": [], "asks": []}"#,
+            "not json",
+            r#"[{"event_type": "trade", "asset_id": "asset_1", "market": "m", "price": "0.5", "size": "10", "side": "BUY"}, {"event_type": "last_trade_price", "asset_id": "asset_1", "price": "0.5"}]"#,
+            r#"{"event_type": "book", "asset_id": "asset_1""#,
+            "PONG",
+        ];
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        assert_eq!(client.metrics(), MetricsSnapshot::default());
+        let (tx, _rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+
+        let metrics = client.metrics();
+        assert_eq!(metrics.parse_failures, 2);
+        assert_eq!(metrics.book_snapshots, 1);
+        assert_eq!(metrics.trades, 1);
+        assert_eq!(metrics.last_trade_prices, 1);
+        assert_eq!(metrics.messages(), 3);
+        assert_eq!(metrics.pongs, 1);
+        assert_eq!(metrics.reconnects, 0);
+        let text_bytes: usize = frames.iter().map(|frame| frame.len()).sum();
+        assert!(metrics.bytes_received >= text_bytes as u64);
+        assert!(metrics.last_message_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sequence_gap_requests_resync() {
+        let frames = [
+            r#"{"event_type": "book", "asset_id": "asset_1", "market": "m", "sequence": 1, "bids": [{"price": "0.5", "size": "10"}], "asks": []}"#,
+            r#"{"event_type": "price_change", "asset_id": "asset_1", "market": "m", "sequence": 2, "changes": [{"price": "0.5", "side": "BUY", "size": "20"}]}"#,
+            r#"{"event_type": "price_change", "asset_id": "asset_1", "market": "m", "sequence": 5, "changes": [{"price": "0.5", "side": "BUY", "size": "30"}]}"#,
+        ];
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let books = Arc::new(RwLock::new(OrderBookManager::new()));
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_order_book_manager(books.clone());
+        let (tx, mut rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+
+        let mut received = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                MarketEvent::OrderBookUpdate(update) => {
+                    received.push(format!("update {}", update.sequence))
+                }
+                MarketEvent::ResyncNeeded { platform, asset_id } => {
+                    assert_eq!(platform, Platform::Polymarket);
+                    received.push(format!("resync {}", asset_id));
+                }
+                _ => {}
+            }
+        }
+        // Raised once, right after the delta that skipped ahead
+        assert_eq!(
+            received,
+            vec!["update 1", "update 2", "update 5", "resync asset_1"]
+        );
+        assert!(books.read().await.needs_resync("asset_1"));
+        // Synthesized events aren't counted as received messages
+        assert_eq!(client.metrics().messages(), 3);
+    }
+
+    /// Serve `frames` to a client with latency tracking set to `enabled`
+    async fn latency_metrics(enabled: bool) -> MetricsSnapshot {
+        let frames = [
+            r#"{"event_type": "book", "asset_id": "asset_1", "market": "m", "bids": [{"price": "0.5", "size": "10"}], "asks": []}"#,
+            r#"{"event_type": "price_change", "asset_id": "asset_1", "market": "m", "changes": [{"price": "0.5", "side": "BUY", "size": "20"}]}"#,
+            "not json",
+            "PONG",
+        ];
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_order_book_manager(Arc::new(RwLock::new(OrderBookManager::new())))
+            .with_latency_tracking(enabled);
+        let (tx, _rx) = mpsc::channel(100);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+        client.metrics()
+    }
+
+    #[tokio::test]
+    async fn test_latency_tracking_records_parse_and_apply_times() {
+        let metrics = latency_metrics(true).await;
+        // Every text frame but the PONG is parsed, failures included
+        let parse = metrics.parse_latency.expect("no parse timings");
+        assert_eq!(parse.samples, 3);
+        assert!(parse.max >= parse.median);
+        // One book snapshot and one delta applied
+        assert_eq!(metrics.apply_latency.expect("no apply timings").samples, 2);
+
+        let metrics = latency_metrics(false).await;
+        assert_eq!(metrics.parse_latency, None);
+        assert_eq!(metrics.apply_latency, None);
+        assert_eq!(metrics.messages(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hanging_handshake_times_out() {
+        // Accepts the TCP connection but never answers the upgrade request
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_connect_timeout(Duration::from_millis(200));
+        let (tx, _rx) = mpsc::channel(10);
+        let started = Instant::now();
+        let err = client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ClientError::Timeout(_)), "got {:?}", err);
+        assert!(err.is_retryable());
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(!client.is_connected());
+    }
+
+    /// Log output shared with a test subscriber
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_lines_carry_market_context() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        // The test runtime is single-threaded, so this covers the reader task
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            let trade = r#"{"event_type": "trade", "asset_id": "asset_7", "market": "m", "price": "0.5", "size": "1", "side": "BUY"}"#;
+            ws.send(Message::Text(trade.to_string())).await.unwrap();
+            ws.send(Message::Text("not json".to_string()))
+                .await
+                .unwrap();
+            ws.close(None).await.unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_min_trade_notional(Decimal::from(100));
+        let (tx, _rx) = mpsc::channel(10);
+        let handles = client
+            .connect_and_subscribe_handles(vec!["asset_7".to_string()], tx)
+            .await
+            .unwrap();
+        timeout(Duration::from_secs(3), handles.reader)
+            .await
+            .expect("reader didn't exit")
+            .unwrap();
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let dropped = logs
+            .lines()
+            .find(|line| line.contains("Dropping trade below minimum notional"))
+            .expect("no log line for the dropped trade");
+        assert!(dropped.contains("platform=polymarket"), "{}", dropped);
+        assert!(dropped.contains("asset_id=\"asset_7\""), "{}", dropped);
+        let failed = logs
+            .lines()
+            .find(|line| line.contains("Failed to parse message"))
+            .expect("no log line for the parse failure");
+        assert!(
+            failed.contains("parse{platform=polymarket bytes=8}"),
+            "{}",
+            failed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_sends_pings_on_schedule() {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let mut client =
+            PolymarketWebSocketClient::new_market_channel(&url).with_heartbeat_interval(1);
+        let (tx, mut rx) = mpsc::channel(100);
+
+        client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+
+        // Subscription goes out first, then a PING per interval
+        assert!(next_frame(&mut frames).await.contains("asset_1"));
+        assert_eq!(next_frame(&mut frames).await, "PING");
+        let first_ping = Instant::now();
+        assert_eq!(next_frame(&mut frames).await, "PING");
+        assert!(first_ping.elapsed() >= Duration::from_millis(900));
+
+        // PONG replies surface as heartbeats and keep the connection alive
+        let mut heartbeats = 0;
+        while let Ok(Some(event)) = timeout(Duration::from_millis(200), rx.recv()).await {
+            match event {
+                MarketEvent::Heartbeat { .. } => heartbeats += 1,
+                MarketEvent::ConnectionStatus { status, .. } => {
+                    assert_eq!(status, ConnectionStatus::Connected)
+                }
+                other => panic!("Unexpected event: {:?}", other),
+            }
+        }
+        assert!(heartbeats >= 1);
+        assert!(client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_missing_pong_reports_error() {
+        let (url, _frames) = spawn_test_server(false).await;
+        let mut client =
+            PolymarketWebSocketClient::new_market_channel(&url).with_heartbeat_interval(1);
+        let (tx, mut rx) = mpsc::channel(100);
+
+        client.connect_and_subscribe(vec![], tx).await.unwrap();
+
+        let status = timeout(Duration::from_secs(5), async {
+            loop {
+                match rx.recv().await {
+                    Some(MarketEvent::ConnectionStatus {
+                        status: ConnectionStatus::Error(reason),
+                        ..
+                    }) => return reason,
+                    Some(_) => continue,
+                    None => panic!("Event channel closed"),
+                }
+            }
+        })
+        .await
+        .expect("expected a pong timeout error");
+
+        assert!(status.contains("PONG"));
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_silent_connection_marked_stale() {
+        // Server accepts the connection and then never sends anything
+        let (url, mut frames) = spawn_test_server(false).await;
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url)
+            .with_max_silence(Duration::from_millis(300));
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let connected_at = Instant::now();
+        client
+            .connect_and_subscribe(vec!["asset_1".to_string()], tx)
+            .await
+            .unwrap();
+        next_data_frame(&mut frames).await;
+
+        let reason = timeout(Duration::from_secs(3), async {
+            loop {
+                match rx.recv().await {
+                    Some(MarketEvent::ConnectionStatus {
+                        status: ConnectionStatus::Error(reason),
+                        ..
+                    }) => return reason,
+                    Some(_) => continue,
+                    None => panic!("Event channel closed"),
+                }
+            }
+        })
+        .await
+        .expect("watchdog never fired");
+
+        assert_eq!(reason, "stale connection");
+        assert!(connected_at.elapsed() >= Duration::from_millis(300));
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_subscribe_and_unsubscribe() {
+        let (url, mut frames) = spawn_test_server(true).await;
+        let mut client = PolymarketWebSocketClient::new_market_channel(&url);
+        let (tx, _rx) = mpsc::channel(100);
+
+        client
+            .connect_and_subscribe(vec!["asset_a".to_string()], tx)
+            .await
+            .unwrap();
+        next_data_frame(&mut frames).await;
+
+        client
+            .subscribe(&["asset_a".to_string(), "asset_b".to_string()])
+            .await
+            .unwrap();
+        let op: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(
+            op,
+            serde_json::json!({"operation": "subscribe", "assets_ids": ["asset_b"]})
+        );
+
+        client
+            .unsubscribe(&["asset_a".to_string(), "unknown".to_string()])
+            .await
+            .unwrap();
+        let op: serde_json::Value =
+            serde_json::from_str(&next_data_frame(&mut frames).await).unwrap();
+        assert_eq!(
+            op,
+            serde_json::json!({"operation": "unsubscribe", "assets_ids": ["asset_a"]})
+        );
+
+        assert_eq!(
+            client.subscribed_assets().await,
+            vec!["asset_b".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_requires_connection() {
+        let client = PolymarketWebSocketClient::new_market_channel("ws://127.0.0.1:1");
+
+        let result = client.subscribe(&["asset_a".to_string()]).await;
+
+        assert!(matches!(result, Err(ClientError::WebSocketConnection(_))));
+        assert!(client.subscribed_assets().await.is_empty());
+    }
 
     #[test]
     fn test_market_channel_creation() {
@@ -416,10 +1633,10 @@ mod tests {
             "asks": [{"price": "0.55", "size": "50"}]
         }"#;
 
-        let result = PolymarketWebSocketClient::parse_message(json);
+        let result = PolymarketWebSocketClient::parse(json);
         assert!(result.is_ok());
 
-        if let Ok(MarketEvent::OrderBookUpdate(update)) = result {
+        if let Ok([MarketEvent::OrderBookUpdate(update)]) = result.as_deref() {
             assert_eq!(update.asset_id, "123456");
             assert_eq!(update.bids.len(), 1);
             assert_eq!(update.asks.len(), 1);
@@ -428,6 +1645,137 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_book_keeps_checksum() {
+        let json = r#"{
+            "event_type": "book",
+            "asset_id": "123",
+            "market": "0xabc",
+            "hash": "f9dbb73a3c30d86573f340abe38677094b2f9e87",
+            "timestamp": 1700000000000,
+            "bids": [{"price": "0.48", "size": "100"}, {"price": "0.50", "size": "20.5"}],
+            "asks": [{"price": "0.55", "size": "200"}, {"price": "0.52", "size": "30"}]
+        }"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        let [MarketEvent::OrderBookUpdate(update)] = events.as_slice() else {
+            panic!("Expected OrderBookUpdate");
+        };
+        assert_eq!(update.timestamp.timestamp_millis(), 1_700_000_000_000);
+
+        // The checksum covers the exchange timestamp. The frame's hash was
+        // computed independently (see `test_verify_checksum` in common::types)
+        let book = OrderBook::from(update);
+        let hash = book.hash.as_deref().unwrap();
+        assert!(book.verify_checksum(hash));
+    }
+
+    #[test]
+    fn test_parse_order_update() {
+        let json = r#"{
+            "event_type": "order",
+            "id": "0xff354cd7ca7539dfa9c28d90943ab5779a4eac34b9b37a757d7b32bdfb11790b",
+            "market": "0xbd31dc8a20211944f6b70f31557f1001557b59905b7738480ca09bd4532f84af",
+            "asset_id": "52114319501245915516055106046884209969926127482827954674443846427813813222426",
+            "owner": "9180014b-33c8-9240-a14b-bdca11c0a465",
+            "outcome": "YES",
+            "type": "UPDATE",
+            "side": "SELL",
+            "price": "0.57",
+            "original_size": "10",
+            "size_matched": "4",
+            "status": "LIVE",
+            "timestamp": 1672290701
+        }"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        let [MarketEvent::OrderUpdate(update)] = events.as_slice() else {
+            panic!("Expected OrderUpdate, got {:?}", events);
+        };
+        assert_eq!(update.platform, Platform::Polymarket);
+        assert!(update.order_id.starts_with("0xff354c"));
+        assert!(update.market_id.starts_with("0xbd31dc"));
+        assert!(update.asset_id.starts_with("5211431"));
+        assert_eq!(update.side, Side::Sell);
+        assert_eq!(update.price, rust_decimal_macros::dec!(0.57));
+        assert_eq!(update.original_size, rust_decimal_macros::dec!(10));
+        assert_eq!(update.size_matched, rust_decimal_macros::dec!(4));
+        assert_eq!(update.remaining(), rust_decimal_macros::dec!(6));
+        assert_eq!(update.status, OrderStatus::PartiallyFilled);
+        assert_eq!(update.timestamp.timestamp(), 1_672_290_701);
+
+        let cancelled = json.replace(r#""status": "LIVE""#, r#""status": "CANCELED""#);
+        let events = PolymarketWebSocketClient::parse(&cancelled).unwrap();
+        let [MarketEvent::OrderUpdate(update)] = events.as_slice() else {
+            panic!("Expected OrderUpdate, got {:?}", events);
+        };
+        assert_eq!(update.status, OrderStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_exchange_time_normalizes_magnitude() {
+        let expected = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(
+            PolymarketWebSocketClient::exchange_time(Some(1_700_000_000)),
+            expected
+        );
+        assert_eq!(
+            PolymarketWebSocketClient::exchange_time(Some(1_700_000_000_000)),
+            expected
+        );
+
+        let before = chrono::Utc::now();
+        assert!(PolymarketWebSocketClient::exchange_time(None) >= before);
+    }
+
+    #[test]
+    fn test_parse_trade_timestamp() {
+        let json = r#"{
+            "event_type": "trade",
+            "asset_id": "123456",
+            "id": "trade_1",
+            "price": "0.52",
+            "size": "100",
+            "timestamp": 1700000000
+        }"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        let [MarketEvent::Trade(trade)] = events.as_slice() else {
+            panic!("Expected Trade");
+        };
+        assert_eq!(trade.timestamp.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_parse_last_trade_price() {
+        let json = r#"{
+            "event_type": "last_trade_price",
+            "asset_id": "123456",
+            "market": "condition_123",
+            "price": "0.456",
+            "side": "BUY",
+            "size": "219.217767",
+            "fee_rate_bps": "0",
+            "timestamp": 1750428146322
+        }"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        let [MarketEvent::LastTradePrice {
+            platform,
+            asset_id,
+            price,
+            timestamp,
+        }] = events.as_slice()
+        else {
+            panic!("Expected LastTradePrice, got {:?}", events);
+        };
+        assert_eq!(*platform, Platform::Polymarket);
+        assert_eq!(asset_id, "123456");
+        assert_eq!(*price, rust_decimal_macros::dec!(0.456));
+        assert_eq!(timestamp.timestamp_millis(), 1_750_428_146_322);
+        assert_eq!(events[0].timestamp(), Some(*timestamp));
+    }
+
     #[test]
     fn test_parse_trade() {
         let json = r#"{
@@ -440,15 +1788,131 @@ mod tests {
             "side": "buy"
         }"#;
 
-        let result = PolymarketWebSocketClient::parse_message(json);
+        let result = PolymarketWebSocketClient::parse(json);
         assert!(result.is_ok());
 
-        if let Ok(MarketEvent::Trade(trade)) = result {
+        if let Ok([MarketEvent::Trade(trade)]) = result.as_deref() {
             assert_eq!(trade.asset_id, "123456");
             assert_eq!(trade.trade_id, "trade_1");
             assert_eq!(trade.side, Side::Buy);
+            assert_eq!(trade.side_source, TradeSideSource::Reported);
         } else {
             panic!("Expected Trade");
         }
     }
+
+    #[tokio::test]
+    async fn test_unlabeled_trade_side_inferred_from_book() {
+        let book = r#"{
+            "event_type": "book",
+            "asset_id": "123456",
+            "market": "condition_123",
+            "bids": [{"price": "0.50", "size": "100"}],
+            "asks": [{"price": "0.56", "size": "50"}]
+        }"#;
+        let trade = |price: &str| {
+            format!(
+                r#"{{"event_type": "trade", "asset_id": "123456", "id": "t", "price": "{}", "size": "5"}}"#,
+                price
+            )
+        };
+
+        // Without a book the side stays a tagged placeholder
+        let books = RwLock::new(OrderBookManager::new());
+        let mut events = PolymarketWebSocketClient::parse(&trade("0.55")).unwrap();
+        PolymarketWebSocketClient::track_event(&books, &mut events[0]).await;
+        match &events[0] {
+            MarketEvent::Trade(trade) => {
+                assert_eq!(trade.side_source, TradeSideSource::Unknown)
+            }
+            other => panic!("Expected Trade, got {:?}", other),
+        }
+
+        let mut events = PolymarketWebSocketClient::parse(book).unwrap();
+        PolymarketWebSocketClient::track_event(&books, &mut events[0]).await;
+
+        for (price, side) in [("0.55", Side::Buy), ("0.51", Side::Sell)] {
+            let mut events = PolymarketWebSocketClient::parse(&trade(price)).unwrap();
+            PolymarketWebSocketClient::track_event(&books, &mut events[0]).await;
+            match &events[0] {
+                MarketEvent::Trade(trade) => {
+                    assert_eq!(trade.side, side);
+                    assert_eq!(trade.side_source, TradeSideSource::Inferred);
+                }
+                other => panic!("Expected Trade, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_batched_array() {
+        let json = r#"[
+            {
+                "event_type": "book",
+                "asset_id": "123456",
+                "market": "condition_123",
+                "bids": [{"price": "0.50", "size": "100"}],
+                "asks": [{"price": "0.55", "size": "50"}]
+            },
+            {
+                "event_type": "trade",
+                "asset_id": "123456",
+                "market": "condition_123",
+                "id": "trade_1",
+                "price": "0.52",
+                "size": "25",
+                "side": "sell"
+            }
+        ]"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        assert_eq!(events.len(), 2);
+
+        match &events[0] {
+            MarketEvent::OrderBookUpdate(update) => {
+                assert_eq!(update.asset_id, "123456");
+                assert!(update.is_snapshot);
+            }
+            other => panic!("Expected OrderBookUpdate, got {:?}", other),
+        }
+        match &events[1] {
+            MarketEvent::Trade(trade) => {
+                assert_eq!(trade.trade_id, "trade_1");
+                assert_eq!(trade.side, Side::Sell);
+            }
+            other => panic!("Expected Trade, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_batch_keeps_valid_events_around_malformed_one() {
+        let json = r#"[
+            {
+                "event_type": "trade",
+                "asset_id": "123456",
+                "id": "trade_1",
+                "price": "0.52",
+                "size": "25"
+            },
+            {"event_type": "trade", "asset_id": "123456"},
+            {
+                "event_type": "trade",
+                "asset_id": "123456",
+                "id": "trade_2",
+                "price": "0.53",
+                "size": "10"
+            }
+        ]"#;
+
+        let events = PolymarketWebSocketClient::parse(json).unwrap();
+        match events.as_slice() {
+            [MarketEvent::Trade(first), MarketEvent::Raw { message, .. }, MarketEvent::Trade(second)] =>
+            {
+                assert_eq!(first.trade_id, "trade_1");
+                assert!(message.contains("\"event_type\":\"trade\""));
+                assert_eq!(second.trade_id, "trade_2");
+            }
+            other => panic!("Expected trade, raw, trade, got {:?}", other),
+        }
+    }
 }
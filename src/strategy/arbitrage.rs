@@ -0,0 +1,213 @@
+use rust_decimal::Decimal;
+
+use crate::common::math::no_price;
+use crate::common::types::OrderBook;
+use crate::strategy::fees::FeeCalculator;
+use crate::strategy::types::{Side, TradeIntent, TradeLeg};
+
+/// NO contracts of a matched pair, bought in place of selling YES
+///
+/// Selling YES needs YES inventory, so the second leg of an arbitrage buys
+/// NO on the other venue instead. Kalshi quotes both outcomes on one
+/// ticker, so its entry is whatever ID the executing sink maps to the
+/// ticker's NO contract. A direction whose NO asset isn't known is skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoAssets {
+    /// ID of the Kalshi ticker's NO contract
+    pub kalshi: Option<String>,
+    /// Polymarket NO token
+    pub polymarket: Option<String>,
+}
+
+/// Look for a cross-platform arbitrage between matched Kalshi and Polymarket books
+///
+/// Both books must quote the YES side of the same event. Two directions are
+/// checked: buying YES at the Kalshi ask and NO on Polymarket, and the
+/// reverse. Because YES + NO pays out 1.0, buying NO against a YES bid `b`
+/// costs `1 - b` and locks in the same edge as selling YES at `b`, without
+/// needing inventory. Both legs are buys; the NO leg is priced at
+/// `1 - b` on the asset from `no_assets`.
+///
+/// Size is limited to what both top-of-book levels can fill, and the
+/// worst-case profit after fees comes from
+/// [`FeeCalculator::arbitrage_profit`].
+///
+/// # Arguments
+/// * `kalshi_book` - Kalshi YES book
+/// * `poly_book` - Polymarket YES token book
+/// * `no_assets` - NO contracts to buy on each venue
+/// * `min_profit` - Minimum net profit per contract
+///
+/// # Returns
+/// A two-leg intent for the more profitable direction, scored by its net
+/// profit per contract, or `None` if neither clears `min_profit`
+pub fn detect_arb(
+    kalshi_book: &OrderBook,
+    poly_book: &OrderBook,
+    no_assets: &NoAssets,
+    min_profit: Decimal,
+) -> Option<TradeIntent> {
+    let buy_kalshi = no_assets
+        .polymarket
+        .as_deref()
+        .and_then(|no| evaluate(kalshi_book, poly_book, no));
+    let buy_poly = no_assets
+        .kalshi
+        .as_deref()
+        .and_then(|no| evaluate(poly_book, kalshi_book, no));
+
+    let best = match (buy_kalshi, buy_poly) {
+        (Some(a), Some(b)) => Some(if a.edge >= b.edge { a } else { b }),
+        (a, b) => a.or(b),
+    }?;
+    if best.edge <= min_profit {
+        return None;
+    }
+
+    let reason = format!(
+        "Buy {} on {} at {}, buy {} on {} at {}: {} per contract after fees",
+        best.buy.asset_id,
+        best.buy.platform,
+        best.buy_price,
+        best.no_asset,
+        best.sell.platform,
+        best.no_price,
+        best.edge.round_dp(4)
+    );
+    let legs = vec![
+        TradeLeg::new(best.buy.platform, &best.buy.asset_id, Side::Buy).with_price(best.buy_price),
+        TradeLeg::new(best.sell.platform, best.no_asset, Side::Buy).with_price(best.no_price),
+    ];
+    Some(TradeIntent::multi(legs, reason).with_score(best.edge))
+}
+
+/// One direction of a cross-platform trade
+struct Opportunity<'a> {
+    buy: &'a OrderBook,
+    sell: &'a OrderBook,
+    /// NO contract bought on `sell`'s venue
+    no_asset: &'a str,
+    buy_price: Decimal,
+    no_price: Decimal,
+    /// Worst-case net profit per contract
+    edge: Decimal,
+}
+
+/// Buy YES at the best ask of `buy` and NO against the best bid of `sell`
+fn evaluate<'a>(
+    buy: &'a OrderBook,
+    sell: &'a OrderBook,
+    no_asset: &'a str,
+) -> Option<Opportunity<'a>> {
+    let ask = buy.best_ask()?;
+    let bid = sell.best_bid()?;
+    let size = ask.size.min(bid.size);
+    if size <= Decimal::ZERO {
+        return None;
+    }
+
+    let profit =
+        FeeCalculator::arbitrage_profit(buy.platform, ask.price, sell.platform, bid.price, size);
+    Some(Opportunity {
+        buy,
+        sell,
+        no_asset,
+        buy_price: ask.price,
+        no_price: no_price(bid.price),
+        edge: profit / size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::PriceLevel;
+    use crate::strategy::types::Platform;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn book(platform: Platform, asset_id: &str, bid: Decimal, ask: Decimal) -> OrderBook {
+        OrderBook {
+            platform,
+            market_id: "market".to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![PriceLevel::new(bid, dec!(100))],
+            asks: vec![PriceLevel::new(ask, dec!(100))],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        }
+    }
+
+    fn no_assets() -> NoAssets {
+        NoAssets {
+            kalshi: Some("KX-NO".to_string()),
+            polymarket: Some("token_no".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_detects_arb_after_fees() {
+        // Buy 100 on Kalshi at 0.40 (fee 1.68), NO on Polymarket against a 0.50 bid
+        let kalshi = book(Platform::Kalshi, "KX-YES", dec!(0.38), dec!(0.40));
+        let poly = book(Platform::Polymarket, "token", dec!(0.50), dec!(0.52));
+
+        let intent = detect_arb(&kalshi, &poly, &no_assets(), dec!(0.05)).unwrap();
+        assert!(intent.is_arbitrage());
+        assert_eq!(intent.legs[0].platform, Platform::Kalshi);
+        assert_eq!(intent.legs[0].market_id, "KX-YES");
+        assert_eq!(intent.legs[0].side, Side::Buy);
+        assert_eq!(intent.legs[0].suggested_price, Some(dec!(0.40)));
+        assert_eq!(intent.legs[1].platform, Platform::Polymarket);
+        assert_eq!(intent.legs[1].market_id, "token_no");
+        assert_eq!(intent.legs[1].side, Side::Buy);
+        assert_eq!(intent.legs[1].suggested_price, Some(dec!(0.50)));
+        assert_eq!(intent.score, Some(dec!(0.0832)));
+
+        // 8.32 over 100 contracts doesn't clear a 0.09 threshold
+        assert!(detect_arb(&kalshi, &poly, &no_assets(), dec!(0.09)).is_none());
+    }
+
+    #[test]
+    fn test_detects_reverse_direction() {
+        // Buy on Polymarket at 0.45, NO on Kalshi against a 0.55 bid (fee 1.74)
+        let kalshi = book(Platform::Kalshi, "KX-YES", dec!(0.55), dec!(0.57));
+        let poly = book(Platform::Polymarket, "token", dec!(0.43), dec!(0.45));
+
+        let intent = detect_arb(&kalshi, &poly, &no_assets(), dec!(0.05)).unwrap();
+        assert_eq!(intent.legs[0].platform, Platform::Polymarket);
+        assert_eq!(intent.legs[0].market_id, "token");
+        assert_eq!(intent.legs[0].side, Side::Buy);
+        assert_eq!(intent.legs[1].platform, Platform::Kalshi);
+        assert_eq!(intent.legs[1].market_id, "KX-NO");
+        assert_eq!(intent.legs[1].side, Side::Buy);
+        assert_eq!(intent.legs[1].suggested_price, Some(dec!(0.45)));
+    }
+
+    #[test]
+    fn test_direction_needs_no_asset() {
+        let kalshi = book(Platform::Kalshi, "KX-YES", dec!(0.55), dec!(0.57));
+        let poly = book(Platform::Polymarket, "token", dec!(0.43), dec!(0.45));
+
+        // The only paying direction buys Kalshi NO
+        let poly_only = NoAssets {
+            kalshi: None,
+            ..no_assets()
+        };
+        assert!(detect_arb(&kalshi, &poly, &poly_only, dec!(0.05)).is_none());
+        assert!(detect_arb(&kalshi, &poly, &NoAssets::default(), Decimal::MIN).is_none());
+    }
+
+    #[test]
+    fn test_spread_eaten_by_fees() {
+        // A one cent gross edge is less than Kalshi's 1.75 fee on 100 contracts
+        let kalshi = book(Platform::Kalshi, "KX-YES", dec!(0.46), dec!(0.48));
+        let poly = book(Platform::Polymarket, "token", dec!(0.49), dec!(0.51));
+        assert!(detect_arb(&kalshi, &poly, &no_assets(), Decimal::ZERO).is_none());
+
+        // No crossing at all
+        let poly = book(Platform::Polymarket, "token", dec!(0.45), dec!(0.50));
+        assert!(detect_arb(&kalshi, &poly, &no_assets(), Decimal::ZERO).is_none());
+    }
+}
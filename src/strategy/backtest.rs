@@ -0,0 +1,462 @@
+//! Offline replay of recorded market events through a strategy
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::common::errors::{ClientError, Result};
+use crate::common::orderbook::OrderBookManager;
+use crate::common::types::MarketEvent;
+use crate::strategy::fees::FeeCalculator;
+use crate::strategy::runner::StrategyRunner;
+use crate::strategy::traits::BoxedStrategy;
+use crate::strategy::types::{
+    Decision, Platform, Position, Side, StrategyContext, TradeIntent, TradeLeg,
+};
+
+/// Contracts filled per leg unless set with [`Backtester::with_trade_size`]
+const DEFAULT_TRADE_SIZE: Decimal = dec!(100);
+
+/// A simulated fill of one leg
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestFill {
+    pub platform: Platform,
+    pub market_id: String,
+    pub side: Side,
+    pub size: Decimal,
+    /// VWAP against the replayed book, or the leg's suggested price
+    pub price: Decimal,
+}
+
+/// A `Go` decision made during a backtest
+#[derive(Debug, Clone)]
+pub struct BacktestIntent {
+    /// Exchange timestamp of the event that triggered the decision
+    pub event_timestamp: Option<DateTime<Utc>>,
+    pub intent: TradeIntent,
+    /// One fill per leg, or empty if any leg couldn't be priced
+    pub fills: Vec<BacktestFill>,
+}
+
+impl BacktestIntent {
+    /// Whether every leg was filled
+    pub fn is_filled(&self) -> bool {
+        !self.fills.is_empty()
+    }
+
+    /// Locked-in profit of a filled buy/sell pair after fees
+    ///
+    /// Returns None for anything else, since the outcome of an unhedged
+    /// position depends on how the market resolves.
+    pub fn estimated_pnl(&self) -> Option<Decimal> {
+        let [a, b] = self.fills.as_slice() else {
+            return None;
+        };
+        let (buy, sell) = match (a.side, b.side) {
+            (Side::Buy, Side::Sell) => (a, b),
+            (Side::Sell, Side::Buy) => (b, a),
+            _ => return None,
+        };
+        Some(FeeCalculator::arbitrage_profit(
+            buy.platform,
+            buy.price,
+            sell.platform,
+            sell.price,
+            buy.size.min(sell.size),
+        ))
+    }
+}
+
+/// Result of a backtest run
+#[derive(Debug, Clone)]
+pub struct BacktestReport {
+    pub strategy: String,
+    /// Number of events replayed
+    pub events: usize,
+    pub intents: Vec<BacktestIntent>,
+    /// Sum of [`BacktestIntent::estimated_pnl`] over all intents
+    pub estimated_pnl: Decimal,
+    /// Simulated context after the last event
+    pub context: StrategyContext,
+}
+
+impl BacktestReport {
+    /// Number of intents with every leg filled
+    pub fn filled(&self) -> usize {
+        self.intents.iter().filter(|i| i.is_filled()).count()
+    }
+}
+
+impl fmt::Display for BacktestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Backtest] {}", self.strategy)?;
+        writeln!(f, "  Events:        {}", self.events)?;
+        writeln!(
+            f,
+            "  Intents:       {} ({} filled)",
+            self.intents.len(),
+            self.filled()
+        )?;
+        writeln!(f, "  Estimated PnL: {}", self.estimated_pnl)?;
+        for intent in &self.intents {
+            let timestamp = intent
+                .event_timestamp
+                .map(|ts| ts.to_rfc3339())
+                .unwrap_or_else(|| "-".to_string());
+            let pnl = intent
+                .estimated_pnl()
+                .map(|pnl| pnl.to_string())
+                .unwrap_or_else(|| "n/a".to_string());
+            writeln!(f, "  {} {} (pnl {})", timestamp, intent.intent.reason, pnl)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays recorded market events through a strategy
+///
+/// Book updates are applied to a local book per asset, and the strategy
+/// receives the resulting full [`MarketEvent::OrderBook`] instead of the raw
+/// delta, so it always sees consistent state. Deltas that arrive before a
+/// snapshot for their asset are dropped.
+///
+/// Every leg of a `Go` intent is filled at a fixed size: at the VWAP of the
+/// replayed book, falling back to the leg's suggested price. Fills update
+/// the simulated positions and balances in the context. Ticks aren't driven.
+pub struct Backtester {
+    runner: StrategyRunner,
+    ctx: StrategyContext,
+    books: OrderBookManager,
+    trade_size: Decimal,
+}
+
+impl Backtester {
+    pub fn new(strategy: BoxedStrategy) -> Self {
+        Self {
+            runner: StrategyRunner::new(strategy),
+            ctx: StrategyContext::new(),
+            books: OrderBookManager::new(),
+            trade_size: DEFAULT_TRADE_SIZE,
+        }
+    }
+
+    /// Set the number of contracts filled per leg
+    pub fn with_trade_size(mut self, trade_size: Decimal) -> Self {
+        self.trade_size = trade_size;
+        self
+    }
+
+    /// Start from an existing context (e.g. with starting balances)
+    pub fn with_context(mut self, ctx: StrategyContext) -> Self {
+        self.ctx = ctx;
+        self
+    }
+
+    /// Replay events from a JSON Lines file of serialized [`MarketEvent`]s
+    pub fn replay_file(self, path: impl AsRef<Path>) -> Result<BacktestReport> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            ClientError::Configuration(format!(
+                "Failed to open event log {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.replay(BufReader::new(file))
+    }
+
+    /// Replay events from JSON Lines, skipping blank lines
+    pub fn replay<R: BufRead>(self, reader: R) -> Result<BacktestReport> {
+        let events = reader
+            .lines()
+            .map(|line| {
+                line.map_err(|e| ClientError::Internal(format!("Failed to read event log: {}", e)))
+            })
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|line| Ok(serde_json::from_str::<MarketEvent>(&line?)?))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.run(events))
+    }
+
+    /// Replay events in order and report the decisions made
+    pub fn run(mut self, events: impl IntoIterator<Item = MarketEvent>) -> BacktestReport {
+        self.runner.on_register(&self.ctx);
+
+        let mut count = 0;
+        let mut intents = Vec::new();
+        for event in events {
+            count += 1;
+            let Some(event) = self.track(event) else {
+                continue;
+            };
+            if let Decision::Go(intent) = self.runner.on_market_event(&event, &self.ctx) {
+                let fills = self.fill(&intent);
+                for fill in &fills {
+                    apply_fill(&mut self.ctx, fill);
+                }
+                intents.push(BacktestIntent {
+                    event_timestamp: event.timestamp(),
+                    intent,
+                    fills,
+                });
+            }
+        }
+        self.runner.on_shutdown();
+
+        let estimated_pnl = intents.iter().filter_map(|i| i.estimated_pnl()).sum();
+        BacktestReport {
+            strategy: self.runner.name().to_string(),
+            events: count,
+            intents,
+            estimated_pnl,
+            context: self.ctx,
+        }
+    }
+
+    /// Update the local books and return the event the strategy should see
+    fn track(&mut self, event: MarketEvent) -> Option<MarketEvent> {
+        let applied = self.books.apply_event(&event);
+        match event {
+            MarketEvent::OrderBookUpdate(update) => {
+                if !applied {
+                    return None;
+                }
+                self.books
+                    .get(&update.asset_id)
+                    .cloned()
+                    .map(MarketEvent::OrderBook)
+            }
+            event => Some(event),
+        }
+    }
+
+    /// Fill every leg of an intent, or none if any leg can't be priced
+    fn fill(&self, intent: &TradeIntent) -> Vec<BacktestFill> {
+        intent
+            .legs
+            .iter()
+            .map(|leg| {
+                self.fill_price(leg).map(|price| BacktestFill {
+                    platform: leg.platform,
+                    market_id: leg.market_id.clone(),
+                    side: leg.side,
+                    size: self.trade_size,
+                    price,
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
+    fn fill_price(&self, leg: &TradeLeg) -> Option<Decimal> {
+        self.books
+            .get(&leg.market_id)
+            .filter(|_| !self.books.needs_resync(&leg.market_id))
+            .and_then(|book| book.vwap_for_size(leg.side, self.trade_size))
+            .or(leg.suggested_price)
+    }
+}
+
+/// Record a fill in the simulated positions and balances
+///
+/// Buys are debited their cost and sells credited their proceeds, both
+/// after fees. Short positions are held as negative size. Zero-size fills
+/// leave the context untouched.
+fn apply_fill(ctx: &mut StrategyContext, fill: &BacktestFill) {
+    if fill.size.is_zero() {
+        return;
+    }
+    ctx.positions
+        .entry((fill.platform, fill.market_id.clone()))
+        .or_insert_with(|| Position::new(fill.platform, &fill.market_id))
+        .apply_fill(fill.side, fill.price, fill.size);
+
+    let cash = FeeCalculator::entry_cost(fill.platform, fill.price, fill.side, fill.size);
+    let balance = ctx.balances.entry(fill.platform).or_default();
+    match fill.side {
+        Side::Buy => *balance -= cash,
+        Side::Sell => *balance += cash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{OrderBook, OrderBookUpdate, PriceLevel};
+    use crate::strategy::traits::Strategy;
+    use crate::strategy::types::MarketSubscription;
+    use std::collections::HashMap;
+
+    /// Buys Kalshi and sells Polymarket when the Polymarket bid clears the
+    /// Kalshi ask by `threshold`
+    struct CrossSpread {
+        threshold: Decimal,
+        best_ask: HashMap<Platform, Decimal>,
+        best_bid: HashMap<Platform, Decimal>,
+    }
+
+    impl Strategy for CrossSpread {
+        fn name(&self) -> &str {
+            "cross_spread"
+        }
+
+        fn on_market_event(&mut self, event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            let MarketEvent::OrderBook(book) = event else {
+                return Decision::NoGo;
+            };
+            if let Some(ask) = book.best_ask() {
+                self.best_ask.insert(book.platform, ask.price);
+            }
+            if let Some(bid) = book.best_bid() {
+                self.best_bid.insert(book.platform, bid.price);
+            }
+
+            match (
+                self.best_ask.get(&Platform::Kalshi),
+                self.best_bid.get(&Platform::Polymarket),
+            ) {
+                (Some(ask), Some(bid)) if *bid - *ask >= self.threshold => Decision::go_arbitrage(
+                    vec![
+                        TradeLeg::new(Platform::Kalshi, "KXRAIN", Side::Buy),
+                        TradeLeg::new(Platform::Polymarket, "rain-yes", Side::Sell),
+                    ],
+                    format!("spread {}", *bid - *ask),
+                ),
+                _ => Decision::NoGo,
+            }
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::AllMatchedPairs]
+        }
+    }
+
+    fn millis(ms: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(ms).unwrap()
+    }
+
+    fn book(
+        platform: Platform,
+        asset_id: &str,
+        bid: Decimal,
+        ask: Decimal,
+        ms: i64,
+    ) -> MarketEvent {
+        MarketEvent::OrderBook(OrderBook {
+            platform,
+            market_id: asset_id.to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![PriceLevel::new(bid, dec!(500))],
+            asks: vec![PriceLevel::new(ask, dec!(500))],
+            timestamp: millis(ms),
+            received_at: millis(ms),
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    /// Recorded session: the Polymarket bid jumps from 0.44 to 0.50 while
+    /// Kalshi offers at 0.40
+    fn recorded_session() -> Vec<u8> {
+        let delta = MarketEvent::OrderBookUpdate(OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "rain-yes".to_string(),
+            asset_id: "rain-yes".to_string(),
+            bids: vec![
+                PriceLevel::new(dec!(0.44), Decimal::ZERO),
+                PriceLevel::new(dec!(0.50), dec!(300)),
+            ],
+            asks: vec![],
+            timestamp: millis(1_700_000_002_000),
+            received_at: millis(1_700_000_002_000),
+            is_snapshot: false,
+            sequence: 1,
+            hash: None,
+        });
+        let events = [
+            book(
+                Platform::Kalshi,
+                "KXRAIN",
+                dec!(0.38),
+                dec!(0.40),
+                1_700_000_000_000,
+            ),
+            book(
+                Platform::Polymarket,
+                "rain-yes",
+                dec!(0.44),
+                dec!(0.52),
+                1_700_000_001_000,
+            ),
+            delta,
+        ];
+
+        let mut jsonl = Vec::new();
+        for event in &events {
+            serde_json::to_writer(&mut jsonl, event).unwrap();
+            jsonl.push(b'\n');
+        }
+        jsonl
+    }
+
+    #[test]
+    fn test_recorded_arbitrage_signal() {
+        let strategy = CrossSpread {
+            threshold: dec!(0.05),
+            best_ask: HashMap::new(),
+            best_bid: HashMap::new(),
+        };
+        let report = Backtester::new(Box::new(strategy))
+            .replay(recorded_session().as_slice())
+            .unwrap();
+
+        assert_eq!(report.events, 3);
+        assert_eq!(report.intents.len(), 1);
+        let intent = &report.intents[0];
+        assert_eq!(intent.event_timestamp, Some(millis(1_700_000_002_000)));
+        assert_eq!(intent.intent.reason, "spread 0.10");
+        assert_eq!(intent.fills[0].price, dec!(0.40));
+        assert_eq!(intent.fills[1].price, dec!(0.50));
+
+        // 0.10 * 100 gross less Kalshi's trading fee of 1.68
+        assert_eq!(report.estimated_pnl, dec!(8.32));
+
+        let kalshi = report
+            .context
+            .get_position(Platform::Kalshi, "KXRAIN")
+            .unwrap();
+        assert_eq!(kalshi.size, dec!(100));
+        assert_eq!(kalshi.avg_entry_price, dec!(0.40));
+        let poly = report
+            .context
+            .get_position(Platform::Polymarket, "rain-yes")
+            .unwrap();
+        assert_eq!(poly.size, dec!(-100));
+        assert_eq!(report.context.get_balance(Platform::Kalshi), dec!(-41.68));
+        assert_eq!(report.context.get_balance(Platform::Polymarket), dec!(50));
+    }
+
+    #[test]
+    fn test_delta_without_snapshot_dropped() {
+        let strategy = CrossSpread {
+            threshold: dec!(0.05),
+            best_ask: HashMap::new(),
+            best_bid: HashMap::new(),
+        };
+        let jsonl = recorded_session();
+        // Drop the Polymarket snapshot so the delta has nothing to apply to
+        let lines: Vec<&[u8]> = jsonl.split(|b| *b == b'\n').collect();
+        let replay = [lines[0], lines[2]].join(&b'\n');
+
+        let report = Backtester::new(Box::new(strategy))
+            .replay(replay.as_slice())
+            .unwrap();
+        assert_eq!(report.events, 2);
+        assert!(report.intents.is_empty());
+        assert_eq!(report.estimated_pnl, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,280 @@
+use tracing::debug;
+
+use crate::common::types::MarketEvent;
+use crate::strategy::traits::{BoxedStrategy, Strategy};
+use crate::strategy::types::{Decision, MarketSubscription, StrategyContext, TradeIntent};
+
+/// How a [`CompositeStrategy`] turns its strategies' decisions into one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// The first `Go`, in the order the strategies were added
+    #[default]
+    FirstGo,
+    /// `Go` only if every strategy wants the same trade: the same markets
+    /// and sides, leg for leg. The first strategy's intent is used.
+    AllMustAgree,
+    /// The `Go` with the highest [`score`](TradeIntent::score); intents
+    /// without one rank lowest and ties go to the earlier strategy
+    HighestConfidence,
+}
+
+/// Runs several strategies on the same feed as one
+///
+/// Every event and tick goes to every strategy, so each keeps its state up
+/// to date whichever decision wins; strategies see all events the
+/// composite is subscribed to, not only their own markets. The decisions
+/// are merged according to the [`MergePolicy`]. The composite is warmed up
+/// once all of its strategies are.
+pub struct CompositeStrategy {
+    name: String,
+    strategies: Vec<BoxedStrategy>,
+    policy: MergePolicy,
+}
+
+impl CompositeStrategy {
+    pub fn new(name: impl Into<String>, strategies: Vec<BoxedStrategy>) -> Self {
+        Self {
+            name: name.into(),
+            strategies,
+            policy: MergePolicy::default(),
+        }
+    }
+
+    /// Set how decisions are merged
+    pub fn with_policy(mut self, policy: MergePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The combined strategies
+    pub fn strategies(&self) -> &[BoxedStrategy] {
+        &self.strategies
+    }
+
+    fn merge(&self, decisions: Vec<Decision>) -> Decision {
+        let count = decisions.len();
+        let intents: Vec<TradeIntent> = decisions
+            .into_iter()
+            .filter_map(|decision| match decision {
+                Decision::Go(intent) => Some(intent),
+                Decision::NoGo => None,
+            })
+            .collect();
+
+        let merged = match self.policy {
+            MergePolicy::FirstGo => intents.into_iter().next(),
+            MergePolicy::AllMustAgree => {
+                if intents.len() < count || intents.is_empty() {
+                    None
+                } else if intents[1..]
+                    .iter()
+                    .all(|intent| same_trade(&intents[0], intent))
+                {
+                    intents.into_iter().next()
+                } else {
+                    debug!("{}: strategies disagree on the trade", self.name);
+                    None
+                }
+            }
+            MergePolicy::HighestConfidence => intents
+                .into_iter()
+                .rev()
+                .max_by(|a, b| a.score.cmp(&b.score)),
+        };
+        merged.map_or(Decision::NoGo, Decision::Go)
+    }
+}
+
+/// Whether two intents trade the same markets in the same directions
+fn same_trade(a: &TradeIntent, b: &TradeIntent) -> bool {
+    a.legs.len() == b.legs.len()
+        && a.legs.iter().zip(&b.legs).all(|(a, b)| {
+            a.platform == b.platform && a.market_id == b.market_id && a.side == b.side
+        })
+}
+
+impl Strategy for CompositeStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_market_event(&mut self, event: &MarketEvent, ctx: &StrategyContext) -> Decision {
+        let decisions = self
+            .strategies
+            .iter_mut()
+            .map(|strategy| strategy.on_market_event(event, ctx))
+            .collect();
+        self.merge(decisions)
+    }
+
+    fn on_tick(&mut self, ctx: &StrategyContext) -> Decision {
+        let decisions = self
+            .strategies
+            .iter_mut()
+            .map(|strategy| strategy.on_tick(ctx))
+            .collect();
+        self.merge(decisions)
+    }
+
+    fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+        let mut subscriptions = Vec::new();
+        for subscription in self.strategies.iter().flat_map(|s| s.subscribed_markets()) {
+            if !subscriptions.contains(&subscription) {
+                subscriptions.push(subscription);
+            }
+        }
+        subscriptions
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.strategies.iter().all(|s| s.warmup_complete())
+    }
+
+    fn on_register(&mut self, ctx: &StrategyContext) {
+        for strategy in &mut self.strategies {
+            strategy.on_register(ctx);
+        }
+    }
+
+    fn on_shutdown(&mut self) {
+        for strategy in &mut self.strategies {
+            strategy.on_shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::types::{Platform, Side, TradeLeg};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    /// Always makes the same decision
+    struct Fixed {
+        name: &'static str,
+        decision: Option<(Side, Option<Decimal>)>,
+        market_id: &'static str,
+        events: usize,
+    }
+
+    fn fixed(name: &'static str, decision: Option<(Side, Option<Decimal>)>) -> BoxedStrategy {
+        Box::new(Fixed {
+            name,
+            decision,
+            market_id: "token",
+            events: 0,
+        })
+    }
+
+    impl Strategy for Fixed {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn on_market_event(&mut self, _event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            self.events += 1;
+            let Some((side, score)) = self.decision else {
+                return Decision::NoGo;
+            };
+            let mut intent = TradeIntent::single(
+                TradeLeg::new(Platform::Polymarket, self.market_id, side),
+                self.name,
+            );
+            intent.score = score;
+            Decision::Go(intent)
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::Specific {
+                platform: Platform::Polymarket,
+                market_id: self.market_id.to_string(),
+            }]
+        }
+
+        fn warmup_complete(&self) -> bool {
+            self.events > 0
+        }
+    }
+
+    /// Reason of the merged decision, if it's a Go
+    fn decide(composite: &mut CompositeStrategy) -> Option<String> {
+        let event = MarketEvent::Heartbeat {
+            platform: Platform::Polymarket,
+        };
+        match composite.on_market_event(&event, &StrategyContext::new()) {
+            Decision::Go(intent) => Some(intent.reason),
+            Decision::NoGo => None,
+        }
+    }
+
+    #[test]
+    fn test_first_go() {
+        let mut composite = CompositeStrategy::new(
+            "combo",
+            vec![
+                fixed("quiet", None),
+                fixed("buyer", Some((Side::Buy, None))),
+                fixed("seller", Some((Side::Sell, None))),
+            ],
+        );
+        assert!(!composite.warmup_complete());
+        assert_eq!(decide(&mut composite).as_deref(), Some("buyer"));
+        // Every strategy saw the event
+        assert!(composite.warmup_complete());
+        assert_eq!(composite.name(), "combo");
+        assert_eq!(composite.subscribed_markets().len(), 1);
+
+        let mut composite = CompositeStrategy::new("combo", vec![fixed("quiet", None)]);
+        assert_eq!(decide(&mut composite), None);
+    }
+
+    #[test]
+    fn test_all_must_agree() {
+        let agree = |strategies| {
+            CompositeStrategy::new("combo", strategies).with_policy(MergePolicy::AllMustAgree)
+        };
+
+        let mut composite = agree(vec![
+            fixed("a", Some((Side::Buy, None))),
+            fixed("b", Some((Side::Buy, Some(dec!(0.9))))),
+        ]);
+        assert_eq!(decide(&mut composite).as_deref(), Some("a"));
+
+        let mut composite = agree(vec![
+            fixed("a", Some((Side::Buy, None))),
+            fixed("b", Some((Side::Sell, None))),
+        ]);
+        assert_eq!(decide(&mut composite), None);
+
+        let mut composite = agree(vec![fixed("a", Some((Side::Buy, None))), fixed("b", None)]);
+        assert_eq!(decide(&mut composite), None);
+    }
+
+    #[test]
+    fn test_highest_confidence() {
+        let mut composite = CompositeStrategy::new(
+            "combo",
+            vec![
+                fixed("unrated", Some((Side::Buy, None))),
+                fixed("low", Some((Side::Sell, Some(dec!(0.2))))),
+                fixed("high", Some((Side::Buy, Some(dec!(0.8))))),
+                fixed("also_high", Some((Side::Sell, Some(dec!(0.8))))),
+                fixed("quiet", None),
+            ],
+        )
+        .with_policy(MergePolicy::HighestConfidence);
+        assert_eq!(decide(&mut composite).as_deref(), Some("high"));
+
+        // Unrated intents still win when nothing is rated
+        let mut composite = CompositeStrategy::new(
+            "combo",
+            vec![
+                fixed("quiet", None),
+                fixed("unrated", Some((Side::Buy, None))),
+            ],
+        )
+        .with_policy(MergePolicy::HighestConfidence);
+        assert_eq!(decide(&mut composite).as_deref(), Some("unrated"));
+    }
+}
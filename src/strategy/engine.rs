@@ -0,0 +1,830 @@
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
+
+use crate::common::errors::{ClientError, Result};
+use crate::common::types::MarketEvent;
+use crate::strategy::execution::{BoxedIntentSink, IntentSink};
+use crate::strategy::pairs::PairMap;
+use crate::strategy::pnl::PositionTracker;
+use crate::strategy::risk::RiskLimits;
+use crate::strategy::runner::StrategyRunner;
+use crate::strategy::size_calculator::{
+    BoxedSizeCalculator, MarketConstraints, SizeCalculator, SizedIntent,
+};
+use crate::strategy::traits::BoxedStrategy;
+use crate::strategy::types::{Decision, MarketSubscription, Platform, Side, StrategyContext};
+
+/// Default interval between `on_tick` calls
+const DEFAULT_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the [`Trader`] does with multi-leg intents that can't execute
+/// atomically
+///
+/// A multi-leg intent is atomic when every leg is FOK or IOC (see
+/// [`SizedIntent::is_executable_atomically`]). Otherwise one leg can fill
+/// while another rests, leaving unhedged exposure. Single-leg intents are
+/// never affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AtomicityPolicy {
+    /// Send them without comment
+    Allow,
+    /// Send them, logging a warning
+    #[default]
+    Warn,
+    /// Drop them
+    Reject,
+}
+
+/// Checks a sized intent has to pass before it is sent
+#[derive(Default)]
+struct IntentChecks {
+    /// Exchange order limits by market
+    constraints: HashMap<String, MarketConstraints>,
+    /// Exposure limits
+    risk_limits: RiskLimits,
+    /// Handling of multi-leg intents that aren't atomic
+    atomicity: AtomicityPolicy,
+}
+
+impl IntentChecks {
+    /// Why `sized` has to be dropped, if it does
+    fn rejection(
+        &self,
+        strategy: &str,
+        sized: &SizedIntent,
+        ctx: &StrategyContext,
+    ) -> Option<String> {
+        if let Err(e) = sized.validate_against(&self.constraints) {
+            return Some(e.to_string());
+        }
+        if let Err(e) = self.risk_limits.check(sized, ctx) {
+            return Some(e.to_string());
+        }
+        if sized.legs.len() > 1 && !sized.is_executable_atomically() {
+            match self.atomicity {
+                AtomicityPolicy::Allow => {}
+                AtomicityPolicy::Warn => warn!(
+                    "Intent from strategy {} has legs that may fill without the others: {}",
+                    strategy, sized.reason
+                ),
+                AtomicityPolicy::Reject => {
+                    return Some("legs can't be executed atomically".to_string());
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A registered strategy and the markets it subscribed to
+struct Registered {
+    runner: StrategyRunner,
+    subscriptions: Vec<MarketSubscription>,
+}
+
+/// Feeds market events into strategies and sizes their decisions
+///
+/// Each event is delivered to the strategies whose subscriptions match it.
+/// `Go` decisions are sized with the size calculator and the resulting
+/// [`SizedIntent`]s are submitted to the [`IntentSink`] for execution. Intents
+/// that can't be fully sized, or that break a market's order constraints or
+/// the [`RiskLimits`], are dropped; multi-leg intents that can't execute
+/// atomically are handled according to the [`AtomicityPolicy`]. When
+/// several strategies go on the same event or tick, higher
+/// [`score`](crate::strategy::types::TradeIntent::score)d intents are
+/// submitted first.
+///
+/// Strategies are driven through a [`StrategyRunner`], so warmup gating
+/// applies. Subscriptions are read once when a strategy is added. Fills
+/// reported through [`apply_fill`](Self::apply_fill) keep the context's
+/// positions current.
+pub struct Trader {
+    strategies: Vec<Registered>,
+    size_calculator: BoxedSizeCalculator,
+    ctx: StrategyContext,
+    sink: BoxedIntentSink,
+    tick_interval: Duration,
+    /// Cross-platform pairs for `MatchedPair`/`AllMatchedPairs` routing
+    pair_map: PairMap,
+    /// Positions and realized PnL from reported fills
+    tracker: PositionTracker,
+    /// Order limits, exposure limits and atomicity checked before intents
+    /// are sent
+    checks: IntentChecks,
+}
+
+impl Trader {
+    pub fn new(
+        strategies: Vec<BoxedStrategy>,
+        size_calculator: BoxedSizeCalculator,
+        ctx: StrategyContext,
+        sink: BoxedIntentSink,
+    ) -> Self {
+        let mut trader = Self {
+            strategies: Vec::with_capacity(strategies.len()),
+            size_calculator,
+            tracker: PositionTracker::from_context(&ctx),
+            ctx,
+            sink,
+            tick_interval: DEFAULT_TICK_INTERVAL,
+            pair_map: PairMap::new(),
+            checks: IntentChecks::default(),
+        };
+        for strategy in strategies {
+            trader.add_strategy(strategy);
+        }
+        trader
+    }
+
+    /// Set the interval between `on_tick` calls
+    pub fn with_tick_interval(mut self, tick_interval: Duration) -> Self {
+        self.tick_interval = tick_interval;
+        self
+    }
+
+    /// Set the market pairs used to route `AllMatchedPairs` subscriptions
+    pub fn with_pair_map(mut self, pair_map: PairMap) -> Self {
+        self.pair_map = pair_map;
+        self
+    }
+
+    /// Set the order limits sized intents are checked against
+    pub fn with_market_constraints(
+        mut self,
+        constraints: HashMap<String, MarketConstraints>,
+    ) -> Self {
+        self.checks.constraints = constraints;
+        self
+    }
+
+    /// Set the exposure limits sized intents are checked against
+    pub fn with_risk_limits(mut self, risk_limits: RiskLimits) -> Self {
+        self.checks.risk_limits = risk_limits;
+        self
+    }
+
+    /// Set what happens to multi-leg intents that can't execute atomically
+    pub fn with_atomicity_policy(mut self, policy: AtomicityPolicy) -> Self {
+        self.checks.atomicity = policy;
+        self
+    }
+
+    /// Register a strategy
+    pub fn add_strategy(&mut self, strategy: BoxedStrategy) {
+        let mut runner = StrategyRunner::new(strategy);
+        runner.on_register(&self.ctx);
+        let subscriptions = runner.subscribed_markets();
+        info!("Registered strategy {}", runner.name());
+        self.strategies.push(Registered {
+            runner,
+            subscriptions,
+        });
+    }
+
+    /// Current strategy context
+    pub fn context(&self) -> &StrategyContext {
+        &self.ctx
+    }
+
+    /// Mutable access to the strategy context (e.g. after reconciliation)
+    pub fn context_mut(&mut self) -> &mut StrategyContext {
+        &mut self.ctx
+    }
+
+    /// Positions and realized PnL tracked from fills
+    pub fn positions(&self) -> &PositionTracker {
+        &self.tracker
+    }
+
+    /// Record an execution and update the context's position for it
+    ///
+    /// The context is the source of truth for positions, so one replaced
+    /// through [`context_mut`](Self::context_mut) (e.g. after reconciliation)
+    /// is what the fill is applied to. Returns the PnL the fill realized.
+    pub fn apply_fill(
+        &mut self,
+        platform: Platform,
+        market_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Decimal {
+        if let Some(position) = self.ctx.get_position(platform, market_id) {
+            self.tracker.set_position(position.clone());
+        }
+        let realized = self
+            .tracker
+            .apply_fill(platform, market_id, side, price, size);
+        if let Some(position) = self.tracker.position(platform, market_id) {
+            self.ctx
+                .positions
+                .insert((platform, market_id.to_string()), position.clone());
+        }
+        realized
+    }
+
+    /// Process events and ticks until `rx` closes
+    ///
+    /// Strategies are shut down when the event stream ends. An intent the
+    /// sink rejects is logged and skipped; the Trader only stops with an
+    /// error if the sink's channel has closed.
+    pub async fn run(&mut self, mut rx: mpsc::Receiver<MarketEvent>) -> Result<()> {
+        let mut ticker = interval_at(Instant::now() + self.tick_interval, self.tick_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let result = loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Some(event) => {
+                        if let Err(e) = self.on_market_event(&event).await {
+                            break Err(e);
+                        }
+                    }
+                    None => {
+                        debug!("Market event stream closed");
+                        break Ok(());
+                    }
+                },
+                _ = ticker.tick() => {
+                    if let Err(e) = self.on_tick().await {
+                        break Err(e);
+                    }
+                }
+            }
+        };
+
+        for registered in &mut self.strategies {
+            registered.runner.on_shutdown();
+        }
+        result
+    }
+
+    async fn on_market_event(&mut self, event: &MarketEvent) -> Result<()> {
+        let mut decisions = Vec::new();
+        for registered in &mut self.strategies {
+            if !registered
+                .subscriptions
+                .iter()
+                .any(|sub| sub.matches(event, &self.pair_map))
+            {
+                continue;
+            }
+            let decision = registered.runner.on_market_event(event, &self.ctx);
+            decisions.push((registered.runner.name().to_string(), decision));
+        }
+        self.dispatch_all(decisions).await
+    }
+
+    async fn on_tick(&mut self) -> Result<()> {
+        let decisions = self
+            .strategies
+            .iter_mut()
+            .map(|registered| {
+                let decision = registered.runner.on_tick(&self.ctx);
+                (registered.runner.name().to_string(), decision)
+            })
+            .collect();
+        self.dispatch_all(decisions).await
+    }
+
+    /// Dispatch the decisions from one event or tick, best scored first
+    ///
+    /// Unscored intents go last; ties keep strategy registration order.
+    async fn dispatch_all(&self, mut decisions: Vec<(String, Decision)>) -> Result<()> {
+        decisions.sort_by_key(|(_, decision)| Reverse(score(decision)));
+        for (strategy, decision) in decisions {
+            Self::dispatch(
+                &*self.size_calculator,
+                &self.checks,
+                &self.ctx,
+                &*self.sink,
+                &strategy,
+                decision,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Size a `Go` decision and submit it for execution
+    async fn dispatch(
+        size_calculator: &dyn SizeCalculator,
+        checks: &IntentChecks,
+        ctx: &StrategyContext,
+        sink: &dyn IntentSink,
+        strategy: &str,
+        decision: Decision,
+    ) -> Result<()> {
+        let Decision::Go(intent) = decision else {
+            return Ok(());
+        };
+
+        match size_calculator.get_sized_intent(&intent) {
+            Some(sized) if sized.is_valid() => {
+                if let Some(reason) = checks.rejection(strategy, &sized, ctx) {
+                    warn!("Dropping intent from strategy {}: {}", strategy, reason);
+                    return Ok(());
+                }
+                debug!(
+                    "Strategy {} sized intent: {} (score {:?})",
+                    strategy, sized.reason, sized.score
+                );
+                match sink.submit(sized).await {
+                    Ok(report) => debug!(
+                        "Intent from strategy {} submitted as {} orders",
+                        strategy,
+                        report.legs.len()
+                    ),
+                    // Nothing downstream is left to execute anything
+                    Err(e @ ClientError::ChannelSend(_)) => return Err(e),
+                    Err(e) => error!("Failed to submit intent from strategy {}: {}", strategy, e),
+                }
+                Ok(())
+            }
+            _ => {
+                warn!(
+                    "Dropping intent from strategy {}: no size available ({})",
+                    strategy, intent.reason
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Score of a `Go` decision's intent, `None` for `NoGo` or unscored
+fn score(decision: &Decision) -> Option<Decimal> {
+    match decision {
+        Decision::Go(intent) => intent.score,
+        Decision::NoGo => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{Trade, TradeSideSource};
+    use crate::strategy::execution::{ExecutionReport, LoggingSink};
+    use crate::strategy::size_calculator::{ComputedSize, InMemorySizeCalculator};
+    use crate::strategy::traits::Strategy;
+    use crate::strategy::types::{OrderType, Platform, Side, TradeIntent, TradeLeg};
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    /// Buys `market` whenever it trades at or above `trigger`
+    struct Breakout {
+        market: String,
+        trigger: Decimal,
+    }
+
+    impl Strategy for Breakout {
+        fn name(&self) -> &str {
+            "breakout"
+        }
+
+        fn on_market_event(&mut self, event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            match event {
+                MarketEvent::Trade(trade) if trade.price >= self.trigger => Decision::go_single(
+                    TradeLeg::new(Platform::Polymarket, &trade.asset_id, Side::Buy),
+                    format!("traded at {}", trade.price),
+                ),
+                _ => Decision::NoGo,
+            }
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::Specific {
+                platform: Platform::Polymarket,
+                market_id: self.market.clone(),
+            }]
+        }
+    }
+
+    fn trade(asset_id: &str, price: Decimal) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: asset_id.to_string(),
+            trade_id: "t".to_string(),
+            price,
+            size: dec!(10),
+            side: Side::Buy,
+            side_source: TradeSideSource::Reported,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        })
+    }
+
+    fn sizes(market_ids: &[&str]) -> BoxedSizeCalculator {
+        let mut sizes = InMemorySizeCalculator::new();
+        for market_id in market_ids {
+            sizes.set_size(ComputedSize {
+                platform: Platform::Polymarket,
+                market_id: market_id.to_string(),
+                side: Side::Buy,
+                size: dec!(25),
+                price: dec!(0.61),
+                computed_at: Utc::now(),
+            });
+        }
+        Box::new(sizes)
+    }
+
+    #[tokio::test]
+    async fn test_go_emits_sized_intent() {
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token", "other"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.55))).await.unwrap();
+        // Unsubscribed market never reaches the strategy
+        tx.send(trade("other", dec!(0.90))).await.unwrap();
+        tx.send(trade("token", dec!(0.61))).await.unwrap();
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+
+        let sized = out_rx.try_recv().unwrap();
+        assert_eq!(sized.legs.len(), 1);
+        assert_eq!(sized.legs[0].market_id, "token");
+        assert_eq!(sized.legs[0].size, dec!(25));
+        assert_eq!(sized.reason, "traded at 0.61");
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_logging_sink_receives_intents() {
+        let sink = Arc::new(LoggingSink::new());
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(sink.clone()),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.61))).await.unwrap();
+        tx.send(trade("token", dec!(0.50))).await.unwrap();
+        tx.send(trade("token", dec!(0.65))).await.unwrap();
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+        assert_eq!(sink.submitted(), 2);
+    }
+
+    /// Rejects every other intent
+    #[derive(Default)]
+    struct Flaky {
+        submitted: std::sync::atomic::AtomicU64,
+    }
+
+    #[async_trait::async_trait]
+    impl IntentSink for Flaky {
+        async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+            let n = self
+                .submitted
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            if n.is_multiple_of(2) {
+                return Err(ClientError::InvalidOrder("rejected".to_string()));
+            }
+            Ok(ExecutionReport {
+                reason: intent.reason,
+                legs: Vec::new(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_errors_skip_intent() {
+        let sink = Arc::new(Flaky::default());
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(sink.clone()),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        for _ in 0..3 {
+            tx.send(trade("token", dec!(0.61))).await.unwrap();
+        }
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+        assert_eq!(sink.submitted.load(std::sync::atomic::Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_closed_sink_stops_trader() {
+        let (out_tx, out_rx) = mpsc::channel(8);
+        drop(out_rx);
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.61))).await.unwrap();
+        drop(tx);
+
+        assert!(matches!(
+            trader.run(rx).await,
+            Err(ClientError::ChannelSend(_))
+        ));
+    }
+
+    /// Goes on every trade with a fixed score
+    struct Scored(&'static str, Option<Decimal>);
+
+    impl Strategy for Scored {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn on_market_event(&mut self, event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            let MarketEvent::Trade(trade) = event else {
+                return Decision::NoGo;
+            };
+            let mut intent = TradeIntent::single(
+                TradeLeg::new(Platform::Polymarket, &trade.asset_id, Side::Buy),
+                self.0,
+            );
+            intent.score = self.1;
+            Decision::Go(intent)
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::AllOnPlatform(Platform::Polymarket)]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_higher_scores_submitted_first() {
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![
+                Box::new(Scored("unscored", None)),
+                Box::new(Scored("low", Some(dec!(0.2)))),
+                Box::new(Scored("high", Some(dec!(0.9)))),
+                Box::new(Scored("also_unscored", None)),
+            ],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.50))).await.unwrap();
+        drop(tx);
+        trader.run(rx).await.unwrap();
+
+        let mut order = Vec::new();
+        while let Ok(sized) = out_rx.try_recv() {
+            order.push((sized.reason, sized.score));
+        }
+        assert_eq!(
+            order,
+            vec![
+                ("high".to_string(), Some(dec!(0.9))),
+                ("low".to_string(), Some(dec!(0.2))),
+                ("unscored".to_string(), None),
+                ("also_unscored".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsized_intent_dropped() {
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&[]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.70))).await.unwrap();
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_fills_update_context_positions() {
+        let (out_tx, _out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            Vec::new(),
+            sizes(&[]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        );
+        let pm = Platform::Polymarket;
+
+        trader.apply_fill(pm, "token", Side::Buy, dec!(0.40), dec!(100));
+        let realized = trader.apply_fill(pm, "token", Side::Sell, dec!(0.50), dec!(40));
+        assert_eq!(realized, dec!(4));
+
+        let position = trader.context().get_position(pm, "token").unwrap();
+        assert_eq!(position.size, dec!(60));
+        assert_eq!(position.avg_entry_price, dec!(0.40));
+        assert_eq!(trader.positions().realized_pnl(), dec!(4));
+
+        // A position replaced in the context is what later fills apply to
+        trader
+            .context_mut()
+            .positions
+            .get_mut(&(pm, "token".to_string()))
+            .unwrap()
+            .size = dec!(10);
+        trader.apply_fill(pm, "token", Side::Sell, dec!(0.45), dec!(10));
+        assert!(!trader.context().has_position(pm, "token"));
+    }
+
+    #[tokio::test]
+    async fn test_intent_breaking_constraints_dropped() {
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        )
+        // The sized leg is 25 contracts but the market needs 50
+        .with_market_constraints(HashMap::from([(
+            "token".to_string(),
+            MarketConstraints::new(dec!(50), dec!(0.01)),
+        )]));
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.70))).await.unwrap();
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_intent_over_risk_limits_dropped() {
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![Box::new(Breakout {
+                market: "token".to_string(),
+                trigger: dec!(0.60),
+            })],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        )
+        // The sized leg would open 25 contracts
+        .with_risk_limits(RiskLimits::new().with_max_position_per_market(dec!(20)));
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("token", dec!(0.70))).await.unwrap();
+        drop(tx);
+
+        trader.run(rx).await.unwrap();
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_atomicity_policy_rejects_resting_legs() {
+        /// Buys both markets together whenever either trades
+        struct Pair {
+            order_type: OrderType,
+        }
+
+        impl Strategy for Pair {
+            fn name(&self) -> &str {
+                "pair"
+            }
+
+            fn on_market_event(
+                &mut self,
+                _event: &MarketEvent,
+                _ctx: &StrategyContext,
+            ) -> Decision {
+                let leg = |market_id| {
+                    TradeLeg::new(Platform::Polymarket, market_id, Side::Buy)
+                        .with_order_type(self.order_type)
+                };
+                Decision::go_arbitrage(vec![leg("a"), leg("b")], "pair")
+            }
+
+            fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+                vec![MarketSubscription::AllOnPlatform(Platform::Polymarket)]
+            }
+        }
+
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![
+                Box::new(Pair {
+                    order_type: OrderType::Gtc,
+                }),
+                Box::new(Pair {
+                    order_type: OrderType::Fok,
+                }),
+            ],
+            sizes(&["a", "b"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        )
+        .with_atomicity_policy(AtomicityPolicy::Reject);
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(trade("a", dec!(0.50))).await.unwrap();
+        drop(tx);
+        trader.run(rx).await.unwrap();
+
+        // Only the all-FOK intent gets through
+        let sized = out_rx.try_recv().unwrap();
+        assert!(sized.is_executable_atomically());
+        assert_eq!(sized.legs.len(), 2);
+        assert!(out_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tick_on_interval() {
+        struct Ticker;
+
+        impl Strategy for Ticker {
+            fn name(&self) -> &str {
+                "ticker"
+            }
+
+            fn on_market_event(
+                &mut self,
+                _event: &MarketEvent,
+                _ctx: &StrategyContext,
+            ) -> Decision {
+                Decision::NoGo
+            }
+
+            fn on_tick(&mut self, _ctx: &StrategyContext) -> Decision {
+                Decision::go_single(
+                    TradeLeg::new(Platform::Polymarket, "token", Side::Buy),
+                    "tick",
+                )
+            }
+
+            fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+                vec![MarketSubscription::AllOnPlatform(Platform::Polymarket)]
+            }
+        }
+
+        let (out_tx, mut out_rx) = mpsc::channel(8);
+        let mut trader = Trader::new(
+            vec![Box::new(Ticker)],
+            sizes(&["token"]),
+            StrategyContext::new(),
+            Box::new(out_tx),
+        )
+        .with_tick_interval(Duration::from_millis(20));
+
+        let (tx, rx) = mpsc::channel(8);
+        let handle = tokio::spawn(async move { trader.run(rx).await });
+
+        let sized = tokio::time::timeout(Duration::from_secs(2), out_rx.recv())
+            .await
+            .expect("no tick intent")
+            .unwrap();
+        assert_eq!(sized.reason, "tick");
+
+        drop(tx);
+        handle.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,433 @@
+//! Where sized intents go once the Trader has approved them
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{info, warn};
+
+use crate::common::book_store::BookStore;
+use crate::common::errors::{ClientError, Result};
+use crate::common::types::OrderBook;
+use crate::polymarket::messages::{OrderRequest, OrderType as ClobOrderType};
+use crate::polymarket::rest::PolymarketRestClient;
+use crate::strategy::fees::FeeCalculator;
+use crate::strategy::pnl::PositionTracker;
+use crate::strategy::size_calculator::{SizedIntent, SizedLeg};
+use crate::strategy::types::{OrderType, Platform, Side};
+
+/// What happened to one leg of a submitted intent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegExecution {
+    pub platform: Platform,
+    pub market_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Exchange order ID, if an order was placed
+    pub order_id: Option<String>,
+    /// Order status as reported by the sink, e.g. `live` or `matched`
+    pub status: String,
+    /// Size known to have filled; zero for orders whose fills arrive later
+    pub filled_size: Decimal,
+    /// Average price of the filled size
+    pub avg_price: Option<Decimal>,
+    /// Fees charged on the filled size
+    pub fee: Decimal,
+}
+
+impl LegExecution {
+    fn new(leg: &SizedLeg, order_id: Option<String>, status: impl Into<String>) -> Self {
+        Self {
+            platform: leg.platform,
+            market_id: leg.market_id.clone(),
+            side: leg.side,
+            price: leg.price,
+            size: leg.size,
+            order_id,
+            status: status.into(),
+            filled_size: Decimal::ZERO,
+            avg_price: None,
+            fee: Decimal::ZERO,
+        }
+    }
+
+    fn filled(mut self, size: Decimal, avg_price: Decimal, fee: Decimal) -> Self {
+        self.filled_size = size;
+        self.avg_price = Some(avg_price);
+        self.fee = fee;
+        self
+    }
+
+    /// Whether the leg's whole size filled
+    pub fn is_filled(&self) -> bool {
+        self.filled_size >= self.size
+    }
+}
+
+/// Result of submitting a [`SizedIntent`] to an [`IntentSink`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionReport {
+    /// Reason carried over from the intent
+    pub reason: String,
+    /// One entry per leg, in the intent's order
+    pub legs: Vec<LegExecution>,
+}
+
+/// Destination for the intents a [`Trader`](super::Trader) decides to trade
+///
+/// Sinks decide what executing an intent means: placing orders, paper
+/// trading, or handing it to another task. The Trader logs and skips an
+/// intent whose submit fails; only `ClientError::ChannelSend`, meaning
+/// nothing is left to take intents, stops it.
+#[async_trait]
+pub trait IntentSink: Send + Sync {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport>;
+}
+
+/// Type alias for boxed intent sinks
+pub type BoxedIntentSink = Box<dyn IntentSink>;
+
+#[async_trait]
+impl<S: IntentSink + ?Sized> IntentSink for Arc<S> {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+        (**self).submit(intent).await
+    }
+}
+
+/// Hands intents to another task; every leg is reported as `queued`
+#[async_trait]
+impl IntentSink for mpsc::Sender<SizedIntent> {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+        let report = ExecutionReport {
+            reason: intent.reason.clone(),
+            legs: intent
+                .legs
+                .iter()
+                .map(|leg| LegExecution::new(leg, None, "queued"))
+                .collect(),
+        };
+        self.send(intent)
+            .await
+            .map_err(|e| ClientError::ChannelSend(e.to_string()))?;
+        Ok(report)
+    }
+}
+
+/// Paper trading sink that logs intents and fills them at their price
+///
+/// Every leg is reported as `matched` for its full size with a made-up
+/// order ID.
+#[derive(Debug, Default)]
+pub struct LoggingSink {
+    orders: AtomicU64,
+    intents: AtomicU64,
+}
+
+impl LoggingSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intents submitted so far
+    pub fn submitted(&self) -> u64 {
+        self.intents.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl IntentSink for LoggingSink {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+        self.intents.fetch_add(1, Ordering::Relaxed);
+        info!("Paper trading intent: {}", intent.reason);
+        let legs = intent
+            .legs
+            .iter()
+            .map(|leg| {
+                let order = self.orders.fetch_add(1, Ordering::Relaxed) + 1;
+                let order_id = format!("paper-{}", order);
+                info!(
+                    "  {} {:?} {} {} @ {} ({})",
+                    leg.platform, leg.side, leg.market_id, leg.size, leg.price, order_id
+                );
+                LegExecution::new(leg, Some(order_id), "matched").filled(
+                    leg.size,
+                    leg.price,
+                    Decimal::ZERO,
+                )
+            })
+            .collect();
+        Ok(ExecutionReport {
+            reason: intent.reason,
+            legs,
+        })
+    }
+}
+
+/// Places each leg as an order on the Polymarket CLOB
+///
+/// Legs are placed one after another, with the leg's market ID as the
+/// token ID. The whole intent is rejected up front if any leg is for
+/// another platform or is IOC, which the CLOB doesn't offer. If a leg
+/// fails after earlier ones were placed, those orders are left working and
+/// logged. The client needs API credentials.
+pub struct RestExecutionSink {
+    client: PolymarketRestClient,
+}
+
+impl RestExecutionSink {
+    pub fn new(client: PolymarketRestClient) -> Self {
+        Self { client }
+    }
+
+    /// Order request for a leg, or why it can't be placed
+    fn order_for(leg: &SizedLeg) -> Result<OrderRequest> {
+        if leg.platform != Platform::Polymarket {
+            return Err(ClientError::InvalidOrder(format!(
+                "{} leg for {} can't be placed on Polymarket",
+                leg.platform, leg.market_id
+            )));
+        }
+        let (order_type, expiration) = match leg.order_type {
+            OrderType::Gtc => (ClobOrderType::Gtc, None),
+            OrderType::Fok => (ClobOrderType::Fok, None),
+            OrderType::Gtd(expiry) => (ClobOrderType::Gtd, Some(expiry.timestamp())),
+            OrderType::Ioc => {
+                return Err(ClientError::InvalidOrder(format!(
+                    "IOC leg for {} isn't supported by Polymarket",
+                    leg.market_id
+                )));
+            }
+        };
+        Ok(OrderRequest {
+            token_id: leg.market_id.clone(),
+            price: leg.price,
+            size: leg.size,
+            side: leg.side,
+            order_type,
+            expiration,
+        })
+    }
+}
+
+#[async_trait]
+impl IntentSink for RestExecutionSink {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+        let orders = intent
+            .legs
+            .iter()
+            .map(Self::order_for)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut legs = Vec::with_capacity(orders.len());
+        for (leg, order) in intent.legs.iter().zip(orders) {
+            match self.client.post_order(order).await {
+                Ok(response) => {
+                    legs.push(LegExecution::new(
+                        leg,
+                        Some(response.order_id),
+                        response.status,
+                    ));
+                }
+                Err(e) => {
+                    if !legs.is_empty() {
+                        let placed: Vec<_> =
+                            legs.iter().filter_map(|l| l.order_id.as_deref()).collect();
+                        warn!(
+                            "Leg {} of intent '{}' failed after placing orders {:?}",
+                            leg.market_id, intent.reason, placed
+                        );
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(ExecutionReport {
+            reason: intent.reason,
+            legs,
+        })
+    }
+}
+
+/// Paper trading sink that fills legs against the live order books
+///
+/// Each leg walks its market's book in `books` (keyed by the leg's market
+/// ID) from the best price down to the leg's price, taking whatever size is
+/// there. A leg the book is too thin for is reported as `partial` with the
+/// size that filled, and one with no book or nothing in range as
+/// `unfilled`. Fills don't take liquidity out of `books`, so repeated
+/// intents can fill against the same levels.
+///
+/// Fees are the platform's taker fees from [`FeeCalculator`]. Fills are
+/// applied to a simulated [`PositionTracker`], giving paper PnL.
+pub struct PaperExecutionSink {
+    books: Arc<RwLock<BookStore>>,
+    account: Mutex<PaperAccount>,
+}
+
+/// Positions and costs accumulated by a [`PaperExecutionSink`]
+#[derive(Default)]
+struct PaperAccount {
+    positions: PositionTracker,
+    fees: Decimal,
+    orders: u64,
+}
+
+impl PaperExecutionSink {
+    pub fn new(books: Arc<RwLock<BookStore>>) -> Self {
+        Self {
+            books,
+            account: Mutex::new(PaperAccount::default()),
+        }
+    }
+
+    /// Start from existing positions instead of a flat book
+    pub fn with_positions(self, positions: PositionTracker) -> Self {
+        self.account.lock().unwrap().positions = positions;
+        self
+    }
+
+    /// Simulated positions and realized PnL
+    pub fn positions(&self) -> PositionTracker {
+        self.account.lock().unwrap().positions.clone()
+    }
+
+    /// Total fees charged on paper fills
+    pub fn fees_paid(&self) -> Decimal {
+        self.account.lock().unwrap().fees
+    }
+
+    /// Size and notional `leg` can take from `book` without going past its
+    /// price
+    fn take(book: &OrderBook, leg: &SizedLeg) -> (Decimal, Decimal) {
+        let mut filled = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        for level in book.levels_to_take(leg.side) {
+            let in_range = match leg.side {
+                Side::Buy => level.price <= leg.price,
+                Side::Sell => level.price >= leg.price,
+            };
+            if !in_range || filled >= leg.size {
+                break;
+            }
+            let size = level.size.min(leg.size - filled);
+            filled += size;
+            notional += size * level.price;
+        }
+        (filled, notional)
+    }
+}
+
+#[async_trait]
+impl IntentSink for PaperExecutionSink {
+    async fn submit(&self, intent: SizedIntent) -> Result<ExecutionReport> {
+        let books = self.books.read().await;
+        let mut account = self.account.lock().unwrap();
+
+        let mut legs = Vec::with_capacity(intent.legs.len());
+        for leg in &intent.legs {
+            let (filled, notional) = books
+                .get(&leg.market_id)
+                .filter(|book| book.platform == leg.platform)
+                .map(|book| Self::take(book, leg))
+                .unwrap_or_default();
+
+            account.orders += 1;
+            let order_id = Some(format!("paper-{}", account.orders));
+            if filled.is_zero() {
+                info!("Paper {:?} {} didn't fill", leg.side, leg.market_id);
+                legs.push(LegExecution::new(leg, order_id, "unfilled"));
+                continue;
+            }
+
+            let avg_price = notional / filled;
+            let fee = (FeeCalculator::entry_cost(leg.platform, avg_price, leg.side, filled)
+                - avg_price * filled)
+                .abs();
+            account
+                .positions
+                .apply_fill(leg.platform, &leg.market_id, leg.side, avg_price, filled);
+            account.fees += fee;
+
+            let status = if filled < leg.size {
+                "partial"
+            } else {
+                "matched"
+            };
+            info!(
+                "Paper {:?} {} filled {} of {} @ {} (fee {})",
+                leg.side, leg.market_id, filled, leg.size, avg_price, fee
+            );
+            legs.push(LegExecution::new(leg, order_id, status).filled(filled, avg_price, fee));
+        }
+
+        Ok(ExecutionReport {
+            reason: intent.reason,
+            legs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn leg(platform: Platform, market_id: &str, order_type: OrderType) -> SizedLeg {
+        SizedLeg {
+            platform,
+            market_id: market_id.to_string(),
+            side: Side::Buy,
+            size: dec!(10),
+            price: dec!(0.45),
+            order_type,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logging_sink_fills_every_leg() {
+        let sink = LoggingSink::new();
+        let intent = SizedIntent {
+            legs: vec![
+                leg(Platform::Polymarket, "a", OrderType::Fok),
+                leg(Platform::Kalshi, "KX", OrderType::Fok),
+            ],
+            reason: "arb".to_string(),
+            score: None,
+        };
+
+        let report = sink.submit(intent).await.unwrap();
+        assert_eq!(report.reason, "arb");
+        let ids: Vec<_> = report.legs.iter().map(|l| l.order_id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("paper-1"), Some("paper-2")]);
+        assert!(report.legs.iter().all(|l| l.status == "matched"));
+        assert_eq!(report.legs[1].market_id, "KX");
+        assert_eq!(sink.submitted(), 1);
+    }
+
+    #[test]
+    fn test_rest_order_for_leg() {
+        let expiry = Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap();
+        let order = RestExecutionSink::order_for(&leg(
+            Platform::Polymarket,
+            "token",
+            OrderType::Gtd(expiry),
+        ))
+        .unwrap();
+        assert_eq!(order.token_id, "token");
+        assert_eq!(order.order_type, ClobOrderType::Gtd);
+        assert_eq!(order.expiration, Some(expiry.timestamp()));
+
+        assert!(matches!(
+            RestExecutionSink::order_for(&leg(Platform::Polymarket, "token", OrderType::Ioc)),
+            Err(ClientError::InvalidOrder(_))
+        ));
+        assert!(matches!(
+            RestExecutionSink::order_for(&leg(Platform::Kalshi, "KX", OrderType::Gtc)),
+            Err(ClientError::InvalidOrder(_))
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+
+use crate::common::book_store::BookStore;
+use crate::common::types::MarketEvent;
+
+/// How much each signal counts towards a [`FairValue`] estimate
+///
+/// Weights are relative; they don't need to sum to one. A zero weight
+/// leaves the signal out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FairValueWeights {
+    /// The asset's own book microprice
+    pub microprice: Decimal,
+    /// The asset's last trade price
+    pub last_trade: Decimal,
+    /// The midpoint of the matched market's book
+    pub reference: Decimal,
+}
+
+impl Default for FairValueWeights {
+    fn default() -> Self {
+        Self {
+            microprice: dec!(0.6),
+            last_trade: dec!(0.2),
+            reference: dec!(0.2),
+        }
+    }
+}
+
+/// Fair value per asset, blended from book, trade and cross-platform signals
+///
+/// The estimate is the weighted average of the signals available for the
+/// asset: its [`microprice`](crate::common::types::OrderBook::microprice),
+/// its last trade price, and the midpoint of its reference asset's book.
+/// Missing signals are dropped and the remaining weights renormalized, so a
+/// market that hasn't traded yet is valued from its book alone.
+///
+/// Books and last trades are kept current with [`apply`](Self::apply).
+/// A reference asset is usually the same outcome on the other platform;
+/// its book must be fed into the same `FairValue`.
+#[derive(Debug, Clone, Default)]
+pub struct FairValue {
+    weights: FairValueWeights,
+    books: BookStore,
+    last_trades: HashMap<String, Decimal>,
+    /// Reference asset for each asset that has one
+    references: HashMap<String, String>,
+}
+
+impl FairValue {
+    pub fn new(weights: FairValueWeights) -> Self {
+        Self {
+            weights,
+            ..Self::default()
+        }
+    }
+
+    /// Start from books already received
+    pub fn with_books(mut self, books: BookStore) -> Self {
+        self.books = books;
+        self
+    }
+
+    /// Start from known last trade prices, by asset ID
+    pub fn with_last_trades(mut self, last_trades: HashMap<String, Decimal>) -> Self {
+        self.last_trades = last_trades;
+        self
+    }
+
+    /// Value `asset_id` partly from the midpoint of `reference_asset_id`
+    pub fn with_reference(
+        mut self,
+        asset_id: impl Into<String>,
+        reference_asset_id: impl Into<String>,
+    ) -> Self {
+        self.references
+            .insert(asset_id.into(), reference_asset_id.into());
+        self
+    }
+
+    /// Update books and last trade prices from a market event
+    pub fn apply(&mut self, event: &MarketEvent) {
+        match event {
+            MarketEvent::Trade(trade) => {
+                self.last_trades.insert(trade.asset_id.clone(), trade.price);
+            }
+            MarketEvent::LastTradePrice {
+                asset_id, price, ..
+            } => {
+                self.last_trades.insert(asset_id.clone(), *price);
+            }
+            _ => self.books.apply(event),
+        }
+    }
+
+    /// Current books
+    pub fn books(&self) -> &BookStore {
+        &self.books
+    }
+
+    /// Blended fair value of `asset_id`, or `None` with no signal for it
+    pub fn estimate(&self, asset_id: &str) -> Option<Decimal> {
+        let book = self.books.get(asset_id);
+        let reference = self
+            .references
+            .get(asset_id)
+            .and_then(|reference| self.books.get(reference))
+            .and_then(|book| book.midpoint());
+        let signals = [
+            (
+                self.weights.microprice,
+                book.and_then(|book| book.microprice()),
+            ),
+            (
+                self.weights.last_trade,
+                self.last_trades.get(asset_id).copied(),
+            ),
+            (self.weights.reference, reference),
+        ];
+
+        let (weighted, total) = signals
+            .into_iter()
+            .filter_map(|(weight, value)| Some((weight, value?)))
+            .filter(|(weight, _)| *weight > Decimal::ZERO)
+            .fold(
+                (Decimal::ZERO, Decimal::ZERO),
+                |(sum, total), (weight, value)| (sum + weight * value, total + weight),
+            );
+        if total.is_zero() {
+            return None;
+        }
+        Some(weighted / total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{OrderBook, Platform, PriceLevel};
+    use chrono::Utc;
+
+    fn book(platform: Platform, asset_id: &str, bid: PriceLevel, ask: PriceLevel) -> MarketEvent {
+        MarketEvent::OrderBook(OrderBook {
+            platform,
+            market_id: asset_id.to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![bid],
+            asks: vec![ask],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    fn last_trade(asset_id: &str, price: Decimal) -> MarketEvent {
+        MarketEvent::LastTradePrice {
+            platform: Platform::Polymarket,
+            asset_id: asset_id.to_string(),
+            price,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Polymarket token with a 0.533 microprice, referenced to a Kalshi
+    /// ticker at 0.56 mid
+    fn fair_value(weights: FairValueWeights) -> FairValue {
+        let mut fv = FairValue::new(weights).with_reference("token", "KX");
+        fv.apply(&book(
+            Platform::Polymarket,
+            "token",
+            PriceLevel::new(dec!(0.50), dec!(100)),
+            PriceLevel::new(dec!(0.55), dec!(50)),
+        ));
+        fv.apply(&book(
+            Platform::Kalshi,
+            "KX",
+            PriceLevel::new(dec!(0.55), dec!(10)),
+            PriceLevel::new(dec!(0.57), dec!(10)),
+        ));
+        fv
+    }
+
+    #[test]
+    fn test_weighted_blend() {
+        let mut fv = fair_value(FairValueWeights {
+            microprice: dec!(2),
+            last_trade: dec!(1),
+            reference: dec!(1),
+        });
+        // (0.50 * 50 + 0.55 * 100) / 150
+        let microprice = dec!(0.80) / dec!(1.5);
+        fv.apply(&last_trade("token", dec!(0.50)));
+
+        let expected = (dec!(2) * microprice + dec!(0.50) + dec!(0.56)) / dec!(4);
+        assert_eq!(fv.estimate("token"), Some(expected));
+
+        // A zero weight leaves its signal out
+        let mut fv = fair_value(FairValueWeights {
+            microprice: dec!(0),
+            last_trade: dec!(1),
+            reference: dec!(3),
+        });
+        fv.apply(&last_trade("token", dec!(0.48)));
+        assert_eq!(fv.estimate("token"), Some(dec!(0.54)));
+    }
+
+    #[test]
+    fn test_missing_signals_renormalize() {
+        let weights = FairValueWeights {
+            microprice: dec!(1),
+            last_trade: dec!(1),
+            reference: dec!(2),
+        };
+
+        // No trade yet: book and reference only
+        let fv = fair_value(weights);
+        let microprice = dec!(0.80) / dec!(1.5);
+        assert_eq!(
+            fv.estimate("token"),
+            Some((microprice + dec!(2) * dec!(0.56)) / dec!(3))
+        );
+
+        // No reference configured for the Kalshi ticker
+        assert_eq!(fv.estimate("KX"), Some(dec!(0.56)));
+
+        // Only a trade
+        let mut fv = FairValue::new(weights);
+        fv.apply(&last_trade("other", dec!(0.31)));
+        assert_eq!(fv.estimate("other"), Some(dec!(0.31)));
+
+        assert_eq!(fv.estimate("unknown"), None);
+    }
+}
@@ -1,8 +1,32 @@
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
+use crate::common::math::no_price;
 use crate::strategy::types::{Platform, Side};
 
+/// Multiplier in Kalshi's taker trading fee formula
+pub const KALSHI_TRADING_FEE_RATE: Decimal = dec!(0.07);
+
+/// Kalshi taker fee for trading `contracts` at `price`
+///
+/// Kalshi charges `0.07 * contracts * price * (1 - price)` on the traded
+/// notional, rounded up to the next cent.
+pub fn kalshi_trading_fee(price: Decimal, contracts: Decimal) -> Decimal {
+    quadratic_trading_fee(KALSHI_TRADING_FEE_RATE, price, contracts)
+}
+
+/// `rate * contracts * price * (1 - price)`, rounded up to the cent
+fn quadratic_trading_fee(rate: Decimal, price: Decimal, contracts: Decimal) -> Decimal {
+    let fee = rate * contracts * price * no_price(price);
+    (fee * dec!(100)).ceil() / dec!(100)
+}
+
+/// Iteration cap when solving for [`FeeCalculator::min_edge_to_profit`]
+const MIN_EDGE_ITERATIONS: usize = 20;
+
+/// Decimal places [`FeeCalculator::min_edge_to_profit`] is rounded to
+const MIN_EDGE_DECIMALS: u32 = 8;
+
 /// Fee configuration for a platform
 #[derive(Debug, Clone)]
 pub struct PlatformFees {
@@ -13,19 +37,40 @@ pub struct PlatformFees {
     pub taker_fee_percent: Decimal,
     /// Whether the fee is based on profit (true for Kalshi) or trade size (false for most)
     pub profit_based: bool,
+    /// Rate for a per-trade `rate * contracts * price * (1 - price)` fee,
+    /// rounded up to the cent (zero if the platform doesn't charge one)
+    pub trading_fee_rate: Decimal,
 }
 
 impl PlatformFees {
     /// Kalshi fee structure
+    /// - Taker fee of `ceil(0.07 * contracts * price * (1 - price))` per trade
+    /// - No maker fees
+    /// - No fees at settlement
+    pub fn kalshi() -> Self {
+        Self {
+            platform: Platform::Kalshi,
+            maker_fee_percent: dec!(0.0),
+            taker_fee_percent: dec!(0.0),
+            profit_based: false,
+            trading_fee_rate: KALSHI_TRADING_FEE_RATE,
+        }
+    }
+
+    /// Legacy Kalshi approximation
     /// - 7% of profit on winning trades
     /// - No maker fees
     /// - No fees on losing trades
-    pub fn kalshi() -> Self {
+    ///
+    /// Kept for comparing against older results; use with the `*_with_fees`
+    /// methods on [`FeeCalculator`].
+    pub fn kalshi_profit_based() -> Self {
         Self {
             platform: Platform::Kalshi,
             maker_fee_percent: dec!(0.0),
             taker_fee_percent: dec!(7.0), // 7% of profit
             profit_based: true,
+            trading_fee_rate: dec!(0.0),
         }
     }
 
@@ -37,6 +82,7 @@ impl PlatformFees {
             maker_fee_percent: dec!(0.0),
             taker_fee_percent: dec!(0.0),
             profit_based: false,
+            trading_fee_rate: dec!(0.0),
         }
     }
 
@@ -47,6 +93,26 @@ impl PlatformFees {
             Platform::Polymarket => Self::polymarket(),
         }
     }
+
+    /// Size-based fee for taking `size` contracts at `price`
+    fn taker_fee(&self, price: Decimal, size: Decimal) -> Decimal {
+        let percent_fee = price * size * self.taker_fee_percent / dec!(100.0);
+        let trading_fee = if self.trading_fee_rate.is_zero() {
+            Decimal::ZERO
+        } else {
+            quadratic_trading_fee(self.trading_fee_rate, price, size)
+        };
+        percent_fee + trading_fee
+    }
+
+    /// Size-based taker fee per contract at `price`, before cent rounding
+    ///
+    /// Rounding adds at most a cent per trade, so this is the per-contract
+    /// fee for trades large enough that the rounding doesn't matter.
+    fn taker_fee_per_contract(&self, price: Decimal) -> Decimal {
+        price * self.taker_fee_percent / dec!(100.0)
+            + self.trading_fee_rate * price * no_price(price)
+    }
 }
 
 /// Fee calculation utilities
@@ -70,45 +136,40 @@ impl FeeCalculator {
     /// # Returns
     /// Total cost/proceeds including fees
     pub fn entry_cost(platform: Platform, price: Decimal, side: Side, size: Decimal) -> Decimal {
-        let fees = PlatformFees::for_platform(platform);
+        Self::entry_cost_with_fees(&PlatformFees::for_platform(platform), price, side, size)
+    }
+
+    /// [`entry_cost`](Self::entry_cost) with an explicit fee structure
+    pub fn entry_cost_with_fees(
+        fees: &PlatformFees,
+        price: Decimal,
+        side: Side,
+        size: Decimal,
+    ) -> Decimal {
+        // Profit-based fees are accounted for on exit instead
+        let fee = if fees.profit_based {
+            Decimal::ZERO
+        } else {
+            fees.taker_fee(price, size)
+        };
 
         match side {
-            Side::Buy => {
-                // Cost = price * size + fees
-                let base_cost = price * size;
-                if fees.profit_based {
-                    // For Kalshi, no entry fee, but we account for it on exit
-                    base_cost
-                } else {
-                    // For size-based fees, add taker fee
-                    let fee = base_cost * fees.taker_fee_percent / dec!(100.0);
-                    base_cost + fee
-                }
-            }
-            Side::Sell => {
-                // Proceeds = price * size - fees
-                let base_proceeds = price * size;
-                if fees.profit_based {
-                    // For Kalshi, no entry fee
-                    base_proceeds
-                } else {
-                    // For size-based fees, subtract taker fee
-                    let fee = base_proceeds * fees.taker_fee_percent / dec!(100.0);
-                    base_proceeds - fee
-                }
-            }
+            // Cost = price * size + fees
+            Side::Buy => price * size + fee,
+            // Proceeds = price * size - fees
+            Side::Sell => price * size - fee,
         }
     }
 
     /// Calculate the effective value when exiting a position (worst-case)
     ///
-    /// For Kalshi (profit-based fees):
+    /// For profit-based fees (legacy Kalshi model):
     /// - If you bought at `entry_price`, worst-case assumes you win
     /// - Profit = (1.0 - entry_price) * size
     /// - Fee = profit * 7%
     /// - Exit value = 1.0 - fee
     ///
-    /// For Polymarket (no fees currently):
+    /// For Kalshi and Polymarket (no settlement fees):
     /// - Exit value = 1.0
     ///
     /// # Arguments
@@ -119,17 +180,25 @@ impl FeeCalculator {
     ///
     /// # Returns
     /// Net value per contract after fees (worst-case)
-    pub fn exit_value(platform: Platform, entry_price: Decimal, side: Side, _size: Decimal) -> Decimal {
-        let fees = PlatformFees::for_platform(platform);
+    pub fn exit_value(platform: Platform, entry_price: Decimal, side: Side, size: Decimal) -> Decimal {
+        Self::exit_value_with_fees(&PlatformFees::for_platform(platform), entry_price, side, size)
+    }
 
+    /// [`exit_value`](Self::exit_value) with an explicit fee structure
+    pub fn exit_value_with_fees(
+        fees: &PlatformFees,
+        entry_price: Decimal,
+        side: Side,
+        _size: Decimal,
+    ) -> Decimal {
         match side {
             Side::Buy => {
                 // If we bought, we profit when market resolves to YES (1.0)
                 // Profit per contract = 1.0 - entry_price
-                let profit_per_contract = dec!(1.0) - entry_price;
+                let profit_per_contract = no_price(entry_price);
 
                 if fees.profit_based {
-                    // Kalshi: Fee is 7% of profit
+                    // Fee is a percentage of profit
                     let fee_per_contract = profit_per_contract * fees.taker_fee_percent / dec!(100.0);
                     dec!(1.0) - fee_per_contract
                 } else {
@@ -143,7 +212,7 @@ impl FeeCalculator {
                 // Additional value = 1.0 - entry_price (what we would have lost)
 
                 if fees.profit_based {
-                    // Kalshi: Fee on the profit we made by selling
+                    // Fee on the profit we made by selling
                     let profit_per_contract = entry_price;
                     let fee_per_contract = profit_per_contract * fees.taker_fee_percent / dec!(100.0);
                     entry_price - fee_per_contract
@@ -176,8 +245,49 @@ impl FeeCalculator {
         }
     }
 
+    /// Smallest favorable price move that pays for a round trip's fees
+    ///
+    /// Entering `side` at `price` and exiting at `price` plus the move (for a
+    /// buy) or minus it (for a sell) pays the taker fee on both trades. The
+    /// exit fee depends on the exit price, so the threshold is solved for
+    /// `move = fee(price) + fee(exit price)`. Fees are per contract without
+    /// cent rounding, so small trades need slightly more. Zero on platforms
+    /// without taker fees.
+    pub fn min_edge_to_profit(platform: Platform, price: Decimal, side: Side) -> Decimal {
+        let fees = PlatformFees::for_platform(platform);
+        if fees.profit_based {
+            return Decimal::ZERO;
+        }
+        let entry_fee = fees.taker_fee_per_contract(price);
+
+        // The fee changes far slower than the price, so this converges fast
+        let mut edge = entry_fee;
+        for _ in 0..MIN_EDGE_ITERATIONS {
+            let exit_price = match side {
+                Side::Buy => price + edge,
+                Side::Sell => price - edge,
+            };
+            let exit_price = exit_price.clamp(Decimal::ZERO, dec!(1.0));
+            let next = entry_fee + fees.taker_fee_per_contract(exit_price);
+            if next == edge {
+                break;
+            }
+            edge = next;
+        }
+        edge.round_dp(MIN_EDGE_DECIMALS)
+    }
+
     /// Calculate expected profit for an arbitrage trade (worst-case fees)
     ///
+    /// Buying YES on one platform and selling YES (equivalently buying NO)
+    /// on the other locks in `(sell_price - buy_price) * size` whichever way
+    /// the market resolves. Fees are what differ between outcomes:
+    /// - Size-based taker fees (including Kalshi's trading fee) are paid on
+    ///   both legs at entry
+    /// - Profit-based fees are only paid by the leg that resolves
+    ///   in the money: the buy leg profits `1.0 - buy_price` per contract if
+    ///   YES wins, the sell leg profits `sell_price` per contract if NO wins
+    ///
     /// # Arguments
     /// * `buy_platform` - Platform where we buy
     /// * `buy_price` - Price to buy at
@@ -186,7 +296,7 @@ impl FeeCalculator {
     /// * `size` - Trade size (must be same on both sides)
     ///
     /// # Returns
-    /// Net profit after fees on both sides
+    /// Net profit after fees in the worse of the two outcomes
     pub fn arbitrage_profit(
         buy_platform: Platform,
         buy_price: Decimal,
@@ -194,20 +304,57 @@ impl FeeCalculator {
         sell_price: Decimal,
         size: Decimal,
     ) -> Decimal {
-        // Cost to buy on first platform
-        let buy_cost = Self::entry_cost(buy_platform, buy_price, Side::Buy, size);
-
-        // Proceeds from selling on second platform
-        let sell_proceeds = Self::entry_cost(sell_platform, sell_price, Side::Sell, size);
-
-        // If our buy side wins (worst case for Kalshi fees)
-        let buy_exit_value = Self::exit_value(buy_platform, buy_price, Side::Buy, size) * size;
-
-        // If our sell side loses (we keep what we sold for)
-        let sell_exit_value = Decimal::ZERO; // We lose the sell side
+        let if_yes = Self::arbitrage_profit_on_resolution(
+            buy_platform,
+            buy_price,
+            sell_platform,
+            sell_price,
+            size,
+            true,
+        );
+        let if_no = Self::arbitrage_profit_on_resolution(
+            buy_platform,
+            buy_price,
+            sell_platform,
+            sell_price,
+            size,
+            false,
+        );
+        if_yes.min(if_no)
+    }
 
-        // Net profit = what we get from winning side - costs on both sides
-        buy_exit_value + sell_proceeds - buy_cost - sell_exit_value
+    /// Net arbitrage profit given which leg resolves in the money
+    fn arbitrage_profit_on_resolution(
+        buy_platform: Platform,
+        buy_price: Decimal,
+        sell_platform: Platform,
+        sell_price: Decimal,
+        size: Decimal,
+        buy_leg_wins: bool,
+    ) -> Decimal {
+        let buy_fees = PlatformFees::for_platform(buy_platform);
+        let sell_fees = PlatformFees::for_platform(sell_platform);
+
+        let gross = (sell_price - buy_price) * size;
+
+        // Entry fees on both legs; zero for profit-based platforms
+        let buy_entry = Self::entry_cost(buy_platform, buy_price, Side::Buy, size);
+        let sell_entry = Self::entry_cost(sell_platform, sell_price, Side::Sell, size);
+        let entry_fees = (buy_entry - buy_price * size) + (sell_price * size - sell_entry);
+
+        // Only the winning leg realizes a profit that profit-based fees apply to
+        let (winning_fees, winning_profit) = if buy_leg_wins {
+            (buy_fees, no_price(buy_price) * size)
+        } else {
+            (sell_fees, sell_price * size)
+        };
+        let resolution_fee = if winning_fees.profit_based {
+            winning_profit * winning_fees.taker_fee_percent / dec!(100.0)
+        } else {
+            Decimal::ZERO
+        };
+
+        gross - entry_fees - resolution_fee
     }
 }
 
@@ -219,8 +366,24 @@ mod tests {
     #[test]
     fn test_kalshi_fees() {
         let fees = PlatformFees::kalshi();
-        assert_eq!(fees.taker_fee_percent, dec!(7.0));
-        assert!(fees.profit_based);
+        assert_eq!(fees.trading_fee_rate, dec!(0.07));
+        assert!(!fees.profit_based);
+
+        let legacy = PlatformFees::kalshi_profit_based();
+        assert_eq!(legacy.taker_fee_percent, dec!(7.0));
+        assert!(legacy.profit_based);
+    }
+
+    #[test]
+    fn test_kalshi_trading_fee_schedule() {
+        // Examples from Kalshi's fee schedule
+        assert_eq!(kalshi_trading_fee(dec!(0.50), dec!(100)), dec!(1.75));
+        assert_eq!(kalshi_trading_fee(dec!(0.50), dec!(1)), dec!(0.02));
+        assert_eq!(kalshi_trading_fee(dec!(0.10), dec!(100)), dec!(0.63));
+        assert_eq!(kalshi_trading_fee(dec!(0.90), dec!(100)), dec!(0.63));
+        assert_eq!(kalshi_trading_fee(dec!(0.01), dec!(1)), dec!(0.01));
+        // Exact cents aren't rounded further
+        assert_eq!(kalshi_trading_fee(dec!(0.40), dec!(250)), dec!(4.20));
     }
 
     #[test]
@@ -233,29 +396,44 @@ mod tests {
     #[test]
     fn test_kalshi_entry_cost() {
         // Buy at 0.40, size 100
+        // Fee = ceil(0.07 * 100 * 0.40 * 0.60) = ceil(1.68) = 1.68
         let cost = FeeCalculator::entry_cost(Platform::Kalshi, dec!(0.40), Side::Buy, dec!(100.0));
-        // Should be 40.0 (no entry fee for Kalshi)
-        assert_eq!(cost, dec!(40.0));
+        assert_eq!(cost, dec!(41.68));
+
+        // Selling pays the same fee out of the proceeds
+        let proceeds =
+            FeeCalculator::entry_cost(Platform::Kalshi, dec!(0.40), Side::Sell, dec!(100.0));
+        assert_eq!(proceeds, dec!(38.32));
     }
 
     #[test]
     fn test_kalshi_exit_value() {
-        // Bought at 0.40, size 100
-        // Profit per contract = 1.0 - 0.40 = 0.60
-        // Fee per contract = 0.60 * 0.07 = 0.042
-        // Exit value per contract = 1.0 - 0.042 = 0.958
+        // No fee at settlement
         let exit = FeeCalculator::exit_value(Platform::Kalshi, dec!(0.40), Side::Buy, dec!(100.0));
-        assert_eq!(exit, dec!(0.958));
+        assert_eq!(exit, dec!(1.0));
     }
 
     #[test]
     fn test_kalshi_net_profit() {
         // Buy at 0.40, size 100
-        // Entry cost = 40.0
-        // Exit value = 0.958 * 100 = 95.8
-        // Net profit = 95.8 - 40.0 = 55.8
+        // Entry cost = 41.68, exit value = 100
         let profit = FeeCalculator::net_profit(Platform::Kalshi, dec!(0.40), Side::Buy, dec!(100.0));
-        assert_eq!(profit, dec!(55.8));
+        assert_eq!(profit, dec!(58.32));
+    }
+
+    #[test]
+    fn test_kalshi_profit_based_legacy() {
+        let fees = PlatformFees::kalshi_profit_based();
+
+        // No entry fee
+        let cost = FeeCalculator::entry_cost_with_fees(&fees, dec!(0.40), Side::Buy, dec!(100.0));
+        assert_eq!(cost, dec!(40.0));
+
+        // Profit per contract = 1.0 - 0.40 = 0.60
+        // Fee per contract = 0.60 * 0.07 = 0.042
+        // Exit value per contract = 1.0 - 0.042 = 0.958
+        let exit = FeeCalculator::exit_value_with_fees(&fees, dec!(0.40), Side::Buy, dec!(100.0));
+        assert_eq!(exit, dec!(0.958));
     }
 
     #[test]
@@ -272,8 +450,41 @@ mod tests {
     }
 
     #[test]
-    fn test_arbitrage_profit() {
-        // Buy Kalshi at 0.45, Sell Polymarket at 0.52, size 100
+    fn test_min_edge_to_profit() {
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(
+                FeeCalculator::min_edge_to_profit(Platform::Polymarket, dec!(0.50), side),
+                dec!(0)
+            );
+        }
+
+        // 0.07 * 0.50 * 0.50 = 0.0175 to enter, slightly less to exit at ~0.535
+        let at_half = FeeCalculator::min_edge_to_profit(Platform::Kalshi, dec!(0.50), Side::Buy);
+        assert!(at_half > dec!(0.0349) && at_half < dec!(0.0350));
+
+        // Fees shrink towards the extremes
+        let at_tenth = FeeCalculator::min_edge_to_profit(Platform::Kalshi, dec!(0.10), Side::Buy);
+        assert!(at_tenth > dec!(0) && at_tenth < at_half);
+
+        // Buying at p mirrors selling at 1 - p
+        assert_eq!(
+            FeeCalculator::min_edge_to_profit(Platform::Kalshi, dec!(0.90), Side::Sell),
+            at_tenth
+        );
+
+        // The move covers both fees on a large round trip, up to cent rounding
+        let size = dec!(10000);
+        let cost = FeeCalculator::entry_cost(Platform::Kalshi, dec!(0.50), Side::Buy, size);
+        let proceeds =
+            FeeCalculator::entry_cost(Platform::Kalshi, dec!(0.50) + at_half, Side::Sell, size);
+        assert!(proceeds - cost > dec!(-0.02));
+    }
+
+    #[test]
+    fn test_arbitrage_profit_kalshi_buy_polymarket_sell() {
+        // Buy YES on Kalshi at 0.45, sell YES on Polymarket at 0.52, size 100
+        // Gross either way: (0.52 - 0.45) * 100 = 7.0
+        // Kalshi fee: ceil(0.07 * 100 * 0.45 * 0.55) = ceil(1.7325) = 1.74
         let profit = FeeCalculator::arbitrage_profit(
             Platform::Kalshi,
             dec!(0.45),
@@ -281,19 +492,46 @@ mod tests {
             dec!(0.52),
             dec!(100.0),
         );
+        assert_eq!(profit, dec!(5.26));
+    }
 
-        // Buy Kalshi: cost = 45.0, exit = 0.9615 * 100 = 96.15
-        // Sell Polymarket: proceeds = 52.0, exit = 0
-        // Profit = 96.15 + 52.0 - 45.0 - 0 = 103.15
-        // Wait, this doesn't look right. Let me recalculate...
+    #[test]
+    fn test_arbitrage_profit_polymarket_buy_kalshi_sell() {
+        // Buy YES on Polymarket at 0.45, sell YES on Kalshi at 0.52, size 100
+        // Kalshi fee: ceil(0.07 * 100 * 0.52 * 0.48) = ceil(1.7472) = 1.75
+        let profit = FeeCalculator::arbitrage_profit(
+            Platform::Polymarket,
+            dec!(0.45),
+            Platform::Kalshi,
+            dec!(0.52),
+            dec!(100.0),
+        );
+        assert_eq!(profit, dec!(5.25));
+    }
 
-        // Actually for arbitrage:
-        // We buy at 0.45 and sell at 0.52
-        // If YES wins: we make 1.00 on buy side, lose 1.00 on sell side, net = sell_price - buy_price
-        // If NO wins: we lose buy, keep sell, net = sell_price - buy_price
-        // Either way, before fees: 0.52 - 0.45 = 0.07 per contract
+    #[test]
+    fn test_arbitrage_profit_no_fees() {
+        let profit = FeeCalculator::arbitrage_profit(
+            Platform::Polymarket,
+            dec!(0.45),
+            Platform::Polymarket,
+            dec!(0.52),
+            dec!(100.0),
+        );
+        assert_eq!(profit, dec!(7.0));
+    }
 
-        // But we need to reconsider the calculation...
-        println!("Arbitrage profit: {}", profit);
+    #[test]
+    fn test_arbitrage_profit_negative_spread() {
+        // Selling below the buy price loses money in both outcomes
+        let profit = FeeCalculator::arbitrage_profit(
+            Platform::Polymarket,
+            dec!(0.52),
+            Platform::Kalshi,
+            dec!(0.45),
+            dec!(100.0),
+        );
+        // -7.0 - ceil(0.07 * 100 * 0.45 * 0.55) = -8.74
+        assert_eq!(profit, dec!(-8.74));
     }
 }
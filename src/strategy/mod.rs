@@ -35,7 +35,24 @@
 //! - [`Decision`]: Go/NoGo enum returned by strategies
 //! - [`TradeIntent`]: Contains one or more [`TradeLeg`]s to execute
 //! - [`SizeCalculator`]: Pre-computes trade sizes asynchronously
+//! - [`LiveSizeCalculator`]: Background size calculator fed by market events
 //! - [`StrategyContext`]: Read-only state provided to strategies
+//! - [`Trader`]: Feeds market events to strategies and sizes their decisions
+//! - [`IntentSink`]: Executes the Trader's sized intents; [`PaperExecutionSink`] simulates fills
+//! - [`OrderBookSimulator`]: Rests paper orders and fills or expires them from market data
+//! - [`StrategyRunner`]: Drives a strategy and suppresses `Go` until warmup completes
+//! - [`reconcile`]: Compares tracked positions against the exchange
+//! - [`DecisionRecorder`]: Logs decisions and their outcomes as JSON Lines
+//! - [`resolution::settle`]: Net payout of a position at market resolution
+//! - [`Backtester`]: Replays recorded market events through a strategy offline
+//! - [`PositionTracker`]: Maintains positions and realized PnL from fills
+//! - [`RiskLimits`]: Position and notional limits checked before execution
+//! - [`PairMap`]: Which Kalshi ticker matches which Polymarket market, loadable from a file
+//! - [`arbitrage::detect_arb`]: Finds a cross-platform arbitrage between matched books
+//! - [`fair_value::FairValue`]: Blends book, trade and cross-platform prices into one estimate
+//! - [`wrappers::Hysteresis`]: Debounces a strategy that flips direction on price jitter
+//! - [`composite::CompositeStrategy`]: Runs several strategies as one and merges their decisions
+//! - [`monitors::SpreadMonitor`]: Reports changes in the cross-platform edge of each pair, without trading
 //!
 //! # Example
 //!
@@ -74,10 +91,27 @@ mod types;
 mod traits;
 mod size_calculator;
 mod fees;
+mod runner;
+pub mod engine;
+pub mod execution;
+mod reconciliation;
+mod recorder;
+pub mod resolution;
+mod backtest;
+pub mod arbitrage;
+pub mod fair_value;
+pub mod wrappers;
+pub mod composite;
+pub mod pairs;
+pub mod monitors;
+mod pnl;
+mod risk;
+mod simulator;
 
 pub use types::{
     Decision,
     MarketSubscription,
+    OrderType,
     Platform,
     Position,
     Side,
@@ -86,16 +120,44 @@ pub use types::{
     TradeLeg,
 };
 
+pub use pairs::PairMap;
+
 pub use traits::{BoxedStrategy, Strategy};
 
 pub use size_calculator::{
     BoxedSizeCalculator,
     ComputedSize,
     InMemorySizeCalculator,
+    LiveSizeCalculator,
+    MarketConstraints,
     SizeCalculator,
     SizeKey,
     SizedIntent,
     SizedLeg,
 };
 
-pub use fees::{FeeCalculator, PlatformFees};
+pub use fees::{kalshi_trading_fee, FeeCalculator, PlatformFees, KALSHI_TRADING_FEE_RATE};
+
+pub use engine::{AtomicityPolicy, Trader};
+
+pub use execution::{
+    BoxedIntentSink, ExecutionReport, IntentSink, LegExecution, LoggingSink, PaperExecutionSink,
+    RestExecutionSink,
+};
+
+pub use runner::StrategyRunner;
+
+pub use reconciliation::{
+    into_strategy_context, polymarket_positions, reconcile, PositionDiscrepancy,
+    ReconciliationReport,
+};
+
+pub use recorder::{DecisionOutcome, DecisionRecord, DecisionRecorder, RecordedLeg};
+
+pub use backtest::{BacktestFill, BacktestIntent, BacktestReport, Backtester};
+
+pub use pnl::PositionTracker;
+
+pub use risk::{RiskLimits, RiskViolation};
+
+pub use simulator::OrderBookSimulator;
@@ -0,0 +1,329 @@
+//! Event handlers that watch the feed without trading
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::common::book_store::BookStore;
+use crate::common::traits::EventHandler;
+use crate::common::types::{MarketEvent, OrderBook};
+use crate::strategy::arbitrage::{detect_arb, NoAssets};
+use crate::strategy::pairs::PairMap;
+use crate::strategy::types::{Platform, TradeIntent};
+
+/// Cross-platform edge of a pair, as reported by [`SpreadMonitor`]
+#[derive(Debug, Clone)]
+pub struct CrossSpread {
+    /// `(kalshi_ticker, polymarket_id)` as listed in the [`PairMap`]
+    pub pair: (String, String),
+    /// Net profit per contract after fees of the better direction; negative
+    /// when neither direction pays
+    pub edge: Decimal,
+    /// The trade that would capture the edge
+    pub intent: TradeIntent,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Watches both legs of every pair and reports changes in their edge
+///
+/// Books are kept in a [`BookStore`] and the edge comes from
+/// [`detect_arb`]. A [`CrossSpread`] is sent the first time both legs of a
+/// pair have a two-sided book, and after that whenever the edge moves by
+/// more than the threshold from the last one sent. Sends don't wait: if the
+/// channel is full the spread is dropped with a warning.
+///
+/// The Polymarket leg is the book whose asset ID is the paired ID. When a
+/// pair lists a condition ID instead, the first token of the condition's
+/// [`MarketInfo`](crate::common::types::MarketInfo) is taken as the YES
+/// token. The second token is the NO token bought against the Polymarket
+/// bid, so that direction is only evaluated once market info for the pair
+/// has been seen. Buying NO on Kalshi needs its ID from
+/// [`with_kalshi_no`](Self::with_kalshi_no).
+pub struct SpreadMonitor {
+    pairs: PairMap,
+    books: BookStore,
+    /// (YES, NO) tokens by paired Polymarket ID, from market info
+    tokens: HashMap<String, (String, String)>,
+    /// NO contract ID by Kalshi ticker
+    kalshi_no: HashMap<String, String>,
+    threshold: Decimal,
+    /// Last edge sent, by Kalshi ticker
+    last_edge: HashMap<String, Decimal>,
+    sender: mpsc::Sender<CrossSpread>,
+}
+
+impl SpreadMonitor {
+    /// Report every change in edge for `pairs` on `sender`
+    pub fn new(pairs: PairMap, sender: mpsc::Sender<CrossSpread>) -> Self {
+        Self {
+            pairs,
+            books: BookStore::new(),
+            tokens: HashMap::new(),
+            kalshi_no: HashMap::new(),
+            threshold: Decimal::ZERO,
+            last_edge: HashMap::new(),
+            sender,
+        }
+    }
+
+    /// Only report edge changes larger than `threshold` per contract
+    pub fn with_threshold(mut self, threshold: Decimal) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// ID to buy the NO side of `kalshi_ticker` with
+    pub fn with_kalshi_no(
+        mut self,
+        kalshi_ticker: impl Into<String>,
+        no_asset: impl Into<String>,
+    ) -> Self {
+        self.kalshi_no.insert(kalshi_ticker.into(), no_asset.into());
+        self
+    }
+
+    /// Books seen so far
+    pub fn books(&self) -> &BookStore {
+        &self.books
+    }
+
+    /// Kalshi ticker of the pair a book event belongs to
+    fn pair_for(&self, event: &MarketEvent) -> Option<String> {
+        let (platform, market_id, asset_id) = match event {
+            MarketEvent::OrderBook(book) => (book.platform, &book.market_id, &book.asset_id),
+            MarketEvent::OrderBookUpdate(update) => {
+                (update.platform, &update.market_id, &update.asset_id)
+            }
+            _ => return None,
+        };
+        let kalshi_ticker = match platform {
+            Platform::Kalshi => self.pairs.get_poly(asset_id).map(|_| asset_id.as_str()),
+            Platform::Polymarket => self
+                .pairs
+                .get_kalshi(asset_id)
+                .or_else(|| self.pairs.get_kalshi(market_id)),
+        };
+        kalshi_ticker.map(str::to_string)
+    }
+
+    /// Book of the Polymarket leg paired with `polymarket_id`
+    fn polymarket_book(&self, polymarket_id: &str) -> Option<&OrderBook> {
+        self.books.get(polymarket_id).or_else(|| {
+            self.tokens
+                .get(polymarket_id)
+                .and_then(|(yes, _)| self.books.get(yes))
+        })
+    }
+
+    /// Recompute the edge of a pair and send it if it moved enough
+    fn check(&mut self, kalshi_ticker: &str) {
+        let Some(polymarket_id) = self.pairs.get_poly(kalshi_ticker) else {
+            return;
+        };
+        let (Some(kalshi_book), Some(polymarket_book)) = (
+            self.books.get(kalshi_ticker),
+            self.polymarket_book(polymarket_id),
+        ) else {
+            return;
+        };
+        let no_assets = NoAssets {
+            kalshi: self.kalshi_no.get(kalshi_ticker).cloned(),
+            polymarket: self.tokens.get(polymarket_id).map(|(_, no)| no.clone()),
+        };
+        // With no minimum this finds the better direction whatever it pays
+        let Some(intent) = detect_arb(kalshi_book, polymarket_book, &no_assets, Decimal::MIN)
+        else {
+            return;
+        };
+        let Some(edge) = intent.score else {
+            return;
+        };
+
+        if let Some(last) = self.last_edge.get(kalshi_ticker) {
+            if (edge - *last).abs() <= self.threshold {
+                return;
+            }
+        }
+        self.last_edge.insert(kalshi_ticker.to_string(), edge);
+
+        let spread = CrossSpread {
+            pair: (kalshi_ticker.to_string(), polymarket_id.to_string()),
+            edge,
+            intent,
+            detected_at: Utc::now(),
+        };
+        debug!(
+            "Edge for {}/{} is now {}",
+            spread.pair.0, spread.pair.1, spread.edge
+        );
+        if let Err(e) = self.sender.try_send(spread) {
+            warn!("Dropping cross spread for {}: {}", kalshi_ticker, e);
+        }
+    }
+}
+
+impl EventHandler for SpreadMonitor {
+    fn handle_event(&mut self, event: &MarketEvent) {
+        if let MarketEvent::MarketInfo(info) = event {
+            if let (Platform::Polymarket, [yes, no, ..]) =
+                (info.platform, info.token_ids.as_slice())
+            {
+                // Pairs list either the condition or its YES token
+                for paired_id in [&info.market_id, yes] {
+                    if self.pairs.is_paired(Platform::Polymarket, paired_id) {
+                        self.tokens
+                            .insert(paired_id.clone(), (yes.clone(), no.clone()));
+                    }
+                }
+            }
+            return;
+        }
+
+        self.books.apply(event);
+        if let Some(kalshi_ticker) = self.pair_for(event) {
+            self.check(&kalshi_ticker);
+        }
+    }
+
+    fn on_connect(&mut self) {}
+
+    /// Forget the edges sent so each pair is reported afresh once its
+    /// books come back
+    fn on_disconnect(&mut self, _reason: Option<&str>) {
+        self.last_edge.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::PriceLevel;
+    use crate::strategy::types::Side;
+    use rust_decimal_macros::dec;
+
+    fn book(platform: Platform, asset_id: &str, bid: Decimal, ask: Decimal) -> MarketEvent {
+        MarketEvent::OrderBook(OrderBook {
+            platform,
+            market_id: "0xcondition".to_string(),
+            asset_id: asset_id.to_string(),
+            bids: vec![PriceLevel::new(bid, dec!(100))],
+            asks: vec![PriceLevel::new(ask, dec!(100))],
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    fn market_info() -> MarketEvent {
+        MarketEvent::MarketInfo(crate::common::types::MarketInfo {
+            platform: Platform::Polymarket,
+            market_id: "0xcondition".to_string(),
+            title: "BTC above 100k".to_string(),
+            description: String::new(),
+            token_ids: vec!["yes_token".to_string(), "no_token".to_string()],
+            is_active: true,
+            end_date: None,
+            tick_size: None,
+            neg_risk: false,
+        })
+    }
+
+    #[test]
+    fn test_reports_edge_when_books_cross() {
+        let pairs = PairMap::from_pairs([("KXBTC", "yes_token")]).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut monitor = SpreadMonitor::new(pairs, tx).with_threshold(dec!(0.01));
+        monitor.handle_event(&market_info());
+
+        // One leg alone has no edge
+        monitor.handle_event(&book(Platform::Kalshi, "KXBTC", dec!(0.38), dec!(0.40)));
+        assert!(rx.try_recv().is_err());
+
+        // Books agree: the best direction loses money
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "yes_token",
+            dec!(0.39),
+            dec!(0.41),
+        ));
+        let spread = rx.try_recv().unwrap();
+        assert_eq!(spread.pair, ("KXBTC".to_string(), "yes_token".to_string()));
+        assert!(spread.edge < Decimal::ZERO);
+
+        // A move within the threshold isn't reported
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "yes_token",
+            dec!(0.395),
+            dec!(0.41),
+        ));
+        assert!(rx.try_recv().is_err());
+
+        // Polymarket bids 0.50 against a 0.40 Kalshi ask
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "yes_token",
+            dec!(0.50),
+            dec!(0.52),
+        ));
+        let spread = rx.try_recv().unwrap();
+        assert_eq!(spread.edge, dec!(0.0832));
+        assert_eq!(spread.intent.legs[0].platform, Platform::Kalshi);
+        assert_eq!(spread.intent.legs[1].market_id, "no_token");
+        assert_eq!(spread.intent.legs[1].side, Side::Buy);
+
+        // Unpaired markets are ignored
+        monitor.handle_event(&book(Platform::Kalshi, "KXETH", dec!(0.10), dec!(0.12)));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(monitor.books().len(), 3);
+    }
+
+    #[test]
+    fn test_condition_pair_uses_yes_token() {
+        let pairs = PairMap::from_pairs([("KXBTC", "0xcondition")]).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut monitor = SpreadMonitor::new(pairs, tx);
+
+        monitor.handle_event(&market_info());
+        monitor.handle_event(&book(Platform::Kalshi, "KXBTC", dec!(0.38), dec!(0.40)));
+        // The NO token's book is not a leg
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "no_token",
+            dec!(0.48),
+            dec!(0.50),
+        ));
+        assert!(rx.try_recv().is_err());
+
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "yes_token",
+            dec!(0.50),
+            dec!(0.52),
+        ));
+        assert_eq!(rx.try_recv().unwrap().edge, dec!(0.0832));
+    }
+
+    #[test]
+    fn test_kalshi_no_enables_reverse_direction() {
+        let pairs = PairMap::from_pairs([("KXBTC", "yes_token")]).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut monitor = SpreadMonitor::new(pairs, tx).with_kalshi_no("KXBTC", "KXBTC-NO");
+
+        // Polymarket asks 0.45 against a 0.55 Kalshi bid
+        monitor.handle_event(&book(Platform::Kalshi, "KXBTC", dec!(0.55), dec!(0.57)));
+        monitor.handle_event(&book(
+            Platform::Polymarket,
+            "yes_token",
+            dec!(0.43),
+            dec!(0.45),
+        ));
+        let legs = rx.try_recv().unwrap().intent.legs;
+        assert_eq!(legs[0].market_id, "yes_token");
+        assert_eq!(legs[1].market_id, "KXBTC-NO");
+        assert_eq!(legs[1].side, Side::Buy);
+    }
+}
@@ -0,0 +1,276 @@
+//! Cross-platform market pairs
+//!
+//! Pairs are listed explicitly in a mapping file, in TOML:
+//!
+//! ```toml
+//! [[pairs]]
+//! kalshi = "KXBTC-25DEC31-B100000"
+//! polymarket = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1"
+//! ```
+//!
+//! or the same shape in JSON: `{"pairs": [{"kalshi": "...", "polymarket": "..."}]}`.
+
+use config::{Config, File, FileFormat};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::common::errors::{ClientError, Result};
+use crate::strategy::types::{MarketSubscription, Platform};
+
+/// One entry of a mapping file
+#[derive(Debug, Clone, Deserialize)]
+struct PairEntry {
+    kalshi: String,
+    polymarket: String,
+}
+
+/// Layout of a mapping file
+#[derive(Debug, Clone, Deserialize)]
+struct PairFile {
+    #[serde(default)]
+    pairs: Vec<PairEntry>,
+}
+
+/// Cross-platform market pairs: Kalshi tickers to Polymarket condition IDs
+///
+/// Each market is in at most one pair. Routes events for
+/// [`MarketSubscription::AllMatchedPairs`] in the
+/// [`Trader`](crate::strategy::engine::Trader), and tells strategies which
+/// books to hand to [`detect_arb`](crate::strategy::arbitrage::detect_arb).
+#[derive(Debug, Clone, Default)]
+pub struct PairMap {
+    kalshi_to_polymarket: HashMap<String, String>,
+    polymarket_to_kalshi: HashMap<String, String>,
+}
+
+impl PairMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from pairs, rejecting a market that appears in two of them
+    pub fn from_pairs<K, P>(pairs: impl IntoIterator<Item = (K, P)>) -> Result<Self>
+    where
+        K: Into<String>,
+        P: Into<String>,
+    {
+        let mut map = Self::new();
+        for (kalshi_ticker, polymarket_condition_id) in pairs {
+            map.try_insert(kalshi_ticker, polymarket_condition_id)?;
+        }
+        Ok(map)
+    }
+
+    /// Load pairs from a TOML mapping file
+    ///
+    /// Returns `ClientError::Configuration` if the file can't be read or
+    /// parsed, or maps a market twice.
+    pub fn from_toml(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file: PairFile = Config::builder()
+            .add_source(File::from(path).format(FileFormat::Toml))
+            .build()
+            .and_then(Config::try_deserialize)
+            .map_err(|e| ClientError::Configuration(format!("{}: {}", path.display(), e)))?;
+        Self::from_file(file)
+    }
+
+    /// Load pairs from a JSON mapping file
+    ///
+    /// Returns `ClientError::Configuration` if the file can't be read or
+    /// parsed, or maps a market twice.
+    pub fn from_json(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let configuration = |e: &dyn std::fmt::Display| {
+            ClientError::Configuration(format!("{}: {}", path.display(), e))
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| configuration(&e))?;
+        let file: PairFile = serde_json::from_str(&text).map_err(|e| configuration(&e))?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: PairFile) -> Result<Self> {
+        Self::from_pairs(
+            file.pairs
+                .into_iter()
+                .map(|entry| (entry.kalshi, entry.polymarket)),
+        )
+    }
+
+    /// Add a pair, replacing any existing pair for either market
+    pub fn insert(
+        &mut self,
+        kalshi_ticker: impl Into<String>,
+        polymarket_condition_id: impl Into<String>,
+    ) {
+        let kalshi_ticker = kalshi_ticker.into();
+        let polymarket_condition_id = polymarket_condition_id.into();
+        if let Some(old) = self
+            .kalshi_to_polymarket
+            .insert(kalshi_ticker.clone(), polymarket_condition_id.clone())
+        {
+            self.polymarket_to_kalshi.remove(&old);
+        }
+        if let Some(old) = self
+            .polymarket_to_kalshi
+            .insert(polymarket_condition_id, kalshi_ticker)
+        {
+            self.kalshi_to_polymarket.remove(&old);
+        }
+    }
+
+    /// Add a pair, failing if either market is already paired
+    ///
+    /// Re-adding an existing pair is allowed. Returns
+    /// `ClientError::Configuration` naming the conflicting pair.
+    pub fn try_insert(
+        &mut self,
+        kalshi_ticker: impl Into<String>,
+        polymarket_condition_id: impl Into<String>,
+    ) -> Result<()> {
+        let kalshi_ticker = kalshi_ticker.into();
+        let polymarket_condition_id = polymarket_condition_id.into();
+        if let Some(existing) = self.get_poly(&kalshi_ticker) {
+            if existing != polymarket_condition_id {
+                return Err(ClientError::Configuration(format!(
+                    "Kalshi market {} is paired with both {} and {}",
+                    kalshi_ticker, existing, polymarket_condition_id
+                )));
+            }
+        }
+        if let Some(existing) = self.get_kalshi(&polymarket_condition_id) {
+            if existing != kalshi_ticker {
+                return Err(ClientError::Configuration(format!(
+                    "Polymarket market {} is paired with both {} and {}",
+                    polymarket_condition_id, existing, kalshi_ticker
+                )));
+            }
+        }
+        self.insert(kalshi_ticker, polymarket_condition_id);
+        Ok(())
+    }
+
+    /// Polymarket condition ID paired with a Kalshi ticker
+    pub fn get_poly(&self, kalshi_ticker: &str) -> Option<&str> {
+        self.kalshi_to_polymarket
+            .get(kalshi_ticker)
+            .map(String::as_str)
+    }
+
+    /// Kalshi ticker paired with a Polymarket condition ID
+    pub fn get_kalshi(&self, polymarket_condition_id: &str) -> Option<&str> {
+        self.polymarket_to_kalshi
+            .get(polymarket_condition_id)
+            .map(String::as_str)
+    }
+
+    /// Every pair as `(kalshi_ticker, polymarket_condition_id)`, by ticker
+    pub fn all_pairs(&self) -> Vec<(&str, &str)> {
+        let mut pairs: Vec<(&str, &str)> = self
+            .kalshi_to_polymarket
+            .iter()
+            .map(|(kalshi, polymarket)| (kalshi.as_str(), polymarket.as_str()))
+            .collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// A [`MarketSubscription::MatchedPair`] for every pair
+    pub fn subscriptions(&self) -> Vec<MarketSubscription> {
+        self.all_pairs()
+            .into_iter()
+            .map(|(kalshi, polymarket)| MarketSubscription::MatchedPair {
+                kalshi_market_id: kalshi.to_string(),
+                polymarket_market_id: polymarket.to_string(),
+            })
+            .collect()
+    }
+
+    /// Whether `market_id` on `platform` is part of a pair
+    pub fn is_paired(&self, platform: Platform, market_id: &str) -> bool {
+        match platform {
+            Platform::Kalshi => self.kalshi_to_polymarket.contains_key(market_id),
+            Platform::Polymarket => self.polymarket_to_kalshi.contains_key(market_id),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.kalshi_to_polymarket.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kalshi_to_polymarket.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempPath;
+
+    /// Write `contents` to a fresh temp file with the given extension
+    fn mapping_file(extension: &str, contents: &str) -> TempPath {
+        let path = tempfile::Builder::new()
+            .suffix(&format!(".{}", extension))
+            .tempfile()
+            .unwrap()
+            .into_temp_path();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_and_look_up_both_ways() {
+        let toml = mapping_file(
+            "toml",
+            r#"
+[[pairs]]
+kalshi = "KXETH"
+polymarket = "0xeth"
+
+[[pairs]]
+kalshi = "KXBTC"
+polymarket = "0xbtc"
+"#,
+        );
+        let json = mapping_file(
+            "json",
+            r#"{"pairs": [{"kalshi": "KXETH", "polymarket": "0xeth"}, {"kalshi": "KXBTC", "polymarket": "0xbtc"}]}"#,
+        );
+
+        for pairs in [PairMap::from_toml(&toml), PairMap::from_json(&json)] {
+            let pairs = pairs.unwrap();
+            assert_eq!(pairs.len(), 2);
+            assert_eq!(pairs.get_poly("KXBTC"), Some("0xbtc"));
+            assert_eq!(pairs.get_kalshi("0xeth"), Some("KXETH"));
+            assert_eq!(pairs.get_poly("0xbtc"), None);
+            assert_eq!(
+                pairs.all_pairs(),
+                vec![("KXBTC", "0xbtc"), ("KXETH", "0xeth")]
+            );
+            assert!(pairs.is_paired(Platform::Polymarket, "0xbtc"));
+            assert_eq!(pairs.subscriptions().len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_mappings_rejected() {
+        let reused_ticker = mapping_file(
+            "json",
+            r#"{"pairs": [{"kalshi": "KXBTC", "polymarket": "0xbtc"}, {"kalshi": "KXBTC", "polymarket": "0xother"}]}"#,
+        );
+        let err = PairMap::from_json(&reused_ticker).unwrap_err();
+        assert!(matches!(err, ClientError::Configuration(_)));
+        assert!(err.to_string().contains("KXBTC"));
+
+        let reused_condition = PairMap::from_pairs([("KXBTC", "0xbtc"), ("KXBTC2", "0xbtc")]);
+        assert!(reused_condition.unwrap_err().to_string().contains("0xbtc"));
+
+        // Listing the same pair twice is harmless
+        let repeated = PairMap::from_pairs([("KXBTC", "0xbtc"), ("KXBTC", "0xbtc")]).unwrap();
+        assert_eq!(repeated.len(), 1);
+
+        assert!(PairMap::from_toml("/nonexistent/pairs.toml").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::strategy::types::{Platform, Position, Side, StrategyContext};
+
+/// Tracks positions and realized PnL from fills
+///
+/// Positions are keyed by (platform, market_id) like
+/// [`StrategyContext::positions`] and updated with [`Position::apply_fill`].
+/// Realized PnL excludes fees.
+#[derive(Debug, Clone, Default)]
+pub struct PositionTracker {
+    positions: HashMap<(Platform, String), Position>,
+    realized_pnl: Decimal,
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the positions already held in `ctx`
+    pub fn from_context(ctx: &StrategyContext) -> Self {
+        Self {
+            positions: ctx.positions.clone(),
+            realized_pnl: Decimal::ZERO,
+        }
+    }
+
+    /// Record a fill, returning the PnL it realized
+    pub fn apply_fill(
+        &mut self,
+        platform: Platform,
+        market_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Decimal {
+        let realized = self
+            .positions
+            .entry((platform, market_id.to_string()))
+            .or_insert_with(|| Position::new(platform, market_id))
+            .apply_fill(side, price, size);
+        self.realized_pnl += realized;
+        realized
+    }
+
+    /// Replace the tracked position for its market (e.g. after reconciliation)
+    pub fn set_position(&mut self, position: Position) {
+        self.positions
+            .insert((position.platform, position.market_id.clone()), position);
+    }
+
+    /// Get the position for a market
+    pub fn position(&self, platform: Platform, market_id: &str) -> Option<&Position> {
+        self.positions.get(&(platform, market_id.to_string()))
+    }
+
+    /// All tracked positions, including flat ones
+    pub fn positions(&self) -> &HashMap<(Platform, String), Position> {
+        &self.positions
+    }
+
+    /// PnL realized by reducing or closing positions
+    pub fn realized_pnl(&self) -> Decimal {
+        self.realized_pnl
+    }
+
+    /// Mark-to-market PnL of open positions
+    ///
+    /// Positions without a mark price are left out.
+    pub fn unrealized_pnl(&self, mark_prices: &HashMap<(Platform, String), Decimal>) -> Decimal {
+        self.positions
+            .iter()
+            .filter_map(|(key, position)| {
+                mark_prices
+                    .get(key)
+                    .map(|mark| position.unrealized_pnl(*mark))
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const MARKET: &str = "token";
+
+    fn fill(tracker: &mut PositionTracker, side: Side, price: Decimal, size: Decimal) -> Decimal {
+        tracker.apply_fill(Platform::Polymarket, MARKET, side, price, size)
+    }
+
+    fn position(tracker: &PositionTracker) -> (Decimal, Decimal) {
+        let position = tracker.position(Platform::Polymarket, MARKET).unwrap();
+        (position.size, position.avg_entry_price)
+    }
+
+    #[test]
+    fn test_long_add_averages_entry() {
+        let mut tracker = PositionTracker::new();
+        assert_eq!(
+            fill(&mut tracker, Side::Buy, dec!(0.40), dec!(100)),
+            dec!(0)
+        );
+        assert_eq!(
+            fill(&mut tracker, Side::Buy, dec!(0.50), dec!(100)),
+            dec!(0)
+        );
+        assert_eq!(position(&tracker), (dec!(200), dec!(0.45)));
+        assert_eq!(tracker.realized_pnl(), dec!(0));
+    }
+
+    #[test]
+    fn test_partial_close_realizes_pnl() {
+        let mut tracker = PositionTracker::new();
+        fill(&mut tracker, Side::Buy, dec!(0.40), dec!(100));
+
+        // Sell 60 at 0.55: 60 * 0.15 realized, entry price unchanged
+        assert_eq!(
+            fill(&mut tracker, Side::Sell, dec!(0.55), dec!(60)),
+            dec!(9)
+        );
+        assert_eq!(position(&tracker), (dec!(40), dec!(0.40)));
+
+        // Close the rest at a loss
+        assert_eq!(
+            fill(&mut tracker, Side::Sell, dec!(0.30), dec!(40)),
+            dec!(-4)
+        );
+        assert_eq!(position(&tracker), (dec!(0), dec!(0)));
+        assert_eq!(tracker.realized_pnl(), dec!(5));
+    }
+
+    #[test]
+    fn test_flip_long_to_short() {
+        let mut tracker = PositionTracker::new();
+        fill(&mut tracker, Side::Buy, dec!(0.40), dec!(50));
+
+        // Closes 50 at +0.10 and opens 30 short at 0.50
+        assert_eq!(
+            fill(&mut tracker, Side::Sell, dec!(0.50), dec!(80)),
+            dec!(5)
+        );
+        assert_eq!(position(&tracker), (dec!(-30), dec!(0.50)));
+
+        // Covering the short below entry is a gain
+        assert_eq!(
+            fill(&mut tracker, Side::Buy, dec!(0.45), dec!(10)),
+            dec!(0.5)
+        );
+        assert_eq!(position(&tracker), (dec!(-20), dec!(0.50)));
+        assert_eq!(tracker.realized_pnl(), dec!(5.5));
+    }
+
+    #[test]
+    fn test_unrealized_mark_to_market() {
+        let mut tracker = PositionTracker::new();
+        fill(&mut tracker, Side::Buy, dec!(0.40), dec!(100));
+        tracker.apply_fill(Platform::Kalshi, "KX", Side::Sell, dec!(0.60), dec!(50));
+        tracker.apply_fill(
+            Platform::Kalshi,
+            "unmarked",
+            Side::Buy,
+            dec!(0.10),
+            dec!(10),
+        );
+
+        let marks = HashMap::from([
+            ((Platform::Polymarket, MARKET.to_string()), dec!(0.45)),
+            ((Platform::Kalshi, "KX".to_string()), dec!(0.70)),
+        ]);
+        // Long +100 * 0.05, short -50 * 0.10
+        assert_eq!(tracker.unrealized_pnl(&marks), dec!(0));
+
+        let marks = HashMap::from([((Platform::Polymarket, MARKET.to_string()), dec!(0.52))]);
+        assert_eq!(tracker.unrealized_pnl(&marks), dec!(12));
+    }
+}
@@ -0,0 +1,226 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::common::errors::Result;
+use crate::polymarket::messages::PositionResponse;
+use crate::strategy::types::{Platform, Position, StrategyContext};
+
+/// A market where local and exchange positions disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionDiscrepancy {
+    pub platform: Platform,
+    pub market_id: String,
+    /// Size tracked locally (zero if unknown locally)
+    pub local_size: Decimal,
+    /// Size reported by the exchange (zero if absent remotely)
+    pub remote_size: Decimal,
+}
+
+impl PositionDiscrepancy {
+    /// Amount the local size must change by to match the exchange
+    pub fn difference(&self) -> Decimal {
+        self.remote_size - self.local_size
+    }
+}
+
+/// Result of comparing locally-tracked positions against the exchange
+#[derive(Debug, Clone)]
+pub struct ReconciliationReport {
+    /// Markets whose sizes differ, sorted by market ID
+    pub discrepancies: Vec<PositionDiscrepancy>,
+    /// Positions reported by the exchange
+    remote: Vec<Position>,
+}
+
+impl ReconciliationReport {
+    /// Returns true if local state matches the exchange
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// Overwrite the positions in `ctx` with the exchange's view
+    ///
+    /// Local positions missing from the exchange are dropped, so the remote
+    /// positions should cover every platform tracked in `ctx`.
+    pub fn apply_to(&self, ctx: &mut StrategyContext) {
+        ctx.positions = self
+            .remote
+            .iter()
+            .map(|p| ((p.platform, p.market_id.clone()), p.clone()))
+            .collect();
+    }
+}
+
+/// Compare local positions against positions reported by the exchange
+///
+/// Markets absent on one side are treated as flat there, so a local position
+/// the exchange doesn't know about (or vice versa) is reported.
+pub fn reconcile(local: &StrategyContext, remote_positions: &[Position]) -> ReconciliationReport {
+    let remote: HashMap<(Platform, String), &Position> = remote_positions
+        .iter()
+        .map(|p| ((p.platform, p.market_id.clone()), p))
+        .collect();
+
+    let mut discrepancies: Vec<PositionDiscrepancy> = Vec::new();
+    let mut check = |key: &(Platform, String)| {
+        let local_size = local.positions.get(key).map(|p| p.size).unwrap_or_default();
+        let remote_size = remote.get(key).map(|p| p.size).unwrap_or_default();
+        if local_size != remote_size {
+            discrepancies.push(PositionDiscrepancy {
+                platform: key.0,
+                market_id: key.1.clone(),
+                local_size,
+                remote_size,
+            });
+        }
+    };
+
+    for key in local.positions.keys() {
+        check(key);
+    }
+    for key in remote
+        .keys()
+        .filter(|key| !local.positions.contains_key(*key))
+    {
+        check(key);
+    }
+
+    discrepancies.sort_by(|a, b| a.market_id.cmp(&b.market_id));
+
+    ReconciliationReport {
+        discrepancies,
+        remote: remote_positions.to_vec(),
+    }
+}
+
+/// Convert Polymarket's GET /positions entries to strategy positions
+///
+/// Positions are keyed by token ID, with shorts as negative size.
+pub fn polymarket_positions(responses: &[PositionResponse]) -> Result<Vec<Position>> {
+    responses
+        .iter()
+        .map(|response| {
+            Ok(Position {
+                platform: Platform::Polymarket,
+                market_id: response.asset_id.clone(),
+                size: response.size_decimal()?,
+                avg_entry_price: response.avg_price_decimal()?,
+            })
+        })
+        .collect()
+}
+
+/// Build a strategy context from Polymarket account state
+///
+/// Lets a Trader refresh positions and balance from the exchange each tick.
+pub fn into_strategy_context(positions: Vec<Position>, balance: Decimal) -> StrategyContext {
+    let mut ctx = StrategyContext::new();
+    for position in positions {
+        ctx.positions
+            .insert((position.platform, position.market_id.clone()), position);
+    }
+    ctx.balances.insert(Platform::Polymarket, balance);
+    ctx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn position(market_id: &str, size: Decimal) -> Position {
+        Position {
+            platform: Platform::Polymarket,
+            market_id: market_id.to_string(),
+            size,
+            avg_entry_price: dec!(0.40),
+        }
+    }
+
+    fn context(positions: &[Position]) -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        for p in positions {
+            ctx.positions
+                .insert((p.platform, p.market_id.clone()), p.clone());
+        }
+        ctx
+    }
+
+    #[test]
+    fn test_matching_positions_are_consistent() {
+        let positions = vec![position("a", dec!(100)), position("b", dec!(-20))];
+        let report = reconcile(&context(&positions), &positions);
+
+        assert!(report.is_consistent());
+    }
+
+    #[test]
+    fn test_mismatches_are_reported() {
+        let local = context(&[position("a", dec!(100)), position("b", dec!(-20))]);
+        let remote = vec![position("a", dec!(80)), position("c", dec!(5))];
+
+        let report = reconcile(&local, &remote);
+
+        assert_eq!(
+            report.discrepancies,
+            vec![
+                PositionDiscrepancy {
+                    platform: Platform::Polymarket,
+                    market_id: "a".to_string(),
+                    local_size: dec!(100),
+                    remote_size: dec!(80),
+                },
+                PositionDiscrepancy {
+                    platform: Platform::Polymarket,
+                    market_id: "b".to_string(),
+                    local_size: dec!(-20),
+                    remote_size: dec!(0),
+                },
+                PositionDiscrepancy {
+                    platform: Platform::Polymarket,
+                    market_id: "c".to_string(),
+                    local_size: dec!(0),
+                    remote_size: dec!(5),
+                },
+            ]
+        );
+        assert_eq!(report.discrepancies[0].difference(), dec!(-20));
+    }
+
+    #[test]
+    fn test_apply_overwrites_local_state() {
+        let mut local = context(&[position("a", dec!(100)), position("b", dec!(-20))]);
+        let remote = vec![position("a", dec!(80)), position("c", dec!(5))];
+
+        reconcile(&local, &remote).apply_to(&mut local);
+
+        assert!(reconcile(&local, &remote).is_consistent());
+        assert!(!local.has_position(Platform::Polymarket, "b"));
+        assert_eq!(
+            local.get_position(Platform::Polymarket, "a").unwrap().size,
+            dec!(80)
+        );
+    }
+
+    #[test]
+    fn test_into_strategy_context() {
+        let responses: Vec<PositionResponse> = serde_json::from_str(
+            r#"[
+                {"asset_id": "token_yes", "size": "150.5", "avg_price": "0.42"},
+                {"asset": "token_no", "size": "-20", "avgPrice": "0.61"}
+            ]"#,
+        )
+        .unwrap();
+        let positions = polymarket_positions(&responses).unwrap();
+        assert_eq!(positions[0].avg_entry_price, dec!(0.42));
+
+        let ctx = into_strategy_context(positions, dec!(500));
+
+        assert_eq!(ctx.get_balance(Platform::Polymarket), dec!(500));
+        let long = ctx.get_position(Platform::Polymarket, "token_yes").unwrap();
+        assert_eq!(long.size, dec!(150.5));
+        let short = ctx.get_position(Platform::Polymarket, "token_no").unwrap();
+        assert_eq!(short.size, dec!(-20));
+        assert!(ctx.has_position(Platform::Polymarket, "token_no"));
+    }
+}
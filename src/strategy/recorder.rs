@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::common::errors::{ClientError, Result};
+use crate::strategy::size_calculator::SizedIntent;
+use crate::strategy::types::{wire, Platform, Side, TradeIntent};
+
+/// What happened to a `Go` decision after the strategy emitted it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionOutcome {
+    /// All legs were sent for execution
+    Executed,
+    /// Not executed (no size available, suppressed, risk limits, ...)
+    Skipped,
+    /// Execution was attempted and failed
+    Failed,
+}
+
+/// One leg of a recorded decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedLeg {
+    #[serde(with = "wire::PlatformDef")]
+    pub platform: Platform,
+    pub market_id: String,
+    #[serde(with = "wire::SideDef")]
+    pub side: Side,
+    /// Size traded, if the intent was sized
+    pub size: Option<Decimal>,
+    /// Price used, if the intent was sized
+    pub price: Option<Decimal>,
+}
+
+/// A single line of the decision log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub timestamp: DateTime<Utc>,
+    pub strategy: String,
+    pub legs: Vec<RecordedLeg>,
+    pub reason: String,
+    pub outcome: DecisionOutcome,
+}
+
+impl DecisionRecord {
+    /// Build a record from an intent and, if available, its sized form
+    pub fn new(
+        strategy: &str,
+        intent: &TradeIntent,
+        sized: Option<&SizedIntent>,
+        outcome: DecisionOutcome,
+    ) -> Self {
+        let legs = intent
+            .legs
+            .iter()
+            .map(|leg| {
+                let sized_leg = sized.and_then(|sized| {
+                    sized.legs.iter().find(|s| {
+                        s.platform == leg.platform
+                            && s.market_id == leg.market_id
+                            && s.side == leg.side
+                    })
+                });
+                RecordedLeg {
+                    platform: leg.platform,
+                    market_id: leg.market_id.clone(),
+                    side: leg.side,
+                    size: sized_leg.map(|s| s.size),
+                    price: sized_leg.map(|s| s.price).or(leg.suggested_price),
+                }
+            })
+            .collect();
+
+        Self {
+            timestamp: Utc::now(),
+            strategy: strategy.to_string(),
+            legs,
+            reason: intent.reason.clone(),
+            outcome,
+        }
+    }
+}
+
+/// Writes strategy decisions to a JSON Lines sink for post-trade analysis
+///
+/// Each record is written as one line and flushed immediately, so the log
+/// stays readable if the process stops mid-session.
+pub struct DecisionRecorder<W: Write> {
+    writer: W,
+}
+
+impl DecisionRecorder<BufWriter<File>> {
+    /// Append decisions to the file at `path`, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                ClientError::Configuration(format!(
+                    "Failed to open decision log {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> DecisionRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Record a decision and what happened to it
+    pub fn record(
+        &mut self,
+        strategy: &str,
+        intent: &TradeIntent,
+        sized: Option<&SizedIntent>,
+        outcome: DecisionOutcome,
+    ) -> Result<()> {
+        self.write_record(&DecisionRecord::new(strategy, intent, sized, outcome))
+    }
+
+    /// Write a prebuilt record
+    pub fn write_record(&mut self, record: &DecisionRecord) -> Result<()> {
+        let line = serde_json::to_string(record)?;
+        writeln!(self.writer, "{}", line)
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| ClientError::Internal(format!("Failed to write decision log: {}", e)))
+    }
+
+    /// Consume the recorder and return the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::size_calculator::SizedLeg;
+    use crate::strategy::types::{OrderType, TradeLeg};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_go_decision_reason_recorded() {
+        let intent = TradeIntent::multi(
+            vec![
+                TradeLeg::new(Platform::Kalshi, "kx", Side::Buy),
+                TradeLeg::new(Platform::Polymarket, "pm", Side::Sell).with_price(dec!(0.52)),
+            ],
+            "Cross-platform spread detected",
+        );
+        let sized = SizedIntent {
+            legs: vec![SizedLeg {
+                platform: Platform::Kalshi,
+                market_id: "kx".to_string(),
+                side: Side::Buy,
+                size: dec!(25),
+                price: dec!(0.45),
+                order_type: OrderType::Gtc,
+            }],
+            reason: intent.reason.clone(),
+            score: None,
+        };
+
+        let mut recorder = DecisionRecorder::new(Vec::new());
+        recorder
+            .record("arb", &intent, Some(&sized), DecisionOutcome::Executed)
+            .unwrap();
+        recorder
+            .record("arb", &intent, None, DecisionOutcome::Skipped)
+            .unwrap();
+
+        let output = String::from_utf8(recorder.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let json: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(json["strategy"], "arb");
+        assert_eq!(json["reason"], "Cross-platform spread detected");
+        assert_eq!(json["outcome"], "executed");
+        assert!(json["timestamp"].is_string());
+        assert_eq!(json["legs"][0]["market_id"], "kx");
+        assert_eq!(json["legs"][0]["side"], "Buy");
+        assert_eq!(json["legs"][0]["size"], "25");
+        assert_eq!(json["legs"][1]["platform"], "Polymarket");
+        assert!(json["legs"][1]["size"].is_null());
+        assert_eq!(json["legs"][1]["price"], "0.52");
+
+        let skipped: DecisionRecord = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(skipped.outcome, DecisionOutcome::Skipped);
+        assert_eq!(skipped.reason, intent.reason);
+        assert!(skipped.legs.iter().all(|leg| leg.size.is_none()));
+    }
+}
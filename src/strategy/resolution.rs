@@ -0,0 +1,132 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::strategy::fees::PlatformFees;
+use crate::strategy::types::Position;
+
+/// Payout per winning contract at resolution
+const WINNING_PAYOUT: Decimal = dec!(1.0);
+
+/// Calculate the net value a position settles for at market resolution
+///
+/// Winning tokens pay 1.0 and losing tokens pay 0.0. A long position holds
+/// YES tokens bought at `avg_entry_price`; a short position is treated as
+/// holding the complementary NO tokens, bought at `1.0 - avg_entry_price`.
+///
+/// For profit-based fee models (the legacy Kalshi model) the taker fee is charged on the
+/// profit realized at resolution. Size-based fees are charged when trading,
+/// not when redeeming, so they do not reduce the settled value.
+///
+/// # Arguments
+/// * `position` - The position being settled
+/// * `winning` - Whether the tokens held by the position won
+/// * `fees` - Fee model for the position's platform
+///
+/// # Returns
+/// Total settled value of the position after resolution fees
+pub fn settle(position: &Position, winning: bool, fees: &PlatformFees) -> Decimal {
+    let contracts = position.size.abs();
+    if !winning || contracts.is_zero() {
+        return Decimal::ZERO;
+    }
+
+    // Cost basis of the tokens actually held
+    let cost_per_contract = if position.size > Decimal::ZERO {
+        position.avg_entry_price
+    } else {
+        WINNING_PAYOUT - position.avg_entry_price
+    };
+
+    let payout = WINNING_PAYOUT * contracts;
+
+    if fees.profit_based {
+        let profit = (WINNING_PAYOUT - cost_per_contract) * contracts;
+        let fee = profit.max(Decimal::ZERO) * fees.taker_fee_percent / dec!(100.0);
+        payout - fee
+    } else {
+        payout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::types::Platform;
+
+    fn position(platform: Platform, size: Decimal, avg_entry_price: Decimal) -> Position {
+        Position {
+            platform,
+            market_id: "market".to_string(),
+            size,
+            avg_entry_price,
+        }
+    }
+
+    #[test]
+    fn test_winning_long() {
+        // Profit = (1.0 - 0.40) * 100 = 60, legacy Kalshi fee = 60 * 7% = 4.2
+        let kalshi = position(Platform::Kalshi, dec!(100), dec!(0.40));
+        assert_eq!(
+            settle(&kalshi, true, &PlatformFees::kalshi_profit_based()),
+            dec!(95.8)
+        );
+        // Kalshi's trading fee is paid at entry, not at settlement
+        assert_eq!(settle(&kalshi, true, &PlatformFees::kalshi()), dec!(100));
+
+        let polymarket = position(Platform::Polymarket, dec!(100), dec!(0.40));
+        assert_eq!(
+            settle(&polymarket, true, &PlatformFees::polymarket()),
+            dec!(100)
+        );
+    }
+
+    #[test]
+    fn test_losing_long() {
+        let kalshi = position(Platform::Kalshi, dec!(100), dec!(0.40));
+        assert_eq!(
+            settle(&kalshi, false, &PlatformFees::kalshi()),
+            Decimal::ZERO
+        );
+
+        let polymarket = position(Platform::Polymarket, dec!(100), dec!(0.40));
+        assert_eq!(
+            settle(&polymarket, false, &PlatformFees::polymarket()),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_winning_short() {
+        // Short at 0.70 holds NO at 0.30: profit = 0.70 * 100 = 70, legacy Kalshi fee = 4.9
+        let kalshi = position(Platform::Kalshi, dec!(-100), dec!(0.70));
+        assert_eq!(
+            settle(&kalshi, true, &PlatformFees::kalshi_profit_based()),
+            dec!(95.1)
+        );
+
+        let polymarket = position(Platform::Polymarket, dec!(-100), dec!(0.70));
+        assert_eq!(
+            settle(&polymarket, true, &PlatformFees::polymarket()),
+            dec!(100)
+        );
+    }
+
+    #[test]
+    fn test_size_based_fees_not_charged_at_resolution() {
+        let fees = PlatformFees {
+            platform: Platform::Polymarket,
+            maker_fee_percent: dec!(0.0),
+            taker_fee_percent: dec!(2.0),
+            profit_based: false,
+            trading_fee_rate: dec!(0.07),
+        };
+        let long = position(Platform::Polymarket, dec!(50), dec!(0.25));
+        assert_eq!(settle(&long, true, &fees), dec!(50));
+    }
+
+    #[test]
+    fn test_flat_position_settles_to_zero() {
+        let flat = Position::new(Platform::Kalshi, "market");
+        assert_eq!(settle(&flat, true, &PlatformFees::kalshi()), Decimal::ZERO);
+    }
+}
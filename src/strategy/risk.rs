@@ -0,0 +1,357 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::strategy::size_calculator::SizedIntent;
+use crate::strategy::types::{Platform, Side, StrategyContext};
+
+/// A limit a sized intent would break
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RiskViolation {
+    #[error("position in {platform} {market_id} would be {size}, limit {limit}")]
+    MarketPosition {
+        platform: Platform,
+        market_id: String,
+        size: Decimal,
+        limit: Decimal,
+    },
+
+    #[error("notional on {platform} would be {notional}, limit {limit}")]
+    PlatformNotional {
+        platform: Platform,
+        notional: Decimal,
+        limit: Decimal,
+    },
+
+    #[error("{open} positions would be open, limit {limit}")]
+    OpenLegs { open: usize, limit: usize },
+}
+
+/// Exposure limits checked before a sized intent is executed
+///
+/// Every limit is optional; unset limits aren't checked.
+#[derive(Debug, Clone, Default)]
+pub struct RiskLimits {
+    /// Largest absolute position in any single market, in contracts
+    pub max_position_per_market: Option<Decimal>,
+    /// Largest total notional (`|size| * entry price`) held on one platform
+    pub max_notional_per_platform: Option<Decimal>,
+    /// Most markets with an open position at once
+    pub max_open_legs: Option<usize>,
+}
+
+impl RiskLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the absolute position in any single market
+    pub fn with_max_position_per_market(mut self, max: Decimal) -> Self {
+        self.max_position_per_market = Some(max);
+        self
+    }
+
+    /// Limit the notional held on each platform
+    pub fn with_max_notional_per_platform(mut self, max: Decimal) -> Self {
+        self.max_notional_per_platform = Some(max);
+        self
+    }
+
+    /// Limit how many markets can have an open position
+    pub fn with_max_open_legs(mut self, max: usize) -> Self {
+        self.max_open_legs = Some(max);
+        self
+    }
+
+    /// Check the positions `intent` would leave behind against the limits
+    ///
+    /// Each leg is applied to the current position in `ctx` for its market.
+    /// Markets the intent trades are valued at the leg price and other
+    /// positions at their average entry price. An intent is only rejected
+    /// for a limit it makes worse, so reducing an oversized position is
+    /// always allowed.
+    pub fn check(&self, intent: &SizedIntent, ctx: &StrategyContext) -> Result<(), RiskViolation> {
+        let after = Exposure::after(intent, ctx);
+        let before = Exposure::current();
+
+        if let Some(limit) = self.max_position_per_market {
+            for (&(platform, market_id), &(size, _)) in &after.traded {
+                let current = ctx
+                    .get_position(platform, market_id)
+                    .map(|p| p.size.abs())
+                    .unwrap_or_default();
+                if size.abs() > limit && size.abs() > current {
+                    return Err(RiskViolation::MarketPosition {
+                        platform,
+                        market_id: market_id.to_string(),
+                        size,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        if let Some(limit) = self.max_notional_per_platform {
+            for leg in &intent.legs {
+                let notional = after.notional(ctx, leg.platform);
+                if notional > limit && notional > before.notional(ctx, leg.platform) {
+                    return Err(RiskViolation::PlatformNotional {
+                        platform: leg.platform,
+                        notional,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        if let Some(limit) = self.max_open_legs {
+            let open = after.open_positions(ctx);
+            if open > limit && open > before.open_positions(ctx) {
+                return Err(RiskViolation::OpenLegs { open, limit });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Positions in `ctx` with some markets overridden by a sized intent
+struct Exposure<'a> {
+    /// Size and price of every market the intent trades
+    traded: HashMap<(Platform, &'a str), (Decimal, Decimal)>,
+}
+
+impl<'a> Exposure<'a> {
+    /// Positions as they are now
+    fn current() -> Self {
+        Self {
+            traded: HashMap::new(),
+        }
+    }
+
+    /// Positions once every leg of `intent` has filled
+    fn after(intent: &'a SizedIntent, ctx: &StrategyContext) -> Self {
+        let mut traded = HashMap::new();
+        for leg in &intent.legs {
+            let signed = match leg.side {
+                Side::Buy => leg.size,
+                Side::Sell => -leg.size,
+            };
+            let (size, price) = traded
+                .entry((leg.platform, leg.market_id.as_str()))
+                .or_insert_with(|| {
+                    let size = ctx
+                        .get_position(leg.platform, &leg.market_id)
+                        .map(|p| p.size)
+                        .unwrap_or_default();
+                    (size, leg.price)
+                });
+            *size += signed;
+            *price = leg.price;
+        }
+        Self { traded }
+    }
+
+    /// Total `|size| * price` held on `platform`
+    fn notional(&self, ctx: &StrategyContext, platform: Platform) -> Decimal {
+        let untraded: Decimal = ctx
+            .positions
+            .iter()
+            .filter(|((p, market_id), _)| {
+                *p == platform && !self.traded.contains_key(&(*p, market_id.as_str()))
+            })
+            .map(|(_, position)| position.size.abs() * position.avg_entry_price)
+            .sum();
+        let traded: Decimal = self
+            .traded
+            .iter()
+            .filter(|((p, _), _)| *p == platform)
+            .map(|(_, (size, price))| size.abs() * price)
+            .sum();
+        untraded + traded
+    }
+
+    /// Number of markets with a non-zero position
+    fn open_positions(&self, ctx: &StrategyContext) -> usize {
+        let untraded = ctx
+            .positions
+            .iter()
+            .filter(|((platform, market_id), position)| {
+                !position.size.is_zero()
+                    && !self.traded.contains_key(&(*platform, market_id.as_str()))
+            })
+            .count();
+        untraded
+            + self
+                .traded
+                .values()
+                .filter(|(size, _)| !size.is_zero())
+                .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::size_calculator::SizedLeg;
+    use crate::strategy::types::{OrderType, Position};
+    use rust_decimal_macros::dec;
+
+    fn leg(
+        platform: Platform,
+        market_id: &str,
+        side: Side,
+        size: Decimal,
+        price: Decimal,
+    ) -> SizedLeg {
+        SizedLeg {
+            platform,
+            market_id: market_id.to_string(),
+            side,
+            size,
+            price,
+            order_type: OrderType::Gtc,
+        }
+    }
+
+    fn intent(legs: Vec<SizedLeg>) -> SizedIntent {
+        SizedIntent {
+            legs,
+            reason: "test".to_string(),
+            score: None,
+        }
+    }
+
+    fn context(positions: &[(Platform, &str, Decimal, Decimal)]) -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        for &(platform, market_id, size, avg_entry_price) in positions {
+            let mut position = Position::new(platform, market_id);
+            position.size = size;
+            position.avg_entry_price = avg_entry_price;
+            ctx.positions
+                .insert((platform, market_id.to_string()), position);
+        }
+        ctx
+    }
+
+    #[test]
+    fn test_within_limits_allowed() {
+        let limits = RiskLimits::new()
+            .with_max_position_per_market(dec!(100))
+            .with_max_notional_per_platform(dec!(50))
+            .with_max_open_legs(2);
+        let ctx = context(&[(Platform::Polymarket, "a", dec!(40), dec!(0.50))]);
+
+        // 100 * 0.30 on Kalshi, Polymarket notional unchanged at 20
+        let arb = intent(vec![
+            leg(Platform::Kalshi, "KX", Side::Buy, dec!(100), dec!(0.30)),
+            leg(Platform::Polymarket, "a", Side::Buy, dec!(0), dec!(0.50)),
+        ]);
+        assert_eq!(limits.check(&arb, &ctx), Ok(()));
+        assert_eq!(RiskLimits::new().check(&arb, &ctx), Ok(()));
+    }
+
+    #[test]
+    fn test_market_position_limit() {
+        let limits = RiskLimits::new().with_max_position_per_market(dec!(100));
+        let ctx = context(&[(Platform::Polymarket, "a", dec!(80), dec!(0.50))]);
+
+        let add = intent(vec![leg(
+            Platform::Polymarket,
+            "a",
+            Side::Buy,
+            dec!(30),
+            dec!(0.50),
+        )]);
+        assert_eq!(
+            limits.check(&add, &ctx),
+            Err(RiskViolation::MarketPosition {
+                platform: Platform::Polymarket,
+                market_id: "a".to_string(),
+                size: dec!(110),
+                limit: dec!(100),
+            })
+        );
+
+        // Shorts count by absolute size too
+        let flip = intent(vec![leg(
+            Platform::Polymarket,
+            "a",
+            Side::Sell,
+            dec!(200),
+            dec!(0.50),
+        )]);
+        assert!(limits.check(&flip, &ctx).is_err());
+
+        // Reducing a position that is already over the limit is allowed
+        let ctx = context(&[(Platform::Polymarket, "a", dec!(150), dec!(0.50))]);
+        let reduce = intent(vec![leg(
+            Platform::Polymarket,
+            "a",
+            Side::Sell,
+            dec!(20),
+            dec!(0.50),
+        )]);
+        assert_eq!(limits.check(&reduce, &ctx), Ok(()));
+    }
+
+    #[test]
+    fn test_platform_notional_limit() {
+        let limits = RiskLimits::new().with_max_notional_per_platform(dec!(100));
+        let ctx = context(&[
+            (Platform::Kalshi, "KX", dec!(100), dec!(0.60)),
+            (Platform::Polymarket, "a", dec!(500), dec!(0.50)),
+        ]);
+
+        // 60 already held on Kalshi plus 100 * 0.45
+        let buy = intent(vec![leg(
+            Platform::Kalshi,
+            "KY",
+            Side::Buy,
+            dec!(100),
+            dec!(0.45),
+        )]);
+        assert_eq!(
+            limits.check(&buy, &ctx),
+            Err(RiskViolation::PlatformNotional {
+                platform: Platform::Kalshi,
+                notional: dec!(105),
+                limit: dec!(100),
+            })
+        );
+
+        let smaller = intent(vec![leg(
+            Platform::Kalshi,
+            "KY",
+            Side::Buy,
+            dec!(80),
+            dec!(0.45),
+        )]);
+        assert_eq!(limits.check(&smaller, &ctx), Ok(()));
+    }
+
+    #[test]
+    fn test_open_legs_limit() {
+        let limits = RiskLimits::new().with_max_open_legs(1);
+        let ctx = context(&[(Platform::Polymarket, "a", dec!(10), dec!(0.50))]);
+
+        let open = intent(vec![leg(
+            Platform::Kalshi,
+            "KX",
+            Side::Buy,
+            dec!(10),
+            dec!(0.45),
+        )]);
+        assert_eq!(
+            limits.check(&open, &ctx),
+            Err(RiskViolation::OpenLegs { open: 2, limit: 1 })
+        );
+
+        // Closing one position while opening another keeps the count at 1
+        let rotate = intent(vec![
+            leg(Platform::Polymarket, "a", Side::Sell, dec!(10), dec!(0.50)),
+            leg(Platform::Kalshi, "KX", Side::Buy, dec!(10), dec!(0.45)),
+        ]);
+        assert_eq!(limits.check(&rotate, &ctx), Ok(()));
+    }
+}
@@ -0,0 +1,293 @@
+use rust_decimal::Decimal;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::common::types::MarketEvent;
+use crate::strategy::traits::BoxedStrategy;
+use crate::strategy::types::{Decision, MarketSubscription, StrategyContext};
+
+/// Drives a single strategy and gates its decisions
+///
+/// Events and ticks are always forwarded so the strategy can build up its
+/// internal state, but `Go` decisions are replaced with `NoGo` until
+/// [`Strategy::warmup_complete`](crate::strategy::Strategy::warmup_complete)
+/// returns true.
+///
+/// An optional latency budget drops `Go` decisions triggered by events that
+/// were received too long before the decision was made.
+pub struct StrategyRunner {
+    strategy: BoxedStrategy,
+    /// Maximum time from an event's `received_at` to its decision
+    max_event_age: Option<Duration>,
+    /// Trades worth less than this never reach the strategy
+    min_trade_notional: Option<Decimal>,
+}
+
+impl StrategyRunner {
+    pub fn new(strategy: BoxedStrategy) -> Self {
+        Self {
+            strategy,
+            max_event_age: None,
+            min_trade_notional: None,
+        }
+    }
+
+    /// Drop decisions on events older than `max_age` when the decision is made
+    pub fn with_max_event_age(mut self, max_age: Duration) -> Self {
+        self.max_event_age = Some(max_age);
+        self
+    }
+
+    /// Skip trades whose notional (price * size) is below `min_notional`
+    pub fn with_min_trade_notional(mut self, min_notional: Decimal) -> Self {
+        self.min_trade_notional = Some(min_notional);
+        self
+    }
+
+    /// Name of the wrapped strategy
+    pub fn name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    /// Access the wrapped strategy
+    pub fn strategy(&self) -> &BoxedStrategy {
+        &self.strategy
+    }
+
+    /// Markets the wrapped strategy subscribes to
+    pub fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+        self.strategy.subscribed_markets()
+    }
+
+    /// Forward registration to the wrapped strategy
+    pub fn on_register(&mut self, ctx: &StrategyContext) {
+        self.strategy.on_register(ctx);
+    }
+
+    /// Forward shutdown to the wrapped strategy
+    pub fn on_shutdown(&mut self) {
+        self.strategy.on_shutdown();
+    }
+
+    /// Returns true once the wrapped strategy has finished warming up
+    pub fn is_warm(&self) -> bool {
+        self.strategy.warmup_complete()
+    }
+
+    /// Forward a market event and gate the resulting decision
+    ///
+    /// Trades below the minimum notional are dropped without reaching the
+    /// strategy.
+    pub fn on_market_event(&mut self, event: &MarketEvent, ctx: &StrategyContext) -> Decision {
+        if self
+            .min_trade_notional
+            .is_some_and(|min| event.is_trade_below(min))
+        {
+            return Decision::NoGo;
+        }
+        let decision = self.strategy.on_market_event(event, ctx);
+        let decision = self.gate(decision);
+        self.enforce_latency_budget(event, decision)
+    }
+
+    /// Forward a tick and gate the resulting decision
+    pub fn on_tick(&mut self, ctx: &StrategyContext) -> Decision {
+        let decision = self.strategy.on_tick(ctx);
+        self.gate(decision)
+    }
+
+    fn gate(&self, decision: Decision) -> Decision {
+        if decision.is_go() && !self.strategy.warmup_complete() {
+            debug!(
+                "Suppressing Go from strategy {} during warmup",
+                self.strategy.name()
+            );
+            return Decision::NoGo;
+        }
+        decision
+    }
+
+    fn enforce_latency_budget(&self, event: &MarketEvent, decision: Decision) -> Decision {
+        let (Some(max_age), Some(received_at)) = (self.max_event_age, event.received_at()) else {
+            return decision;
+        };
+        if !decision.is_go() {
+            return decision;
+        }
+
+        let age = (chrono::Utc::now() - received_at)
+            .to_std()
+            .unwrap_or_default();
+        if age > max_age {
+            warn!(
+                "Dropping Go from strategy {}: too slow ({}ms > {}ms budget)",
+                self.strategy.name(),
+                age.as_millis(),
+                max_age.as_millis()
+            );
+            return Decision::NoGo;
+        }
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::traits::Strategy;
+    use crate::strategy::types::{MarketSubscription, Platform, Side, TradeLeg};
+    use rust_decimal_macros::dec;
+
+    /// Always wants to trade, but only reports ready after `required` events
+    struct EagerStrategy {
+        seen: usize,
+        required: usize,
+    }
+
+    impl Strategy for EagerStrategy {
+        fn name(&self) -> &str {
+            "eager"
+        }
+
+        fn on_market_event(&mut self, _event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            self.seen += 1;
+            Decision::go_single(
+                TradeLeg::new(Platform::Polymarket, "market", Side::Buy),
+                "always",
+            )
+        }
+
+        fn on_tick(&mut self, _ctx: &StrategyContext) -> Decision {
+            Decision::go_single(
+                TradeLeg::new(Platform::Polymarket, "market", Side::Buy),
+                "tick",
+            )
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::AllMatchedPairs]
+        }
+
+        fn warmup_complete(&self) -> bool {
+            self.seen >= self.required
+        }
+    }
+
+    fn event() -> MarketEvent {
+        MarketEvent::Heartbeat {
+            platform: crate::common::types::Platform::Polymarket,
+        }
+    }
+
+    #[test]
+    fn test_no_go_until_warm() {
+        let mut runner = StrategyRunner::new(Box::new(EagerStrategy {
+            seen: 0,
+            required: 3,
+        }));
+        let ctx = StrategyContext::new();
+
+        for _ in 0..2 {
+            assert!(!runner.on_market_event(&event(), &ctx).is_go());
+            assert!(!runner.on_tick(&ctx).is_go());
+        }
+        assert!(!runner.is_warm());
+
+        // Third event completes warmup, so its own decision goes through
+        assert!(runner.on_market_event(&event(), &ctx).is_go());
+        assert!(runner.is_warm());
+        assert!(runner.on_tick(&ctx).is_go());
+    }
+
+    fn trade_received_at(received_at: chrono::DateTime<chrono::Utc>) -> MarketEvent {
+        MarketEvent::Trade(crate::common::types::Trade {
+            platform: crate::common::types::Platform::Polymarket,
+            market_id: "market".to_string(),
+            asset_id: "asset".to_string(),
+            trade_id: "trade".to_string(),
+            price: dec!(0.5),
+            size: dec!(10),
+            side: crate::common::types::Side::Buy,
+            side_source: crate::common::types::TradeSideSource::Reported,
+            timestamp: received_at,
+            received_at,
+        })
+    }
+
+    #[test]
+    fn test_stale_event_decision_dropped() {
+        let mut runner = StrategyRunner::new(Box::new(EagerStrategy {
+            seen: 0,
+            required: 0,
+        }))
+        .with_max_event_age(Duration::from_millis(200));
+        let ctx = StrategyContext::new();
+
+        let fresh = trade_received_at(chrono::Utc::now());
+        assert!(runner.on_market_event(&fresh, &ctx).is_go());
+
+        let aged = trade_received_at(chrono::Utc::now() - chrono::Duration::seconds(1));
+        assert!(!runner.on_market_event(&aged, &ctx).is_go());
+
+        // Events without a receive time aren't subject to the budget
+        assert!(runner.on_market_event(&event(), &ctx).is_go());
+    }
+
+    #[test]
+    fn test_default_warmup_passes_through() {
+        struct Ready;
+
+        impl Strategy for Ready {
+            fn name(&self) -> &str {
+                "ready"
+            }
+
+            fn on_market_event(
+                &mut self,
+                _event: &MarketEvent,
+                _ctx: &StrategyContext,
+            ) -> Decision {
+                Decision::go_single(
+                    TradeLeg::new(Platform::Kalshi, "market", Side::Sell),
+                    "ready",
+                )
+            }
+
+            fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+                vec![MarketSubscription::AllMatchedPairs]
+            }
+        }
+
+        let mut runner = StrategyRunner::new(Box::new(Ready));
+        assert!(runner
+            .on_market_event(&event(), &StrategyContext::new())
+            .is_go());
+    }
+
+    #[test]
+    fn test_dust_trades_filtered() {
+        let mut runner = StrategyRunner::new(Box::new(EagerStrategy {
+            seen: 0,
+            required: 1,
+        }))
+        .with_min_trade_notional(dec!(5));
+        let ctx = StrategyContext::new();
+
+        let trade = |size| {
+            let mut event = trade_received_at(chrono::Utc::now());
+            if let MarketEvent::Trade(trade) = &mut event {
+                trade.size = size;
+            }
+            event
+        };
+
+        // 0.5 * 2 = 1 is dust and never reaches the strategy
+        assert!(!runner.on_market_event(&trade(dec!(2)), &ctx).is_go());
+        assert!(!runner.is_warm());
+
+        // 0.5 * 10 = 5 passes
+        assert!(runner.on_market_event(&trade(dec!(10)), &ctx).is_go());
+        assert!(runner.on_market_event(&trade(dec!(40)), &ctx).is_go());
+        assert!(runner.on_market_event(&event(), &ctx).is_go());
+    }
+}
@@ -0,0 +1,425 @@
+//! Paper orders that rest on the book and fill from the market data stream
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use crate::common::book_store::BookStore;
+use crate::common::types::{MarketEvent, OrderStatus, OrderUpdate, PriceLevel};
+use crate::strategy::size_calculator::SizedLeg;
+use crate::strategy::types::{OrderType, Platform, Side};
+
+/// A working paper order
+struct RestingOrder {
+    /// State as of the last update sent for it
+    state: OrderUpdate,
+    /// Good-til-date expiry
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// Simulates the lifecycle of orders placed on a book it doesn't control
+///
+/// A submitted leg first takes whatever the current book offers up to its
+/// price, like a taker order. What's left depends on its time in force:
+/// IOC cancels the remainder straight away, FOK cancels the whole order
+/// unless it can fill completely, and GTC and GTD rest. Resting orders fill
+/// at their own price when a trade prints at or through it, or when the
+/// book moves to cross it, up to the size traded or offered. Queue position
+/// isn't modelled, so resting fills are optimistic. GTD orders expire once
+/// an event or [`expire`](Self::expire) moves the clock past their expiry.
+///
+/// Every change is reported as a `MarketEvent::OrderUpdate`, in the order
+/// it happened. Simulated fills never take liquidity out of the book.
+#[derive(Default)]
+pub struct OrderBookSimulator {
+    books: BookStore,
+    orders: Vec<RestingOrder>,
+    next_id: u64,
+}
+
+impl OrderBookSimulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Orders still working, oldest first
+    pub fn open_orders(&self) -> impl Iterator<Item = &OrderUpdate> {
+        self.orders.iter().map(|order| &order.state)
+    }
+
+    /// Place a paper order for `leg` at time `at`
+    ///
+    /// The leg's market ID is the asset whose book it trades against.
+    /// Returns the order's ID and the updates it produced straight away,
+    /// after any expiries up to `at`.
+    pub fn submit(&mut self, leg: &SizedLeg, at: DateTime<Utc>) -> (String, Vec<MarketEvent>) {
+        let mut events = self.expire(at);
+
+        self.next_id += 1;
+        let order_id = format!("sim-{}", self.next_id);
+        let book = self
+            .books
+            .get(&leg.market_id)
+            .filter(|book| book.platform == leg.platform);
+        let mut state = OrderUpdate {
+            platform: leg.platform,
+            order_id: order_id.clone(),
+            market_id: book.map_or_else(|| leg.market_id.clone(), |b| b.market_id.clone()),
+            asset_id: leg.market_id.clone(),
+            side: leg.side,
+            price: leg.price,
+            original_size: leg.size,
+            size_matched: Decimal::ZERO,
+            fill: None,
+            status: OrderStatus::Live,
+            timestamp: at,
+        };
+        let expires_at = match leg.order_type {
+            OrderType::Gtd(expiry) if expiry <= at => {
+                events.push(transition(&state, OrderStatus::Expired, at));
+                return (order_id, events);
+            }
+            OrderType::Gtd(expiry) => Some(expiry),
+            _ => None,
+        };
+
+        let crossing: Vec<PriceLevel> = book
+            .map(|book| {
+                book.levels_to_take(leg.side)
+                    .iter()
+                    .take_while(|level| crosses(leg.side, leg.price, level.price))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let available: Decimal = crossing.iter().map(|level| level.size).sum();
+        if leg.order_type == OrderType::Fok && available < leg.size {
+            events.push(transition(&state, OrderStatus::Cancelled, at));
+            return (order_id, events);
+        }
+
+        for level in crossing {
+            if state.remaining().is_zero() {
+                break;
+            }
+            let size = level.size.min(state.remaining());
+            state = fill(&state, level.price, size, at);
+            events.push(MarketEvent::OrderUpdate(state.clone()));
+        }
+
+        if state.remaining().is_zero() {
+            return (order_id, events);
+        }
+        if leg.order_type.is_immediate() {
+            events.push(transition(&state, OrderStatus::Cancelled, at));
+            return (order_id, events);
+        }
+        if state.size_matched.is_zero() {
+            events.push(MarketEvent::OrderUpdate(state.clone()));
+        }
+        self.orders.push(RestingOrder { state, expires_at });
+        (order_id, events)
+    }
+
+    /// Cancel a working order, returning its final update
+    pub fn cancel(&mut self, order_id: &str, at: DateTime<Utc>) -> Option<MarketEvent> {
+        let index = self
+            .orders
+            .iter()
+            .position(|order| order.state.order_id == order_id)?;
+        let order = self.orders.remove(index);
+        Some(transition(&order.state, OrderStatus::Cancelled, at))
+    }
+
+    /// Expire GTD orders whose expiry is at or before `now`
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<MarketEvent> {
+        let mut events = Vec::new();
+        self.orders.retain(|order| match order.expires_at {
+            Some(expiry) if expiry <= now => {
+                events.push(transition(&order.state, OrderStatus::Expired, expiry));
+                false
+            }
+            _ => true,
+        });
+        events
+    }
+
+    /// Update the book from a market event and fill resting orders it crosses
+    ///
+    /// The event's timestamp first expires GTD orders that are due. Trades
+    /// and book events for one asset share their size among the resting
+    /// orders it crosses, oldest first.
+    pub fn on_event(&mut self, event: &MarketEvent) -> Vec<MarketEvent> {
+        let mut events = event
+            .timestamp()
+            .map(|now| self.expire(now))
+            .unwrap_or_default();
+
+        match event {
+            MarketEvent::Trade(trade) => {
+                let mut available = trade.size;
+                for order in &mut self.orders {
+                    let state = &order.state;
+                    if state.platform != trade.platform
+                        || state.asset_id != trade.asset_id
+                        || !crosses(state.side, state.price, trade.price)
+                    {
+                        continue;
+                    }
+                    let size = available.min(state.remaining());
+                    if size.is_zero() {
+                        break;
+                    }
+                    available -= size;
+                    order.state = fill(state, state.price, size, trade.timestamp);
+                    events.push(MarketEvent::OrderUpdate(order.state.clone()));
+                }
+            }
+            MarketEvent::OrderBook(book) => {
+                self.books.apply(event);
+                events.extend(self.fill_from_book(book.platform, &book.asset_id, book.timestamp));
+            }
+            MarketEvent::OrderBookUpdate(update) => {
+                self.books.apply(event);
+                events.extend(self.fill_from_book(
+                    update.platform,
+                    &update.asset_id,
+                    update.timestamp,
+                ));
+            }
+            _ => {}
+        }
+
+        self.orders.retain(|order| order.state.status.is_open());
+        events
+    }
+
+    /// Fill resting orders for an asset that its updated book crosses
+    fn fill_from_book(
+        &mut self,
+        platform: Platform,
+        asset_id: &str,
+        at: DateTime<Utc>,
+    ) -> Vec<MarketEvent> {
+        let Some(book) = self
+            .books
+            .get(asset_id)
+            .filter(|book| book.platform == platform)
+        else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        // Size already given to older orders on each side
+        let (mut bought, mut sold) = (Decimal::ZERO, Decimal::ZERO);
+        for order in &mut self.orders {
+            let state = &order.state;
+            if state.platform != platform || state.asset_id != asset_id {
+                continue;
+            }
+            let taken = match state.side {
+                Side::Buy => &mut bought,
+                Side::Sell => &mut sold,
+            };
+            let size = (book.depth_within(state.side, state.price) - *taken).min(state.remaining());
+            if size <= Decimal::ZERO {
+                continue;
+            }
+            *taken += size;
+            order.state = fill(state, state.price, size, at);
+            events.push(MarketEvent::OrderUpdate(order.state.clone()));
+        }
+        events
+    }
+}
+
+/// Whether an order on `side` at `limit` trades at `price`
+fn crosses(side: Side, limit: Decimal, price: Decimal) -> bool {
+    match side {
+        Side::Buy => price <= limit,
+        Side::Sell => price >= limit,
+    }
+}
+
+/// `state` after filling `size` at `price`
+fn fill(state: &OrderUpdate, price: Decimal, size: Decimal, at: DateTime<Utc>) -> OrderUpdate {
+    let size_matched = state.size_matched + size;
+    OrderUpdate {
+        size_matched,
+        fill: Some(PriceLevel::new(price, size)),
+        status: if size_matched >= state.original_size {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        },
+        timestamp: at,
+        ..state.clone()
+    }
+}
+
+/// Update moving `state` to a final `status` without a fill
+fn transition(state: &OrderUpdate, status: OrderStatus, at: DateTime<Utc>) -> MarketEvent {
+    MarketEvent::OrderUpdate(OrderUpdate {
+        fill: None,
+        status,
+        timestamp: at,
+        ..state.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{OrderBook, Trade, TradeSideSource};
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+
+    fn book(at: DateTime<Utc>, asks: Vec<PriceLevel>) -> MarketEvent {
+        MarketEvent::OrderBook(OrderBook {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: "token".to_string(),
+            bids: vec![PriceLevel::new(dec!(0.45), dec!(100))],
+            asks,
+            timestamp: at,
+            received_at: at,
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    fn trade(at: DateTime<Utc>, price: Decimal, size: Decimal) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: "token".to_string(),
+            trade_id: "t".to_string(),
+            price,
+            size,
+            side: Side::Sell,
+            side_source: TradeSideSource::Reported,
+            timestamp: at,
+            received_at: at,
+        })
+    }
+
+    fn buy(size: Decimal, price: Decimal, order_type: OrderType) -> SizedLeg {
+        SizedLeg {
+            platform: Platform::Polymarket,
+            market_id: "token".to_string(),
+            side: Side::Buy,
+            size,
+            price,
+            order_type,
+        }
+    }
+
+    /// Status, filled size and fill of each order update
+    fn updates(events: &[MarketEvent]) -> Vec<(OrderStatus, Decimal, Option<PriceLevel>)> {
+        events
+            .iter()
+            .map(|event| match event {
+                MarketEvent::OrderUpdate(update) => {
+                    (update.status, update.size_matched, update.fill.clone())
+                }
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ioc_partially_fills_then_cancels() {
+        let t0 = Utc::now();
+        let mut sim = OrderBookSimulator::new();
+        sim.on_event(&book(
+            t0,
+            vec![
+                PriceLevel::new(dec!(0.55), dec!(80)),
+                PriceLevel::new(dec!(0.58), dec!(120)),
+            ],
+        ));
+
+        let (order_id, events) = sim.submit(&buy(dec!(100), dec!(0.55), OrderType::Ioc), t0);
+        assert_eq!(order_id, "sim-1");
+        assert_eq!(
+            updates(&events),
+            vec![
+                (
+                    OrderStatus::PartiallyFilled,
+                    dec!(80),
+                    Some(PriceLevel::new(dec!(0.55), dec!(80)))
+                ),
+                (OrderStatus::Cancelled, dec!(80), None),
+            ]
+        );
+        assert_eq!(sim.open_orders().count(), 0);
+
+        // FOK doesn't take the 80 it could get
+        let (_, events) = sim.submit(&buy(dec!(100), dec!(0.55), OrderType::Fok), t0);
+        assert_eq!(
+            updates(&events),
+            vec![(OrderStatus::Cancelled, dec!(0), None)]
+        );
+    }
+
+    #[test]
+    fn test_gtd_expires_unfilled() {
+        let t0 = Utc::now();
+        let mut sim = OrderBookSimulator::new();
+        sim.on_event(&book(t0, vec![PriceLevel::new(dec!(0.55), dec!(80))]));
+
+        let expiry = t0 + Duration::seconds(60);
+        let (order_id, events) = sim.submit(&buy(dec!(50), dec!(0.50), OrderType::Gtd(expiry)), t0);
+        assert_eq!(updates(&events), vec![(OrderStatus::Live, dec!(0), None)]);
+
+        // Trades above the bid don't reach it
+        let events = sim.on_event(&trade(t0 + Duration::seconds(30), dec!(0.55), dec!(10)));
+        assert!(events.is_empty());
+        assert_eq!(sim.open_orders().count(), 1);
+
+        // Once past expiry, a trade through the price is too late
+        let events = sim.on_event(&trade(t0 + Duration::seconds(90), dec!(0.45), dec!(100)));
+        assert_eq!(
+            updates(&events),
+            vec![(OrderStatus::Expired, dec!(0), None)]
+        );
+        let MarketEvent::OrderUpdate(expired) = &events[0] else {
+            unreachable!()
+        };
+        assert_eq!(expired.order_id, order_id);
+        assert_eq!(expired.timestamp, expiry);
+        assert_eq!(sim.open_orders().count(), 0);
+    }
+
+    #[test]
+    fn test_resting_order_fills_from_trades_and_book() {
+        let t0 = Utc::now();
+        let mut sim = OrderBookSimulator::new();
+        sim.on_event(&book(t0, vec![PriceLevel::new(dec!(0.55), dec!(80))]));
+        let (_, events) = sim.submit(&buy(dec!(50), dec!(0.50), OrderType::Gtc), t0);
+        assert_eq!(updates(&events), vec![(OrderStatus::Live, dec!(0), None)]);
+
+        // A trade through the bid fills at the order's own price
+        let events = sim.on_event(&trade(t0, dec!(0.49), dec!(20)));
+        assert_eq!(
+            updates(&events),
+            vec![(
+                OrderStatus::PartiallyFilled,
+                dec!(20),
+                Some(PriceLevel::new(dec!(0.50), dec!(20)))
+            )]
+        );
+
+        // The ask dropping onto the bid fills the rest
+        let events = sim.on_event(&book(t0, vec![PriceLevel::new(dec!(0.50), dec!(100))]));
+        assert_eq!(
+            updates(&events),
+            vec![(
+                OrderStatus::Filled,
+                dec!(50),
+                Some(PriceLevel::new(dec!(0.50), dec!(30)))
+            )]
+        );
+        assert_eq!(sim.open_orders().count(), 0);
+        assert!(sim.cancel("sim-1", t0).is_none());
+    }
+}
@@ -1,7 +1,15 @@
 use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock as SyncRwLock};
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::debug;
 
-use crate::strategy::types::{Platform, Side, TradeLeg, TradeIntent};
+use crate::common::errors::{ClientError, Result};
+use crate::common::orderbook::OrderBookManager;
+use crate::common::types::{MarketEvent, OrderBook};
+use crate::strategy::fees::FeeCalculator;
+use crate::strategy::types::{OrderType, Platform, Side, StrategyContext, TradeLeg, TradeIntent};
 
 /// Pre-computed size for a potential trade
 #[derive(Debug, Clone)]
@@ -49,6 +57,8 @@ pub struct SizedLeg {
     pub side: Side,
     pub size: Decimal,
     pub price: Decimal,
+    /// Time in force, carried over from the trade leg
+    pub order_type: OrderType,
 }
 
 /// Sized trade intent ready for execution
@@ -56,12 +66,68 @@ pub struct SizedLeg {
 pub struct SizedIntent {
     pub legs: Vec<SizedLeg>,
     pub reason: String,
+    /// Score carried over from the trade intent
+    pub score: Option<Decimal>,
 }
 
 impl SizedIntent {
     pub fn is_valid(&self) -> bool {
         !self.legs.is_empty() && self.legs.iter().all(|leg| leg.size > Decimal::ZERO)
     }
+
+    /// Whether every leg is FOK or IOC, so no leg can be left resting
+    pub fn is_executable_atomically(&self) -> bool {
+        self.legs.iter().all(|leg| leg.order_type.is_immediate())
+    }
+
+    /// Check every leg against the exchange limits for its market
+    ///
+    /// `constraints` is keyed by leg `market_id`; legs without an entry
+    /// aren't checked. Returns `ClientError::InvalidOrder` for the first leg
+    /// below the minimum size or priced off the tick grid.
+    pub fn validate_against(&self, constraints: &HashMap<String, MarketConstraints>) -> Result<()> {
+        for leg in &self.legs {
+            let Some(limits) = constraints.get(&leg.market_id) else {
+                continue;
+            };
+            if leg.size < limits.min_size {
+                return Err(ClientError::InvalidOrder(format!(
+                    "{} size {} is below the minimum of {}",
+                    leg.market_id, leg.size, limits.min_size
+                )));
+            }
+            if !limits.is_on_tick(leg.price) {
+                return Err(ClientError::InvalidOrder(format!(
+                    "{} price {} is not a multiple of the {} tick",
+                    leg.market_id, leg.price, limits.tick_size
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Exchange limits on orders in a market
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketConstraints {
+    /// Smallest order size accepted
+    pub min_size: Decimal,
+    /// Price increment; zero means any price is accepted
+    pub tick_size: Decimal,
+}
+
+impl MarketConstraints {
+    pub fn new(min_size: Decimal, tick_size: Decimal) -> Self {
+        Self {
+            min_size,
+            tick_size,
+        }
+    }
+
+    /// Whether `price` is a multiple of the tick size
+    pub fn is_on_tick(&self, price: Decimal) -> bool {
+        self.tick_size.is_zero() || (price % self.tick_size).is_zero()
+    }
 }
 
 /// SizeCalculator trait
@@ -102,12 +168,14 @@ pub trait SizeCalculator: Send + Sync {
                 side: leg.side,
                 size: computed.size,
                 price: leg.suggested_price.unwrap_or(computed.price),
+                order_type: leg.order_type,
             });
         }
 
         Some(SizedIntent {
             legs: sized_legs,
             reason: intent.reason.clone(),
+            score: intent.score,
         })
     }
 
@@ -137,7 +205,7 @@ pub trait SizeCalculator: Send + Sync {
 /// Simple in-memory size calculator implementation
 ///
 /// Stores pre-computed sizes in a HashMap.
-/// In production, this would be updated by a background task.
+/// See [`LiveSizeCalculator`] for one updated by a background task.
 pub struct InMemorySizeCalculator {
     sizes: HashMap<SizeKey, ComputedSize>,
 }
@@ -190,3 +258,388 @@ impl SizeCalculator for InMemorySizeCalculator {
 
 /// Boxed size calculator for dynamic dispatch
 pub type BoxedSizeCalculator = Box<dyn SizeCalculator>;
+
+/// Size calculator kept current from live market data
+///
+/// [`spawn`](Self::spawn) starts a background task that maintains order
+/// books from the event stream. On every book change it recomputes, for
+/// both sides of that asset, the largest whole number of contracts the
+/// platform balance in the shared [`StrategyContext`] can pay for when
+/// walking the book, fees included. Buys cost the VWAP plus fees; sells
+/// are treated as buying the complement, so they lock up `1 - VWAP` per
+/// contract plus fees. The quoted price is the worst level that size
+/// reaches, i.e. the limit price needed to fill it.
+///
+/// Sizes are keyed by asset ID, matching how positions are keyed.
+#[derive(Clone, Default)]
+pub struct LiveSizeCalculator {
+    sizes: Arc<SyncRwLock<HashMap<SizeKey, ComputedSize>>>,
+}
+
+impl LiveSizeCalculator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start updating sizes from `rx`, using balances from `ctx`
+    ///
+    /// The task runs until the sender side of `rx` is dropped.
+    pub fn spawn(
+        &self,
+        mut rx: mpsc::Receiver<MarketEvent>,
+        ctx: Arc<RwLock<StrategyContext>>,
+    ) -> JoinHandle<()> {
+        let sizes = self.sizes.clone();
+        tokio::spawn(async move {
+            let mut books = OrderBookManager::new();
+            while let Some(event) = rx.recv().await {
+                let asset_id = match &event {
+                    MarketEvent::OrderBook(book) => &book.asset_id,
+                    MarketEvent::OrderBookUpdate(update) => &update.asset_id,
+                    _ => continue,
+                };
+                if !books.apply_event(&event) {
+                    continue;
+                }
+                let Some(book) = books.get(asset_id) else {
+                    continue;
+                };
+
+                let ctx = ctx.read().await;
+                let mut sizes = sizes.write().unwrap();
+                for side in [Side::Buy, Side::Sell] {
+                    let key = SizeKey::new(book.platform, &book.asset_id, side);
+                    match Self::compute(book, side, &ctx) {
+                        Some(size) => {
+                            sizes.insert(key, size);
+                        }
+                        None => {
+                            sizes.remove(&key);
+                        }
+                    }
+                }
+            }
+            debug!("Market event stream closed, stopping size updates");
+        })
+    }
+
+    /// Number of cached sizes
+    pub fn len(&self) -> usize {
+        self.sizes.read().unwrap().len()
+    }
+
+    /// Check if cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.sizes.read().unwrap().is_empty()
+    }
+
+    /// Largest affordable size for `side` against `book`, if any
+    fn compute(book: &OrderBook, side: Side, ctx: &StrategyContext) -> Option<ComputedSize> {
+        let platform = book.platform;
+        let balance = ctx.get_balance(platform);
+        let levels = match side {
+            Side::Buy => &book.asks,
+            Side::Sell => &book.bids,
+        };
+        let depth: Decimal = levels.iter().map(|level| level.size).sum();
+
+        // Capital needed to take `size` contracts, or None if too deep
+        let cost = |size: Decimal| {
+            let vwap = book.vwap_for_size(side, size)?;
+            let cost = match side {
+                Side::Buy => FeeCalculator::entry_cost(platform, vwap, side, size),
+                Side::Sell => size - FeeCalculator::entry_cost(platform, vwap, side, size),
+            };
+            Some((cost, vwap))
+        };
+
+        // Cost grows with size, so binary search the whole-contract range
+        let mut low = Decimal::ZERO;
+        let mut high = depth.floor();
+        while low < high {
+            let mid = ((low + high + Decimal::ONE) / Decimal::TWO).floor();
+            match cost(mid) {
+                Some((cost, _)) if cost <= balance => low = mid,
+                _ => high = mid - Decimal::ONE,
+            }
+        }
+
+        cost(low).filter(|_| low > Decimal::ZERO)?;
+        // Quote the worst level the size reaches rather than the VWAP, so
+        // the price is a limit that fills the whole size and is on the tick
+        let mut reached = Decimal::ZERO;
+        let price = levels
+            .iter()
+            .find(|level| {
+                reached += level.size;
+                reached >= low
+            })?
+            .price;
+        Some(ComputedSize {
+            platform,
+            market_id: book.asset_id.clone(),
+            side,
+            size: low,
+            price,
+            computed_at: chrono::Utc::now(),
+        })
+    }
+}
+
+impl SizeCalculator for LiveSizeCalculator {
+    fn get_size(&self, key: &SizeKey) -> Option<ComputedSize> {
+        self.sizes.read().unwrap().get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{OrderBookUpdate, PriceLevel};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use std::time::Duration;
+
+    fn book_update(asks: Vec<PriceLevel>, is_snapshot: bool) -> MarketEvent {
+        MarketEvent::OrderBookUpdate(OrderBookUpdate {
+            platform: Platform::Polymarket,
+            market_id: "market".to_string(),
+            asset_id: "token".to_string(),
+            bids: vec![PriceLevel::new(dec!(0.40), dec!(1000))],
+            asks,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            is_snapshot,
+            sequence: 0,
+            hash: None,
+        })
+    }
+
+    fn sized(size: Decimal, price: Decimal) -> SizedIntent {
+        SizedIntent {
+            legs: vec![SizedLeg {
+                platform: Platform::Polymarket,
+                market_id: "token".to_string(),
+                side: Side::Buy,
+                size,
+                price,
+                order_type: OrderType::Gtc,
+            }],
+            reason: "test".to_string(),
+            score: None,
+        }
+    }
+
+    #[test]
+    fn test_score_survives_sizing() {
+        let mut calc = InMemorySizeCalculator::new();
+        calc.set_size(ComputedSize {
+            platform: Platform::Polymarket,
+            market_id: "token".to_string(),
+            side: Side::Buy,
+            size: dec!(10),
+            price: dec!(0.50),
+            computed_at: Utc::now(),
+        });
+
+        let leg = TradeLeg::new(Platform::Polymarket, "token", Side::Buy);
+        let intent = TradeIntent::single(leg.clone(), "scored")
+            .with_score(dec!(0.75))
+            .with_tag("breakout");
+        assert_eq!(intent.score, Some(dec!(0.75)));
+        assert_eq!(intent.tags, vec!["breakout".to_string()]);
+        assert_eq!(
+            calc.get_sized_intent(&intent).unwrap().score,
+            Some(dec!(0.75))
+        );
+
+        let unscored = TradeIntent::single(leg, "plain");
+        assert_eq!(unscored.score, None);
+        assert!(unscored.tags.is_empty());
+        assert_eq!(calc.get_sized_intent(&unscored).unwrap().score, None);
+    }
+
+    #[test]
+    fn test_order_types_survive_sizing() {
+        let mut calc = InMemorySizeCalculator::new();
+        for (platform, market_id, side) in [
+            (Platform::Kalshi, "kx", Side::Buy),
+            (Platform::Polymarket, "pm", Side::Sell),
+            (Platform::Polymarket, "other", Side::Buy),
+        ] {
+            calc.set_size(ComputedSize {
+                platform,
+                market_id: market_id.to_string(),
+                side,
+                size: dec!(10),
+                price: dec!(0.50),
+                computed_at: Utc::now(),
+            });
+        }
+
+        let expiry = Utc::now() + chrono::Duration::hours(1);
+        let intent = TradeIntent::multi(
+            vec![
+                TradeLeg::new(Platform::Kalshi, "kx", Side::Buy).with_order_type(OrderType::Fok),
+                TradeLeg::new(Platform::Polymarket, "pm", Side::Sell)
+                    .with_order_type(OrderType::Gtd(expiry)),
+                TradeLeg::new(Platform::Polymarket, "other", Side::Buy),
+            ],
+            "mixed",
+        );
+
+        let sized = calc.get_sized_intent(&intent).unwrap();
+        let order_types: Vec<_> = sized.legs.iter().map(|leg| leg.order_type).collect();
+        assert_eq!(
+            order_types,
+            vec![OrderType::Fok, OrderType::Gtd(expiry), OrderType::Gtc]
+        );
+    }
+
+    #[test]
+    fn test_atomic_execution_needs_immediate_legs() {
+        let pair = |order_type| {
+            TradeIntent::multi(
+                vec![
+                    TradeLeg::new(Platform::Kalshi, "kx", Side::Buy).with_order_type(order_type),
+                    TradeLeg::new(Platform::Polymarket, "pm", Side::Sell)
+                        .with_order_type(order_type),
+                ],
+                "arb",
+            )
+        };
+        let sized = |intent: &TradeIntent| SizedIntent {
+            legs: intent
+                .legs
+                .iter()
+                .map(|leg| SizedLeg {
+                    platform: leg.platform,
+                    market_id: leg.market_id.clone(),
+                    side: leg.side,
+                    size: dec!(10),
+                    price: dec!(0.50),
+                    order_type: leg.order_type,
+                })
+                .collect(),
+            reason: intent.reason.clone(),
+            score: intent.score,
+        };
+
+        let gtc = pair(OrderType::Gtc);
+        assert!(gtc.requires_hedge());
+        assert!(!sized(&gtc).is_executable_atomically());
+
+        let fok = pair(OrderType::Fok);
+        assert!(!fok.requires_hedge());
+        assert!(sized(&fok).is_executable_atomically());
+
+        // One resting leg is enough to break atomicity
+        let mut mixed = pair(OrderType::Ioc);
+        mixed.legs[1].order_type = OrderType::Gtd(Utc::now());
+        assert!(mixed.requires_hedge());
+        assert!(!sized(&mixed).is_executable_atomically());
+
+        // A single leg has nothing to hedge against
+        let single = TradeIntent::single(TradeLeg::new(Platform::Kalshi, "kx", Side::Buy), "one");
+        assert!(!single.requires_hedge());
+    }
+
+    #[test]
+    fn test_validate_against_market_constraints() {
+        let constraints = HashMap::from([(
+            "token".to_string(),
+            MarketConstraints::new(dec!(5), dec!(0.01)),
+        )]);
+
+        assert!(sized(dec!(5), dec!(0.42))
+            .validate_against(&constraints)
+            .is_ok());
+        // Markets without known constraints aren't checked
+        assert!(sized(dec!(1), dec!(0.425))
+            .validate_against(&HashMap::new())
+            .is_ok());
+
+        let err = sized(dec!(4.5), dec!(0.42))
+            .validate_against(&constraints)
+            .unwrap_err();
+        assert!(matches!(err, ClientError::InvalidOrder(ref msg) if msg.contains("minimum")));
+
+        let err = sized(dec!(10), dec!(0.425))
+            .validate_against(&constraints)
+            .unwrap_err();
+        assert!(matches!(err, ClientError::InvalidOrder(ref msg) if msg.contains("tick")));
+    }
+
+    async fn wait_for_size(calculator: &LiveSizeCalculator, key: &SizeKey, size: Decimal) {
+        for _ in 0..100 {
+            if calculator.get_size(key).map(|c| c.size) == Some(size) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("size for {:?} never became {}", key, size);
+    }
+
+    #[tokio::test]
+    async fn test_sizes_follow_book_and_balance() {
+        let mut ctx = StrategyContext::new();
+        ctx.balances.insert(Platform::Polymarket, dec!(100));
+        let ctx = Arc::new(RwLock::new(ctx));
+
+        let calculator = LiveSizeCalculator::new();
+        let (tx, rx) = mpsc::channel(16);
+        let handle = calculator.spawn(rx, ctx.clone());
+
+        // 100 @ 0.50 then 300 @ 0.60: $100 buys 100 + 83 contracts
+        tx.send(book_update(
+            vec![
+                PriceLevel::new(dec!(0.50), dec!(100)),
+                PriceLevel::new(dec!(0.60), dec!(300)),
+            ],
+            true,
+        ))
+        .await
+        .unwrap();
+
+        let buy = SizeKey::new(Platform::Polymarket, "token", Side::Buy);
+        wait_for_size(&calculator, &buy, dec!(183)).await;
+        // Priced at the deepest level reached, not the 0.5453.. VWAP
+        assert_eq!(calculator.get_size(&buy).unwrap().price, dec!(0.60));
+        let across_levels = calculator
+            .get_sized_intent(&TradeIntent::single(
+                TradeLeg::new(Platform::Polymarket, "token", Side::Buy),
+                "test",
+            ))
+            .unwrap();
+        let constraints = HashMap::from([(
+            "token".to_string(),
+            MarketConstraints::new(dec!(5), dec!(0.01)),
+        )]);
+        assert!(across_levels.validate_against(&constraints).is_ok());
+
+        // Selling at 0.40 locks up 0.60 per contract
+        let sell = SizeKey::new(Platform::Polymarket, "token", Side::Sell);
+        assert_eq!(calculator.get_size(&sell).unwrap().size, dec!(166));
+
+        // Thin book: limited by depth rather than balance
+        tx.send(book_update(
+            vec![PriceLevel::new(dec!(0.60), dec!(0))],
+            false,
+        ))
+        .await
+        .unwrap();
+        wait_for_size(&calculator, &buy, dec!(100)).await;
+
+        // Hot path reads through the trait unchanged
+        let intent = TradeIntent::single(
+            TradeLeg::new(Platform::Polymarket, "token", Side::Buy),
+            "test",
+        );
+        let sized = calculator.get_sized_intent(&intent).unwrap();
+        assert!(sized.is_valid());
+        assert_eq!(sized.legs[0].size, dec!(100));
+
+        drop(tx);
+        handle.await.unwrap();
+    }
+}
@@ -68,6 +68,17 @@ pub trait Strategy: Send + Sync {
     /// This avoids unnecessary processing for irrelevant events.
     fn subscribed_markets(&self) -> Vec<MarketSubscription>;
 
+    /// Whether the strategy has seen enough data to trade
+    ///
+    /// Strategies that need history (moving averages, momentum windows)
+    /// should return false until their state is populated. The runner keeps
+    /// feeding events during warmup but suppresses any `Go` decisions.
+    ///
+    /// Default implementation returns true.
+    fn warmup_complete(&self) -> bool {
+        true
+    }
+
     /// Called once when strategy is registered with Trader
     ///
     /// Use for any initialization that requires async or context.
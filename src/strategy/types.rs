@@ -1,18 +1,66 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
-/// Platform identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Platform {
-    Kalshi,
-    Polymarket,
+use crate::common::errors::{ClientError, Result};
+use crate::common::state::{load_json, save_json};
+use crate::common::types::MarketEvent;
+use crate::strategy::pairs::PairMap;
+
+// Platform and side are shared with market data so strategies can compare
+// them directly against incoming events
+pub use crate::common::types::{Platform, Side};
+
+/// Strategy-side serde representations of [`Platform`] and [`Side`]
+///
+/// Market data uses the exchange spellings (`"polymarket"`, `"BUY"`), while
+/// decision logs and saved contexts keep the variant names (`"Polymarket"`,
+/// `"Buy"`). Use with `#[serde(with = "...")]` on strategy-facing fields.
+pub(crate) mod wire {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Platform, Side};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "Platform")]
+    pub(crate) enum PlatformDef {
+        Polymarket,
+        Kalshi,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "Side")]
+    pub(crate) enum SideDef {
+        Buy,
+        Sell,
+    }
+
+    /// Map key form of [`PlatformDef`]
+    #[derive(PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub(crate) struct PlatformKey(#[serde(with = "PlatformDef")] pub(crate) Platform);
 }
 
-/// Trade side
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum Side {
-    Buy,
-    Sell,
+/// How long an order for a leg stays working
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OrderType {
+    /// Good-til-cancelled: rests on the book until filled or cancelled
+    #[default]
+    Gtc,
+    /// Fill-or-kill: fills completely straight away or not at all
+    Fok,
+    /// Good-til-date: rests on the book until filled or the given time
+    Gtd(DateTime<Utc>),
+    /// Immediate-or-cancel: whatever can't fill straight away is cancelled
+    Ioc,
+}
+
+impl OrderType {
+    /// Whether the order is done as soon as it reaches the book, so it can
+    /// never be left resting (FOK and IOC)
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, OrderType::Fok | OrderType::Ioc)
+    }
 }
 
 /// A single leg of a trade
@@ -27,6 +75,8 @@ pub struct TradeLeg {
     /// Optional price suggestion from strategy
     /// SizeCalculator or Trader may override based on current book
     pub suggested_price: Option<Decimal>,
+    /// Time in force for the leg's order
+    pub order_type: OrderType,
 }
 
 impl TradeLeg {
@@ -36,6 +86,7 @@ impl TradeLeg {
             market_id: market_id.into(),
             side,
             suggested_price: None,
+            order_type: OrderType::default(),
         }
     }
 
@@ -43,6 +94,11 @@ impl TradeLeg {
         self.suggested_price = Some(price);
         self
     }
+
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
 }
 
 /// A trade intent containing one or more legs
@@ -50,11 +106,25 @@ impl TradeLeg {
 /// Single leg: Simple directional trade (momentum, mean reversion)
 /// Multiple legs: Arbitrage or complex multi-platform trades
 ///
-/// All legs in a single intent are treated as atomic - execute all or none.
+/// All legs in a single intent are meant to execute together. Nothing
+/// enforces that on the exchanges: legs are separate orders, so unless every
+/// leg is FOK or IOC one can fill while another is left resting. See
+/// [`requires_hedge`](Self::requires_hedge).
 #[derive(Debug, Clone)]
 pub struct TradeIntent {
     pub legs: Vec<TradeLeg>,
     pub reason: String,
+    /// How strongly the strategy rates the trade, on a scale of its choosing
+    ///
+    /// Used to pick between intents that fire at once: the [`Trader`]
+    /// submits higher scores first, and
+    /// [`MergePolicy::HighestConfidence`](super::composite::MergePolicy)
+    /// picks the best-scored one.
+    ///
+    /// [`Trader`]: super::engine::Trader
+    pub score: Option<Decimal>,
+    /// Free-form labels, e.g. the signal that fired
+    pub tags: Vec<String>,
 }
 
 impl TradeIntent {
@@ -63,6 +133,8 @@ impl TradeIntent {
         Self {
             legs: vec![leg],
             reason: reason.into(),
+            score: None,
+            tags: Vec::new(),
         }
     }
 
@@ -71,14 +143,36 @@ impl TradeIntent {
         Self {
             legs,
             reason: reason.into(),
+            score: None,
+            tags: Vec::new(),
         }
     }
 
+    /// Rate the trade
+    pub fn with_score(mut self, score: Decimal) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    /// Label the trade
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
     /// Returns true if this is an arbitrage (multi-leg) intent
     pub fn is_arbitrage(&self) -> bool {
         self.legs.len() > 1
     }
 
+    /// Whether some legs could fill while others rest on the book
+    ///
+    /// True for multi-leg intents with any leg that isn't FOK or IOC; such
+    /// intents can leave unhedged exposure that needs managing.
+    pub fn requires_hedge(&self) -> bool {
+        self.is_arbitrage() && !self.legs.iter().all(|leg| leg.order_type.is_immediate())
+    }
+
     /// Returns the number of legs
     pub fn leg_count(&self) -> usize {
         self.legs.len()
@@ -117,8 +211,9 @@ impl Decision {
 }
 
 /// Current position in a market
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Position {
+    #[serde(with = "wire::PlatformDef")]
     pub platform: Platform,
     pub market_id: String,
     /// Positive = long (bought YES/contracts), Negative = short (sold/bought NO)
@@ -136,20 +231,132 @@ impl Position {
             avg_entry_price: Decimal::ZERO,
         }
     }
+
+    /// Update the position for a fill, returning the PnL it realized
+    ///
+    /// Fills that add to the position re-average the entry price. Fills
+    /// against it realize `(price - avg_entry_price)` per closed contract
+    /// (negated for shorts) and leave the entry price alone; if the fill
+    /// flips the position through flat, the remainder is opened at `price`.
+    /// Fees are not included. Zero-size fills are ignored.
+    pub fn apply_fill(&mut self, side: Side, price: Decimal, size: Decimal) -> Decimal {
+        if size.is_zero() {
+            return Decimal::ZERO;
+        }
+        let signed = match side {
+            Side::Buy => size,
+            Side::Sell => -size,
+        };
+        let new_size = self.size + signed;
+
+        let adding =
+            self.size.is_zero() || self.size.is_sign_positive() == signed.is_sign_positive();
+        if adding {
+            self.avg_entry_price =
+                (self.avg_entry_price * self.size.abs() + price * size) / new_size.abs();
+            self.size = new_size;
+            return Decimal::ZERO;
+        }
+
+        let closed = size.min(self.size.abs());
+        let realized = if self.size.is_sign_positive() {
+            (price - self.avg_entry_price) * closed
+        } else {
+            (self.avg_entry_price - price) * closed
+        };
+        if new_size.is_zero() {
+            self.avg_entry_price = Decimal::ZERO;
+        } else if new_size.is_sign_positive() != self.size.is_sign_positive() {
+            // Flipped through flat: the remainder was opened at this fill
+            self.avg_entry_price = price;
+        }
+        self.size = new_size;
+        realized
+    }
+
+    /// Unrealized PnL if the position were closed at `mark_price`
+    pub fn unrealized_pnl(&self, mark_price: Decimal) -> Decimal {
+        (mark_price - self.avg_entry_price) * self.size
+    }
 }
 
 /// Context provided to strategies by the Trader
 ///
 /// Contains read-only information about current state.
 /// Strategies use this to make informed decisions without owning the state.
-#[derive(Debug, Clone, Default)]
+///
+/// Serializes with positions as a list, since JSON map keys can't be
+/// tuples; each position carries its own platform and market.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StrategyContext {
     /// Current positions by (platform, market_id)
+    #[serde(default, with = "positions_as_list")]
     pub positions: std::collections::HashMap<(Platform, String), Position>,
     /// Available balance per platform
+    #[serde(default, with = "balances_by_platform")]
     pub balances: std::collections::HashMap<Platform, Decimal>,
 }
 
+/// (De)serializes positions keyed by (platform, market_id) as a list
+mod positions_as_list {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    use super::{Platform, Position};
+
+    pub fn serialize<S: Serializer>(
+        positions: &HashMap<(Platform, String), Position>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut list: Vec<&Position> = positions.values().collect();
+        list.sort_by_key(|position| (position.platform.to_string(), position.market_id.clone()));
+        list.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(Platform, String), Position>, D::Error> {
+        let list = Vec::<Position>::deserialize(deserializer)?;
+        Ok(list
+            .into_iter()
+            .map(|position| ((position.platform, position.market_id.clone()), position))
+            .collect())
+    }
+}
+
+/// (De)serializes balances keyed by the strategy spelling of the platform
+mod balances_by_platform {
+    use rust_decimal::Decimal;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    use super::wire::PlatformKey;
+    use super::Platform;
+
+    pub fn serialize<S: Serializer>(
+        balances: &HashMap<Platform, Decimal>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut sorted: Vec<(&Platform, &Decimal)> = balances.iter().collect();
+        sorted.sort_by_key(|(platform, _)| platform.to_string());
+        serializer.collect_map(
+            sorted
+                .into_iter()
+                .map(|(platform, balance)| (PlatformKey(*platform), balance)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<Platform, Decimal>, D::Error> {
+        let balances = HashMap::<PlatformKey, Decimal>::deserialize(deserializer)?;
+        Ok(balances
+            .into_iter()
+            .map(|(PlatformKey(platform), balance)| (platform, balance))
+            .collect())
+    }
+}
+
 impl StrategyContext {
     pub fn new() -> Self {
         Self::default()
@@ -171,10 +378,23 @@ impl StrategyContext {
             .map(|p| p.size != Decimal::ZERO)
             .unwrap_or(false)
     }
+
+    /// Write positions and balances to `path` as JSON
+    ///
+    /// Written atomically, like
+    /// [`SubscriptionState::save`](crate::common::state::SubscriptionState::save).
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        save_json(path.as_ref(), self, "strategy context").await
+    }
+
+    /// Read a context previously written by [`save`](Self::save)
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        load_json(path.as_ref(), "strategy context").await
+    }
 }
 
 /// Subscription specifying which markets a strategy cares about
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MarketSubscription {
     /// Subscribe to a specific market on a platform
     Specific { platform: Platform, market_id: String },
@@ -185,3 +405,362 @@ pub enum MarketSubscription {
     /// Subscribe to all matched pairs
     AllMatchedPairs,
 }
+
+impl MarketSubscription {
+    /// Parse a comma-separated list of subscriptions, e.g. from the CLI
+    ///
+    /// Recognized forms:
+    /// - `poly:<condition id>` or `kalshi:<ticker>` for a specific market
+    /// - `pair:<ticker>=<condition id>` for a matched pair
+    /// - `all:poly` or `all:kalshi` for every market on a platform
+    /// - `all:pairs` for every matched pair
+    ///
+    /// `polymarket` is accepted for `poly`, and prefixes and platform names
+    /// are case-insensitive. Empty entries are skipped; anything else
+    /// unrecognized is a `ClientError::Configuration`.
+    pub fn parse(s: &str) -> Result<Vec<MarketSubscription>> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(Self::parse_one)
+            .collect()
+    }
+
+    fn parse_one(entry: &str) -> Result<MarketSubscription> {
+        let invalid = |why: &str| {
+            ClientError::Configuration(format!("Invalid market subscription {:?}: {}", entry, why))
+        };
+        let (prefix, value) = entry
+            .split_once(':')
+            .ok_or_else(|| invalid("expected <prefix>:<value>"))?;
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(invalid("missing value"));
+        }
+
+        match prefix.trim().to_lowercase().as_str() {
+            "all" if value.eq_ignore_ascii_case("pairs") => Ok(Self::AllMatchedPairs),
+            "all" => parse_platform(value)
+                .map(Self::AllOnPlatform)
+                .ok_or_else(|| invalid("unknown platform")),
+            "pair" => {
+                let (kalshi, polymarket) = value
+                    .split_once('=')
+                    .ok_or_else(|| invalid("expected pair:<ticker>=<condition id>"))?;
+                let (kalshi, polymarket) = (kalshi.trim(), polymarket.trim());
+                if kalshi.is_empty() || polymarket.is_empty() {
+                    return Err(invalid("missing market in pair"));
+                }
+                Ok(Self::MatchedPair {
+                    kalshi_market_id: kalshi.to_string(),
+                    polymarket_market_id: polymarket.to_string(),
+                })
+            }
+            other => parse_platform(other)
+                .map(|platform| Self::Specific {
+                    platform,
+                    market_id: value.to_string(),
+                })
+                .ok_or_else(|| invalid("unknown prefix")),
+        }
+    }
+
+    /// Whether `event` falls under this subscription
+    ///
+    /// Market data matches a market ID against both the event's market and
+    /// asset IDs. `AllMatchedPairs` matches events whose market is in
+    /// `pair_map`. Events that aren't tied to a market (connection status,
+    /// heartbeats) only match `AllOnPlatform`.
+    pub fn matches(&self, event: &MarketEvent, pair_map: &PairMap) -> bool {
+        let platform = event.platform();
+        let market_id = match event {
+            MarketEvent::OrderBook(book) => Some(book.market_id.as_str()),
+            MarketEvent::OrderBookUpdate(update) => Some(update.market_id.as_str()),
+            MarketEvent::Trade(trade) => Some(trade.market_id.as_str()),
+            MarketEvent::MarketInfo(info) => Some(info.market_id.as_str()),
+            MarketEvent::OrderUpdate(update) => Some(update.market_id.as_str()),
+            _ => None,
+        };
+        let refers_to = |id: &str| match event {
+            MarketEvent::OrderBook(book) => book.market_id == id || book.asset_id == id,
+            MarketEvent::OrderBookUpdate(update) => update.market_id == id || update.asset_id == id,
+            MarketEvent::Trade(trade) => trade.market_id == id || trade.asset_id == id,
+            MarketEvent::LastTradePrice { asset_id, .. } => asset_id == id,
+            MarketEvent::MarketInfo(info) => {
+                info.market_id == id || info.token_ids.iter().any(|token| token == id)
+            }
+            MarketEvent::OrderUpdate(update) => update.market_id == id || update.asset_id == id,
+            _ => false,
+        };
+
+        match self {
+            Self::Specific {
+                platform: subscribed,
+                market_id,
+            } => *subscribed == platform && refers_to(market_id),
+            Self::AllOnPlatform(subscribed) => *subscribed == platform,
+            Self::MatchedPair {
+                kalshi_market_id,
+                polymarket_market_id,
+            } => match platform {
+                Platform::Kalshi => refers_to(kalshi_market_id),
+                Platform::Polymarket => refers_to(polymarket_market_id),
+            },
+            Self::AllMatchedPairs => {
+                market_id.is_some_and(|market_id| pair_map.is_paired(platform, market_id))
+            }
+        }
+    }
+}
+
+/// Platform from its CLI name
+fn parse_platform(name: &str) -> Option<Platform> {
+    match name.trim().to_lowercase().as_str() {
+        "poly" | "polymarket" => Some(Platform::Polymarket),
+        "kalshi" => Some(Platform::Kalshi),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{Trade, TradeSideSource};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn trade(platform: Platform, market_id: &str, asset_id: &str) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            platform,
+            market_id: market_id.to_string(),
+            asset_id: asset_id.to_string(),
+            trade_id: "t".to_string(),
+            price: dec!(0.5),
+            size: dec!(10),
+            side: Side::Buy,
+            side_source: TradeSideSource::Reported,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_platform_and_side_shared_with_market_data() {
+        // Same types as market data, so events compare without conversion
+        let platform: crate::common::types::Platform = Platform::Kalshi;
+        let side: crate::common::types::Side = Side::Buy;
+
+        // Wire formats are the market data ones
+        assert_eq!(serde_json::to_string(&platform).unwrap(), "\"kalshi\"");
+        assert_eq!(serde_json::to_string(&side).unwrap(), "\"BUY\"");
+        assert_eq!(
+            serde_json::from_str::<Platform>("\"polymarket\"").unwrap(),
+            Platform::Polymarket
+        );
+        assert_eq!(serde_json::from_str::<Side>("\"SELL\"").unwrap(), Side::Sell);
+    }
+
+    fn pairs() -> PairMap {
+        let mut pairs = PairMap::new();
+        pairs.insert("KXBTC", "0xcondition");
+        pairs
+    }
+
+    #[test]
+    fn test_specific_matches_market_or_asset() {
+        let sub = MarketSubscription::Specific {
+            platform: Platform::Polymarket,
+            market_id: "token_yes".to_string(),
+        };
+        let pairs = pairs();
+
+        assert!(sub.matches(
+            &trade(Platform::Polymarket, "0xcondition", "token_yes"),
+            &pairs
+        ));
+        assert!(!sub.matches(
+            &trade(Platform::Polymarket, "0xcondition", "token_no"),
+            &pairs
+        ));
+        assert!(!sub.matches(
+            &trade(Platform::Kalshi, "token_yes", "token_yes"),
+            &pairs
+        ));
+    }
+
+    #[test]
+    fn test_all_on_platform() {
+        let sub = MarketSubscription::AllOnPlatform(Platform::Kalshi);
+        let pairs = pairs();
+
+        assert!(sub.matches(&trade(Platform::Kalshi, "ANY", "ANY"), &pairs));
+        assert!(!sub.matches(&trade(Platform::Polymarket, "ANY", "ANY"), &pairs));
+        assert!(sub.matches(
+            &MarketEvent::Heartbeat {
+                platform: Platform::Kalshi
+            },
+            &pairs
+        ));
+    }
+
+    #[test]
+    fn test_matched_pair() {
+        let sub = MarketSubscription::MatchedPair {
+            kalshi_market_id: "KXBTC".to_string(),
+            polymarket_market_id: "0xcondition".to_string(),
+        };
+        let pairs = pairs();
+
+        assert!(sub.matches(&trade(Platform::Kalshi, "KXBTC", "KXBTC"), &pairs));
+        assert!(sub.matches(
+            &trade(Platform::Polymarket, "0xcondition", "token_yes"),
+            &pairs
+        ));
+        // IDs only count on their own platform
+        assert!(!sub.matches(
+            &trade(Platform::Polymarket, "KXBTC", "token_yes"),
+            &pairs
+        ));
+    }
+
+    #[test]
+    fn test_all_matched_pairs_uses_pair_map() {
+        let sub = MarketSubscription::AllMatchedPairs;
+        let pairs = pairs();
+
+        assert!(sub.matches(&trade(Platform::Kalshi, "KXBTC", "KXBTC"), &pairs));
+        assert!(sub.matches(
+            &trade(Platform::Polymarket, "0xcondition", "token_yes"),
+            &pairs
+        ));
+        assert!(!sub.matches(&trade(Platform::Kalshi, "KXETH", "KXETH"), &pairs));
+        assert!(!sub.matches(
+            &trade(Platform::Polymarket, "0xother", "token_yes"),
+            &pairs
+        ));
+        assert!(!sub.matches(
+            &MarketEvent::Heartbeat {
+                platform: Platform::Kalshi
+            },
+            &pairs
+        ));
+        assert_eq!(pairs.get_kalshi("0xcondition"), Some("KXBTC"));
+        assert_eq!(pairs.get_poly("KXBTC"), Some("0xcondition"));
+    }
+
+    #[test]
+    fn test_parse_subscriptions() {
+        let subs = MarketSubscription::parse(
+            "poly:0xabc, kalshi:KXBTC,pair:KXETH=0xdef,all:poly,ALL:Kalshi,all:pairs,",
+        )
+        .unwrap();
+        assert_eq!(
+            subs,
+            vec![
+                MarketSubscription::Specific {
+                    platform: Platform::Polymarket,
+                    market_id: "0xabc".to_string(),
+                },
+                MarketSubscription::Specific {
+                    platform: Platform::Kalshi,
+                    market_id: "KXBTC".to_string(),
+                },
+                MarketSubscription::MatchedPair {
+                    kalshi_market_id: "KXETH".to_string(),
+                    polymarket_market_id: "0xdef".to_string(),
+                },
+                MarketSubscription::AllOnPlatform(Platform::Polymarket),
+                MarketSubscription::AllOnPlatform(Platform::Kalshi),
+                MarketSubscription::AllMatchedPairs,
+            ]
+        );
+
+        assert_eq!(
+            MarketSubscription::parse("polymarket:0xabc").unwrap(),
+            MarketSubscription::parse("poly:0xabc").unwrap()
+        );
+        assert!(MarketSubscription::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_subscription_errors() {
+        for input in [
+            "binance:BTC",
+            "0xabc",
+            "poly:",
+            "all:binance",
+            "pair:KXBTC",
+            "pair:=0xabc",
+            "poly:0xabc,nope:1",
+        ] {
+            assert!(
+                matches!(
+                    MarketSubscription::parse(input),
+                    Err(ClientError::Configuration(_))
+                ),
+                "{} should not parse",
+                input
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_context_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("context.json");
+
+        let mut ctx = StrategyContext::new();
+        let mut long = Position::new(Platform::Kalshi, "KXBTC");
+        long.apply_fill(Side::Buy, dec!(0.42), dec!(10));
+        let mut short = Position::new(Platform::Polymarket, "0xcondition");
+        short.apply_fill(Side::Sell, dec!(0.61), dec!(5));
+        for position in [long.clone(), short.clone()] {
+            ctx.positions
+                .insert((position.platform, position.market_id.clone()), position);
+        }
+        ctx.balances.insert(Platform::Kalshi, dec!(250.50));
+        ctx.balances.insert(Platform::Polymarket, dec!(1000));
+
+        ctx.save(&path).await.unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let loaded = StrategyContext::load(&path).await.unwrap();
+
+        // Strategy spelling, not the market data one
+        assert_eq!(saved["positions"][0]["platform"], "Kalshi");
+        assert_eq!(saved["balances"]["Polymarket"], "1000");
+
+        assert_eq!(loaded.positions.len(), 2);
+        assert_eq!(loaded.get_position(Platform::Kalshi, "KXBTC"), Some(&long));
+        assert_eq!(
+            loaded.get_position(Platform::Polymarket, "0xcondition"),
+            Some(&short)
+        );
+        assert_eq!(loaded.balances, ctx.balances);
+        assert_eq!(loaded.get_balance(Platform::Kalshi), dec!(250.50));
+
+        assert!(matches!(
+            StrategyContext::load(dir.path().join("missing.json")).await,
+            Err(ClientError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_size_fill_ignored() {
+        let mut flat = Position::new(Platform::Kalshi, "KXBTC");
+        assert_eq!(
+            flat.apply_fill(Side::Buy, dec!(0.42), Decimal::ZERO),
+            Decimal::ZERO
+        );
+        assert_eq!(flat, Position::new(Platform::Kalshi, "KXBTC"));
+
+        let mut long = Position::new(Platform::Kalshi, "KXBTC");
+        long.apply_fill(Side::Buy, dec!(0.42), dec!(10));
+        let before = long.clone();
+        assert_eq!(
+            long.apply_fill(Side::Sell, dec!(0.50), Decimal::ZERO),
+            Decimal::ZERO
+        );
+        assert_eq!(long, before);
+    }
+}
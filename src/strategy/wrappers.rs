@@ -0,0 +1,265 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tracing::debug;
+
+use crate::common::types::MarketEvent;
+use crate::strategy::traits::{BoxedStrategy, Strategy};
+use crate::strategy::types::{
+    Decision, MarketSubscription, Platform, Side, StrategyContext, TradeIntent,
+};
+
+/// The last `Go` let through for a market
+struct LastGo {
+    side: Side,
+    price: Option<Decimal>,
+    at: DateTime<Utc>,
+}
+
+/// Stops a strategy flipping between buying and selling on price jitter
+///
+/// A `Go` is keyed by its first leg's market and direction. One in the
+/// opposite direction to the last `Go` for the same market is turned into
+/// `NoGo` if it comes within `cooldown` of it, or if its suggested price is
+/// within `min_edge_change` of the last one. `Go`s in the same direction
+/// always pass. Every `Go` let through restarts the window; suppressed
+/// ones don't.
+///
+/// Time is taken from event timestamps so replays behave like live
+/// trading; ticks and events without one use the current time. Everything
+/// else is delegated to the wrapped strategy.
+pub struct Hysteresis {
+    inner: BoxedStrategy,
+    cooldown: Duration,
+    min_edge_change: Decimal,
+    last: HashMap<(Platform, String), LastGo>,
+}
+
+impl Hysteresis {
+    /// Wrap `inner`; with no cooldown or minimum change every `Go` passes
+    pub fn new(inner: BoxedStrategy) -> Self {
+        Self {
+            inner,
+            cooldown: Duration::zero(),
+            min_edge_change: Decimal::ZERO,
+            last: HashMap::new(),
+        }
+    }
+
+    /// Suppress reversals within `cooldown` of the last `Go`
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Suppress reversals whose price moved less than `min_edge_change`
+    pub fn with_min_edge_change(mut self, min_edge_change: Decimal) -> Self {
+        self.min_edge_change = min_edge_change;
+        self
+    }
+
+    /// The wrapped strategy
+    pub fn inner(&self) -> &dyn Strategy {
+        &*self.inner
+    }
+
+    fn filter(&mut self, decision: Decision, now: DateTime<Utc>) -> Decision {
+        let Decision::Go(intent) = decision else {
+            return decision;
+        };
+        let Some(leg) = intent.legs.first() else {
+            return Decision::Go(intent);
+        };
+
+        let key = (leg.platform, leg.market_id.clone());
+        if let Some(last) = self.last.get(&key) {
+            if last.side != leg.side && self.is_too_soon(last, &intent, now) {
+                debug!(
+                    "{}: suppressing {:?} {} reversal: {}",
+                    self.inner.name(),
+                    leg.side,
+                    leg.market_id,
+                    intent.reason
+                );
+                return Decision::NoGo;
+            }
+        }
+
+        self.last.insert(
+            key,
+            LastGo {
+                side: leg.side,
+                price: leg.suggested_price,
+                at: now,
+            },
+        );
+        Decision::Go(intent)
+    }
+
+    /// Whether a reversal from `last` to `intent` is within the deadband
+    fn is_too_soon(&self, last: &LastGo, intent: &TradeIntent, now: DateTime<Utc>) -> bool {
+        if now - last.at < self.cooldown {
+            return true;
+        }
+        match (last.price, intent.legs[0].suggested_price) {
+            (Some(before), Some(after)) => (after - before).abs() < self.min_edge_change,
+            _ => false,
+        }
+    }
+}
+
+impl Strategy for Hysteresis {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn on_market_event(&mut self, event: &MarketEvent, ctx: &StrategyContext) -> Decision {
+        let decision = self.inner.on_market_event(event, ctx);
+        self.filter(decision, event.timestamp().unwrap_or_else(Utc::now))
+    }
+
+    fn on_tick(&mut self, ctx: &StrategyContext) -> Decision {
+        let decision = self.inner.on_tick(ctx);
+        self.filter(decision, Utc::now())
+    }
+
+    fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+        self.inner.subscribed_markets()
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.inner.warmup_complete()
+    }
+
+    fn on_register(&mut self, ctx: &StrategyContext) {
+        self.inner.on_register(ctx);
+    }
+
+    fn on_shutdown(&mut self) {
+        self.inner.on_shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::{Trade, TradeSideSource};
+    use crate::strategy::types::TradeLeg;
+    use rust_decimal_macros::dec;
+
+    /// Buys trades below 0.50 and sells trades at or above it
+    struct FlipFlop;
+
+    impl Strategy for FlipFlop {
+        fn name(&self) -> &str {
+            "flip_flop"
+        }
+
+        fn on_market_event(&mut self, event: &MarketEvent, _ctx: &StrategyContext) -> Decision {
+            let MarketEvent::Trade(trade) = event else {
+                return Decision::NoGo;
+            };
+            let side = if trade.price < dec!(0.50) {
+                Side::Buy
+            } else {
+                Side::Sell
+            };
+            Decision::go_single(
+                TradeLeg::new(Platform::Polymarket, &trade.asset_id, side).with_price(trade.price),
+                "flip",
+            )
+        }
+
+        fn subscribed_markets(&self) -> Vec<MarketSubscription> {
+            vec![MarketSubscription::AllOnPlatform(Platform::Polymarket)]
+        }
+    }
+
+    fn trade(asset_id: &str, price: Decimal, at: DateTime<Utc>) -> MarketEvent {
+        MarketEvent::Trade(Trade {
+            platform: Platform::Polymarket,
+            market_id: "condition".to_string(),
+            asset_id: asset_id.to_string(),
+            trade_id: "t".to_string(),
+            price,
+            size: dec!(10),
+            side: Side::Buy,
+            side_source: TradeSideSource::Reported,
+            timestamp: at,
+            received_at: at,
+        })
+    }
+
+    /// Side of each decision, `None` for NoGo
+    fn sides(strategy: &mut Hysteresis, trades: &[MarketEvent]) -> Vec<Option<Side>> {
+        let ctx = StrategyContext::new();
+        trades
+            .iter()
+            .map(|event| match strategy.on_market_event(event, &ctx) {
+                Decision::Go(intent) => Some(intent.legs[0].side),
+                Decision::NoGo => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cooldown_debounces_reversals() {
+        let t0 = Utc::now();
+        let at = |secs| t0 + Duration::seconds(secs);
+        let mut strategy = Hysteresis::new(Box::new(FlipFlop)).with_cooldown(Duration::seconds(10));
+
+        let trades = [
+            trade("a", dec!(0.49), at(0)),
+            trade("a", dec!(0.51), at(2)),
+            trade("a", dec!(0.49), at(4)),
+            // Other markets have their own window
+            trade("b", dec!(0.51), at(5)),
+            // The window runs from the last Go let through, at 4
+            trade("a", dec!(0.51), at(13)),
+            trade("a", dec!(0.51), at(14)),
+            trade("a", dec!(0.49), at(20)),
+        ];
+        assert_eq!(
+            sides(&mut strategy, &trades),
+            vec![
+                Some(Side::Buy),
+                None,
+                Some(Side::Buy),
+                Some(Side::Sell),
+                None,
+                Some(Side::Sell),
+                None,
+            ]
+        );
+        assert_eq!(strategy.name(), "flip_flop");
+    }
+
+    #[test]
+    fn test_min_edge_change_debounces_jitter() {
+        let t0 = Utc::now();
+        let mut strategy = Hysteresis::new(Box::new(FlipFlop)).with_min_edge_change(dec!(0.03));
+
+        let trades = [
+            trade("a", dec!(0.49), t0),
+            // One tick either side of 0.50 is jitter
+            trade("a", dec!(0.50), t0),
+            trade("a", dec!(0.52), t0),
+            trade("a", dec!(0.48), t0),
+            trade("a", dec!(0.50), t0),
+        ];
+        assert_eq!(
+            sides(&mut strategy, &trades),
+            vec![
+                Some(Side::Buy),
+                None,
+                Some(Side::Sell),
+                Some(Side::Buy),
+                None,
+            ]
+        );
+
+        // Without limits the wrapper passes everything through
+        let mut strategy = Hysteresis::new(Box::new(FlipFlop));
+        assert!(sides(&mut strategy, &trades).iter().all(Option::is_some));
+    }
+}
@@ -1,7 +1,8 @@
 //! Common test utilities and fixtures
 
-use polymarket_websocket::common::types::{OrderBook, Platform, PriceLevel, Side, Trade};
-use rust_decimal::Decimal;
+use polymarket_websocket::common::types::{
+    OrderBook, Platform, PriceLevel, Side, Trade, TradeSideSource,
+};
 use rust_decimal_macros::dec;
 
 /// Create a sample order book for testing
@@ -21,7 +22,9 @@ pub fn sample_order_book() -> OrderBook {
             PriceLevel::new(dec!(0.60), dec!(90)),
         ],
         timestamp: chrono::Utc::now(),
+        received_at: chrono::Utc::now(),
         sequence: 1,
+        hash: None,
     }
 }
 
@@ -35,7 +38,9 @@ pub fn sample_trade() -> Trade {
         price: dec!(0.52),
         size: dec!(50),
         side: Side::Buy,
+        side_source: TradeSideSource::Reported,
         timestamp: chrono::Utc::now(),
+        received_at: chrono::Utc::now(),
     }
 }
 
@@ -109,16 +114,64 @@ pub mod api_responses {
                     {"token_id": "token_yes", "outcome": "Yes"},
                     {"token_id": "token_no", "outcome": "No"}
                 ],
+                "minimum_tick_size": "0.01",
+                "end_date_iso": "2024-12-31T12:00:00Z",
                 "active": true
             }
         ]
     }"#;
 
+    /// Markets from one neg-risk group, each an outcome of the same event
+    pub const NEG_RISK_MARKETS: &str = r#"{
+        "data": [
+            {
+                "condition_id": "0xaaa",
+                "question": "Will Alice win the election?",
+                "tokens": [
+                    {"token_id": "alice_yes", "outcome": "Yes"},
+                    {"token_id": "alice_no", "outcome": "No"}
+                ],
+                "neg_risk": true,
+                "neg_risk_market_id": "0xelection"
+            },
+            {
+                "condition_id": "0xbbb",
+                "question": "Will Bob win the election?",
+                "tokens": [
+                    {"token_id": "bob_no", "outcome": "No"},
+                    {"token_id": "bob_yes", "outcome": "Yes"}
+                ],
+                "neg_risk": true,
+                "neg_risk_market_id": "0xelection"
+            },
+            {
+                "condition_id": "0xccc",
+                "question": "Will Carol win the election?",
+                "tokens": [
+                    {"token_id": "carol_yes", "outcome": "Yes"},
+                    {"token_id": "carol_no", "outcome": "No"}
+                ],
+                "neg_risk": true,
+                "neg_risk_market_id": "0xelection"
+            },
+            {
+                "condition_id": "0xddd",
+                "question": "Will it snow?",
+                "tokens": [
+                    {"token_id": "snow_yes", "outcome": "Yes"},
+                    {"token_id": "snow_no", "outcome": "No"}
+                ],
+                "neg_risk": false
+            }
+        ]
+    }"#;
+
     /// Sample Gamma market response
     pub const GAMMA_MARKET: &str = r#"{
         "id": "market_001",
         "question": "Will it rain tomorrow?",
         "condition_id": "0x123456",
+        "end_date": "2024-12-31",
         "active": true,
         "tokens": [
             {"token_id": "token_yes", "outcome": "Yes", "price": 0.65},
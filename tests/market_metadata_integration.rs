@@ -0,0 +1,108 @@
+//! Tests for market metadata helpers over the shared API fixtures
+//!
+//! These need no network access.
+
+#[allow(dead_code)]
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::api_responses::{GAMMA_MARKET, MARKETS, NEG_RISK_MARKETS};
+use polymarket_websocket::common::math::round_to_tick;
+use polymarket_websocket::common::types::{MarketInfo, Platform, Side};
+use polymarket_websocket::polymarket::messages::{GammaMarket, MarketsResponse};
+use rust_decimal_macros::dec;
+
+#[test]
+fn test_complementary_token() {
+    let markets: MarketsResponse = serde_json::from_str(MARKETS).unwrap();
+    let market = &markets.data[0];
+
+    assert!(!market.is_neg_risk());
+    assert_eq!(market.complementary_token("token_yes"), Some("token_no"));
+    assert_eq!(market.complementary_token("token_no"), Some("token_yes"));
+    assert_eq!(market.complementary_token("other"), None);
+    assert!(market.neg_risk_siblings(&markets.data).is_empty());
+}
+
+#[test]
+fn test_gamma_complementary_token() {
+    let market: GammaMarket = serde_json::from_str(GAMMA_MARKET).unwrap();
+
+    assert!(!market.is_neg_risk());
+    assert_eq!(market.complementary_token("token_yes"), Some("token_no"));
+    assert_eq!(market.complementary_token("token_no"), Some("token_yes"));
+    assert_eq!(market.complementary_token("other"), None);
+}
+
+#[test]
+fn test_neg_risk_siblings() {
+    let markets: MarketsResponse = serde_json::from_str(NEG_RISK_MARKETS).unwrap();
+    let alice = &markets.data[0];
+
+    assert!(alice.is_neg_risk());
+    assert_eq!(alice.complementary_token("alice_yes"), Some("alice_no"));
+    // YES tokens of the other outcomes, whatever order the tokens come in
+    assert_eq!(
+        alice.neg_risk_siblings(&markets.data),
+        vec!["bob_yes", "carol_yes"]
+    );
+    assert!(markets.data[3].neg_risk_siblings(&markets.data).is_empty());
+}
+
+#[test]
+fn test_tick_size_rounds_strategy_prices() {
+    let markets: MarketsResponse = serde_json::from_str(MARKETS).unwrap();
+    let tick = markets.data[0].tick_size().unwrap().unwrap();
+    assert_eq!(tick, dec!(0.01));
+
+    assert_eq!(round_to_tick(dec!(0.4567), tick, Side::Buy), dec!(0.45));
+    assert_eq!(round_to_tick(dec!(0.4567), tick, Side::Sell), dec!(0.46));
+
+    let untagged: MarketsResponse = serde_json::from_str(NEG_RISK_MARKETS).unwrap();
+    assert_eq!(untagged.data[0].tick_size().unwrap(), None);
+}
+
+#[test]
+fn test_market_info_from_clob() {
+    let markets: MarketsResponse = serde_json::from_str(MARKETS).unwrap();
+    let info = MarketInfo::from_clob(&markets.data[0]);
+
+    assert_eq!(info.platform, Platform::Polymarket);
+    assert_eq!(info.market_id, "0x123456");
+    assert_eq!(info.title, "Will it rain tomorrow?");
+    assert_eq!(info.token_ids, vec!["token_yes", "token_no"]);
+    assert!(info.is_active);
+    assert_eq!(
+        info.end_date,
+        Some(Utc.with_ymd_and_hms(2024, 12, 31, 12, 0, 0).unwrap())
+    );
+    assert_eq!(info.tick_size, Some(dec!(0.01)));
+    assert!(!info.neg_risk);
+
+    let neg_risk: MarketsResponse = serde_json::from_str(NEG_RISK_MARKETS).unwrap();
+    let info = MarketInfo::from_clob(&neg_risk.data[0]);
+    assert!(info.neg_risk);
+    assert!(!info.is_active);
+    assert_eq!(info.end_date, None);
+    assert_eq!(info.tick_size, None);
+}
+
+#[test]
+fn test_market_info_from_gamma() {
+    let mut market: GammaMarket = serde_json::from_str(GAMMA_MARKET).unwrap();
+    let info = MarketInfo::from_gamma(&market).unwrap();
+
+    assert_eq!(info.market_id, "0x123456");
+    assert_eq!(info.title, "Will it rain tomorrow?");
+    assert_eq!(info.token_ids, vec!["token_yes", "token_no"]);
+    assert!(info.is_active);
+    // A bare date is midnight UTC
+    assert_eq!(
+        info.end_date,
+        Some(Utc.with_ymd_and_hms(2024, 12, 31, 0, 0, 0).unwrap())
+    );
+    assert_eq!(info.tick_size, None);
+
+    market.condition_id = None;
+    assert_eq!(MarketInfo::from_gamma(&market), None);
+}
@@ -0,0 +1,154 @@
+//! Tests for order book depth and VWAP calculations
+//!
+//! These run against the shared fixtures and need no network access.
+
+#[allow(dead_code)]
+mod common;
+
+use common::sample_order_book;
+use polymarket_websocket::common::types::{PriceLevel, Side};
+use rust_decimal_macros::dec;
+
+#[test]
+fn test_vwap_across_levels() {
+    let ob = sample_order_book();
+
+    // 80 @ 0.55 + 20 @ 0.58
+    assert_eq!(ob.vwap_for_size(Side::Buy, dec!(100)), Some(dec!(0.556)));
+    // 100 @ 0.50 + 100 @ 0.48
+    assert_eq!(ob.vwap_for_size(Side::Sell, dec!(200)), Some(dec!(0.49)));
+    // Fits entirely within the top level
+    assert_eq!(ob.vwap_for_size(Side::Buy, dec!(50)), Some(dec!(0.55)));
+}
+
+#[test]
+fn test_vwap_illiquid_book() {
+    let ob = sample_order_book();
+
+    assert_eq!(ob.vwap_for_size(Side::Buy, dec!(291)), None);
+    assert_eq!(ob.vwap_for_size(Side::Sell, dec!(451)), None);
+}
+
+#[test]
+fn test_depth_within_price_bound() {
+    let ob = sample_order_book();
+
+    assert_eq!(ob.depth_within(Side::Buy, dec!(0.58)), dec!(200));
+    assert_eq!(ob.depth_within(Side::Buy, dec!(0.54)), dec!(0));
+    assert_eq!(ob.depth_within(Side::Sell, dec!(0.48)), dec!(300));
+    assert_eq!(ob.depth_within(Side::Sell, dec!(0.40)), dec!(450));
+}
+
+#[test]
+fn test_price_level_notional() {
+    let ob = sample_order_book();
+
+    let bids: Vec<_> = ob.bids.iter().map(PriceLevel::notional).collect();
+    assert_eq!(bids, vec![dec!(50), dec!(96), dec!(67.5)]);
+    let asks: Vec<_> = ob.asks.iter().map(PriceLevel::notional).collect();
+    assert_eq!(asks, vec![dec!(44), dec!(69.6), dec!(54)]);
+}
+
+#[test]
+fn test_liquidity_score_prefers_tight_deep_books() {
+    let ob = sample_order_book();
+
+    // Within 0.06 of the 0.525 mid: 300 bid, 200 ask; spread 0.05
+    let score = ob.liquidity_score(dec!(0.06));
+    assert!(score > dec!(180) && score < dec!(181));
+
+    // Same levels with less size
+    let mut thinner = ob.clone();
+    for level in thinner.bids.iter_mut().chain(thinner.asks.iter_mut()) {
+        level.size /= dec!(2);
+    }
+    assert!(thinner.liquidity_score(dec!(0.06)) < score);
+
+    // Same sizes with the asks pushed out
+    let mut wider = ob.clone();
+    for level in wider.asks.iter_mut() {
+        level.price += dec!(0.10);
+    }
+    assert!(wider.liquidity_score(dec!(0.06)) < score);
+
+    // A one-sided book isn't liquid at all
+    let mut one_sided = ob.clone();
+    one_sided.asks.clear();
+    assert_eq!(one_sided.liquidity_score(dec!(0.06)), dec!(0));
+}
+
+#[test]
+fn test_is_tradeable() {
+    let ob = sample_order_book();
+
+    assert!(ob.is_tradeable(dec!(0.05), dec!(80)));
+    assert!(ob.is_tradeable(dec!(0.06), dec!(200)));
+    // Spread is wider than allowed
+    assert!(!ob.is_tradeable(dec!(0.04), dec!(10)));
+    // Only 80 offered within 0.05 of the mid
+    assert!(!ob.is_tradeable(dec!(0.05), dec!(100)));
+
+    let mut empty = ob.clone();
+    empty.bids.clear();
+    assert!(!empty.is_tradeable(dec!(1), dec!(0)));
+}
+
+#[test]
+fn test_imbalance() {
+    let ob = sample_order_book();
+
+    // Top of book: 100 bid vs 80 ask
+    assert_eq!(ob.imbalance(1).unwrap().round_dp(4), dec!(0.1111));
+    // All levels: 450 bid vs 290 ask
+    assert!(ob.imbalance(3).unwrap() > dec!(0));
+    assert_eq!(ob.imbalance(10), ob.imbalance(3));
+    assert_eq!(ob.imbalance(0), None);
+
+    let mut ask_heavy = ob.clone();
+    ask_heavy.asks[0].size = dec!(300);
+    assert!(ask_heavy.imbalance(1).unwrap() < dec!(0));
+
+    let mut bids_only = ob.clone();
+    bids_only.asks.clear();
+    assert_eq!(bids_only.imbalance(3), Some(dec!(1)));
+}
+
+#[test]
+fn test_microprice() {
+    let ob = sample_order_book();
+    let microprice = ob.microprice().unwrap();
+
+    // (0.50 * 80 + 0.55 * 100) / 180: between the touch prices and above
+    // the midpoint since the bid is heavier
+    assert!(microprice > ob.best_bid().unwrap().price);
+    assert!(microprice < ob.best_ask().unwrap().price);
+    assert!(microprice > ob.midpoint().unwrap());
+    assert_eq!(microprice.round_dp(4), dec!(0.5278));
+
+    let mut one_sided = ob.clone();
+    one_sided.bids.clear();
+    assert_eq!(one_sided.microprice(), None);
+}
+
+#[test]
+fn test_format_ladder() {
+    let ob = sample_order_book();
+
+    let ladder = ob.format_ladder(2);
+    assert_eq!(
+        ladder,
+        "polymarket test_token_456\n\
+         \x20 ask 0.58  120\n\
+         \x20 ask 0.55   80\n\
+         \x20 bid 0.50  100\n\
+         \x20 bid 0.48  200\n\
+         \x20 mid 0.525  spread 0.05"
+    );
+
+    // Display shows every level of this small book
+    let shown = ob.to_string();
+    assert!(shown.contains("ask 0.55   80"));
+    assert!(shown.contains("bid 0.50  100"));
+    assert!(shown.contains("spread 0.05"));
+    assert_eq!(shown.lines().count(), 8);
+}
@@ -0,0 +1,117 @@
+//! Tests for paper trading fills against the shared order book fixture
+//!
+//! These need no network access.
+
+#[allow(dead_code)]
+mod common;
+
+use common::sample_order_book;
+use polymarket_websocket::common::types::{MarketEvent, Platform, Side};
+use polymarket_websocket::strategy::{OrderType, SizedIntent, SizedLeg};
+use polymarket_websocket::{BookStore, IntentSink, PaperExecutionSink};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+fn paper_sink(platform: Platform) -> PaperExecutionSink {
+    let mut book = sample_order_book();
+    book.platform = platform;
+    let mut store = BookStore::new();
+    store.apply(&MarketEvent::OrderBook(book));
+    PaperExecutionSink::new(Arc::new(RwLock::new(store)))
+}
+
+fn intent(platform: Platform, side: Side, size: Decimal, price: Decimal) -> SizedIntent {
+    SizedIntent {
+        legs: vec![SizedLeg {
+            platform,
+            market_id: sample_order_book().asset_id,
+            side,
+            size,
+            price,
+            order_type: OrderType::Fok,
+        }],
+        reason: "paper".to_string(),
+        score: None,
+    }
+}
+
+#[tokio::test]
+async fn test_small_order_fills_completely() {
+    let sink = paper_sink(Platform::Kalshi);
+
+    // 80 @ 0.55 + 20 @ 0.58
+    let report = sink
+        .submit(intent(Platform::Kalshi, Side::Buy, dec!(100), dec!(0.60)))
+        .await
+        .unwrap();
+    let leg = &report.legs[0];
+    assert!(leg.is_filled());
+    assert_eq!(leg.status, "matched");
+    assert_eq!(leg.filled_size, dec!(100));
+    assert_eq!(leg.avg_price, Some(dec!(0.556)));
+    // ceil(0.07 * 100 * 0.556 * 0.444) cents
+    assert_eq!(leg.fee, dec!(1.73));
+
+    let positions = sink.positions();
+    let position = positions
+        .position(Platform::Kalshi, &leg.market_id)
+        .unwrap();
+    assert_eq!(position.size, dec!(100));
+    assert_eq!(position.avg_entry_price, dec!(0.556));
+    assert_eq!(sink.fees_paid(), dec!(1.73));
+}
+
+#[tokio::test]
+async fn test_large_order_fills_partially() {
+    let sink = paper_sink(Platform::Polymarket);
+
+    // Only 100 @ 0.50 and 200 @ 0.48 are at or above the limit
+    let report = sink
+        .submit(intent(
+            Platform::Polymarket,
+            Side::Sell,
+            dec!(500),
+            dec!(0.48),
+        ))
+        .await
+        .unwrap();
+    let leg = &report.legs[0];
+    assert!(!leg.is_filled());
+    assert_eq!(leg.status, "partial");
+    assert_eq!(leg.filled_size, dec!(300));
+    assert_eq!(leg.avg_price.unwrap().round_dp(4), dec!(0.4867));
+    assert_eq!(leg.fee, Decimal::ZERO);
+
+    // The whole book isn't enough for a buy either
+    let report = sink
+        .submit(intent(
+            Platform::Polymarket,
+            Side::Buy,
+            dec!(1000),
+            dec!(0.99),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(report.legs[0].filled_size, dec!(290));
+
+    // Nothing on the book at the price
+    let report = sink
+        .submit(intent(
+            Platform::Polymarket,
+            Side::Buy,
+            dec!(10),
+            dec!(0.50),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(report.legs[0].status, "unfilled");
+    assert_eq!(report.legs[0].filled_size, Decimal::ZERO);
+
+    let positions = sink.positions();
+    let position = positions
+        .position(Platform::Polymarket, &report.legs[0].market_id)
+        .unwrap();
+    assert_eq!(position.size, dec!(-10));
+}
@@ -287,19 +287,8 @@ async fn test_get_order_book_for_active_market() {
             // Verify order book structure
             assert_eq!(order_book.asset_id, token_id);
             
-            // Print top of book if available
-            if let Some(best_bid) = order_book.best_bid() {
-                println!("Best bid: {} @ size {}", best_bid.price, best_bid.size);
-            }
-            if let Some(best_ask) = order_book.best_ask() {
-                println!("Best ask: {} @ size {}", best_ask.price, best_ask.size);
-            }
-            if let Some(mid) = order_book.midpoint() {
-                println!("Midpoint: {}", mid);
-            }
-            if let Some(spread) = order_book.spread() {
-                println!("Spread: {}", spread);
-            }
+            // Print the top of the book
+            println!("{}", order_book);
             
             // Verify bids are sorted descending by price
             for window in order_book.bids.windows(2) {
@@ -11,9 +11,17 @@
 //! Note: These tests connect to live WebSocket servers and may take some time.
 //! They also depend on active markets having data.
 
-use polymarket_websocket::common::types::{ConnectionStatus, MarketEvent, Platform};
+#[allow(dead_code)]
+mod common;
+
+use common::ws_messages::{BOOK_UPDATE, PRICE_CHANGE, TRADE};
+use polymarket_websocket::common::orderbook::OrderBookManager;
+use polymarket_websocket::common::types::{
+    ConnectionStatus, MarketEvent, OrderBook, OrderBookUpdate, Platform, Side, TradeSideSource,
+};
 use polymarket_websocket::polymarket::rest::PolymarketRestClient;
 use polymarket_websocket::polymarket::websocket::PolymarketWebSocketClient;
+use rust_decimal_macros::dec;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::{sleep, timeout};
@@ -391,17 +399,91 @@ async fn test_connection_state_tracking() {
 // Data Parsing Tests (using recorded messages)
 // ============================================================================
 
-#[tokio::test]
-async fn test_parse_order_book_message() {
-    // Test parsing a sample order book message
-    // This tests the parsing logic without making network calls
-    
-    use polymarket_websocket::polymarket::websocket::PolymarketWebSocketClient;
-    
-    // Note: We can't directly test parse_message as it's private
-    // But we can verify the WebSocket client is created correctly
-    let ws_client = PolymarketWebSocketClient::new_market_channel(WS_BASE_URL);
-    assert!(ws_client.is_connected() == false);
+/// Parse a fixture that holds exactly one book update
+fn parse_update(text: &str) -> OrderBookUpdate {
+    let mut events = PolymarketWebSocketClient::parse(text).unwrap();
+    assert_eq!(events.len(), 1);
+    match events.remove(0) {
+        MarketEvent::OrderBookUpdate(update) => update,
+        other => panic!("Expected an order book update, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_order_book_message() {
+    let update = parse_update(BOOK_UPDATE);
+
+    assert!(update.is_snapshot);
+    assert_eq!(update.platform, Platform::Polymarket);
+    assert_eq!(update.market_id, "0x123456");
+    assert_eq!(update.hash.as_deref(), Some("0xabc123"));
+    assert_eq!(update.timestamp.timestamp(), 1704067200);
+    assert_eq!(update.bids.len(), 2);
+    assert_eq!(update.asks.len(), 2);
+    assert_eq!(update.bids[0].price, dec!(0.50));
+    assert_eq!(update.asks[0].price, dec!(0.55));
+}
+
+#[test]
+fn test_parse_price_change_message() {
+    let update = parse_update(PRICE_CHANGE);
+
+    assert!(!update.is_snapshot);
+    assert!(update.asks.is_empty());
+    assert_eq!(update.bids.len(), 1);
+    assert_eq!(update.bids[0].price, dec!(0.51));
+    assert_eq!(update.bids[0].size, dec!(150));
+}
+
+#[test]
+fn test_parse_trade_message() {
+    let events = PolymarketWebSocketClient::parse(TRADE).unwrap();
+
+    match events.as_slice() {
+        [MarketEvent::Trade(trade)] => {
+            assert_eq!(trade.trade_id, "trade_001");
+            assert_eq!(trade.price, dec!(0.52));
+            assert_eq!(trade.size, dec!(25));
+            assert_eq!(trade.side, Side::Buy);
+            assert_eq!(trade.side_source, TradeSideSource::Reported);
+        }
+        other => panic!("Expected one trade, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_batched_fixtures() {
+    let batch = format!("[{}, {}, {}]", BOOK_UPDATE, PRICE_CHANGE, TRADE);
+    let events = PolymarketWebSocketClient::parse(&batch).unwrap();
+
+    assert_eq!(events.len(), 3);
+    assert!(matches!(&events[0], MarketEvent::OrderBookUpdate(u) if u.is_snapshot));
+    assert!(matches!(&events[1], MarketEvent::OrderBookUpdate(u) if !u.is_snapshot));
+    assert!(matches!(&events[2], MarketEvent::Trade(_)));
+
+    assert!(PolymarketWebSocketClient::parse("not json").is_err());
+}
+
+#[test]
+fn test_snapshot_then_delta_consistency() {
+    let snapshot = parse_update(BOOK_UPDATE);
+    let delta = parse_update(PRICE_CHANGE);
+
+    let mut manager = OrderBookManager::new();
+    assert!(manager.apply_update(&snapshot));
+    assert!(manager.apply_update(&delta));
+    let book = manager.get(&snapshot.asset_id).unwrap();
+
+    // The delta adds a bid level above the old best
+    assert_eq!(book.bids.len(), 3);
+    assert_eq!(book.best_bid().unwrap().price, dec!(0.51));
+    assert_eq!(book.best_ask().unwrap().price, dec!(0.55));
+
+    // Applying the same events to a bare book gives the same levels
+    let mut direct = OrderBook::from(&snapshot);
+    direct.apply_update(&delta);
+    assert_eq!(direct.bids, book.bids);
+    assert_eq!(direct.asks, book.asks);
 }
 
 // ============================================================================